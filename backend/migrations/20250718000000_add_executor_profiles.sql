@@ -0,0 +1,24 @@
+PRAGMA foreign_keys = ON;
+
+-- Named per-project executor profiles: command overrides, extra args, env vars,
+-- model flag and pinned npm package version for coding agents
+CREATE TABLE executor_profiles (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL,
+    executor    TEXT NOT NULL,  -- Base executor type, e.g. 'claude'
+    command     TEXT,           -- Replaces the executor's default command
+    extra_args  TEXT,           -- Appended to the command line
+    env_vars    TEXT NOT NULL DEFAULT '{}',  -- JSON object of environment variables
+    model       TEXT,           -- Passed as --model
+    npm_version TEXT,           -- Pins the npx package version (replaces @latest)
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_executor_profiles_unique_name_project
+ON executor_profiles(project_id, name);
+
+-- Attempts remember the profile they were started with so follow-ups reuse it
+ALTER TABLE task_attempts ADD COLUMN executor_profile_id BLOB REFERENCES executor_profiles(id) ON DELETE SET NULL;
@@ -0,0 +1,51 @@
+PRAGMA foreign_keys = ON;
+
+-- Append-only storage for execution process output, replacing the
+-- execution_processes.stdout/stderr TEXT columns that were rewritten on every line
+CREATE TABLE execution_log_chunks (
+    id                    INTEGER PRIMARY KEY AUTOINCREMENT,
+    execution_process_id  BLOB NOT NULL,
+    seq                   INTEGER NOT NULL,  -- Order of the chunk within the process
+    stream                TEXT NOT NULL
+                             CHECK (stream IN ('stdout','stderr')),
+    content               TEXT NOT NULL,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    UNIQUE (execution_process_id, seq)
+);
+
+CREATE INDEX idx_execution_log_chunks_process_stream
+    ON execution_log_chunks(execution_process_id, stream, seq);
+
+-- Existing stdout becomes a single chunk
+INSERT INTO execution_log_chunks (execution_process_id, seq, stream, content, created_at)
+SELECT id, 0, 'stdout', stdout, updated_at
+FROM execution_processes
+WHERE stdout IS NOT NULL AND stdout != '';
+
+-- Existing stderr is split on the old '---STDERR_CHUNK_BOUNDARY---' delimiter,
+-- one chunk per delimited section, numbered after the stdout chunk
+WITH RECURSIVE stderr_parts(process_id, part, rest, idx, created_at) AS (
+    SELECT id, NULL, stderr || '---STDERR_CHUNK_BOUNDARY---', 0, updated_at
+    FROM execution_processes
+    WHERE stderr IS NOT NULL AND stderr != ''
+    UNION ALL
+    SELECT process_id,
+           substr(rest, 1, instr(rest, '---STDERR_CHUNK_BOUNDARY---') - 1),
+           substr(rest, instr(rest, '---STDERR_CHUNK_BOUNDARY---') + length('---STDERR_CHUNK_BOUNDARY---')),
+           idx + 1,
+           created_at
+    FROM stderr_parts
+    WHERE rest != ''
+)
+INSERT INTO execution_log_chunks (execution_process_id, seq, stream, content, created_at)
+SELECT process_id,
+       ROW_NUMBER() OVER (PARTITION BY process_id ORDER BY idx),
+       'stderr',
+       trim(part, char(10, 13, 32)) || char(10),
+       created_at
+FROM stderr_parts
+WHERE part IS NOT NULL AND trim(part, char(10, 13, 32)) != '';
+
+ALTER TABLE execution_processes DROP COLUMN stdout;
+ALTER TABLE execution_processes DROP COLUMN stderr;
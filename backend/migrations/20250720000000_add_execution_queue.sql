@@ -0,0 +1,93 @@
+PRAGMA foreign_keys = ON;
+
+-- Adds the 'queued' execution process status and per-project agent concurrency limits.
+-- SQLite can't alter a CHECK constraint, so execution_processes is rebuilt. Migrations
+-- run inside a transaction where foreign_keys can't be switched off, and dropping the
+-- table would cascade into executor_sessions and execution_log_chunks, so those are
+-- set aside first and rebuilt afterwards.
+CREATE TABLE execution_processes_new (
+    id                BLOB PRIMARY KEY,
+    task_attempt_id   BLOB NOT NULL,
+    process_type      TEXT NOT NULL DEFAULT 'setupscript'
+                         CHECK (process_type IN ('setupscript','codingagent','devserver')),
+    executor_type     TEXT,
+    status            TEXT NOT NULL DEFAULT 'running'
+                         CHECK (status IN ('queued','running','completed','failed','killed')),
+    command           TEXT NOT NULL,
+    args              TEXT,  -- JSON array of arguments
+    working_directory TEXT NOT NULL,
+    exit_code         INTEGER,
+    started_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    completed_at      TEXT,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
+
+INSERT INTO execution_processes_new (
+    id, task_attempt_id, process_type, executor_type, status, command, args,
+    working_directory, exit_code, started_at, completed_at, created_at, updated_at
+)
+SELECT id, task_attempt_id, process_type, executor_type, status, command, args,
+       working_directory, exit_code, started_at, completed_at, created_at, updated_at
+FROM execution_processes;
+
+CREATE TABLE executor_sessions_backup AS SELECT * FROM executor_sessions;
+CREATE TABLE execution_log_chunks_backup AS SELECT * FROM execution_log_chunks;
+
+DROP TABLE executor_sessions;
+DROP TABLE execution_log_chunks;
+DROP TABLE execution_processes;
+ALTER TABLE execution_processes_new RENAME TO execution_processes;
+
+CREATE INDEX idx_execution_processes_task_attempt_id ON execution_processes(task_attempt_id);
+CREATE INDEX idx_execution_processes_status ON execution_processes(status);
+CREATE INDEX idx_execution_processes_type ON execution_processes(process_type);
+
+CREATE TABLE executor_sessions (
+    id                    BLOB PRIMARY KEY,
+    task_attempt_id       BLOB NOT NULL,
+    execution_process_id  BLOB NOT NULL,
+    session_id            TEXT,  -- External session ID from Claude/Amp
+    prompt                TEXT,  -- The prompt sent to the executor
+    summary               TEXT,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+INSERT INTO executor_sessions (
+    id, task_attempt_id, execution_process_id, session_id, prompt, summary, created_at, updated_at
+)
+SELECT id, task_attempt_id, execution_process_id, session_id, prompt, summary, created_at, updated_at
+FROM executor_sessions_backup;
+
+CREATE INDEX idx_executor_sessions_task_attempt_id ON executor_sessions(task_attempt_id);
+CREATE INDEX idx_executor_sessions_execution_process_id ON executor_sessions(execution_process_id);
+CREATE INDEX idx_executor_sessions_session_id ON executor_sessions(session_id);
+
+CREATE TABLE execution_log_chunks (
+    id                    INTEGER PRIMARY KEY AUTOINCREMENT,
+    execution_process_id  BLOB NOT NULL,
+    seq                   INTEGER NOT NULL,  -- Order of the chunk within the process
+    stream                TEXT NOT NULL
+                             CHECK (stream IN ('stdout','stderr')),
+    content               TEXT NOT NULL,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    UNIQUE (execution_process_id, seq)
+);
+
+INSERT INTO execution_log_chunks (id, execution_process_id, seq, stream, content, created_at)
+SELECT id, execution_process_id, seq, stream, content, created_at
+FROM execution_log_chunks_backup;
+
+CREATE INDEX idx_execution_log_chunks_process_stream
+    ON execution_log_chunks(execution_process_id, stream, seq);
+
+DROP TABLE executor_sessions_backup;
+DROP TABLE execution_log_chunks_backup;
+
+-- NULL means no per-project limit
+ALTER TABLE projects ADD COLUMN max_concurrent_agents INTEGER;
@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Explicit ordering between tasks of a project: task_id can't start
+-- until blocked_by_task_id is merged or done
+CREATE TABLE task_dependencies (
+    task_id             BLOB NOT NULL,
+    blocked_by_task_id  BLOB NOT NULL,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, blocked_by_task_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (blocked_by_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    CHECK (task_id != blocked_by_task_id)
+);
+
+CREATE INDEX idx_task_dependencies_blocked_by_task_id ON task_dependencies(blocked_by_task_id);
@@ -0,0 +1,7 @@
+PRAGMA foreign_keys = ON;
+
+-- When the process last produced output, for the idle-output watchdog
+ALTER TABLE execution_processes ADD COLUMN last_output_at TEXT;
+
+-- Why a process was killed by vibe-kanban itself (e.g. timeouts), NULL otherwise
+ALTER TABLE execution_processes ADD COLUMN kill_reason TEXT;
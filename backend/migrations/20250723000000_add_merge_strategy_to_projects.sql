@@ -0,0 +1,5 @@
+PRAGMA foreign_keys = ON;
+
+-- How attempts are merged into their base branch unless a merge asks for something else
+ALTER TABLE projects ADD COLUMN merge_strategy TEXT NOT NULL DEFAULT 'squash'
+    CHECK (merge_strategy IN ('squash','merge','rebase','keep-commits'));
@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Outbound webhooks called on task and attempt lifecycle events of a project
+CREATE TABLE webhooks (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    url         TEXT NOT NULL,
+    secret      TEXT NOT NULL,  -- Key of the HMAC-SHA256 payload signature
+    events      TEXT NOT NULL DEFAULT '[]',  -- JSON array of event names, empty for all events
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_webhooks_project_id ON webhooks(project_id);
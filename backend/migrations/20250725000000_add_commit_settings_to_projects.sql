@@ -0,0 +1,3 @@
+-- How changes left by coding agents are committed: message template, author override,
+-- Co-authored-by trailers, conventional commit prefix and commit or amend mode
+ALTER TABLE projects ADD COLUMN commit_settings TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,10 @@
+PRAGMA foreign_keys = ON;
+
+-- Number of automatic follow-ups started when a setup script, dev server or check fails,
+-- 0 turns the auto-fix loop off
+ALTER TABLE projects ADD COLUMN auto_fix_max_iterations INTEGER NOT NULL DEFAULT 0;
+
+-- Coding agent executions started by the auto-fix loop point at the failed process they
+-- were asked to fix, and count how many fixes in a row have been attempted
+ALTER TABLE execution_processes ADD COLUMN auto_fix_of_process_id BLOB;
+ALTER TABLE execution_processes ADD COLUMN auto_fix_iteration INTEGER;
@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Tokens used by an execution process, one row per model it called, holding running totals
+CREATE TABLE execution_usage (
+    execution_process_id        BLOB NOT NULL,
+    model                       TEXT NOT NULL,
+    input_tokens                INTEGER NOT NULL DEFAULT 0,
+    output_tokens               INTEGER NOT NULL DEFAULT 0,
+    cache_read_input_tokens     INTEGER NOT NULL DEFAULT 0,
+    cache_creation_input_tokens INTEGER NOT NULL DEFAULT 0,
+    created_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (execution_process_id, model),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
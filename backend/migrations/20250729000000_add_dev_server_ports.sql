@@ -0,0 +1,8 @@
+PRAGMA foreign_keys = ON;
+
+-- Extra environment variables and readiness detection for the project's dev servers
+ALTER TABLE projects ADD COLUMN dev_server_settings TEXT NOT NULL DEFAULT '{}';
+
+-- Port allocated to a dev server process, and when its output showed it was ready to serve
+ALTER TABLE execution_processes ADD COLUMN port INTEGER;
+ALTER TABLE execution_processes ADD COLUMN ready_at TEXT;
@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Environment variables set on every process spawned for the project's tasks
+CREATE TABLE project_env_vars (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL,
+    value       TEXT NOT NULL,  -- Encrypted with the local secret key when is_secret
+    is_secret   BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
+
+CREATE INDEX idx_project_env_vars_project_id ON project_env_vars(project_id);
@@ -0,0 +1,2 @@
+-- JSON SandboxSettings: whether executions run in a container, with which image and limits
+ALTER TABLE projects ADD COLUMN sandbox_settings TEXT NOT NULL DEFAULT '{}';
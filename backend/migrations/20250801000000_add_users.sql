@@ -0,0 +1,29 @@
+PRAGMA foreign_keys = ON;
+
+-- Accounts of a shared instance, only used in multi-user mode
+CREATE TABLE users (
+    id             BLOB PRIMARY KEY,
+    username       TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    password_hash  TEXT NOT NULL,
+    is_admin       BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+-- Browser sessions and API tokens, stored as the SHA-256 of the secret
+CREATE TABLE api_tokens (
+    id            BLOB PRIMARY KEY,
+    user_id       BLOB NOT NULL,
+    kind          TEXT NOT NULL CHECK (kind IN ('session', 'api')),
+    name          TEXT NOT NULL,
+    token_hash    TEXT NOT NULL UNIQUE,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    last_used_at  TEXT,
+    expires_at    TEXT,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_api_tokens_user_id ON api_tokens(user_id);
+
+ALTER TABLE tasks ADD COLUMN created_by BLOB REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE task_attempts ADD COLUMN started_by BLOB REFERENCES users(id) ON DELETE SET NULL;
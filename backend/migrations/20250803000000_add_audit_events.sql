@@ -0,0 +1,32 @@
+PRAGMA foreign_keys = ON;
+
+-- Who changed what, written by every mutating route and MCP tool. Ids aren't foreign keys so
+-- events outlive what they are about, and triggers keep the table append-only.
+CREATE TABLE audit_events (
+    id          BLOB PRIMARY KEY,
+    actor_id    BLOB,           -- NULL in single-user mode
+    actor_name  TEXT,           -- Username when the event happened
+    source      TEXT NOT NULL CHECK (source IN ('api', 'mcp')),
+    action      TEXT NOT NULL,  -- e.g. 'task.update' or 'attempt.merge'
+    project_id  BLOB,
+    task_id     BLOB,
+    attempt_id  BLOB,
+    target      TEXT,           -- Anything else acted on, e.g. a webhook id or a file path
+    before      TEXT,           -- JSON
+    after       TEXT,           -- JSON
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_audit_events_created_at ON audit_events(created_at);
+CREATE INDEX idx_audit_events_project_id ON audit_events(project_id, created_at);
+CREATE INDEX idx_audit_events_actor_id ON audit_events(actor_id, created_at);
+
+CREATE TRIGGER audit_events_no_update BEFORE UPDATE ON audit_events
+BEGIN
+    SELECT RAISE(ABORT, 'audit events are append-only');
+END;
+
+CREATE TRIGGER audit_events_no_delete BEFORE DELETE ON audit_events
+BEGIN
+    SELECT RAISE(ABORT, 'audit events are append-only');
+END;
@@ -0,0 +1,5 @@
+PRAGMA foreign_keys = ON;
+
+-- Commit the attempt's branch pointed to when a check run started, so a rebase or merge of
+-- the base branch afterwards makes its result outdated
+ALTER TABLE execution_processes ADD COLUMN head_commit TEXT;
@@ -0,0 +1,11 @@
+PRAGMA foreign_keys = ON;
+
+-- When a task was last moved to done, by any route, MCP tool or the PR monitor. The execution
+-- monitor watches it to start the tasks that were waiting on it.
+ALTER TABLE tasks ADD COLUMN done_at TEXT;
+
+CREATE TRIGGER tasks_set_done_at AFTER UPDATE OF status ON tasks
+WHEN NEW.status = 'done' AND OLD.status != 'done'
+BEGIN
+    UPDATE tasks SET done_at = datetime('now', 'subsec') WHERE id = NEW.id;
+END;
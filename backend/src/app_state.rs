@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 #[cfg(unix)]
 use nix::{sys::signal::Signal, unistd::Pid};
@@ -12,6 +16,7 @@ pub enum ExecutionType {
     SetupScript,
     CodingAgent,
     DevServer,
+    CheckScript,
 }
 
 #[derive(Debug)]
@@ -24,10 +29,15 @@ pub struct RunningExecution {
 #[derive(Debug, Clone)]
 pub struct AppState {
     running_executions: Arc<Mutex<HashMap<Uuid, RunningExecution>>>,
+    // Serializes the capacity check and start/enqueue of coding agents
+    execution_queue_lock: Arc<Mutex<()>>,
     pub db_pool: sqlx::SqlitePool,
     config: Arc<tokio::sync::RwLock<crate::models::config::Config>>,
     pub analytics: Arc<TokioRwLock<AnalyticsService>>,
     user_id: String,
+    multi_user: bool,
+    // Port of the listener serving previews in multi-user mode, set once it is bound
+    preview_port: Arc<OnceLock<u16>>,
 }
 
 impl AppState {
@@ -46,13 +56,31 @@ impl AppState {
 
         Self {
             running_executions: Arc::new(Mutex::new(HashMap::new())),
+            execution_queue_lock: Arc::new(Mutex::new(())),
             db_pool,
             config,
             analytics,
             user_id: generate_user_id(),
+            multi_user: crate::utils::is_multi_user(),
+            preview_port: Arc::new(OnceLock::new()),
         }
     }
 
+    /// Whether requests must come from a signed in user, see `require_user_middleware`
+    pub fn multi_user(&self) -> bool {
+        self.multi_user
+    }
+
+    /// Port previews are served from when it isn't the app's own, so that the code of a
+    /// dev server runs on another origin than the API
+    pub fn preview_port(&self) -> Option<u16> {
+        self.preview_port.get().copied()
+    }
+
+    pub fn set_preview_port(&self, port: u16) {
+        let _ = self.preview_port.set(port);
+    }
+
     pub async fn update_analytics_config(&self, user_enabled: bool) {
         // Check if analytics was disabled before this update
         let was_analytics_disabled = {
@@ -113,6 +141,7 @@ impl AppState {
         // Remove completed executions from the map
         for (execution_id, _, _, _) in &completed_executions {
             executions.remove(execution_id);
+            crate::executors::sandbox::forget_container(*execution_id);
         }
 
         completed_executions
@@ -124,6 +153,11 @@ impl AppState {
         executions.insert(execution_id, execution);
     }
 
+    /// Hold this while deciding whether a coding agent can start or must be queued
+    pub async fn lock_execution_queue(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.execution_queue_lock.lock().await
+    }
+
     pub async fn stop_running_execution_by_id(
         &self,
         execution_id: Uuid,
@@ -151,6 +185,7 @@ impl AppState {
         // final fallback – command_group already targets the group
         exec.child.kill().await.ok();
         exec.child.wait().await.ok(); // reap
+        crate::executors::sandbox::remove_container(execution_id).await;
 
         // only NOW remove it
         executions.remove(&execution_id);
@@ -173,6 +208,11 @@ impl AppState {
         config.sound_file.clone()
     }
 
+    pub async fn get_max_concurrent_agents(&self) -> Option<u32> {
+        let config = self.config.read().await;
+        config.max_concurrent_agents
+    }
+
     pub fn get_config(&self) -> &Arc<tokio::sync::RwLock<crate::models::config::Config>> {
         &self.config
     }
@@ -3,20 +3,27 @@ use std::{env, fs, path::Path};
 use ts_rs::TS;
 // in [build-dependencies]
 
-fn generate_constants() -> String {
-    r#"// Generated constants
-export const EXECUTOR_TYPES: string[] = [
-    "echo",
-    "claude",
-    "claude-plan",
-    "amp",
-    "gemini",
-    "charm-opencode",
-    "claude-code-router",
-    "sst-opencode"
-];
+/// The built-in executors, custom ones from executors.json being listed by /config/constants
+fn generate_executor_constants() -> String {
+    let executors = vibe_kanban::executor::ExecutorConfig::built_in();
+    let types: Vec<String> = executors
+        .iter()
+        .map(|executor| format!("    \"{}\"", executor))
+        .collect();
+    let labels: Vec<String> = executors
+        .iter()
+        .map(|executor| format!("    \"{}\": \"{}\"", executor, executor.display_name()))
+        .collect();
+    format!(
+        "export const EXECUTOR_TYPES: string[] = [\n{}\n];\n\nexport const EXECUTOR_LABELS: Record<string, string> = {{\n{}\n}};",
+        types.join(",\n"),
+        labels.join(",\n")
+    )
+}
 
-export const EDITOR_TYPES: EditorType[] = [
+fn generate_constants() -> String {
+    let executor_constants = generate_executor_constants();
+    let other_constants = r#"export const EDITOR_TYPES: EditorType[] = [
     "vscode",
     "cursor", 
     "windsurf",
@@ -25,17 +32,6 @@ export const EDITOR_TYPES: EditorType[] = [
     "custom"
 ];
 
-export const EXECUTOR_LABELS: Record<string, string> = {
-    "echo": "Echo (Test Mode)",
-    "claude": "Claude",
-    "claude-plan": "Claude Plan",
-    "amp": "Amp",
-    "gemini": "Gemini",
-    "charm-opencode": "Charm Opencode",
-    "claude-code-router": "Claude Code Router",
-    "sst-opencode": "SST Opencode"
-};
-
 export const EDITOR_LABELS: Record<string, string> = {
     "vscode": "VS Code",
     "cursor": "Cursor",
@@ -63,8 +59,8 @@ export const SOUND_LABELS: Record<string, string> = {
     "cow-mooing": "Cow Mooing",
     "phone-vibration": "Phone Vibration",
     "rooster": "Rooster Call"
-};"#
-    .to_string()
+};"#;
+    format!("// Generated constants\n{executor_constants}\n\n{other_constants}")
 }
 
 fn generate_types_content() -> String {
@@ -78,9 +74,14 @@ fn generate_types_content() -> String {
     let decls = [
         vibe_kanban::models::ApiResponse::<()>::decl(),
         vibe_kanban::models::config::Config::decl(),
+        vibe_kanban::models::config::ExecutionTimeoutConfig::decl(),
+        vibe_kanban::models::config::ExecutorTimeouts::decl(),
+        vibe_kanban::models::config::ModelPricing::decl(),
         vibe_kanban::models::config::ThemeMode::decl(),
         vibe_kanban::models::config::EditorConfig::decl(),
         vibe_kanban::models::config::GitHubConfig::decl(),
+        vibe_kanban::models::config::GitProviderKind::decl(),
+        vibe_kanban::models::config::GitHostConfig::decl(),
         vibe_kanban::models::config::EditorType::decl(),
         vibe_kanban::models::config::EditorConstants::decl(),
         vibe_kanban::models::config::SoundFile::decl(),
@@ -88,7 +89,14 @@ fn generate_types_content() -> String {
         vibe_kanban::routes::config::ConfigConstants::decl(),
         vibe_kanban::executor::ExecutorConfig::decl(),
         vibe_kanban::executor::ExecutorConstants::decl(),
+        vibe_kanban::executor::ExecutorOption::decl(),
         vibe_kanban::models::project::CreateProject::decl(),
+        vibe_kanban::models::project::MergeStrategy::decl(),
+        vibe_kanban::models::project::CommitMode::decl(),
+        vibe_kanban::models::project::CommitSettings::decl(),
+        vibe_kanban::models::project::DevServerSettings::decl(),
+        vibe_kanban::models::project::ContainerRuntime::decl(),
+        vibe_kanban::models::project::SandboxSettings::decl(),
         vibe_kanban::models::project::Project::decl(),
         vibe_kanban::models::project::ProjectWithBranch::decl(),
         vibe_kanban::models::project::UpdateProject::decl(),
@@ -102,6 +110,9 @@ fn generate_types_content() -> String {
         vibe_kanban::models::task::Task::decl(),
         vibe_kanban::models::task::TaskWithAttemptStatus::decl(),
         vibe_kanban::models::task::UpdateTask::decl(),
+        vibe_kanban::models::task_dependency::TaskDependency::decl(),
+        vibe_kanban::models::task_dependency::CreateTaskDependency::decl(),
+        vibe_kanban::models::task_dependency::TaskDependencies::decl(),
         vibe_kanban::models::task_template::TaskTemplate::decl(),
         vibe_kanban::models::task_template::CreateTaskTemplate::decl(),
         vibe_kanban::models::task_template::UpdateTaskTemplate::decl(),
@@ -110,6 +121,35 @@ fn generate_types_content() -> String {
         vibe_kanban::models::task_attempt::CreateTaskAttempt::decl(),
         vibe_kanban::models::task_attempt::UpdateTaskAttempt::decl(),
         vibe_kanban::models::task_attempt::CreateFollowUpAttempt::decl(),
+        vibe_kanban::models::task_attempt::MergeTaskAttempt::decl(),
+        vibe_kanban::models::task_attempt::MergeResult::decl(),
+        vibe_kanban::models::webhook::WebhookEvent::decl(),
+        vibe_kanban::models::webhook::Webhook::decl(),
+        vibe_kanban::models::webhook::CreateWebhook::decl(),
+        vibe_kanban::models::webhook::UpdateWebhook::decl(),
+        vibe_kanban::models::project_check::ProjectCheck::decl(),
+        vibe_kanban::models::project_check::CreateProjectCheck::decl(),
+        vibe_kanban::models::project_check::UpdateProjectCheck::decl(),
+        vibe_kanban::models::project_env_var::ProjectEnvVar::decl(),
+        vibe_kanban::models::project_env_var::CreateProjectEnvVar::decl(),
+        vibe_kanban::models::project_env_var::UpdateProjectEnvVar::decl(),
+        vibe_kanban::models::user::User::decl(),
+        vibe_kanban::models::user::CreateUser::decl(),
+        vibe_kanban::models::user::LoginRequest::decl(),
+        vibe_kanban::models::user::ChangePassword::decl(),
+        vibe_kanban::models::user::AuthSession::decl(),
+        vibe_kanban::models::api_token::ApiToken::decl(),
+        vibe_kanban::models::api_token::CreateApiToken::decl(),
+        vibe_kanban::models::api_token::CreatedApiToken::decl(),
+        vibe_kanban::models::project_member::ProjectRole::decl(),
+        vibe_kanban::models::project_member::ProjectMember::decl(),
+        vibe_kanban::models::project_member::SetProjectMember::decl(),
+        vibe_kanban::models::audit_event::AuditSource::decl(),
+        vibe_kanban::models::audit_event::AuditEvent::decl(),
+        vibe_kanban::models::audit_event::AuditEventQuery::decl(),
+        vibe_kanban::models::audit_event::AuditEventPage::decl(),
+        vibe_kanban::models::project_check::CheckStatus::decl(),
+        vibe_kanban::models::project_check::CheckResult::decl(),
         vibe_kanban::routes::filesystem::DirectoryEntry::decl(),
         vibe_kanban::routes::filesystem::DirectoryListResponse::decl(),
         vibe_kanban::routes::auth::DeviceStartResponse::decl(),
@@ -118,16 +158,31 @@ fn generate_types_content() -> String {
         vibe_kanban::models::task_attempt::DiffChunk::decl(),
         vibe_kanban::models::task_attempt::FileDiff::decl(),
         vibe_kanban::models::task_attempt::WorktreeDiff::decl(),
+        vibe_kanban::models::task_attempt::DiffStats::decl(),
+        vibe_kanban::models::task_attempt::AttemptComparison::decl(),
+        vibe_kanban::models::execution_usage::UsageRollup::decl(),
+        vibe_kanban::models::execution_usage::UsageReport::decl(),
+        vibe_kanban::models::task_attempt::ConflictOperation::decl(),
+        vibe_kanban::models::task_attempt::ConflictHunk::decl(),
+        vibe_kanban::models::task_attempt::ConflictFile::decl(),
+        vibe_kanban::models::task_attempt::ConflictReport::decl(),
+        vibe_kanban::models::task_attempt::StartConflictResolution::decl(),
+        vibe_kanban::models::task_attempt::ConflictResolution::decl(),
+        vibe_kanban::models::task_attempt::ResolveConflicts::decl(),
         vibe_kanban::models::task_attempt::BranchStatus::decl(),
         vibe_kanban::models::task_attempt::ExecutionState::decl(),
         vibe_kanban::models::task_attempt::TaskAttemptState::decl(),
         vibe_kanban::models::execution_process::ExecutionProcess::decl(),
         vibe_kanban::models::execution_process::ExecutionProcessSummary::decl(),
         vibe_kanban::models::execution_process::ExecutionProcessStatus::decl(),
+        vibe_kanban::models::execution_process::QueuedExecution::decl(),
         vibe_kanban::models::execution_process::ExecutionProcessType::decl(),
         vibe_kanban::models::execution_process::CreateExecutionProcess::decl(),
         vibe_kanban::models::execution_process::UpdateExecutionProcess::decl(),
         vibe_kanban::models::executor_session::ExecutorSession::decl(),
+        vibe_kanban::models::executor_profile::ExecutorProfile::decl(),
+        vibe_kanban::models::executor_profile::CreateExecutorProfile::decl(),
+        vibe_kanban::models::executor_profile::UpdateExecutorProfile::decl(),
         vibe_kanban::models::executor_session::CreateExecutorSession::decl(),
         vibe_kanban::models::executor_session::UpdateExecutorSession::decl(),
         vibe_kanban::executor::NormalizedConversation::decl(),
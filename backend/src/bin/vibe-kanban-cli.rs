@@ -0,0 +1,569 @@
+//! Headless client for a running vibe-kanban server, for scripts, CI and shell aliases.
+//!
+//! The server is found through `--url`, `VIBE_KANBAN_URL`, `BACKEND_PORT`/`PORT`, or the port
+//! file the server writes on startup, in that order. Servers in multi-user mode need an API
+//! token in `VIBE_KANBAN_TOKEN`.
+
+use std::{collections::HashMap, process::ExitCode, time::Duration};
+
+use anyhow::{anyhow, bail, Context};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+use vibe_kanban::{
+    models::{
+        execution_process::{ExecutionProcess, ExecutionProcessStatus, ExecutionProcessSummary},
+        execution_usage::{UsageReport, UsageRollup},
+        project::Project,
+        project_check::{CheckResult, CheckStatus},
+        task::{Task, TaskWithAttemptStatus},
+        task_attempt::{AttemptComparison, MergeResult, TaskAttempt},
+        ApiResponse,
+    },
+    utils::{client_api_token, server_port_path},
+};
+
+const USAGE: &str = "Usage: vibe-kanban-cli [--url URL] <command> [args]
+
+Commands:
+  projects                                  List projects
+  tasks <project-id>                        List tasks of a project
+  start <project-id> <title>                Create a task and start an attempt per executor
+      [--description TEXT] [--executor NAME[,NAME...]] [--profile PROFILE-ID]
+  attempts <project-id> <task-id>           List attempts of a task
+  compare <project-id> <task-id>            Compare the diff size, agent outcome and
+                                            check results of a task's attempts
+  usage <project-id> [--task TASK-ID]       Show token usage and cost per task, day and model
+  logs <project-id> <task-id>               Print the output of an attempt
+      [--attempt ATTEMPT-ID] [--follow]
+  checks <project-id> <task-id>             Show the project's check results on an attempt
+      [--run] [--attempt ATTEMPT-ID]
+  merge <project-id> <task-id>              Merge an attempt into its base branch
+      [--strategy squash|merge|rebase|keep-commits] [--skip-checks] [--attempt ATTEMPT-ID]
+  pr <project-id> <task-id> --title TITLE   Open a GitHub pull request for an attempt
+      [--body TEXT] [--base BRANCH] [--skip-checks] [--attempt ATTEMPT-ID]
+
+Attempt commands use the task's latest attempt unless --attempt is given. Servers in
+multi-user mode need an API token, created in the settings, in VIBE_KANBAN_TOKEN.";
+
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+struct Client {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl Client {
+    fn new(url: Option<String>) -> anyhow::Result<Self> {
+        let base_url = match url.or_else(|| std::env::var("VIBE_KANBAN_URL").ok()) {
+            Some(url) => url,
+            None => {
+                let port = match std::env::var("BACKEND_PORT").or_else(|_| std::env::var("PORT")) {
+                    Ok(port) => port,
+                    Err(_) => std::fs::read_to_string(server_port_path()).context(
+                        "Could not find a running server, pass --url or set VIBE_KANBAN_URL",
+                    )?,
+                };
+                format!("http://127.0.0.1:{}", port.trim())
+            }
+        };
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(token) = client_api_token() {
+            let mut authorization =
+                reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                    .context("VIBE_KANBAN_TOKEN contains invalid characters")?;
+            authorization.set_sensitive(true);
+            headers.insert(reqwest::header::AUTHORIZATION, authorization);
+        }
+
+        Ok(Self {
+            http: reqwest::Client::builder()
+                .default_headers(headers)
+                .build()?,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
+        let request = self.http.get(format!("{}/api{}", self.base_url, path));
+        Self::data(Self::send(request).await?)
+    }
+
+    async fn post<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+    ) -> anyhow::Result<ApiResponse<T>> {
+        let request = self
+            .http
+            .post(format!("{}/api{}", self.base_url, path))
+            .json(&body);
+        Self::send(request).await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        request: reqwest::RequestBuilder,
+    ) -> anyhow::Result<ApiResponse<T>> {
+        let response = request.send().await.context("Failed to reach the server")?;
+        let status = response.status();
+        let body = response.text().await?;
+
+        match serde_json::from_str::<ApiResponse<T>>(&body) {
+            Ok(api_response) if api_response.success => Ok(api_response),
+            Ok(api_response) => bail!(api_response
+                .message
+                .unwrap_or_else(|| format!("Request failed with {}", status))),
+            Err(_) if !status.is_success() => bail!("Request failed with {}", status),
+            Err(e) => Err(anyhow!(e).context("Unexpected response from the server")),
+        }
+    }
+
+    fn data<T>(response: ApiResponse<T>) -> anyhow::Result<T> {
+        response
+            .data
+            .ok_or_else(|| anyhow!("The server returned no data"))
+    }
+
+    /// The given attempt, or the most recent attempt of the task
+    async fn resolve_attempt(
+        &self,
+        project_id: &str,
+        task_id: &str,
+        attempt_id: Option<String>,
+    ) -> anyhow::Result<String> {
+        if let Some(attempt_id) = attempt_id {
+            return Ok(attempt_id);
+        }
+
+        let attempts: Vec<TaskAttempt> = self
+            .get(&format!(
+                "/projects/{}/tasks/{}/attempts",
+                project_id, task_id
+            ))
+            .await?;
+        attempts
+            .into_iter()
+            .max_by_key(|a| a.created_at)
+            .map(|a| a.id.to_string())
+            .ok_or_else(|| anyhow!("Task {} has no attempts", task_id))
+    }
+}
+
+/// Remaining command line arguments, consumed as flags and positionals are read
+struct Args(Vec<String>);
+
+impl Args {
+    fn flag(&mut self, name: &str) -> anyhow::Result<Option<String>> {
+        let Some(index) = self.0.iter().position(|a| a == name) else {
+            return Ok(None);
+        };
+        if index + 1 >= self.0.len() {
+            bail!("{} needs a value", name);
+        }
+        let value = self.0.remove(index + 1);
+        self.0.remove(index);
+        Ok(Some(value))
+    }
+
+    fn switch(&mut self, name: &str) -> bool {
+        match self.0.iter().position(|a| a == name) {
+            Some(index) => {
+                self.0.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn positional(&mut self, name: &str) -> anyhow::Result<String> {
+        match self.0.iter().position(|a| !a.starts_with("--")) {
+            Some(index) => Ok(self.0.remove(index)),
+            None => bail!("Missing <{}>\n\n{}", name, USAGE),
+        }
+    }
+
+    fn finish(self) -> anyhow::Result<()> {
+        match self.0.first() {
+            Some(unexpected) => bail!("Unexpected argument '{}'\n\n{}", unexpected, USAGE),
+            None => Ok(()),
+        }
+    }
+}
+
+async fn run(mut args: Args) -> anyhow::Result<()> {
+    let client = Client::new(args.flag("--url")?)?;
+    let command = args.positional("command")?;
+
+    match command.as_str() {
+        "projects" => {
+            args.finish()?;
+            let projects: Vec<Project> = client.get("/projects").await?;
+            for project in projects {
+                println!(
+                    "{}\t{}\t{}",
+                    project.id, project.name, project.git_repo_path
+                );
+            }
+        }
+        "tasks" => {
+            let project_id = args.positional("project-id")?;
+            args.finish()?;
+            let tasks: Vec<TaskWithAttemptStatus> = client
+                .get(&format!("/projects/{}/tasks", project_id))
+                .await?;
+            for task in tasks {
+                let status = serde_json::to_value(&task.status)?;
+                println!(
+                    "{}\t{}\t{}",
+                    task.id,
+                    status.as_str().unwrap_or_default(),
+                    task.title
+                );
+            }
+        }
+        "start" => {
+            let description = args.flag("--description")?;
+            let executor = args.flag("--executor")?;
+            let profile = args.flag("--profile")?;
+            let project_id = args.positional("project-id")?;
+            let title = args.positional("title")?;
+            args.finish()?;
+
+            let executors: Vec<serde_json::Value> = executor
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|e| !e.is_empty())
+                .map(|e| serde_json::json!({ "type": e }))
+                .collect();
+            let (executor, executors) = match executors.len() {
+                0 => (None, Vec::new()),
+                1 => (executors.into_iter().next(), Vec::new()),
+                _ => (None, executors),
+            };
+
+            let body = serde_json::json!({
+                "project_id": project_id,
+                "title": title,
+                "description": description,
+                "parent_task_attempt": null,
+                "executor": executor,
+                "executor_profile_id": profile,
+                "executors": executors,
+            });
+            let task: Task = Client::data(
+                client
+                    .post(
+                        &format!("/projects/{}/tasks/create-and-start", project_id),
+                        body,
+                    )
+                    .await?,
+            )?;
+            println!("{}", task.id);
+        }
+        "attempts" => {
+            let project_id = args.positional("project-id")?;
+            let task_id = args.positional("task-id")?;
+            args.finish()?;
+            let mut attempts: Vec<TaskAttempt> = client
+                .get(&format!(
+                    "/projects/{}/tasks/{}/attempts",
+                    project_id, task_id
+                ))
+                .await?;
+            attempts.sort_by_key(|a| a.created_at);
+            for attempt in attempts {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    attempt.id,
+                    attempt.executor.as_deref().unwrap_or("-"),
+                    attempt.branch,
+                    attempt.pr_url.as_deref().unwrap_or("-")
+                );
+            }
+        }
+        "logs" => {
+            let attempt_id = args.flag("--attempt")?;
+            let follow = args.switch("--follow");
+            let project_id = args.positional("project-id")?;
+            let task_id = args.positional("task-id")?;
+            args.finish()?;
+
+            let attempt_id = client
+                .resolve_attempt(&project_id, &task_id, attempt_id)
+                .await?;
+            print_logs(&client, &project_id, &task_id, &attempt_id, follow).await?;
+        }
+        "compare" => {
+            let project_id = args.positional("project-id")?;
+            let task_id = args.positional("task-id")?;
+            args.finish()?;
+            let comparisons: Vec<AttemptComparison> = client
+                .get(&format!(
+                    "/projects/{}/tasks/{}/attempts/compare",
+                    project_id, task_id
+                ))
+                .await?;
+            for comparison in comparisons {
+                let status = match &comparison.agent_status {
+                    Some(status) => serde_json::to_value(status)?
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    None => "-".to_string(),
+                };
+                let diff = comparison
+                    .diff_stats
+                    .map(|d| {
+                        format!(
+                            "{} files +{} -{}",
+                            d.files_changed, d.lines_added, d.lines_removed
+                        )
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                let passed = comparison
+                    .checks
+                    .iter()
+                    .filter(|c| c.status == CheckStatus::Passed && !c.outdated)
+                    .count();
+                println!(
+                    "{}\t{}\t{}\t{}\t{}\tchecks {}/{}",
+                    comparison.attempt.id,
+                    comparison.attempt.executor.as_deref().unwrap_or("-"),
+                    status,
+                    comparison
+                        .agent_duration_seconds
+                        .map(|s| format!("{}s", s))
+                        .unwrap_or_else(|| "-".to_string()),
+                    diff,
+                    passed,
+                    comparison.checks.len()
+                );
+            }
+        }
+        "usage" => {
+            let task_id = args.flag("--task")?;
+            let project_id = args.positional("project-id")?;
+            args.finish()?;
+            let path = match task_id {
+                Some(task_id) => format!("/projects/{}/tasks/{}/usage", project_id, task_id),
+                None => format!("/projects/{}/usage", project_id),
+            };
+            let report: UsageReport = client.get(&path).await?;
+            for (heading, rollups) in [
+                ("Tasks", &report.by_task),
+                ("Days", &report.by_day),
+                ("Models", &report.by_model),
+            ] {
+                println!("{}:", heading);
+                for rollup in rollups {
+                    print_usage_rollup(rollup);
+                }
+            }
+            print_usage_rollup(&report.total);
+            if !report.unpriced_models.is_empty() {
+                println!(
+                    "No pricing configured for: {}",
+                    report.unpriced_models.join(", ")
+                );
+            }
+        }
+        "checks" => {
+            let run = args.switch("--run");
+            let attempt_id = args.flag("--attempt")?;
+            let project_id = args.positional("project-id")?;
+            let task_id = args.positional("task-id")?;
+            args.finish()?;
+
+            let attempt_id = client
+                .resolve_attempt(&project_id, &task_id, attempt_id)
+                .await?;
+            let path = format!(
+                "/projects/{}/tasks/{}/attempts/{}/checks",
+                project_id, task_id, attempt_id
+            );
+            if run {
+                client
+                    .post::<()>(&format!("{}/run", path), serde_json::json!({}))
+                    .await?;
+            }
+            let results: Vec<CheckResult> = client.get(&path).await?;
+            for result in results {
+                let status = match result.status {
+                    CheckStatus::NotRun => "not-run",
+                    CheckStatus::Running => "running",
+                    CheckStatus::Passed => "passed",
+                    CheckStatus::Failed => "failed",
+                };
+                println!(
+                    "{}\t{}{}\t{}\t{}",
+                    result.check_id,
+                    status,
+                    if result.outdated { " (outdated)" } else { "" },
+                    if result.required {
+                        "required"
+                    } else {
+                        "optional"
+                    },
+                    result.name
+                );
+            }
+        }
+        "merge" => {
+            let strategy = args.flag("--strategy")?;
+            let skip_checks = args.switch("--skip-checks");
+            let attempt_id = args.flag("--attempt")?;
+            let project_id = args.positional("project-id")?;
+            let task_id = args.positional("task-id")?;
+            args.finish()?;
+
+            let attempt_id = client
+                .resolve_attempt(&project_id, &task_id, attempt_id)
+                .await?;
+            let result: MergeResult = Client::data(
+                client
+                    .post(
+                        &format!(
+                            "/projects/{}/tasks/{}/attempts/{}/merge",
+                            project_id, task_id, attempt_id
+                        ),
+                        serde_json::json!({ "strategy": strategy, "skip_checks": skip_checks }),
+                    )
+                    .await?,
+            )?;
+            println!("{}", result.merge_commit.unwrap_or_default());
+        }
+        "pr" => {
+            let title = args
+                .flag("--title")?
+                .ok_or_else(|| anyhow!("Missing --title\n\n{}", USAGE))?;
+            let body = args.flag("--body")?;
+            let base_branch = args.flag("--base")?;
+            let skip_checks = args.switch("--skip-checks");
+            let attempt_id = args.flag("--attempt")?;
+            let project_id = args.positional("project-id")?;
+            let task_id = args.positional("task-id")?;
+            args.finish()?;
+
+            let attempt_id = client
+                .resolve_attempt(&project_id, &task_id, attempt_id)
+                .await?;
+            let pr_url: String = Client::data(
+                client
+                    .post(
+                        &format!(
+                            "/projects/{}/tasks/{}/attempts/{}/create-pr",
+                            project_id, task_id, attempt_id
+                        ),
+                        serde_json::json!({
+                            "title": title,
+                            "body": body,
+                            "base_branch": base_branch,
+                            "skip_checks": skip_checks,
+                        }),
+                    )
+                    .await?,
+            )?;
+            println!("{}", pr_url);
+        }
+        "help" | "--help" | "-h" => println!("{}", USAGE),
+        other => bail!("Unknown command '{}'\n\n{}", other, USAGE),
+    }
+
+    Ok(())
+}
+
+/// Print the output of every process of an attempt in order. With `follow`, keep polling
+/// and print new output until nothing is running or queued anymore.
+fn print_usage_rollup(rollup: &UsageRollup) {
+    println!(
+        "{}\t${:.2}\tin {}\tout {}\tcache read {}\tcache write {}{}",
+        rollup.key,
+        rollup.cost_usd,
+        rollup.input_tokens,
+        rollup.output_tokens,
+        rollup.cache_read_input_tokens,
+        rollup.cache_creation_input_tokens,
+        rollup
+            .label
+            .as_ref()
+            .map(|label| format!("\t{}", label))
+            .unwrap_or_default()
+    );
+}
+
+async fn print_logs(
+    client: &Client,
+    project_id: &str,
+    task_id: &str,
+    attempt_id: &str,
+    follow: bool,
+) -> anyhow::Result<()> {
+    // Bytes of stdout/stderr already printed per process
+    let mut printed: HashMap<Uuid, (usize, usize)> = HashMap::new();
+
+    loop {
+        let processes: Vec<ExecutionProcessSummary> = client
+            .get(&format!(
+                "/projects/{}/tasks/{}/attempts/{}/execution-processes",
+                project_id, task_id, attempt_id
+            ))
+            .await?;
+
+        let mut active = false;
+        for summary in processes {
+            if matches!(summary.status, ExecutionProcessStatus::Queued) {
+                active = true;
+                continue;
+            }
+            let finished = !matches!(summary.status, ExecutionProcessStatus::Running);
+            active |= !finished;
+
+            let (stdout_len, stderr_len) = printed.entry(summary.id).or_default();
+            if finished && *stdout_len == usize::MAX {
+                continue; // Fully printed already
+            }
+
+            let process: ExecutionProcess = client
+                .get(&format!(
+                    "/projects/{}/execution-processes/{}",
+                    project_id, summary.id
+                ))
+                .await?;
+
+            let stdout = process.stdout.unwrap_or_default();
+            let stderr = process.stderr.unwrap_or_default();
+            if let Some(new_output) = stdout.get(*stdout_len..) {
+                print!("{}", new_output);
+            }
+            if let Some(new_output) = stderr.get(*stderr_len..) {
+                eprint!("{}", new_output);
+            }
+            *stdout_len = if finished { usize::MAX } else { stdout.len() };
+            *stderr_len = stderr.len();
+        }
+
+        if !follow || !active {
+            return Ok(());
+        }
+        tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args(std::env::args().skip(1).collect());
+    if args.0.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
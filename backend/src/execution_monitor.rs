@@ -1,34 +1,41 @@
+use chrono::{DateTime, Utc};
 use git2::Repository;
 use uuid::Uuid;
 
 use crate::{
     app_state::AppState,
+    executors::wal::{ExecutionWal, FINISHED_WAL_RETENTION},
     models::{
         execution_process::{ExecutionProcess, ExecutionProcessStatus, ExecutionProcessType},
+        project::{CommitMessageContext, CommitMode, CommitSettings, Project},
         task::{Task, TaskStatus},
-        task_attempt::TaskAttempt,
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+        task_dependency::TaskDependency,
+        webhook::WebhookEvent,
+    },
+    services::{
+        AutoFixService, NotificationConfig, NotificationService, ProcessService, WebhookService,
     },
-    services::{NotificationConfig, NotificationService, ProcessService},
     utils::worktree_manager::WorktreeManager,
 };
 
 /// Delegation context structure
 #[derive(Debug, serde::Deserialize)]
-struct DelegationContext {
-    delegate_to: String,
-    operation_params: DelegationOperationParams,
+pub(crate) struct DelegationContext {
+    pub(crate) delegate_to: String,
+    pub(crate) operation_params: DelegationOperationParams,
 }
 
 #[derive(Debug, serde::Deserialize)]
-struct DelegationOperationParams {
-    task_id: uuid::Uuid,
-    project_id: uuid::Uuid,
-    attempt_id: uuid::Uuid,
-    additional: Option<serde_json::Value>,
+pub(crate) struct DelegationOperationParams {
+    pub(crate) task_id: uuid::Uuid,
+    pub(crate) project_id: uuid::Uuid,
+    pub(crate) attempt_id: uuid::Uuid,
+    pub(crate) additional: Option<serde_json::Value>,
 }
 
 /// Parse delegation context from process args JSON
-fn parse_delegation_context(args_json: &str) -> Option<DelegationContext> {
+pub(crate) fn parse_delegation_context(args_json: &str) -> Option<DelegationContext> {
     // Parse the args JSON array
     if let Ok(args_array) = serde_json::from_str::<serde_json::Value>(args_json) {
         if let Some(args) = args_array.as_array() {
@@ -86,6 +93,16 @@ async fn handle_setup_delegation(app_state: &AppState, delegation_context: Deleg
             )
             .await
         }
+        "checks" => {
+            ProcessService::start_checks_direct(
+                &app_state.db_pool,
+                app_state,
+                attempt_id,
+                task_id,
+                project_id,
+            )
+            .await
+        }
         "followup" => {
             let prompt = params
                 .additional
@@ -101,6 +118,7 @@ async fn handle_setup_delegation(app_state: &AppState, delegation_context: Deleg
                 task_id,
                 project_id,
                 prompt,
+                None,
             )
             .await
             .map(|_| ())
@@ -129,17 +147,57 @@ async fn handle_setup_delegation(app_state: &AppState, delegation_context: Deleg
 }
 
 /// Commit any unstaged changes in the worktree after execution completion
+/// Continue the rebase or merge in progress in an attempt's worktree, if the coding agent left
+/// no conflict markers behind
+async fn continue_conflict_resolution(app_state: &AppState, task_attempt: &TaskAttempt) {
+    let Ok(Some(task)) = Task::find_by_id(&app_state.db_pool, task_attempt.task_id).await else {
+        return;
+    };
+
+    match TaskAttempt::resolve_conflicts(
+        &app_state.db_pool,
+        task_attempt.id,
+        task.id,
+        task.project_id,
+        &[],
+    )
+    .await
+    {
+        Ok(None) => tracing::info!(
+            "Completed rebase or merge for attempt {} after conflict resolution",
+            task_attempt.id
+        ),
+        Ok(Some(report)) => tracing::info!(
+            "Attempt {} still has {} conflicting file(s) to resolve",
+            task_attempt.id,
+            report.files.len()
+        ),
+        Err(e) => tracing::error!(
+            "Failed to continue conflict resolution for attempt {}: {}",
+            task_attempt.id,
+            e
+        ),
+    }
+}
+
+/// Commit whatever a coding agent left uncommitted, following the project's commit settings
 async fn commit_execution_changes(
     worktree_path: &str,
-    attempt_id: Uuid,
-    summary: Option<&str>,
+    base_branch: &str,
+    commit_message: String,
+    settings: CommitSettings,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Run git operations in a blocking task since git2 is synchronous
     let worktree_path = worktree_path.to_string();
-    let summary = summary.map(|s| s.to_string());
+    let base_branch = base_branch.to_string();
     tokio::task::spawn_blocking(move || {
         let worktree_repo = Repository::open(&worktree_path)?;
 
+        // Changes made during a rebase or merge belong to its conflict resolution
+        if worktree_repo.state() != git2::RepositoryState::Clean {
+            return Ok(());
+        }
+
         // Check if there are any changes to commit
         let status = worktree_repo.statuses(None)?;
         let has_changes = status.iter().any(|entry| {
@@ -156,8 +214,8 @@ async fn commit_execution_changes(
             return Ok::<(), Box<dyn std::error::Error + Send + Sync>>(());
         }
 
-        // Get the current signature for commits
-        let signature = worktree_repo.signature()?;
+        // Author and committer, overridden by the project settings
+        let signature = settings.signature(&worktree_repo)?;
 
         // Get the current HEAD commit
         let head = worktree_repo.head()?;
@@ -171,20 +229,39 @@ async fn commit_execution_changes(
         let tree_id = worktree_index.write_tree()?;
         let tree = worktree_repo.find_tree(tree_id)?;
 
-        // Create commit for the changes
-        let commit_message = if let Some(ref summary_msg) = summary {
-            summary_msg.clone()
+        // Only amend a commit of the attempt itself, never one from the base branch
+        let amend = settings.mode == CommitMode::Amend
+            && parent_commit.parent_count() == 1
+            && worktree_repo
+                .find_branch(&base_branch, git2::BranchType::Local)
+                .ok()
+                .and_then(|branch| branch.get().target())
+                .is_some_and(|base_oid| {
+                    base_oid != parent_commit.id()
+                        && !worktree_repo
+                            .graph_descendant_of(base_oid, parent_commit.id())
+                            .unwrap_or(true)
+                });
+
+        if amend {
+            parent_commit.amend(
+                Some("HEAD"),
+                Some(&signature),
+                Some(&signature),
+                None,
+                Some(&commit_message),
+                Some(&tree),
+            )?;
         } else {
-            format!("Task attempt {} - Final changes", attempt_id)
-        };
-        worktree_repo.commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            &commit_message,
-            &tree,
-            &[&parent_commit],
-        )?;
+            worktree_repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &commit_message,
+                &tree,
+                &[&parent_commit],
+            )?;
+        }
 
         Ok(())
     })
@@ -514,6 +591,7 @@ async fn cleanup_orphaned_worktree_directory(
 pub async fn execution_monitor(app_state: AppState) {
     let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
     let mut cleanup_interval = tokio::time::interval(tokio::time::Duration::from_secs(1800)); // 30 minutes
+    let mut done_since = Utc::now();
 
     loop {
         tokio::select! {
@@ -588,6 +666,16 @@ pub async fn execution_monitor(app_state: AppState) {
                                 )
                                 .await;
                             }
+                            ExecutionProcessType::CheckScript => {
+                                handle_check_script_completion(
+                                    &app_state,
+                                    task_attempt_id,
+                                    execution_process,
+                                    success,
+                                    exit_code,
+                                )
+                                .await;
+                            }
                             ExecutionProcessType::DevServer => {
                                 handle_dev_server_completion(
                                     &app_state,
@@ -608,6 +696,18 @@ pub async fn execution_monitor(app_state: AppState) {
                     }
                 }
 
+                // Kill coding agents that hang or run past their time limit
+                kill_timed_out_executions(&app_state).await;
+
+                // Start queued coding agents in the slots that just freed up
+                ProcessService::drain_execution_queue(&app_state).await;
+
+                // Start tasks that were waiting on ones finished since the last tick
+                start_dependents_of_done_tasks(&app_state, &mut done_since).await;
+
+                // Drop streaming WALs nobody needs anymore
+                ExecutionWal::purge_expired(FINISHED_WAL_RETENTION);
+
                 // Check for orphaned execution processes AFTER handling completions
                 // Add a small delay to ensure completed processes are properly handled first
                 tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -736,6 +836,103 @@ pub async fn execution_monitor(app_state: AppState) {
     }
 }
 
+/// Kill running coding agents that exceeded their wall-clock timeout or stopped producing
+/// output, marking them killed with the reason
+async fn kill_timed_out_executions(app_state: &AppState) {
+    let timeouts = app_state
+        .get_config()
+        .read()
+        .await
+        .execution_timeouts
+        .clone();
+
+    let running_processes = match ExecutionProcess::find_running(&app_state.db_pool).await {
+        Ok(processes) => processes,
+        Err(e) => {
+            tracing::error!("Failed to query running execution processes: {}", e);
+            return;
+        }
+    };
+
+    let now = chrono::Utc::now();
+    for process in running_processes {
+        if !matches!(process.process_type, ExecutionProcessType::CodingAgent) {
+            continue;
+        }
+
+        let Some(reason) = timeouts
+            .for_executor(process.executor_type.as_deref())
+            .exceeded(process.started_at, process.last_output_at, now)
+        else {
+            continue;
+        };
+
+        tracing::warn!(
+            "Killing execution process {} for task attempt {}: {}",
+            process.id,
+            process.task_attempt_id,
+            reason
+        );
+
+        // Marked first so the next tick doesn't pick it up again while it's being killed
+        if let Err(e) = ExecutionProcess::mark_killed(&app_state.db_pool, process.id, &reason).await
+        {
+            tracing::error!(
+                "Failed to mark execution process {} as killed: {}",
+                process.id,
+                e
+            );
+            continue;
+        }
+
+        // Stopping a process takes seconds, so it doesn't hold up the monitor
+        tokio::spawn(kill_execution(
+            app_state.clone(),
+            process.id,
+            process.task_attempt_id,
+        ));
+    }
+}
+
+/// Stop a coding agent that was marked killed, then finish it like any other coding agent
+async fn kill_execution(app_state: AppState, execution_process_id: Uuid, task_attempt_id: Uuid) {
+    match app_state
+        .stop_running_execution_by_id(execution_process_id)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => return, // Exited on its own in the meantime, or not ours to kill
+        Err(e) => {
+            tracing::error!(
+                "Failed to kill execution process {}: {}",
+                execution_process_id,
+                e
+            );
+            return;
+        }
+    }
+
+    match ExecutionProcess::find_by_id(&app_state.db_pool, execution_process_id).await {
+        Ok(Some(execution_process)) => {
+            handle_coding_agent_completion(
+                &app_state,
+                task_attempt_id,
+                execution_process_id,
+                execution_process,
+                false,
+                None,
+            )
+            .await;
+        }
+        Ok(None) => {}
+        Err(e) => tracing::error!(
+            "Failed to load killed execution process {}: {}",
+            execution_process_id,
+            e
+        ),
+    }
+}
+
 /// Handle setup script completion
 async fn handle_setup_completion(
     app_state: &AppState,
@@ -816,6 +1013,8 @@ async fn handle_setup_completion(
                 }
             }
         }
+
+        AutoFixService::handle_failure(app_state, &execution_process).await;
     }
 }
 
@@ -925,11 +1124,40 @@ async fn handle_coding_agent_completion(
     if let Ok(Some(task_attempt)) =
         TaskAttempt::find_by_id(&app_state.db_pool, task_attempt_id).await
     {
+        // A rebase or merge that stopped on conflicts continues once the agent resolved them
+        let resolving_conflicts = Repository::open(&task_attempt.worktree_path)
+            .map(|repo| repo.state() != git2::RepositoryState::Clean)
+            .unwrap_or(false);
+        if resolving_conflicts {
+            continue_conflict_resolution(app_state, &task_attempt).await;
+        }
+
         // Commit any unstaged changes after execution completion
+        let task = Task::find_by_id(&app_state.db_pool, task_attempt.task_id)
+            .await
+            .ok()
+            .flatten();
+        let settings = match &task {
+            Some(task) => Project::find_by_id(&app_state.db_pool, task.project_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|project| project.commit_settings.0)
+                .unwrap_or_default(),
+            None => CommitSettings::default(),
+        };
+        let commit_message = settings.render_message(&CommitMessageContext {
+            task_title: task.as_ref().map(|t| t.title.as_str()).unwrap_or_default(),
+            task_id: task_attempt.task_id,
+            attempt_id: task_attempt_id,
+            executor: execution_process.executor_type.as_deref(),
+            summary: summary.as_deref(),
+        });
         if let Err(e) = commit_execution_changes(
             &task_attempt.worktree_path,
-            task_attempt_id,
-            summary.as_deref(),
+            &task_attempt.base_branch,
+            commit_message,
+            settings,
         )
         .await
         {
@@ -952,7 +1180,7 @@ async fn handle_coding_agent_completion(
         );
 
         // Get task to access task_id and project_id for status update
-        if let Ok(Some(task)) = Task::find_by_id(&app_state.db_pool, task_attempt.task_id).await {
+        if let Some(task) = task {
             app_state
                 .track_analytics_event(
                     "task_attempt_finished",
@@ -966,6 +1194,26 @@ async fn handle_coding_agent_completion(
                 )
                 .await;
 
+            WebhookService::dispatch(
+                &app_state.db_pool,
+                if success {
+                    WebhookEvent::AgentFinished
+                } else {
+                    WebhookEvent::AgentFailed
+                },
+                task.project_id,
+                task.id,
+                Some(task_attempt_id),
+                serde_json::json!({
+                    "task": task,
+                    "executor": execution_process.executor_type,
+                    "exit_code": exit_code,
+                    "summary": summary,
+                    "reason": execution_process.kill_reason,
+                }),
+            )
+            .await;
+
             // Update task status to InReview
             if let Err(e) = Task::update_status(
                 &app_state.db_pool,
@@ -981,6 +1229,11 @@ async fn handle_coding_agent_completion(
                 );
             }
         }
+
+        // An auto-fix follow-up is checked by running the command that failed again
+        if success {
+            AutoFixService::rerun_fixed_command(app_state, &execution_process).await;
+        }
     } else {
         tracing::error!(
             "Failed to find task attempt {} for coding agent completion",
@@ -989,12 +1242,119 @@ async fn handle_coding_agent_completion(
     }
 }
 
+/// Start the dependents of tasks moved to done after `since`, however they got there: merges,
+/// merged PRs, plan approval, the API or MCP. Advances `since` past the tasks it handled.
+async fn start_dependents_of_done_tasks(app_state: &AppState, since: &mut DateTime<Utc>) {
+    let done = match Task::find_done_since(&app_state.db_pool, *since).await {
+        Ok(done) => done,
+        Err(e) => {
+            tracing::error!("Failed to query tasks moved to done: {}", e);
+            return;
+        }
+    };
+    if let Some((_, done_at)) = done.last() {
+        *since = *done_at;
+    } else {
+        return;
+    }
+
+    // Creating worktrees takes a while, so it doesn't hold up the monitor
+    let app_state = app_state.clone();
+    tokio::spawn(async move {
+        for (task_id, _) in done {
+            start_unblocked_dependents(&app_state, task_id).await;
+        }
+    });
+}
+
+/// Start dependents of `task_id` whose blockers are now all merged or done, if enabled in
+/// the config. Each gets a fresh attempt with the default executor.
+async fn start_unblocked_dependents(app_state: &AppState, task_id: Uuid) {
+    let executor = {
+        let config = app_state.get_config().read().await;
+        if !config.auto_start_unblocked_tasks {
+            return;
+        }
+        config.executor.to_string()
+    };
+
+    let dependents =
+        match TaskDependency::find_unblocked_dependents(&app_state.db_pool, task_id).await {
+            Ok(dependents) => dependents,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to find unblocked dependents of task {}: {}",
+                    task_id,
+                    e
+                );
+                return;
+            }
+        };
+
+    for dependent in dependents {
+        tracing::info!(
+            "Task {} is no longer blocked, starting it with executor {}",
+            dependent.id,
+            executor
+        );
+
+        let attempt_payload = CreateTaskAttempt {
+            executor: Some(executor.clone()),
+            base_branch: None,
+            executor_profile_id: None,
+        };
+        // Started by vibe-kanban itself rather than by a user
+        let attempt =
+            match TaskAttempt::create(&app_state.db_pool, &attempt_payload, dependent.id, None)
+                .await
+            {
+                Ok(attempt) => attempt,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to create attempt for unblocked task {}: {}",
+                        dependent.id,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+        app_state
+            .track_analytics_event(
+                "task_attempt_started",
+                Some(serde_json::json!({
+                    "task_id": dependent.id.to_string(),
+                    "executor_type": executor,
+                    "attempt_id": attempt.id.to_string(),
+                    "auto_started": true,
+                })),
+            )
+            .await;
+
+        if let Err(e) = TaskAttempt::start_execution(
+            &app_state.db_pool,
+            app_state,
+            attempt.id,
+            dependent.id,
+            dependent.project_id,
+        )
+        .await
+        {
+            tracing::error!(
+                "Failed to start execution for unblocked task {}: {}",
+                dependent.id,
+                e
+            );
+        }
+    }
+}
+
 /// Handle dev server completion (future functionality)
 async fn handle_dev_server_completion(
     app_state: &AppState,
     task_attempt_id: Uuid,
     execution_process_id: Uuid,
-    _execution_process: ExecutionProcess,
+    execution_process: ExecutionProcess,
     success: bool,
     exit_code: Option<i64>,
 ) {
@@ -1030,4 +1390,37 @@ async fn handle_dev_server_completion(
             e
         );
     }
+
+    if !success {
+        AutoFixService::handle_failure(app_state, &execution_process).await;
+    }
+}
+
+/// Check results are read back from the process records, so completion only needs logging
+/// and, for failures, the auto-fix loop
+async fn handle_check_script_completion(
+    app_state: &AppState,
+    task_attempt_id: Uuid,
+    execution_process: ExecutionProcess,
+    success: bool,
+    exit_code: Option<i64>,
+) {
+    let name = execution_process
+        .args
+        .as_deref()
+        .and_then(|args| serde_json::from_str::<Vec<String>>(args).ok())
+        .and_then(|args| args.last().cloned())
+        .unwrap_or_default();
+
+    tracing::info!(
+        "Check '{}' for task attempt {} {} (exit code {:?})",
+        name,
+        task_attempt_id,
+        if success { "passed" } else { "failed" },
+        exit_code
+    );
+
+    if !success {
+        AutoFixService::handle_failure(app_state, &execution_process).await;
+    }
 }
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -6,9 +6,22 @@ use tokio::io::{AsyncBufReadExt, BufReader};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use crate::executors::{
-    AmpExecutor, CCRExecutor, CharmOpencodeExecutor, ClaudeExecutor, EchoExecutor, GeminiExecutor,
-    SetupScriptExecutor, SstOpencodeExecutor,
+use crate::{
+    executors::{
+        aider::AIDER_COMMAND,
+        amp::AMP_COMMAND,
+        ccr::CCR_COMMAND,
+        charm_opencode::CHARM_OPENCODE_COMMAND,
+        claude::{CLAUDE_COMMAND, CLAUDE_PLAN_COMMAND},
+        codex::CODEX_COMMAND,
+        custom::{custom_executors, find_custom_executor},
+        gemini::GEMINI_COMMAND,
+        sst_opencode::SST_OPENCODE_COMMAND,
+        AiderExecutor, AmpExecutor, CCRExecutor, CharmOpencodeExecutor, ClaudeExecutor,
+        CodexExecutor, CustomExecutor, EchoExecutor, GeminiExecutor, SetupScriptExecutor,
+        SstOpencodeExecutor,
+    },
+    models::executor_profile::ExecutorProfile,
 };
 
 // Constants for database streaming - fast for near-real-time updates
@@ -221,8 +234,8 @@ impl From<crate::models::task_attempt::TaskAttemptError> for ExecutorError {
             crate::models::task_attempt::TaskAttemptError::GitService(e) => {
                 ExecutorError::GitError(format!("Git service error: {}", e))
             }
-            crate::models::task_attempt::TaskAttemptError::GitHubService(e) => {
-                ExecutorError::GitError(format!("GitHub service error: {}", e))
+            crate::models::task_attempt::TaskAttemptError::GitProvider(e) => {
+                ExecutorError::GitError(format!("Git provider error: {}", e))
             }
         }
     }
@@ -278,9 +291,15 @@ pub trait Executor: Send + Sync {
         })
     }
 
-    // Note: Fast-path streaming is now handled by the Gemini WAL system.
-    // The Gemini executor uses its own push_patch() method to emit patches,
-    // which are automatically served via SSE endpoints with resumable streaming.
+    /// Extract the agent's session id from a line of stdout, to resume the session on
+    /// follow-ups
+    fn parse_session_id(&self, line: &str) -> Option<String> {
+        parse_session_id_from_line(line)
+    }
+
+    // Note: stdout streamed through stream_output_to_db is normalized line by line
+    // into the shared execution WAL (see executors::wal), which the SSE endpoint
+    // serves with resumable streaming. Gemini pushes its own patches to the same WAL.
 
     /// Execute the command and stream output to database in real-time
     async fn execute_streaming(
@@ -332,7 +351,18 @@ pub trait Executor: Send + Sync {
 #[derive(Debug, Clone)]
 pub enum ExecutorType {
     SetupScript(String),
-    DevServer(String),
+    DevServer {
+        script: String,
+        port: u16,
+        env_vars: HashMap<String, String>,
+        ready_pattern: Option<String>,
+    },
+    CheckScript {
+        check_id: Uuid,
+        name: String,
+        script: String,
+        head_commit: Option<String>, // Commit of the attempt's branch the check runs on
+    },
     CodingAgent(ExecutorConfig),
     FollowUpCodingAgent {
         config: ExecutorConfig,
@@ -359,9 +389,12 @@ pub enum ExecutorConfig {
     #[serde(alias = "charmopencode")]
     CharmOpencode,
     SstOpencode,
-    // Future executors can be added here
-    // Shell { command: String },
-    // Docker { image: String, command: String },
+    Aider,
+    Codex,
+    /// A generic CLI executor defined in `executors.json`
+    Custom {
+        id: String,
+    },
 }
 
 // Constants for frontend
@@ -372,6 +405,17 @@ pub struct ExecutorConstants {
     pub executor_labels: Vec<String>,
 }
 
+/// A coding agent attempts can be started with, as listed in the UI
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExecutorOption {
+    pub id: String, // The id attempts and follow-ups select the executor by
+    pub label: String,
+    pub config: ExecutorConfig,
+    pub supports_mcp: bool,
+    pub custom: bool, // Defined in executors.json
+}
+
 impl FromStr for ExecutorConfig {
     type Err = String;
 
@@ -385,28 +429,147 @@ impl FromStr for ExecutorConfig {
             "charm-opencode" => Ok(ExecutorConfig::CharmOpencode),
             "claude-code-router" => Ok(ExecutorConfig::ClaudeCodeRouter),
             "sst-opencode" => Ok(ExecutorConfig::SstOpencode),
+            "aider" => Ok(ExecutorConfig::Aider),
+            "codex" => Ok(ExecutorConfig::Codex),
             "setup-script" => Ok(ExecutorConfig::SetupScript {
                 script: "setup script".to_string(),
             }),
-            _ => Err(format!("Unknown executor type: {}", s)),
+            _ => find_custom_executor(s)
+                .map(|definition| ExecutorConfig::Custom {
+                    id: definition.id.clone(),
+                })
+                .ok_or_else(|| format!("Unknown executor type: {}", s)),
         }
     }
 }
 
 impl ExecutorConfig {
+    /// The coding agents built into vibe-kanban, in the order the UI lists them
+    pub fn built_in() -> Vec<ExecutorConfig> {
+        vec![
+            ExecutorConfig::Echo,
+            ExecutorConfig::Claude,
+            ExecutorConfig::ClaudePlan,
+            ExecutorConfig::Amp,
+            ExecutorConfig::Gemini,
+            ExecutorConfig::CharmOpencode,
+            ExecutorConfig::ClaudeCodeRouter,
+            ExecutorConfig::SstOpencode,
+            ExecutorConfig::Aider,
+            ExecutorConfig::Codex,
+        ]
+    }
+
+    /// Every coding agent, the built-in ones followed by the ones in `executors.json`
+    pub fn options() -> Vec<ExecutorOption> {
+        let custom = custom_executors()
+            .iter()
+            .map(|definition| ExecutorConfig::Custom {
+                id: definition.id.clone(),
+            });
+        Self::built_in()
+            .into_iter()
+            .chain(custom)
+            .map(|config| ExecutorOption {
+                id: config.to_string(),
+                label: config.display_name().to_string(),
+                supports_mcp: config.supports_mcp(),
+                custom: matches!(config, ExecutorConfig::Custom { .. }),
+                config,
+            })
+            .collect()
+    }
+
     pub fn create_executor(&self) -> Box<dyn Executor> {
+        self.create_executor_with_profile(None, &HashMap::new())
+    }
+
+    /// Create an executor, applying the command line and environment of a
+    /// project executor profile on top of the built-in defaults. The profile's
+    /// variables take precedence over the project's `project_env`.
+    pub fn create_executor_with_profile(
+        &self,
+        profile: Option<&ExecutorProfile>,
+        project_env: &HashMap<String, String>,
+    ) -> Box<dyn Executor> {
+        let env_vars = Self::resolve_env_vars(profile, project_env);
+        let command = self.resolve_command(profile);
+
         match self {
-            ExecutorConfig::Echo => Box::new(EchoExecutor),
-            ExecutorConfig::Claude => Box::new(ClaudeExecutor::new()),
-            ExecutorConfig::ClaudePlan => Box::new(ClaudeExecutor::new_plan_mode()),
-            ExecutorConfig::Amp => Box::new(AmpExecutor),
-            ExecutorConfig::Gemini => Box::new(GeminiExecutor),
-            ExecutorConfig::ClaudeCodeRouter => Box::new(CCRExecutor::new()),
-            ExecutorConfig::CharmOpencode => Box::new(CharmOpencodeExecutor),
-            ExecutorConfig::SstOpencode => Box::new(SstOpencodeExecutor::new()),
+            ExecutorConfig::Echo => Box::new(EchoExecutor::default().with_env_vars(env_vars)),
+            ExecutorConfig::Claude => Box::new(
+                ClaudeExecutor::with_command("Claude".to_string(), command).with_env_vars(env_vars),
+            ),
+            ExecutorConfig::ClaudePlan => {
+                Box::new(ClaudeExecutor::plan_mode_with_command(&command).with_env_vars(env_vars))
+            }
+            ExecutorConfig::Amp => {
+                Box::new(AmpExecutor::with_command(command).with_env_vars(env_vars))
+            }
+            ExecutorConfig::Gemini => {
+                Box::new(GeminiExecutor::with_command(command).with_env_vars(env_vars))
+            }
+            ExecutorConfig::ClaudeCodeRouter => {
+                Box::new(CCRExecutor::with_command(command).with_env_vars(env_vars))
+            }
+            ExecutorConfig::CharmOpencode => {
+                Box::new(CharmOpencodeExecutor::with_command(command).with_env_vars(env_vars))
+            }
+            ExecutorConfig::SstOpencode => {
+                Box::new(SstOpencodeExecutor::with_command(command).with_env_vars(env_vars))
+            }
+            ExecutorConfig::Aider => {
+                Box::new(AiderExecutor::with_command(command).with_env_vars(env_vars))
+            }
+            ExecutorConfig::Codex => {
+                Box::new(CodexExecutor::with_command(command).with_env_vars(env_vars))
+            }
             ExecutorConfig::SetupScript { script } => {
-                Box::new(SetupScriptExecutor::new(script.clone()))
+                Box::new(SetupScriptExecutor::new(script.clone()).with_env_vars(env_vars))
+            }
+            ExecutorConfig::Custom { id } => {
+                Box::new(CustomExecutor::with_command(id.clone(), command).with_env_vars(env_vars))
+            }
+        }
+    }
+
+    /// Environment for an executor: the project's variables, overridden by the profile's
+    pub fn resolve_env_vars(
+        profile: Option<&ExecutorProfile>,
+        project_env: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        let mut env_vars = project_env.clone();
+        if let Some(profile) = profile {
+            env_vars.extend(profile.env_vars());
+        }
+        env_vars
+    }
+
+    /// Get the command line to launch this executor's CLI with, after applying `profile`
+    pub fn resolve_command(&self, profile: Option<&ExecutorProfile>) -> String {
+        match (self.default_command(), profile) {
+            (Some(default), Some(profile)) => profile.resolve_command(default),
+            (Some(default), None) => default.to_string(),
+            (None, _) => String::new(),
+        }
+    }
+
+    /// Get the built-in command line used to launch this executor's CLI
+    pub fn default_command(&self) -> Option<&'static str> {
+        match self {
+            ExecutorConfig::Claude => Some(CLAUDE_COMMAND),
+            ExecutorConfig::ClaudePlan => Some(CLAUDE_PLAN_COMMAND),
+            ExecutorConfig::Amp => Some(AMP_COMMAND),
+            ExecutorConfig::Gemini => Some(GEMINI_COMMAND),
+            ExecutorConfig::ClaudeCodeRouter => Some(CCR_COMMAND),
+            ExecutorConfig::CharmOpencode => Some(CHARM_OPENCODE_COMMAND),
+            ExecutorConfig::SstOpencode => Some(SST_OPENCODE_COMMAND),
+            ExecutorConfig::Aider => Some(AIDER_COMMAND),
+            ExecutorConfig::Codex => Some(CODEX_COMMAND),
+            ExecutorConfig::Custom { id } => {
+                find_custom_executor(id).map(|definition| definition.command.as_str())
             }
+            ExecutorConfig::Echo | ExecutorConfig::SetupScript { .. } => None,
         }
     }
 
@@ -430,11 +593,16 @@ impl ExecutorConfig {
             ExecutorConfig::SstOpencode => {
                 xdg::BaseDirectories::with_prefix("opencode").get_config_file("opencode.json")
             }
+            ExecutorConfig::Aider => None,
+            ExecutorConfig::Codex => {
+                dirs::home_dir().map(|home| home.join(".codex").join("config.toml"))
+            }
             ExecutorConfig::SetupScript { .. } => None,
+            ExecutorConfig::Custom { id } => find_custom_executor(id)?.config_path(),
         }
     }
 
-    /// Get the JSON attribute path for MCP servers in the config file
+    /// Get the attribute path for MCP servers in the config file, a JSON or TOML one
     pub fn mcp_attribute_path(&self) -> Option<Vec<&'static str>> {
         match self {
             ExecutorConfig::Echo => None, // Echo doesn't support MCP
@@ -445,16 +613,29 @@ impl ExecutorConfig {
             ExecutorConfig::Amp => Some(vec!["amp", "mcpServers"]), // Nested path for Amp
             ExecutorConfig::Gemini => Some(vec!["mcpServers"]),
             ExecutorConfig::ClaudeCodeRouter => Some(vec!["mcpServers"]),
+            ExecutorConfig::Aider => None, // Aider doesn't support MCP
+            ExecutorConfig::Codex => Some(vec!["mcp_servers"]), // A table of ~/.codex/config.toml
             ExecutorConfig::SetupScript { .. } => None, // Setup scripts don't support MCP
+            ExecutorConfig::Custom { id } => {
+                // Only when the definition says where its servers are configured
+                let definition = find_custom_executor(id)?;
+                if definition.config_path.is_none() || definition.mcp_attribute_path.is_empty() {
+                    return None;
+                }
+                Some(
+                    definition
+                        .mcp_attribute_path
+                        .iter()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            }
         }
     }
 
     /// Check if this executor supports MCP configuration
     pub fn supports_mcp(&self) -> bool {
-        !matches!(
-            self,
-            ExecutorConfig::Echo | ExecutorConfig::SetupScript { .. }
-        )
+        self.mcp_attribute_path().is_some()
     }
 
     /// Get the display name for this executor
@@ -468,7 +649,12 @@ impl ExecutorConfig {
             ExecutorConfig::Amp => "Amp",
             ExecutorConfig::Gemini => "Gemini",
             ExecutorConfig::ClaudeCodeRouter => "Claude Code Router",
+            ExecutorConfig::Aider => "Aider",
+            ExecutorConfig::Codex => "Codex",
             ExecutorConfig::SetupScript { .. } => "Setup Script",
+            ExecutorConfig::Custom { id } => find_custom_executor(id)
+                .map(|definition| definition.label.as_str())
+                .unwrap_or("Custom executor"),
         }
     }
 }
@@ -484,7 +670,10 @@ impl std::fmt::Display for ExecutorConfig {
             ExecutorConfig::SstOpencode => "sst-opencode",
             ExecutorConfig::CharmOpencode => "charm-opencode",
             ExecutorConfig::ClaudeCodeRouter => "claude-code-router",
+            ExecutorConfig::Aider => "aider",
+            ExecutorConfig::Codex => "codex",
             ExecutorConfig::SetupScript { .. } => "setup-script",
+            ExecutorConfig::Custom { id } => id.as_str(),
         };
         write!(f, "{}", s)
     }
@@ -506,28 +695,42 @@ pub async fn stream_output_to_db(
 }
 
 /// Stream stdout from a child process to the database (immediate updates)
+/// and feed normalized entries into the execution WAL, with project secrets redacted
 async fn stream_stdout_to_db(
     output: impl tokio::io::AsyncRead + Unpin,
     pool: sqlx::SqlitePool,
     attempt_id: Uuid,
     execution_process_id: Uuid,
 ) {
-    use crate::models::{execution_process::ExecutionProcess, executor_session::ExecutorSession};
+    use crate::{
+        executors::{usage::UsageTracker, wal::NormalizedLogFeed},
+        models::{execution_process::ExecutionProcess, executor_session::ExecutorSession},
+        services::SecretRedactor,
+    };
 
     let mut reader = BufReader::new(output);
     let mut line = String::new();
     let mut accumulated_output = String::new();
     let mut update_counter = 0;
     let mut session_id_parsed = false;
+    let mut log_feed = NormalizedLogFeed::for_process(&pool, execution_process_id).await;
+    let mut usage = UsageTracker::new();
+    let redactor = SecretRedactor::for_process(&pool, execution_process_id).await;
 
     loop {
         line.clear();
         match reader.read_line(&mut line).await {
             Ok(0) => break, // EOF
             Ok(_) => {
-                // Parse session ID from the first JSONL line
+                let line = redactor.redact(&line);
+
+                // Parse session ID from the first JSONL line that has one
                 if !session_id_parsed {
-                    if let Some(external_session_id) = parse_session_id_from_line(&line) {
+                    let external_session_id = match log_feed.as_ref() {
+                        Some(feed) => feed.parse_session_id(&line),
+                        None => parse_session_id_from_line(&line),
+                    };
+                    if let Some(external_session_id) = external_session_id {
                         if let Err(e) = ExecutorSession::update_session_id(
                             &pool,
                             execution_process_id,
@@ -551,6 +754,11 @@ async fn stream_stdout_to_db(
                     }
                 }
 
+                if let Some(feed) = log_feed.as_mut() {
+                    feed.push(&line);
+                }
+                usage.push_line(&line);
+
                 accumulated_output.push_str(&line);
                 update_counter += 1;
 
@@ -574,6 +782,7 @@ async fn stream_stdout_to_db(
                     }
                     accumulated_output.clear();
                     update_counter = 0;
+                    record_usage(&pool, execution_process_id, &mut usage).await;
                 }
             }
             Err(e) => {
@@ -596,9 +805,39 @@ async fn stream_stdout_to_db(
             tracing::error!("Failed to flush stdout for attempt {}: {}", attempt_id, e);
         }
     }
+    record_usage(&pool, execution_process_id, &mut usage).await;
+
+    if let Some(feed) = log_feed {
+        feed.finish();
+    }
 }
 
-/// Stream stderr from a child process to the database (buffered with timeout)
+/// Store the token usage seen so far, if it changed since it was last stored
+async fn record_usage(
+    pool: &sqlx::SqlitePool,
+    execution_process_id: Uuid,
+    usage: &mut crate::executors::usage::UsageTracker,
+) {
+    if !usage.take_changed() {
+        return;
+    }
+    if let Err(e) = crate::models::execution_usage::ExecutionUsage::record(
+        pool,
+        execution_process_id,
+        &usage.totals(),
+    )
+    .await
+    {
+        tracing::error!(
+            "Failed to record token usage for execution process {}: {}",
+            execution_process_id,
+            e
+        );
+    }
+}
+
+/// Stream stderr from a child process to the database (buffered with timeout),
+/// with project secrets redacted
 async fn stream_stderr_to_db(
     output: impl tokio::io::AsyncRead + Unpin,
     pool: sqlx::SqlitePool,
@@ -607,6 +846,9 @@ async fn stream_stderr_to_db(
 ) {
     use tokio::time::{timeout, Duration};
 
+    use crate::services::SecretRedactor;
+
+    let redactor = SecretRedactor::for_process(&pool, execution_process_id).await;
     let mut reader = BufReader::new(output);
     let mut line = String::new();
     let mut accumulated_output = String::new();
@@ -626,7 +868,7 @@ async fn stream_stderr_to_db(
             }
             Ok(Ok(_)) => {
                 // Successfully read a line - just accumulate it
-                accumulated_output.push_str(&line);
+                accumulated_output.push_str(&redactor.redact(&line));
             }
             Ok(Err(e)) => {
                 tracing::error!("Error reading stderr for attempt {}: {}", attempt_id, e);
@@ -668,16 +910,11 @@ async fn flush_stderr_chunk(
         return;
     }
 
-    // Add a delimiter to separate chunks in the database
-    let chunk_with_delimiter = format!("{}\n---STDERR_CHUNK_BOUNDARY---\n", trimmed);
+    // Each flush is stored as its own log chunk
+    let chunk = format!("{}\n", trimmed);
 
-    if let Err(e) = ExecutionProcess::append_output(
-        pool,
-        execution_process_id,
-        None,
-        Some(&chunk_with_delimiter),
-    )
-    .await
+    if let Err(e) =
+        ExecutionProcess::append_output(pool, execution_process_id, None, Some(&chunk)).await
     {
         tracing::error!(
             "Failed to flush stderr chunk for attempt {}: {}",
@@ -878,7 +1115,7 @@ mod tests {
 
     #[test]
     fn test_amp_log_normalization() {
-        let amp_executor = AmpExecutor;
+        let amp_executor = AmpExecutor::new();
         let amp_logs = r#"{"type":"initial","threadID":"T-f8f7fec0-b330-47ab-b63a-b72c42f1ef6a"}
 {"type":"messages","messages":[[0,{"role":"user","content":[{"type":"text","text":"Task title: Create and start should open task\nTask description: When I press 'create & start' on task creation dialog it should then open the task in the sidebar"}],"meta":{"sentAt":1751544747623}}]],"toolResults":[]}
 {"type":"messages","messages":[[1,{"role":"assistant","content":[{"type":"thinking","thinking":"The user wants to implement a feature where pressing \"create & start\" on the task creation dialog should open the task in the sidebar."},{"type":"text","text":"I'll help you implement the \"create & start\" functionality. Let me explore the codebase to understand the current task creation and sidebar structure."},{"type":"tool_use","id":"toolu_01FQqskzGAhZaZu8H6qSs5pV","name":"todo_write","input":{"todos":[{"id":"1","content":"Explore task creation dialog component","status":"todo","priority":"high"}]}}],"state":{"type":"complete","stopReason":"tool_use"}}]],"toolResults":[]}"#;
@@ -0,0 +1,517 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use async_trait::async_trait;
+use command_group::AsyncGroupChild;
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::{
+    executor::{
+        ActionType, Executor, ExecutorError, NormalizedConversation, NormalizedEntry,
+        NormalizedEntryType, SpawnContext,
+    },
+    models::task::Task,
+    utils::shell::{get_shell_command, shell_quote},
+};
+
+/// Default Aider command line, answering yes to every question and printing plain text.
+/// vibe-kanban commits the attempt's changes itself, so Aider doesn't.
+pub const AIDER_COMMAND: &str = "aider --yes-always --no-pretty --no-stream --no-fancy-input --no-check-update --no-show-model-warnings --no-auto-commits";
+
+/// Printed before Aider runs, as Aider has no session ids of its own to resume
+const SESSION_MARKER: &str = "Aider session: ";
+
+/// Lines Aider prints about its setup and usage rather than as part of the conversation
+const SYSTEM_PREFIXES: &[&str] = &[
+    "Aider v",
+    "Main model:",
+    "Weak model:",
+    "Editor model:",
+    "Git repo:",
+    "Repo-map:",
+    "Restored previous conversation history",
+    "Tokens:",
+];
+
+/// An executor that uses Aider to process tasks
+pub struct AiderExecutor {
+    command: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl Default for AiderExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AiderExecutor {
+    /// Create a new AiderExecutor with default settings
+    pub fn new() -> Self {
+        Self::with_command(AIDER_COMMAND.to_string())
+    }
+
+    /// Create a new AiderExecutor around a custom Aider command
+    pub fn with_command(command: String) -> Self {
+        Self {
+            command,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+}
+
+/// An executor that continues an Aider chat from its history
+pub struct AiderFollowupExecutor {
+    pub session_id: String,
+    pub prompt: String,
+    executor: AiderExecutor,
+}
+
+impl AiderFollowupExecutor {
+    /// Create a new AiderFollowupExecutor around a custom Aider command
+    pub fn with_command(session_id: String, prompt: String, command_base: String) -> Self {
+        Self {
+            session_id,
+            prompt,
+            executor: AiderExecutor::with_command(command_base),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.executor.env_vars = env_vars;
+        self
+    }
+}
+
+/// Directory of the worktree holding vibe-kanban's own files, ignored by git
+const STATE_DIR: &str = ".vibe-kanban";
+
+/// Chat and input history files of a session. They live in the worktree, the only directory
+/// a sandboxed execution can write to, so follow-ups find them.
+fn history_files(worktree_path: &Path, session_id: &str) -> (PathBuf, PathBuf) {
+    let dir = worktree_path.join(STATE_DIR).join("aider");
+    (
+        dir.join(format!("{}.chat.md", session_id)),
+        dir.join(format!("{}.input", session_id)),
+    )
+}
+
+/// Create the history directory, ignoring it so the history isn't committed with the attempt
+fn create_history_dir(worktree_path: &Path) -> std::io::Result<()> {
+    let state_dir = worktree_path.join(STATE_DIR);
+    std::fs::create_dir_all(state_dir.join("aider"))?;
+    let gitignore = state_dir.join(".gitignore");
+    if !gitignore.exists() {
+        std::fs::write(gitignore, "*\n")?;
+    }
+    Ok(())
+}
+
+impl AiderExecutor {
+    /// The command line running Aider on `prompt` in session `session_id`, restoring the
+    /// session's chat history when following up
+    fn build_command(
+        &self,
+        prompt: &str,
+        session_id: &str,
+        restore: bool,
+        worktree_path: &str,
+    ) -> String {
+        let (chat_history, input_history) = history_files(Path::new(worktree_path), session_id);
+        format!(
+            "echo {}{} && {} --chat-history-file {} --input-history-file {}{} --message {}",
+            SESSION_MARKER,
+            session_id,
+            self.command,
+            shell_quote(&chat_history.to_string_lossy()),
+            shell_quote(&input_history.to_string_lossy()),
+            if restore {
+                " --restore-chat-history"
+            } else {
+                ""
+            },
+            shell_quote(prompt)
+        )
+    }
+
+    fn command(
+        &self,
+        prompt: &str,
+        session_id: &str,
+        restore: bool,
+        worktree_path: &str,
+    ) -> Command {
+        // Use shell command for cross-platform compatibility
+        let (shell_cmd, shell_arg) = get_shell_command();
+        let mut command = Command::new(shell_cmd);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(worktree_path)
+            .arg(shell_arg)
+            .arg(self.build_command(prompt, session_id, restore, worktree_path))
+            .envs(&self.env_vars);
+        command
+    }
+
+    async fn spawn_with_prompt(
+        &self,
+        prompt: &str,
+        session_id: &str,
+        restore: bool,
+        worktree_path: &str,
+        context: impl Fn(SpawnContext) -> SpawnContext,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        create_history_dir(Path::new(worktree_path)).map_err(|e| {
+            ExecutorError::ContextCollectionFailed(format!(
+                "Failed to create the Aider history directory: {}",
+                e
+            ))
+        })?;
+
+        let mut command = self.command(prompt, session_id, restore, worktree_path);
+
+        let child =
+            crate::executors::sandbox::group_spawn(&mut command, false) // Create new process group so we can kill entire tree
+                .map_err(|e| {
+                    context(SpawnContext::from_command(&command, "Aider")).spawn_error(e)
+                })?;
+
+        Ok(child)
+    }
+}
+
+#[async_trait]
+impl Executor for AiderExecutor {
+    async fn spawn(
+        &self,
+        pool: &sqlx::SqlitePool,
+        task_id: Uuid,
+        worktree_path: &str,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        // Get the task to fetch its description
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(ExecutorError::TaskNotFound)?;
+
+        let prompt = if let Some(task_description) = task.description {
+            format!(
+                r#"project_id: {}
+
+Task title: {}
+Task description: {}"#,
+                task.project_id, task.title, task_description
+            )
+        } else {
+            format!(
+                r#"project_id: {}
+
+Task title: {}"#,
+                task.project_id, task.title
+            )
+        };
+
+        let session_id = Uuid::new_v4().to_string();
+        self.spawn_with_prompt(&prompt, &session_id, false, worktree_path, |context| {
+            context
+                .with_task(task_id, Some(task.title.clone()))
+                .with_context("Aider CLI execution for new task")
+        })
+        .await
+    }
+
+    fn normalize_logs(
+        &self,
+        logs: &str,
+        _worktree_path: &str,
+    ) -> Result<NormalizedConversation, String> {
+        let mut entries = Vec::new();
+        let mut session_id = None;
+        // Lines of the assistant message being read, which ends at the next other entry
+        let mut message: Vec<&str> = Vec::new();
+        // Whether Aider was just allowed to run a shell command, which it prints next
+        let mut running_command = false;
+
+        fn flush(message: &mut Vec<&str>, entries: &mut Vec<NormalizedEntry>) {
+            let content = message.join("\n").trim().to_string();
+            message.clear();
+            if !content.is_empty() {
+                entries.push(NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::AssistantMessage,
+                    content,
+                    metadata: None,
+                });
+            }
+        }
+
+        for line in logs.lines() {
+            let trimmed = line.trim();
+
+            if let Some(id) = trimmed.strip_prefix(SESSION_MARKER) {
+                session_id.get_or_insert_with(|| id.trim().to_string());
+                continue;
+            }
+            // Questions answered by --yes-always
+            if trimmed.contains("(Y)es/(N)o") {
+                running_command = trimmed.starts_with("Run shell command");
+                continue;
+            }
+
+            let entry = if SYSTEM_PREFIXES.iter().any(|p| trimmed.starts_with(p))
+                || (trimmed.starts_with("Added ") && trimmed.ends_with(" to the chat."))
+            {
+                Some((NormalizedEntryType::SystemMessage, trimmed.to_string()))
+            } else if let Some(path) = trimmed.strip_prefix("Applied edit to ") {
+                Some((
+                    NormalizedEntryType::ToolUse {
+                        tool_name: "edit".to_string(),
+                        action_type: ActionType::FileWrite {
+                            path: path.to_string(),
+                        },
+                    },
+                    format!("`{}`", path),
+                ))
+            } else if let Some(shell_command) =
+                trimmed.strip_prefix("Running ").filter(|_| running_command)
+            {
+                Some((
+                    NormalizedEntryType::ToolUse {
+                        tool_name: "shell".to_string(),
+                        action_type: ActionType::CommandRun {
+                            command: shell_command.to_string(),
+                        },
+                    },
+                    format!("`{}`", shell_command),
+                ))
+            } else if trimmed.starts_with("Error:") || trimmed.starts_with("litellm.") {
+                Some((NormalizedEntryType::ErrorMessage, trimmed.to_string()))
+            } else {
+                None
+            };
+
+            if !trimmed.is_empty() {
+                running_command = false;
+            }
+            match entry {
+                Some((entry_type, content)) => {
+                    flush(&mut message, &mut entries);
+                    entries.push(NormalizedEntry {
+                        timestamp: None,
+                        entry_type,
+                        content,
+                        metadata: None,
+                    });
+                }
+                None => message.push(line),
+            }
+        }
+        flush(&mut message, &mut entries);
+
+        Ok(NormalizedConversation {
+            entries,
+            session_id,
+            executor_type: "aider".to_string(),
+            prompt: None,
+            summary: None,
+        })
+    }
+
+    fn parse_session_id(&self, line: &str) -> Option<String> {
+        line.trim()
+            .strip_prefix(SESSION_MARKER)
+            .map(|id| id.trim().to_string())
+    }
+}
+
+#[async_trait]
+impl Executor for AiderFollowupExecutor {
+    async fn spawn(
+        &self,
+        _pool: &sqlx::SqlitePool,
+        _task_id: Uuid,
+        worktree_path: &str,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        self.executor
+            .spawn_with_prompt(
+                &self.prompt,
+                &self.session_id,
+                true,
+                worktree_path,
+                |context| {
+                    context.with_context(format!(
+                        "Aider CLI followup execution for session {}",
+                        self.session_id
+                    ))
+                },
+            )
+            .await
+    }
+
+    fn normalize_logs(
+        &self,
+        logs: &str,
+        worktree_path: &str,
+    ) -> Result<NormalizedConversation, String> {
+        self.executor.normalize_logs(logs, worktree_path)
+    }
+
+    fn parse_session_id(&self, line: &str) -> Option<String> {
+        self.executor.parse_session_id(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        executors::sandbox::{ContainerSandbox, SandboxContext},
+        models::project::{ContainerRuntime, SandboxSettings},
+    };
+
+    #[test]
+    fn test_aider_log_normalization() {
+        let executor = AiderExecutor::new();
+        let logs = "Aider session: 6f1c2d7e-3b0a-4c1e-9a55-0d2f1b7c8e90
+Aider v0.86.1
+Main model: anthropic/claude-sonnet-4-20250514 with diff edit format, infinite output
+Git repo: .git with 42 files
+Repo-map: using 4096 tokens, auto refresh
+Added src/parser.rs to the chat.
+
+I'll fix the off-by-one in the parser.
+
+src/parser.rs
+<<<<<<< SEARCH
+    for i in 0..=len {
+=======
+    for i in 0..len {
+>>>>>>> REPLACE
+
+Tokens: 2.5k sent, 120 received. Cost: $0.0093 message, $0.0093 session.
+Applied edit to src/parser.rs
+
+cargo test
+Run shell command? (Y)es/(N)o/(D)on't ask again [Yes]: y
+Running cargo test
+Error: test suite exited with code 101";
+
+        let result = executor.normalize_logs(logs, "/tmp/test-worktree").unwrap();
+
+        assert_eq!(result.executor_type, "aider");
+        assert_eq!(
+            result.session_id,
+            Some("6f1c2d7e-3b0a-4c1e-9a55-0d2f1b7c8e90".to_string())
+        );
+
+        let system_messages = result
+            .entries
+            .iter()
+            .filter(|e| matches!(e.entry_type, NormalizedEntryType::SystemMessage))
+            .count();
+        assert_eq!(system_messages, 6);
+
+        let assistant_messages: Vec<_> = result
+            .entries
+            .iter()
+            .filter(|e| matches!(e.entry_type, NormalizedEntryType::AssistantMessage))
+            .collect();
+        assert_eq!(assistant_messages.len(), 2);
+        assert!(assistant_messages[0]
+            .content
+            .starts_with("I'll fix the off-by-one in the parser."));
+        assert!(assistant_messages[0].content.ends_with(">>>>>>> REPLACE"));
+        assert_eq!(assistant_messages[1].content, "cargo test");
+
+        assert!(result.entries.iter().any(|e| matches!(
+            &e.entry_type,
+            NormalizedEntryType::ToolUse { action_type: ActionType::FileWrite { path }, .. }
+                if path == "src/parser.rs"
+        )));
+        assert!(result.entries.iter().any(|e| matches!(
+            &e.entry_type,
+            NormalizedEntryType::ToolUse { action_type: ActionType::CommandRun { command }, .. }
+                if command == "cargo test"
+        )));
+        assert!(matches!(
+            result.entries.last().unwrap().entry_type,
+            NormalizedEntryType::ErrorMessage
+        ));
+    }
+
+    #[test]
+    fn test_build_command_restores_history_on_followups() {
+        let executor = AiderExecutor::with_command("aider --yes-always".to_string());
+        let command = executor.build_command("Don't stop", "abc", false, "/tmp/vk-1");
+        assert!(command.starts_with("echo Aider session: abc && aider --yes-always"));
+        assert!(!command.contains("--restore-chat-history"));
+        assert!(command.ends_with(&format!("--message {}", shell_quote("Don't stop"))));
+
+        let followup = executor.build_command("Continue", "abc", true, "/tmp/vk-1");
+        assert!(followup.contains("/tmp/vk-1/.vibe-kanban/aider/abc.chat.md"));
+        assert!(followup.contains(" --restore-chat-history --message "));
+    }
+
+    #[test]
+    fn test_sandboxed_history_is_in_the_mounted_worktree() {
+        let sandbox = ContainerSandbox {
+            runtime: ContainerRuntime::Docker,
+            settings: SandboxSettings {
+                enabled: true,
+                image: "python:3.12".to_string(),
+                ..Default::default()
+            },
+        };
+        let context = SandboxContext {
+            sandbox,
+            execution_process_id: Uuid::nil(),
+            worktree_path: "/tmp/worktrees/vk-1".to_string(),
+            git_dir: None,
+            published_port: None,
+        };
+        let executor = AiderExecutor::with_command("aider".to_string());
+        let command = executor.command("Continue", "abc", true, "/tmp/worktrees/vk-1");
+        let args: Vec<String> = context
+            .container_args(command.as_std(), false, None)
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect();
+
+        assert!(args.windows(2).any(|pair| pair[0] == "--mount"
+            && pair[1] == "type=bind,source=/tmp/worktrees/vk-1,target=/tmp/worktrees/vk-1"));
+        let script = args.last().unwrap();
+        for history in ["abc.chat.md", "abc.input"] {
+            assert!(script.contains(&shell_quote(&format!(
+                "/tmp/worktrees/vk-1/.vibe-kanban/aider/{}",
+                history
+            ))));
+        }
+    }
+
+    #[test]
+    fn test_history_dir_is_ignored_by_git() {
+        let worktree = tempfile::TempDir::new().unwrap();
+        create_history_dir(worktree.path()).unwrap();
+        let (chat_history, _) = history_files(worktree.path(), "abc");
+        std::fs::write(&chat_history, "# aider chat started").unwrap();
+
+        let repo = git2::Repository::init(worktree.path()).unwrap();
+        assert!(repo
+            .is_path_ignored(".vibe-kanban/aider/abc.chat.md")
+            .unwrap());
+        assert!(!repo.is_path_ignored("main.py").unwrap());
+    }
+}
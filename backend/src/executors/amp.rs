@@ -1,7 +1,7 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -14,13 +14,66 @@ use crate::{
     utils::shell::get_shell_command,
 };
 
+/// Default Amp command line
+pub const AMP_COMMAND: &str = "npx @sourcegraph/amp@0.0.1752148945-gd8844f";
+
 /// An executor that uses Amp to process tasks
-pub struct AmpExecutor;
+pub struct AmpExecutor {
+    command: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl Default for AmpExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AmpExecutor {
+    /// Create a new AmpExecutor with default settings
+    pub fn new() -> Self {
+        Self::with_command(AMP_COMMAND.to_string())
+    }
+
+    /// Create a new AmpExecutor around a custom Amp command
+    pub fn with_command(command: String) -> Self {
+        Self {
+            command,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+}
 
 /// An executor that continues an Amp thread
 pub struct AmpFollowupExecutor {
     pub thread_id: String,
     pub prompt: String,
+    command_base: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl AmpFollowupExecutor {
+    /// Create a new AmpFollowupExecutor around a custom Amp command
+    pub fn with_command(thread_id: String, prompt: String, command_base: String) -> Self {
+        Self {
+            thread_id,
+            prompt,
+            command_base,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
@@ -229,7 +282,7 @@ Task title: {}"#,
         // Use shell command for cross-platform compatibility
         let (shell_cmd, shell_arg) = get_shell_command();
         // --format=jsonl is deprecated in latest versions of Amp CLI
-        let amp_command = "npx @sourcegraph/amp@0.0.1752148945-gd8844f --format=jsonl";
+        let amp_command = format!("{} --format=jsonl", self.command);
 
         let mut command = Command::new(shell_cmd);
         command
@@ -239,10 +292,10 @@ Task title: {}"#,
             .stderr(Stdio::piped())
             .current_dir(worktree_path)
             .arg(shell_arg)
-            .arg(amp_command);
+            .arg(&amp_command)
+            .envs(&self.env_vars);
 
-        let mut child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let mut child = crate::executors::sandbox::group_spawn(&mut command, true) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, "Amp")
                     .with_task(task_id, Some(task.title.clone()))
@@ -605,8 +658,8 @@ impl Executor for AmpFollowupExecutor {
         // Use shell command for cross-platform compatibility
         let (shell_cmd, shell_arg) = get_shell_command();
         let amp_command = format!(
-            "npx @sourcegraph/amp@0.0.1752148945-gd8844f threads continue {} --format=jsonl",
-            self.thread_id
+            "{} threads continue {} --format=jsonl",
+            self.command_base, self.thread_id
         );
 
         let mut command = Command::new(shell_cmd);
@@ -617,10 +670,10 @@ impl Executor for AmpFollowupExecutor {
             .stderr(Stdio::piped())
             .current_dir(worktree_path)
             .arg(shell_arg)
-            .arg(&amp_command);
+            .arg(&amp_command)
+            .envs(&self.env_vars);
 
-        let mut child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let mut child = crate::executors::sandbox::group_spawn(&mut command, true) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, "Amp")
                     .with_context(format!(
@@ -645,7 +698,7 @@ impl Executor for AmpFollowupExecutor {
         worktree_path: &str,
     ) -> Result<NormalizedConversation, String> {
         // Reuse the same logic as the main AmpExecutor
-        let main_executor = AmpExecutor;
+        let main_executor = AmpExecutor::new();
         main_executor.normalize_logs(logs, worktree_path)
     }
 }
@@ -657,7 +710,7 @@ mod tests {
     #[test]
     fn test_filter_streaming_messages() {
         // Test logs that simulate the actual normalize_logs behavior
-        let amp_executor = AmpExecutor;
+        let amp_executor = AmpExecutor::new();
         let logs = r#"{"type":"messages","messages":[[7,{"role":"assistant","content":[{"type":"text","text":"Created all three files: test1.txt, test2.txt, and test3.txt"}],"state":{"type":"streaming"}}]],"toolResults":[]}
 {"type":"messages","messages":[[7,{"role":"assistant","content":[{"type":"text","text":"Created all three files: test1.txt, test2.txt, and test3.txt, each with a line of text."}],"state":{"type":"streaming"}}]],"toolResults":[]}
 {"type":"messages","messages":[[7,{"role":"assistant","content":[{"type":"text","text":"Created all three files: test1.txt, test2.txt, and test3.txt, each with a line of text."}],"state":{"type":"complete","stopReason":"end_turn"}}]],"toolResults":[]}"#;
@@ -681,7 +734,7 @@ mod tests {
     #[test]
     fn test_filter_preserves_messages_without_state() {
         // Test that messages without state metadata are preserved (for compatibility)
-        let amp_executor = AmpExecutor;
+        let amp_executor = AmpExecutor::new();
         let logs = r#"{"type":"messages","messages":[[1,{"role":"assistant","content":[{"type":"text","text":"Regular message"}]}]],"toolResults":[]}"#;
 
         let result = amp_executor.normalize_logs(logs, "/tmp/test");
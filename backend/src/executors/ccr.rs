@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use command_group::AsyncGroupChild;
 use uuid::Uuid;
@@ -7,6 +9,9 @@ use crate::{
     executors::{ClaudeExecutor, ClaudeFollowupExecutor},
 };
 
+/// Default Claude Code Router command line
+pub const CCR_COMMAND: &str = "npx -y @musistudio/claude-code-router code -p --dangerously-skip-permissions --verbose --output-format=stream-json";
+
 /// An executor that uses Claude Code Router (CCR) to process tasks
 /// This is a thin wrapper around ClaudeExecutor that uses Claude Code Router instead of Claude CLI
 pub struct CCRExecutor(ClaudeExecutor);
@@ -19,11 +24,19 @@ impl Default for CCRExecutor {
 
 impl CCRExecutor {
     pub fn new() -> Self {
+        Self::with_command(CCR_COMMAND.to_string())
+    }
+
+    pub fn with_command(command: String) -> Self {
         Self(ClaudeExecutor::with_command(
             "claude-code-router".to_string(),
-            "npx -y @musistudio/claude-code-router code -p --dangerously-skip-permissions --verbose --output-format=stream-json".to_string(),
+            command,
         ))
     }
+
+    pub fn with_env_vars(self, env_vars: HashMap<String, String>) -> Self {
+        Self(self.0.with_env_vars(env_vars))
+    }
 }
 
 #[async_trait]
@@ -81,14 +94,18 @@ fn filter_ccr_service_messages(logs: &str) -> String {
 pub struct CCRFollowupExecutor(ClaudeFollowupExecutor);
 
 impl CCRFollowupExecutor {
-    pub fn new(session_id: String, prompt: String) -> Self {
+    pub fn with_command(session_id: String, prompt: String, command: String) -> Self {
         Self(ClaudeFollowupExecutor::with_command(
             session_id,
             prompt,
             "claude-code-router".to_string(),
-            "npx -y @musistudio/claude-code-router code -p --dangerously-skip-permissions --verbose --output-format=stream-json".to_string(),
+            command,
         ))
     }
+
+    pub fn with_env_vars(self, env_vars: HashMap<String, String>) -> Self {
+        Self(self.0.with_env_vars(env_vars))
+    }
 }
 
 #[async_trait]
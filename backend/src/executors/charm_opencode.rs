@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use uuid::Uuid;
 
 use crate::{
@@ -8,13 +10,66 @@ use crate::{
     utils::shell::get_shell_command,
 };
 
+/// Default Charm OpenCode command
+pub const CHARM_OPENCODE_COMMAND: &str = "opencode";
+
 /// An executor that uses OpenCode to process tasks
-pub struct CharmOpencodeExecutor;
+pub struct CharmOpencodeExecutor {
+    command: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl Default for CharmOpencodeExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CharmOpencodeExecutor {
+    /// Create a new CharmOpencodeExecutor with default settings
+    pub fn new() -> Self {
+        Self::with_command(CHARM_OPENCODE_COMMAND.to_string())
+    }
+
+    /// Create a new CharmOpencodeExecutor around a custom OpenCode command
+    pub fn with_command(command: String) -> Self {
+        Self {
+            command,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+}
 
 /// An executor that continues an OpenCode thread
 pub struct CharmOpencodeFollowupExecutor {
     pub session_id: String,
     pub prompt: String,
+    command_base: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl CharmOpencodeFollowupExecutor {
+    /// Create a new CharmOpencodeFollowupExecutor around a custom OpenCode command
+    pub fn with_command(session_id: String, prompt: String, command_base: String) -> Self {
+        Self {
+            session_id,
+            prompt,
+            command_base,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
 }
 
 #[async_trait]
@@ -54,7 +109,8 @@ Task title: {}"#,
         // Use shell command for cross-platform compatibility
         let (shell_cmd, shell_arg) = get_shell_command();
         let opencode_command = format!(
-            "opencode -p \"{}\" --output-format=json",
+            "{} -p \"{}\" --output-format=json",
+            self.command,
             prompt.replace('"', "\\\"")
         );
 
@@ -65,10 +121,10 @@ Task title: {}"#,
             .stderr(Stdio::piped())
             .current_dir(worktree_path)
             .arg(shell_arg)
-            .arg(opencode_command);
+            .arg(&opencode_command)
+            .envs(&self.env_vars);
 
-        let child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let child = crate::executors::sandbox::group_spawn(&mut command, false) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, "CharmOpenCode")
                     .with_task(task_id, Some(task.title.clone()))
@@ -95,7 +151,8 @@ impl Executor for CharmOpencodeFollowupExecutor {
         // Use shell command for cross-platform compatibility
         let (shell_cmd, shell_arg) = get_shell_command();
         let opencode_command = format!(
-            "opencode -p \"{}\" --output-format=json",
+            "{} -p \"{}\" --output-format=json",
+            self.command_base,
             self.prompt.replace('"', "\\\"")
         );
 
@@ -106,10 +163,10 @@ impl Executor for CharmOpencodeFollowupExecutor {
             .stderr(Stdio::piped())
             .current_dir(worktree_path)
             .arg(shell_arg)
-            .arg(&opencode_command);
+            .arg(&opencode_command)
+            .envs(&self.env_vars);
 
-        let child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let child = crate::executors::sandbox::group_spawn(&mut command, false) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, "CharmOpenCode")
                     .with_context(format!(
@@ -1,7 +1,7 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use tokio::process::Command;
 use uuid::Uuid;
 
@@ -39,10 +39,18 @@ exit "$exit_code"
     )
 }
 
+/// Default Claude Code command line
+pub const CLAUDE_COMMAND: &str = "npx -y @anthropic-ai/claude-code@latest -p --dangerously-skip-permissions --verbose --output-format=stream-json";
+
+/// Default Claude Code command line in plan mode
+pub const CLAUDE_PLAN_COMMAND: &str =
+    "npx -y @anthropic-ai/claude-code@latest -p --permission-mode=plan --verbose --output-format=stream-json";
+
 /// An executor that uses Claude CLI to process tasks
 pub struct ClaudeExecutor {
     executor_type: String,
     command: String,
+    env_vars: HashMap<String, String>,
 }
 
 impl Default for ClaudeExecutor {
@@ -54,19 +62,13 @@ impl Default for ClaudeExecutor {
 impl ClaudeExecutor {
     /// Create a new ClaudeExecutor with default settings
     pub fn new() -> Self {
-        Self {
-            executor_type: "Claude".to_string(),
-            command: "npx -y @anthropic-ai/claude-code@latest -p --dangerously-skip-permissions --verbose --output-format=stream-json".to_string(),
-        }
+        Self::with_command("Claude".to_string(), CLAUDE_COMMAND.to_string())
     }
 
-    pub fn new_plan_mode() -> Self {
-        let command = "npx -y @anthropic-ai/claude-code@latest -p --permission-mode=plan --verbose --output-format=stream-json";
+    /// Create a plan mode ClaudeExecutor around a custom Claude command
+    pub fn plan_mode_with_command(command: &str) -> Self {
         let script = create_watchkill_script(command);
-        Self {
-            executor_type: "ClaudePlan".to_string(),
-            command: script,
-        }
+        Self::with_command("ClaudePlan".to_string(), script)
     }
 
     /// Create a new ClaudeExecutor with custom settings
@@ -74,8 +76,15 @@ impl ClaudeExecutor {
         Self {
             executor_type,
             command,
+            env_vars: HashMap::new(),
         }
     }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
 }
 
 /// An executor that resumes a Claude session
@@ -84,33 +93,15 @@ pub struct ClaudeFollowupExecutor {
     pub prompt: String,
     executor_type: String,
     command_base: String,
+    env_vars: HashMap<String, String>,
 }
 
 impl ClaudeFollowupExecutor {
-    /// Create a new ClaudeFollowupExecutor with default settings
-    pub fn new(session_id: String, prompt: String) -> Self {
-        Self {
-            session_id,
-            prompt,
-            executor_type: "Claude".to_string(),
-            command_base: "npx -y @anthropic-ai/claude-code@latest -p --dangerously-skip-permissions --verbose --output-format=stream-json".to_string(),
-        }
-    }
-
-    pub fn new_plan_mode(session_id: String, prompt: String) -> Self {
-        let command = format!(
-            "npx -y @anthropic-ai/claude-code@latest -p --permission-mode=plan --verbose --output-format=stream-json --resume={}",
-            session_id
-        );
-
+    /// Create a plan mode ClaudeFollowupExecutor around a custom Claude command
+    pub fn plan_mode_with_command(session_id: String, prompt: String, command: &str) -> Self {
+        let command = format!("{} --resume={}", command, session_id);
         let script = create_watchkill_script(&command);
-
-        Self {
-            session_id,
-            prompt,
-            executor_type: "ClaudePlan".to_string(),
-            command_base: script,
-        }
+        Self::with_command(session_id, prompt, "ClaudePlan".to_string(), script)
     }
 
     /// Create a new ClaudeFollowupExecutor with custom settings
@@ -125,8 +116,15 @@ impl ClaudeFollowupExecutor {
             prompt,
             executor_type,
             command_base,
+            env_vars: HashMap::new(),
         }
     }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
 }
 
 #[async_trait]
@@ -173,10 +171,10 @@ Task title: {}"#,
             .current_dir(worktree_path)
             .arg(shell_arg)
             .arg(claude_command)
-            .env("NODE_NO_WARNINGS", "1");
+            .env("NODE_NO_WARNINGS", "1")
+            .envs(&self.env_vars);
 
-        let mut child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let mut child = crate::executors::sandbox::group_spawn(&mut command, true) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, &self.executor_type)
                     .with_task(task_id, Some(task.title.clone()))
@@ -688,10 +686,10 @@ impl Executor for ClaudeFollowupExecutor {
             .stderr(std::process::Stdio::piped())
             .current_dir(worktree_path)
             .arg(shell_arg)
-            .arg(&claude_command);
+            .arg(&claude_command)
+            .envs(&self.env_vars);
 
-        let mut child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let mut child = crate::executors::sandbox::group_spawn(&mut command, true) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, &self.executor_type)
                     .with_context(format!(
@@ -0,0 +1,470 @@
+use std::{collections::HashMap, process::Stdio};
+
+use async_trait::async_trait;
+use command_group::AsyncGroupChild;
+use serde_json::Value;
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::{
+    executor::{
+        ActionType, Executor, ExecutorError, NormalizedConversation, NormalizedEntry,
+        NormalizedEntryType, SpawnContext,
+    },
+    models::task::Task,
+    utils::{path::make_path_relative, shell::get_shell_command},
+};
+
+/// Default Codex CLI command line. The prompt is read from stdin, and `--json` prints one
+/// event per line.
+pub const CODEX_COMMAND: &str = "npx -y @openai/codex@latest exec --json --dangerously-bypass-approvals-and-sandbox --skip-git-repo-check";
+
+/// An executor that uses the OpenAI Codex CLI to process tasks
+pub struct CodexExecutor {
+    command: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl Default for CodexExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodexExecutor {
+    /// Create a new CodexExecutor with default settings
+    pub fn new() -> Self {
+        Self::with_command(CODEX_COMMAND.to_string())
+    }
+
+    /// Create a new CodexExecutor around a custom Codex command
+    pub fn with_command(command: String) -> Self {
+        Self {
+            command,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+}
+
+/// An executor that resumes a Codex thread
+pub struct CodexFollowupExecutor {
+    pub session_id: String,
+    pub prompt: String,
+    executor: CodexExecutor,
+}
+
+impl CodexFollowupExecutor {
+    /// Create a new CodexFollowupExecutor around a custom Codex command
+    pub fn with_command(session_id: String, prompt: String, command_base: String) -> Self {
+        Self {
+            session_id,
+            prompt,
+            executor: CodexExecutor::with_command(command_base),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.executor.env_vars = env_vars;
+        self
+    }
+}
+
+impl CodexExecutor {
+    /// Run `command_line`, writing the prompt to its stdin
+    async fn spawn_with_prompt(
+        &self,
+        command_line: &str,
+        prompt: &str,
+        worktree_path: &str,
+        context: impl Fn(SpawnContext) -> SpawnContext,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        // Use shell command for cross-platform compatibility
+        let (shell_cmd, shell_arg) = get_shell_command();
+        let mut command = Command::new(shell_cmd);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(worktree_path)
+            .arg(shell_arg)
+            .arg(command_line)
+            .env("NODE_NO_WARNINGS", "1")
+            .envs(&self.env_vars);
+
+        let mut child =
+            crate::executors::sandbox::group_spawn(&mut command, true) // Create new process group so we can kill entire tree
+                .map_err(|e| {
+                    context(SpawnContext::from_command(&command, "Codex")).spawn_error(e)
+                })?;
+
+        if let Some(mut stdin) = child.inner().stdin.take() {
+            use tokio::io::AsyncWriteExt;
+            stdin.write_all(prompt.as_bytes()).await.map_err(|e| {
+                context(SpawnContext::from_command(&command, "Codex"))
+                    .with_context("Failed to write prompt to Codex CLI stdin")
+                    .spawn_error(e)
+            })?;
+            stdin.shutdown().await.map_err(|e| {
+                context(SpawnContext::from_command(&command, "Codex"))
+                    .with_context("Failed to close Codex CLI stdin")
+                    .spawn_error(e)
+            })?;
+        }
+
+        Ok(child)
+    }
+
+    /// Entries for an `item.*` event. Commands and MCP tool calls are shown when they
+    /// start, plans whenever they change, and everything else once it has completed.
+    fn item_entries(event: &str, item: &Value, worktree_path: &str) -> Vec<NormalizedEntry> {
+        let item_type = item
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default();
+        let text = |key: &str| {
+            item.get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let entry = |entry_type: NormalizedEntryType, content: String| NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content,
+            metadata: Some(item.clone()),
+        };
+        let tool_use = |tool_name: &str, action_type: ActionType, content: String| {
+            entry(
+                NormalizedEntryType::ToolUse {
+                    tool_name: tool_name.to_string(),
+                    action_type,
+                },
+                content,
+            )
+        };
+
+        match (event, item_type) {
+            ("item.started", "command_execution") => {
+                let command = text("command");
+                vec![tool_use(
+                    "shell",
+                    ActionType::CommandRun {
+                        command: command.clone(),
+                    },
+                    format!("`{}`", command),
+                )]
+            }
+            ("item.started", "mcp_tool_call") => {
+                let tool = format!("{}.{}", text("server"), text("tool"));
+                vec![tool_use(
+                    "mcp",
+                    ActionType::Other {
+                        description: tool.clone(),
+                    },
+                    format!("`{}`", tool),
+                )]
+            }
+            ("item.started" | "item.updated", "todo_list") => {
+                let items = item
+                    .get("items")
+                    .and_then(|i| i.as_array())
+                    .map(|items| {
+                        items
+                            .iter()
+                            .map(|todo| {
+                                let done = todo
+                                    .get("completed")
+                                    .and_then(|c| c.as_bool())
+                                    .unwrap_or(false);
+                                let text = todo.get("text").and_then(|t| t.as_str()).unwrap_or("");
+                                format!("\n{} {}", if done { "✅" } else { "⏳" }, text)
+                            })
+                            .collect::<String>()
+                    })
+                    .unwrap_or_default();
+                vec![tool_use(
+                    "todo_list",
+                    ActionType::Other {
+                        description: "Manage TODO list".to_string(),
+                    },
+                    format!("TODO List:{}", items),
+                )]
+            }
+            ("item.completed", "agent_message") => {
+                vec![entry(NormalizedEntryType::AssistantMessage, text("text"))]
+            }
+            ("item.completed", "reasoning") => {
+                vec![entry(NormalizedEntryType::Thinking, text("text"))]
+            }
+            ("item.completed", "file_change") => item
+                .get("changes")
+                .and_then(|c| c.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|change| {
+                    let path = change.get("path").and_then(|p| p.as_str())?;
+                    let path = make_path_relative(path, worktree_path);
+                    let kind = change
+                        .get("kind")
+                        .and_then(|k| k.as_str())
+                        .unwrap_or("update");
+                    Some(tool_use(
+                        "apply_patch",
+                        ActionType::FileWrite { path: path.clone() },
+                        format!("`{}` ({})", path, kind),
+                    ))
+                })
+                .collect(),
+            ("item.completed", "web_search") => {
+                let query = text("query");
+                vec![tool_use(
+                    "web_search",
+                    ActionType::Search {
+                        query: query.clone(),
+                    },
+                    format!("`{}`", query),
+                )]
+            }
+            ("item.completed", "error") => {
+                vec![entry(NormalizedEntryType::ErrorMessage, text("message"))]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[async_trait]
+impl Executor for CodexExecutor {
+    async fn spawn(
+        &self,
+        pool: &sqlx::SqlitePool,
+        task_id: Uuid,
+        worktree_path: &str,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        // Get the task to fetch its description
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(ExecutorError::TaskNotFound)?;
+
+        let prompt = if let Some(task_description) = task.description {
+            format!(
+                r#"project_id: {}
+
+Task title: {}
+Task description: {}"#,
+                task.project_id, task.title, task_description
+            )
+        } else {
+            format!(
+                r#"project_id: {}
+
+Task title: {}"#,
+                task.project_id, task.title
+            )
+        };
+
+        self.spawn_with_prompt(&self.command, &prompt, worktree_path, |context| {
+            context
+                .with_task(task_id, Some(task.title.clone()))
+                .with_context("Codex CLI execution for new task")
+        })
+        .await
+    }
+
+    fn normalize_logs(
+        &self,
+        logs: &str,
+        worktree_path: &str,
+    ) -> Result<NormalizedConversation, String> {
+        let mut entries = Vec::new();
+        let mut session_id = None;
+
+        for line in logs.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let json: Value = match serde_json::from_str(trimmed) {
+                Ok(json) => json,
+                Err(_) => {
+                    // If line isn't valid JSON, add it as raw text
+                    entries.push(NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::SystemMessage,
+                        content: format!("Raw output: {}", trimmed),
+                        metadata: None,
+                    });
+                    continue;
+                }
+            };
+
+            let event = json
+                .get("type")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default();
+            match event {
+                "thread.started" => {
+                    if session_id.is_none() {
+                        session_id = json
+                            .get("thread_id")
+                            .and_then(|t| t.as_str())
+                            .map(str::to_string);
+                    }
+                }
+                "item.started" | "item.updated" | "item.completed" => {
+                    if let Some(item) = json.get("item") {
+                        entries.extend(Self::item_entries(event, item, worktree_path));
+                    }
+                }
+                "turn.failed" | "error" => {
+                    let message = json
+                        .pointer("/error/message")
+                        .or_else(|| json.get("message"))
+                        .and_then(|m| m.as_str())
+                        .unwrap_or("Codex failed");
+                    entries.push(NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::ErrorMessage,
+                        content: message.to_string(),
+                        metadata: Some(json.clone()),
+                    });
+                }
+                // Turn boundaries and token usage
+                _ => {}
+            }
+        }
+
+        Ok(NormalizedConversation {
+            entries,
+            session_id,
+            executor_type: "codex".to_string(),
+            prompt: None,
+            summary: None,
+        })
+    }
+
+    fn parse_session_id(&self, line: &str) -> Option<String> {
+        let json: Value = serde_json::from_str(line.trim()).ok()?;
+        if json.get("type").and_then(|t| t.as_str()) != Some("thread.started") {
+            return None;
+        }
+        json.get("thread_id")
+            .and_then(|t| t.as_str())
+            .map(str::to_string)
+    }
+}
+
+#[async_trait]
+impl Executor for CodexFollowupExecutor {
+    async fn spawn(
+        &self,
+        _pool: &sqlx::SqlitePool,
+        _task_id: Uuid,
+        worktree_path: &str,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        // Options of `codex exec` go before its `resume` subcommand
+        let command_line = format!("{} resume {}", self.executor.command, self.session_id);
+        self.executor
+            .spawn_with_prompt(&command_line, &self.prompt, worktree_path, |context| {
+                context.with_context(format!(
+                    "Codex CLI followup execution for session {}",
+                    self.session_id
+                ))
+            })
+            .await
+    }
+
+    fn normalize_logs(
+        &self,
+        logs: &str,
+        worktree_path: &str,
+    ) -> Result<NormalizedConversation, String> {
+        self.executor.normalize_logs(logs, worktree_path)
+    }
+
+    fn parse_session_id(&self, line: &str) -> Option<String> {
+        self.executor.parse_session_id(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codex_log_normalization() {
+        let executor = CodexExecutor::new();
+        let logs = r#"{"type":"thread.started","thread_id":"0199a213-81c0-7800-8aa1-bbab2a035a53"}
+{"type":"turn.started"}
+{"type":"item.completed","item":{"id":"item_0","type":"reasoning","text":"**Looking for the failing test**"}}
+{"type":"item.started","item":{"id":"item_1","type":"command_execution","command":"bash -lc 'cargo test'","aggregated_output":"","exit_code":null,"status":"in_progress"}}
+{"type":"item.completed","item":{"id":"item_1","type":"command_execution","command":"bash -lc 'cargo test'","aggregated_output":"test result: FAILED","exit_code":101,"status":"failed"}}
+{"type":"item.started","item":{"id":"item_2","type":"todo_list","items":[{"text":"Fix the parser","completed":false}]}}
+{"type":"item.completed","item":{"id":"item_3","type":"file_change","changes":[{"path":"/tmp/test-worktree/src/parser.rs","kind":"update"}],"status":"completed"}}
+{"type":"item.updated","item":{"id":"item_2","type":"todo_list","items":[{"text":"Fix the parser","completed":true}]}}
+{"type":"item.completed","item":{"id":"item_2","type":"todo_list","items":[{"text":"Fix the parser","completed":true}]}}
+{"type":"item.completed","item":{"id":"item_4","type":"agent_message","text":"Fixed the off-by-one in the parser."}}
+{"type":"turn.completed","usage":{"input_tokens":24763,"cached_input_tokens":24448,"output_tokens":122}}
+Reading prompt from stdin..."#;
+
+        let result = executor.normalize_logs(logs, "/tmp/test-worktree").unwrap();
+
+        assert_eq!(result.executor_type, "codex");
+        assert_eq!(
+            result.session_id,
+            Some("0199a213-81c0-7800-8aa1-bbab2a035a53".to_string())
+        );
+        assert_eq!(result.entries.len(), 7);
+        assert!(matches!(
+            result.entries[0].entry_type,
+            NormalizedEntryType::Thinking
+        ));
+        assert!(matches!(
+            &result.entries[1].entry_type,
+            NormalizedEntryType::ToolUse { tool_name, action_type: ActionType::CommandRun { command } }
+                if tool_name == "shell" && command == "bash -lc 'cargo test'"
+        ));
+        assert_eq!(result.entries[2].content, "TODO List:\n⏳ Fix the parser");
+        assert!(matches!(
+            &result.entries[3].entry_type,
+            NormalizedEntryType::ToolUse { action_type: ActionType::FileWrite { path }, .. }
+                if path == "src/parser.rs"
+        ));
+        assert_eq!(result.entries[4].content, "TODO List:\n✅ Fix the parser");
+        assert!(matches!(
+            result.entries[5].entry_type,
+            NormalizedEntryType::AssistantMessage
+        ));
+        assert_eq!(
+            result.entries[5].content,
+            "Fixed the off-by-one in the parser."
+        );
+        assert_eq!(
+            result.entries[6].content,
+            "Raw output: Reading prompt from stdin..."
+        );
+    }
+
+    #[test]
+    fn test_parse_codex_thread_id() {
+        let executor = CodexExecutor::new();
+        assert_eq!(
+            executor.parse_session_id(r#"{"type":"thread.started","thread_id":"0199a213"}"#),
+            Some("0199a213".to_string())
+        );
+        assert_eq!(
+            executor.parse_session_id(r#"{"type":"turn.started"}"#),
+            None
+        );
+        assert_eq!(executor.parse_session_id("not json"), None);
+    }
+}
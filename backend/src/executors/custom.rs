@@ -0,0 +1,789 @@
+//! Executors defined in `executors.json` rather than in code, so that an agent with a CLI
+//! taking a prompt and printing text or JSON lines can be added without recompiling.
+//!
+//! ```json
+//! [
+//!   {
+//!     "id": "my-agent",
+//!     "label": "My Agent",
+//!     "command": "my-agent --json --prompt {prompt}",
+//!     "prompt_input": "argument",
+//!     "resume_args": "--resume {session_id}",
+//!     "session_id_path": "/session_id",
+//!     "log_format": "jsonl",
+//!     "entries": [
+//!       { "match": { "/type": "message" }, "entry_type": "assistant_message", "content": "/text" },
+//!       { "match": { "/type": "exec" }, "entry_type": "tool_use", "tool_name": "bash", "action": "command_run", "content": "/command" }
+//!     ],
+//!     "config_path": "~/.my-agent/config.json",
+//!     "mcp_attribute_path": ["mcpServers"]
+//!   }
+//! ]
+//! ```
+
+use std::{collections::HashMap, path::Path, process::Stdio, sync::OnceLock};
+
+use async_trait::async_trait;
+use command_group::AsyncGroupChild;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::{
+    executor::{
+        ActionType, Executor, ExecutorConfig, ExecutorError, NormalizedConversation,
+        NormalizedEntry, NormalizedEntryType, SpawnContext,
+    },
+    models::task::Task,
+    utils::{
+        self,
+        path::make_path_relative,
+        shell::{get_shell_command, shell_quote},
+    },
+};
+
+/// Where a custom executor's CLI reads the prompt from
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptInput {
+    #[default]
+    Stdin,
+    Argument, // In place of `{prompt}` in the command, or after it
+}
+
+/// What a custom executor's CLI prints on stdout
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    #[default]
+    Text, // Every line is shown as an assistant message
+    Jsonl, // Lines are JSON objects, mapped to entries by `entries`
+}
+
+/// The kind of conversation entry a JSON line becomes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomEntryType {
+    UserMessage,
+    AssistantMessage,
+    ToolUse,
+    SystemMessage,
+    ErrorMessage,
+    Thinking,
+}
+
+/// What a tool use does, its entry's content being the path, command, query or url
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomActionKind {
+    FileRead,
+    FileWrite,
+    CommandRun,
+    Search,
+    WebFetch,
+    Other,
+}
+
+/// Maps the JSON lines matching `matches` to a conversation entry. Fields are addressed with
+/// JSON pointers such as "/item/text".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntryMapping {
+    /// Values fields of the line must have, by pointer
+    #[serde(default, rename = "match")]
+    pub matches: HashMap<String, Value>,
+    pub entry_type: CustomEntryType,
+    /// Pointer to the entry's text, non-string values being shown as JSON
+    pub content: String,
+    /// Pointer to the tool's name, or the name itself when it doesn't start with '/'
+    #[serde(default)]
+    pub tool_name: Option<String>,
+    #[serde(default)]
+    pub action: Option<CustomActionKind>,
+}
+
+/// A generic CLI executor, as defined in `executors.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomExecutorDefinition {
+    /// Id the executor is selected by, lowercase letters, digits and dashes
+    pub id: String,
+    /// Name shown in the UI, the id when left out
+    #[serde(default)]
+    pub label: String,
+    /// Command line to run, with `{prompt}` where an argument prompt goes
+    pub command: String,
+    #[serde(default)]
+    pub prompt_input: PromptInput,
+    /// Arguments resuming a session on follow-ups, with `{session_id}` where its id goes
+    #[serde(default)]
+    pub resume_args: Option<String>,
+    /// Pointer to the session id in the JSON lines, e.g. "/session_id"
+    #[serde(default)]
+    pub session_id_path: Option<String>,
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// How JSON lines become conversation entries, the first matching mapping winning
+    #[serde(default)]
+    pub entries: Vec<LogEntryMapping>,
+    /// Config file the CLI reads MCP servers from, `~` standing for the home directory
+    #[serde(default)]
+    pub config_path: Option<String>,
+    /// Path of the MCP servers object in that file
+    #[serde(default)]
+    pub mcp_attribute_path: Vec<String>,
+}
+
+/// Ids taken by executors that aren't coding agents
+const RESERVED_IDS: &[&str] = &["setup-script", "check-script", "custom"];
+
+static CUSTOM_EXECUTORS: OnceLock<Vec<CustomExecutorDefinition>> = OnceLock::new();
+
+/// The executors defined in `executors.json`, read the first time they are needed
+pub fn custom_executors() -> &'static [CustomExecutorDefinition] {
+    CUSTOM_EXECUTORS.get_or_init(|| load_definitions(&utils::custom_executors_path()))
+}
+
+pub fn find_custom_executor(id: &str) -> Option<&'static CustomExecutorDefinition> {
+    custom_executors()
+        .iter()
+        .find(|definition| definition.id == id)
+}
+
+fn load_definitions(path: &Path) -> Vec<CustomExecutorDefinition> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => {
+            let definitions = parse_definitions(&content);
+            tracing::info!(
+                "Loaded {} custom executor(s) from {}",
+                definitions.len(),
+                path.display()
+            );
+            definitions
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            tracing::warn!("Failed to read {}: {}", path.display(), e);
+            Vec::new()
+        }
+    }
+}
+
+/// Parse a list of definitions, skipping the invalid ones so they don't take the others down
+fn parse_definitions(content: &str) -> Vec<CustomExecutorDefinition> {
+    let values: Vec<Value> = match serde_json::from_str(content) {
+        Ok(values) => values,
+        Err(e) => {
+            tracing::warn!("Custom executors must be a JSON list of definitions: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut definitions: Vec<CustomExecutorDefinition> = Vec::new();
+    for value in values {
+        let result = serde_json::from_value::<CustomExecutorDefinition>(value)
+            .map_err(|e| e.to_string())
+            .and_then(|definition| definition.validate(&definitions));
+        match result {
+            Ok(definition) => definitions.push(definition),
+            Err(e) => tracing::warn!("Skipping custom executor: {}", e),
+        }
+    }
+    definitions
+}
+
+impl CustomExecutorDefinition {
+    /// Check the definition against the built-in executors and the ones defined before it
+    fn validate(mut self, defined: &[CustomExecutorDefinition]) -> Result<Self, String> {
+        self.id = self.id.trim().to_string();
+        if self.id.is_empty()
+            || !self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(format!(
+                "'{}' is not a valid id, use lowercase letters, digits and dashes",
+                self.id
+            ));
+        }
+        let built_in = ExecutorConfig::built_in()
+            .iter()
+            .any(|config| config.to_string() == self.id);
+        if built_in || RESERVED_IDS.contains(&self.id.as_str()) {
+            return Err(format!("'{}' is the id of a built-in executor", self.id));
+        }
+        if defined.iter().any(|definition| definition.id == self.id) {
+            return Err(format!("'{}' is defined more than once", self.id));
+        }
+        if self.command.trim().is_empty() {
+            return Err(format!("'{}' has no command", self.id));
+        }
+        if self.prompt_input == PromptInput::Stdin && self.command.contains("{prompt}") {
+            return Err(format!(
+                "'{}' has {{prompt}} in its command but reads the prompt from stdin",
+                self.id
+            ));
+        }
+        if self.label.trim().is_empty() {
+            self.label = self.id.clone();
+        }
+        Ok(self)
+    }
+
+    pub fn config_path(&self) -> Option<std::path::PathBuf> {
+        let path = self.config_path.as_deref()?;
+        match path.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+            None => Some(std::path::PathBuf::from(path)),
+        }
+    }
+
+    /// Command line for a run from `command`, the definition's command after applying the
+    /// executor profile, resuming `session_id` when given
+    pub fn build_command(
+        &self,
+        command: &str,
+        prompt: &str,
+        session_id: Option<&str>,
+    ) -> Result<String, String> {
+        let mut template = command.trim().to_string();
+        let mut values = Vec::new();
+
+        if let Some(session_id) = session_id {
+            let resume_args = self
+                .resume_args
+                .as_deref()
+                .ok_or_else(|| format!("{} can't resume a session", self.label))?;
+            template.push(' ');
+            template.push_str(resume_args);
+            values.push(("{session_id}", shell_quote(session_id)));
+        }
+
+        if self.prompt_input == PromptInput::Argument {
+            if !template.contains("{prompt}") {
+                template.push_str(" {prompt}");
+            }
+            values.push(("{prompt}", shell_quote(prompt)));
+        }
+
+        Ok(substitute(&template, &values))
+    }
+
+    /// Session id a line of output carries, if it is JSON and has one at `session_id_path`
+    pub fn parse_session_id(&self, line: &str) -> Option<String> {
+        let pointer = self.session_id_path.as_deref()?;
+        let json: Value = serde_json::from_str(line.trim()).ok()?;
+        match json.pointer(pointer)? {
+            Value::String(session_id) if !session_id.is_empty() => Some(session_id.clone()),
+            Value::Number(session_id) => Some(session_id.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn normalize_logs(&self, logs: &str, worktree_path: &str) -> NormalizedConversation {
+        let mut entries = Vec::new();
+        let mut session_id = None;
+
+        for line in logs.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            if self.log_format == LogFormat::Text {
+                entries.push(NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::AssistantMessage,
+                    content: trimmed.to_string(),
+                    metadata: None,
+                });
+                continue;
+            }
+
+            let json: Value = match serde_json::from_str(trimmed) {
+                Ok(json) => json,
+                Err(_) => {
+                    entries.push(NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::SystemMessage,
+                        content: format!("Raw output: {}", trimmed),
+                        metadata: None,
+                    });
+                    continue;
+                }
+            };
+
+            if session_id.is_none() {
+                session_id = self.parse_session_id(trimmed);
+            }
+
+            // Lines no mapping matches, such as usage reports, are left out
+            if let Some(entry) = self
+                .entries
+                .iter()
+                .find(|mapping| mapping.matches(&json))
+                .and_then(|mapping| mapping.to_entry(&json, worktree_path))
+            {
+                entries.push(entry);
+            }
+        }
+
+        NormalizedConversation {
+            entries,
+            session_id,
+            executor_type: self.id.clone(),
+            prompt: None,
+            summary: None,
+        }
+    }
+}
+
+impl LogEntryMapping {
+    fn matches(&self, json: &Value) -> bool {
+        self.matches
+            .iter()
+            .all(|(pointer, expected)| json.pointer(pointer) == Some(expected))
+    }
+
+    fn to_entry(&self, json: &Value, worktree_path: &str) -> Option<NormalizedEntry> {
+        let content = match json.pointer(&self.content)? {
+            Value::String(text) => text.clone(),
+            Value::Null => return None,
+            value => value.to_string(),
+        };
+
+        let entry_type = match self.entry_type {
+            CustomEntryType::UserMessage => NormalizedEntryType::UserMessage,
+            CustomEntryType::AssistantMessage => NormalizedEntryType::AssistantMessage,
+            CustomEntryType::SystemMessage => NormalizedEntryType::SystemMessage,
+            CustomEntryType::ErrorMessage => NormalizedEntryType::ErrorMessage,
+            CustomEntryType::Thinking => NormalizedEntryType::Thinking,
+            CustomEntryType::ToolUse => {
+                let tool_name = match self.tool_name.as_deref() {
+                    Some(pointer) if pointer.starts_with('/') => json
+                        .pointer(pointer)
+                        .and_then(Value::as_str)
+                        .unwrap_or("tool")
+                        .to_string(),
+                    Some(name) => name.to_string(),
+                    None => "tool".to_string(),
+                };
+                let action_type = match self.action.unwrap_or(CustomActionKind::Other) {
+                    CustomActionKind::FileRead => ActionType::FileRead {
+                        path: make_path_relative(&content, worktree_path),
+                    },
+                    CustomActionKind::FileWrite => ActionType::FileWrite {
+                        path: make_path_relative(&content, worktree_path),
+                    },
+                    CustomActionKind::CommandRun => ActionType::CommandRun {
+                        command: content.clone(),
+                    },
+                    CustomActionKind::Search => ActionType::Search {
+                        query: content.clone(),
+                    },
+                    CustomActionKind::WebFetch => ActionType::WebFetch {
+                        url: content.clone(),
+                    },
+                    CustomActionKind::Other => ActionType::Other {
+                        description: content.clone(),
+                    },
+                };
+                NormalizedEntryType::ToolUse {
+                    tool_name,
+                    action_type,
+                }
+            }
+        };
+
+        Some(NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content,
+            metadata: Some(json.clone()),
+        })
+    }
+}
+
+fn task_prompt(task: &Task) -> String {
+    if let Some(task_description) = &task.description {
+        format!(
+            r#"project_id: {}
+
+Task title: {}
+Task description: {}"#,
+            task.project_id, task.title, task_description
+        )
+    } else {
+        format!(
+            r#"project_id: {}
+
+Task title: {}"#,
+            task.project_id, task.title
+        )
+    }
+}
+
+/// An executor running the CLI of a custom executor definition
+pub struct CustomExecutor {
+    id: String,
+    command: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl CustomExecutor {
+    /// Create a CustomExecutor for the definition `id` around a custom command
+    pub fn with_command(id: String, command: String) -> Self {
+        Self {
+            id,
+            command,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+}
+
+/// An executor resuming a session of a custom executor's CLI
+pub struct CustomFollowupExecutor {
+    pub session_id: String,
+    pub prompt: String,
+    executor: CustomExecutor,
+}
+
+impl CustomFollowupExecutor {
+    /// Create a CustomFollowupExecutor for the definition `id` around a custom command
+    pub fn with_command(id: String, session_id: String, prompt: String, command: String) -> Self {
+        Self {
+            session_id,
+            prompt,
+            executor: CustomExecutor::with_command(id, command),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.executor.env_vars = env_vars;
+        self
+    }
+}
+
+impl CustomExecutor {
+    fn definition(&self) -> Result<&'static CustomExecutorDefinition, String> {
+        find_custom_executor(&self.id).ok_or_else(|| {
+            format!(
+                "Custom executor '{}' is not defined in {}",
+                self.id,
+                utils::custom_executors_path().display()
+            )
+        })
+    }
+
+    async fn spawn_with_prompt(
+        &self,
+        prompt: &str,
+        session_id: Option<&str>,
+        worktree_path: &str,
+        context: impl Fn(SpawnContext) -> SpawnContext,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let definition = self
+            .definition()
+            .map_err(ExecutorError::ContextCollectionFailed)?;
+        let command_line = definition
+            .build_command(&self.command, prompt, session_id)
+            .map_err(ExecutorError::ContextCollectionFailed)?;
+        let pipes_stdin = definition.prompt_input == PromptInput::Stdin;
+
+        // Use shell command for cross-platform compatibility
+        let (shell_cmd, shell_arg) = get_shell_command();
+        let mut command = Command::new(shell_cmd);
+        command
+            .kill_on_drop(true)
+            .stdin(if pipes_stdin {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(worktree_path)
+            .arg(shell_arg)
+            .arg(&command_line)
+            .envs(&self.env_vars);
+
+        let mut child = crate::executors::sandbox::group_spawn(&mut command, pipes_stdin) // Create new process group so we can kill entire tree
+            .map_err(|e| {
+                context(SpawnContext::from_command(&command, &definition.label)).spawn_error(e)
+            })?;
+
+        if pipes_stdin {
+            if let Some(mut stdin) = child.inner().stdin.take() {
+                use tokio::io::AsyncWriteExt;
+                stdin.write_all(prompt.as_bytes()).await.map_err(|e| {
+                    context(SpawnContext::from_command(&command, &definition.label))
+                        .with_context(format!(
+                            "Failed to write prompt to {} stdin",
+                            definition.label
+                        ))
+                        .spawn_error(e)
+                })?;
+                stdin.shutdown().await.map_err(|e| {
+                    context(SpawnContext::from_command(&command, &definition.label))
+                        .with_context(format!("Failed to close {} stdin", definition.label))
+                        .spawn_error(e)
+                })?;
+            }
+        }
+
+        Ok(child)
+    }
+}
+
+#[async_trait]
+impl Executor for CustomExecutor {
+    async fn spawn(
+        &self,
+        pool: &sqlx::SqlitePool,
+        task_id: Uuid,
+        worktree_path: &str,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        // Get the task to fetch its description
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(ExecutorError::TaskNotFound)?;
+
+        self.spawn_with_prompt(&task_prompt(&task), None, worktree_path, |context| {
+            context
+                .with_task(task_id, Some(task.title.clone()))
+                .with_context(format!("{} CLI execution for new task", self.id))
+        })
+        .await
+    }
+
+    fn normalize_logs(
+        &self,
+        logs: &str,
+        worktree_path: &str,
+    ) -> Result<NormalizedConversation, String> {
+        let definition = self.definition()?;
+        Ok(definition.normalize_logs(logs, worktree_path))
+    }
+
+    fn parse_session_id(&self, line: &str) -> Option<String> {
+        find_custom_executor(&self.id)?.parse_session_id(line)
+    }
+}
+
+#[async_trait]
+impl Executor for CustomFollowupExecutor {
+    async fn spawn(
+        &self,
+        _pool: &sqlx::SqlitePool,
+        _task_id: Uuid,
+        worktree_path: &str,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        self.executor
+            .spawn_with_prompt(
+                &self.prompt,
+                Some(&self.session_id),
+                worktree_path,
+                |context| {
+                    context.with_context(format!(
+                        "{} CLI followup execution for session {}",
+                        self.executor.id, self.session_id
+                    ))
+                },
+            )
+            .await
+    }
+
+    fn normalize_logs(
+        &self,
+        logs: &str,
+        worktree_path: &str,
+    ) -> Result<NormalizedConversation, String> {
+        self.executor.normalize_logs(logs, worktree_path)
+    }
+
+    fn parse_session_id(&self, line: &str) -> Option<String> {
+        self.executor.parse_session_id(line)
+    }
+}
+
+/// `template` with its placeholders replaced in a single pass, so a value containing
+/// another placeholder is never substituted again
+fn substitute(template: &str, values: &[(&str, String)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    'outer: while let Some(c) = rest.chars().next() {
+        for (placeholder, value) in values {
+            if let Some(after) = rest.strip_prefix(placeholder) {
+                result.push_str(value);
+                rest = after;
+                continue 'outer;
+            }
+        }
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFINITIONS: &str = r#"[
+        {
+            "id": "my-agent",
+            "label": "My Agent",
+            "command": "my-agent --json --prompt {prompt}",
+            "prompt_input": "argument",
+            "resume_args": "--resume {session_id}",
+            "session_id_path": "/session/id",
+            "log_format": "jsonl",
+            "entries": [
+                { "match": { "/type": "message", "/role": "user" }, "entry_type": "user_message", "content": "/text" },
+                { "match": { "/type": "message" }, "entry_type": "assistant_message", "content": "/text" },
+                { "match": { "/type": "exec" }, "entry_type": "tool_use", "tool_name": "bash", "action": "command_run", "content": "/command" },
+                { "match": { "/type": "tool" }, "entry_type": "tool_use", "tool_name": "/name", "action": "file_write", "content": "/input/path" }
+            ],
+            "config_path": "~/.my-agent/config.json",
+            "mcp_attribute_path": ["mcp", "servers"]
+        },
+        { "id": "claude", "command": "not-claude" },
+        { "id": "Bad Id", "command": "agent" },
+        { "id": "no-command", "command": "  " },
+        { "id": "my-agent", "command": "duplicate" },
+        { "id": "stdin-agent", "command": "agent --quiet" },
+        { "id": "missing-command" }
+    ]"#;
+
+    #[test]
+    fn test_parse_definitions_skips_invalid_ones() {
+        let definitions = parse_definitions(DEFINITIONS);
+        let ids: Vec<_> = definitions.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["my-agent", "stdin-agent"]);
+
+        // The label defaults to the id, and the prompt to stdin
+        assert_eq!(definitions[1].label, "stdin-agent");
+        assert_eq!(definitions[1].prompt_input, PromptInput::Stdin);
+        assert_eq!(definitions[1].log_format, LogFormat::Text);
+
+        assert!(parse_definitions("{}").is_empty());
+    }
+
+    #[test]
+    fn test_build_command() {
+        let definitions = parse_definitions(DEFINITIONS);
+        let agent = &definitions[0];
+
+        assert_eq!(
+            agent
+                .build_command(&agent.command, "it's done", None)
+                .unwrap(),
+            format!("my-agent --json --prompt {}", shell_quote("it's done"))
+        );
+        assert_eq!(
+            agent
+                .build_command("my-agent --json --prompt {prompt}", "next", Some("s-1"))
+                .unwrap(),
+            format!(
+                "my-agent --json --prompt {} --resume {}",
+                shell_quote("next"),
+                shell_quote("s-1")
+            )
+        );
+
+        // A session id from the agent's output can't splice the prompt in unquoted
+        assert_eq!(
+            agent
+                .build_command(&agent.command, "; rm -rf ~", Some("{prompt}"))
+                .unwrap(),
+            format!(
+                "my-agent --json --prompt {} --resume {}",
+                shell_quote("; rm -rf ~"),
+                shell_quote("{prompt}")
+            )
+        );
+
+        // Prompts on stdin are left out of the command, which can't resume without resume_args
+        let stdin_agent = &definitions[1];
+        assert_eq!(
+            stdin_agent
+                .build_command("agent --quiet --model x", "prompt", None)
+                .unwrap(),
+            "agent --quiet --model x"
+        );
+        assert!(stdin_agent
+            .build_command(&stdin_agent.command, "prompt", Some("s-1"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_custom_log_normalization() {
+        let definitions = parse_definitions(DEFINITIONS);
+        let agent = &definitions[0];
+        let logs = r#"{"type":"start","session":{"id":"sess-42"}}
+{"type":"message","role":"user","text":"Add a README"}
+{"type":"message","role":"assistant","text":"I'll add a README."}
+{"type":"exec","command":"ls -la"}
+{"type":"tool","name":"write_file","input":{"path":"/tmp/test-worktree/README.md"}}
+{"type":"usage","tokens":12}
+warning: not json"#;
+
+        let result = agent.normalize_logs(logs, "/tmp/test-worktree");
+
+        assert_eq!(result.executor_type, "my-agent");
+        assert_eq!(result.session_id, Some("sess-42".to_string()));
+        assert_eq!(result.entries.len(), 5);
+        assert!(matches!(
+            result.entries[0].entry_type,
+            NormalizedEntryType::UserMessage
+        ));
+        assert!(matches!(
+            result.entries[1].entry_type,
+            NormalizedEntryType::AssistantMessage
+        ));
+        assert_eq!(result.entries[1].content, "I'll add a README.");
+        match &result.entries[2].entry_type {
+            NormalizedEntryType::ToolUse {
+                tool_name,
+                action_type: ActionType::CommandRun { command },
+            } => {
+                assert_eq!(tool_name, "bash");
+                assert_eq!(command, "ls -la");
+            }
+            other => panic!("Unexpected entry type {:?}", other),
+        }
+        match &result.entries[3].entry_type {
+            NormalizedEntryType::ToolUse {
+                tool_name,
+                action_type: ActionType::FileWrite { path },
+            } => {
+                assert_eq!(tool_name, "write_file");
+                assert_eq!(path, "README.md");
+            }
+            other => panic!("Unexpected entry type {:?}", other),
+        }
+        assert_eq!(result.entries[4].content, "Raw output: warning: not json");
+
+        assert_eq!(
+            agent.parse_session_id(r#"{"session":{"id":"sess-42"}}"#),
+            Some("sess-42".to_string())
+        );
+        assert_eq!(agent.parse_session_id("not json"), None);
+    }
+
+    #[test]
+    fn test_text_log_normalization() {
+        let definitions = parse_definitions(DEFINITIONS);
+        let result = definitions[1].normalize_logs("Working on it\n\nDone\n", "/tmp");
+        let contents: Vec<_> = result.entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["Working on it", "Done"]);
+        assert_eq!(result.session_id, None);
+    }
+}
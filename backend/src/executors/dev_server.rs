@@ -1,17 +1,35 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+};
+
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
-use tokio::process::Command;
+use command_group::AsyncGroupChild;
+use regex::Regex;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    process::Command,
+};
 use uuid::Uuid;
 
 use crate::{
-    executor::{Executor, ExecutorError},
-    models::{project::Project, task::Task},
+    executor::{stream_output_to_db, Executor, ExecutorError},
+    models::{execution_process::ExecutionProcess, project::Project, task::Task},
     utils::shell::get_shell_command,
 };
 
+/// Longest partial line kept while waiting for its newline
+const MAX_PENDING_LINE_LEN: usize = 4096;
+
 /// Executor for running project dev server scripts
 pub struct DevServerExecutor {
     pub script: String,
+    pub port: u16, // Allocated for this attempt, also set as PORT
+    pub env_vars: HashMap<String, String>, // Includes PORT
+    pub ready_pattern: Option<String>, // Project's readiness regex, None for the default
 }
 
 #[async_trait]
@@ -39,9 +57,10 @@ impl Executor for DevServerExecutor {
             .stderr(std::process::Stdio::piped())
             .arg(shell_arg)
             .arg(&self.script)
-            .current_dir(worktree_path);
+            .current_dir(worktree_path)
+            .envs(&self.env_vars);
 
-        let child = command.group_spawn().map_err(|e| {
+        let child = crate::executors::sandbox::group_spawn(&mut command, false).map_err(|e| {
             crate::executor::SpawnContext::from_command(&command, "DevServer")
                 .with_task(task_id, Some(task.title.clone()))
                 .with_context("Development server execution")
@@ -50,4 +69,178 @@ impl Executor for DevServerExecutor {
 
         Ok(child)
     }
+
+    /// Stream output like other executors, watching it for the line that says the server is
+    /// ready on the way
+    async fn execute_streaming(
+        &self,
+        pool: &sqlx::SqlitePool,
+        task_id: Uuid,
+        attempt_id: Uuid,
+        execution_process_id: Uuid,
+        worktree_path: &str,
+    ) -> Result<AsyncGroupChild, ExecutorError> {
+        let mut child = self.spawn(pool, task_id, worktree_path).await?;
+
+        let stdout = child
+            .inner()
+            .stdout
+            .take()
+            .expect("Failed to take stdout from child process");
+        let stderr = child
+            .inner()
+            .stderr
+            .take()
+            .expect("Failed to take stderr from child process");
+
+        let ready_pattern = match self.ready_pattern.as_deref().map(Regex::new) {
+            Some(Ok(pattern)) => Some(pattern),
+            Some(Err(e)) => {
+                tracing::warn!("Ignoring invalid dev server ready pattern: {}", e);
+                None
+            }
+            None => None,
+        };
+        let watch = Arc::new(ReadinessWatch {
+            pool: pool.clone(),
+            execution_process_id,
+            port: self.port,
+            ready_pattern,
+            ready: AtomicBool::new(false),
+        });
+
+        tokio::spawn(watch.clone().forward(stdout, attempt_id, true));
+        tokio::spawn(watch.forward(stderr, attempt_id, false));
+
+        Ok(child)
+    }
+}
+
+/// Marks a dev server ready the first time a line of its output says so
+struct ReadinessWatch {
+    pool: sqlx::SqlitePool,
+    execution_process_id: Uuid,
+    port: u16,
+    ready_pattern: Option<Regex>,
+    ready: AtomicBool,
+}
+
+impl ReadinessWatch {
+    /// Pass `output` on to the database as it arrives, checking each line for readiness
+    async fn forward(
+        self: Arc<Self>,
+        mut output: impl AsyncRead + Unpin,
+        attempt_id: Uuid,
+        is_stdout: bool,
+    ) {
+        let (mut writer, reader) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(stream_output_to_db(
+            reader,
+            self.pool.clone(),
+            attempt_id,
+            self.execution_process_id,
+            is_stdout,
+        ));
+
+        let mut buf = [0u8; 8192];
+        let mut pending = String::new();
+        loop {
+            let n = match output.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            if !self.ready.load(Ordering::Relaxed) {
+                pending.push_str(&String::from_utf8_lossy(&buf[..n]));
+                self.check_lines(&mut pending).await;
+            }
+            if writer.write_all(&buf[..n]).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Check the complete lines in `pending`, leaving the unfinished last one
+    async fn check_lines(&self, pending: &mut String) {
+        let Some(end) = pending.rfind('\n') else {
+            if pending.len() > MAX_PENDING_LINE_LEN {
+                pending.clear();
+            }
+            return;
+        };
+        let ready = pending[..end]
+            .lines()
+            .any(|line| is_ready_line(line, self.port, self.ready_pattern.as_ref()));
+        pending.drain(..=end);
+
+        if ready && !self.ready.swap(true, Ordering::Relaxed) {
+            tracing::info!(
+                "Dev server {} is ready on port {}",
+                self.execution_process_id,
+                self.port
+            );
+            if let Err(e) =
+                ExecutionProcess::mark_ready(&self.pool, self.execution_process_id).await
+            {
+                tracing::error!(
+                    "Failed to mark dev server {} ready: {}",
+                    self.execution_process_id,
+                    e
+                );
+            }
+        }
+    }
+}
+
+fn default_ready_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(ready|listening|compiled successfully|server (is )?running|started server)\b|\blocal:\s",
+        )
+        .unwrap()
+    })
+}
+
+/// Whether an output line says the dev server is ready: it matches the project's pattern,
+/// or without one, mentions the allocated port or a typical "ready"/"listening" message
+pub fn is_ready_line(line: &str, port: u16, ready_pattern: Option<&Regex>) -> bool {
+    let line = strip_ansi_escapes::strip_str(line);
+    if let Some(pattern) = ready_pattern {
+        return pattern.is_match(&line);
+    }
+
+    let port_suffix = format!(":{}", port);
+    let mentions_port = line
+        .match_indices(&port_suffix)
+        .any(|(i, _)| !line[i + port_suffix.len()..].starts_with(|c: char| c.is_ascii_digit()));
+    mentions_port || default_ready_regex().is_match(&line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ready_line() {
+        assert!(is_ready_line(
+            "  \u{1b}[32m➜\u{1b}[39m  \u{1b}[1mLocal\u{1b}[22m:   http://localhost:5173/",
+            5173,
+            None
+        ));
+        assert!(is_ready_line("Server listening on port 8080", 41234, None));
+        assert!(is_ready_line(
+            "ready - started server on 0.0.0.0:41234",
+            41234,
+            None
+        ));
+        assert!(is_ready_line("http://127.0.0.1:41234", 41234, None));
+        assert!(!is_ready_line("http://127.0.0.1:412345", 41234, None));
+        assert!(!is_ready_line("Installing dependencies...", 41234, None));
+        assert!(!is_ready_line("already up to date", 41234, None));
+
+        let pattern = Regex::new(r"^Booted in \d+ms$").unwrap();
+        assert!(is_ready_line("Booted in 120ms", 41234, Some(&pattern)));
+        // The project's pattern replaces the default detection
+        assert!(!is_ready_line("listening on :41234", 41234, Some(&pattern)));
+    }
 }
@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use tokio::process::Command;
 use uuid::Uuid;
 
@@ -10,7 +12,18 @@ use crate::{
 };
 
 /// A dummy executor that echoes the task title and description
-pub struct EchoExecutor;
+#[derive(Default)]
+pub struct EchoExecutor {
+    env_vars: HashMap<String, String>,
+}
+
+impl EchoExecutor {
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+}
 
 #[async_trait]
 impl Executor for EchoExecutor {
@@ -63,10 +76,10 @@ echo "Task completed: {}""#,
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .arg(shell_arg)
-            .arg(&script);
+            .arg(&script)
+            .envs(&self.env_vars);
 
-        let child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let child = crate::executors::sandbox::group_spawn(&mut command, false) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, "Echo")
                     .with_task(task_id, Some(task.title.clone()))
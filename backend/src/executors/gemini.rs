@@ -5,16 +5,14 @@
 mod config;
 mod streaming;
 
-use std::{process::Stdio, time::Instant};
+use std::{collections::HashMap, process::Stdio, time::Instant};
 
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use config::{
     max_chunk_size, max_display_size, max_latency_ms, max_message_size, GeminiStreamConfig,
 };
-// Re-export for external use
 use serde_json::Value;
-pub use streaming::GeminiPatchBatch;
 use streaming::GeminiStreaming;
 use tokio::{io::AsyncWriteExt, process::Command};
 use uuid::Uuid;
@@ -24,16 +22,62 @@ use crate::{
         Executor, ExecutorError, NormalizedConversation, NormalizedEntry, NormalizedEntryType,
     },
     models::task::Task,
+    services::SecretRedactor,
     utils::shell::get_shell_command,
 };
 
+/// Default Gemini CLI command line
+pub const GEMINI_COMMAND: &str = "npx @google/gemini-cli@latest --yolo";
+
 /// An executor that uses Gemini CLI to process tasks
-pub struct GeminiExecutor;
+pub struct GeminiExecutor {
+    command: String,
+    env_vars: HashMap<String, String>,
+}
+
+impl Default for GeminiExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GeminiExecutor {
+    /// Create a new GeminiExecutor with default settings
+    pub fn new() -> Self {
+        Self::with_command(GEMINI_COMMAND.to_string())
+    }
+
+    /// Create a new GeminiExecutor around a custom Gemini CLI command
+    pub fn with_command(command: String) -> Self {
+        Self {
+            command,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+}
 
 /// An executor that continues a Gemini task with context from previous execution
 pub struct GeminiFollowupExecutor {
     pub attempt_id: Uuid,
     pub prompt: String,
+    executor: GeminiExecutor,
+}
+
+impl GeminiFollowupExecutor {
+    /// Create a new GeminiFollowupExecutor that spawns through the given executor's command
+    pub fn with_executor(attempt_id: Uuid, prompt: String, executor: GeminiExecutor) -> Self {
+        Self {
+            attempt_id,
+            prompt,
+            executor,
+        }
+    }
 }
 
 #[async_trait]
@@ -66,10 +110,9 @@ Task title: {}"#,
             )
         };
 
-        let mut command = Self::create_gemini_command(worktree_path);
+        let mut command = self.create_gemini_command(worktree_path);
 
-        let mut child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let mut child = crate::executors::sandbox::group_spawn(&mut command, true) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, "Gemini")
                     .with_task(task_id, Some(task.title.clone()))
@@ -208,15 +251,14 @@ Task title: {}"#,
         })
     }
 
-    // Note: Gemini streaming is handled by the Gemini-specific WAL system.
-    // See emit_content_batch() method which calls GeminiExecutor::push_patch().
+    // Note: Gemini streams chunked output itself instead of going through
+    // stream_stdout_to_db; see emit_message_patch() which calls GeminiExecutor::push_patch().
 }
 
 impl GeminiExecutor {
     /// Create a standardized Gemini CLI command
-    fn create_gemini_command(worktree_path: &str) -> Command {
+    fn create_gemini_command(&self, worktree_path: &str) -> Command {
         let (shell_cmd, shell_arg) = get_shell_command();
-        let gemini_command = "npx @google/gemini-cli@latest --yolo";
 
         let mut command = Command::new(shell_cmd);
         command
@@ -226,8 +268,9 @@ impl GeminiExecutor {
             .stderr(Stdio::piped())
             .current_dir(worktree_path)
             .arg(shell_arg)
-            .arg(gemini_command)
-            .env("NODE_NO_WARNINGS", "1");
+            .arg(&self.command)
+            .env("NODE_NO_WARNINGS", "1")
+            .envs(&self.env_vars);
         command
     }
 
@@ -297,20 +340,12 @@ impl GeminiExecutor {
         ));
     }
 
-    /// Push patches to the Gemini WAL system
+    /// Push patches to the shared execution WAL
     pub fn push_patch(execution_process_id: Uuid, patches: Vec<Value>, content_length: usize) {
         GeminiStreaming::push_patch(execution_process_id, patches, content_length);
     }
 
-    /// Get WAL batches for an execution process, optionally filtering by cursor
-    pub fn get_wal_batches(
-        execution_process_id: Uuid,
-        after_batch_id: Option<u64>,
-    ) -> Option<Vec<GeminiPatchBatch>> {
-        GeminiStreaming::get_wal_batches(execution_process_id, after_batch_id)
-    }
-
-    /// Clean up WAL when execution process finishes
+    /// Flush remaining content and close the WAL when execution process finishes
     pub async fn finalize_execution(
         pool: &sqlx::SqlitePool,
         execution_process_id: Uuid,
@@ -487,16 +522,17 @@ You are continuing work on the above task. The execution history shows what has
             comprehensive_prompt.len()
         );
 
-        let mut command = GeminiExecutor::create_gemini_command(worktree_path);
+        let mut command = self.executor.create_gemini_command(worktree_path);
 
-        let mut child = command.group_spawn().map_err(|e| {
-            crate::executor::SpawnContext::from_command(&command, "Gemini")
-                .with_context(format!(
-                    "Gemini CLI followup execution with context for attempt {}",
-                    self.attempt_id
-                ))
-                .spawn_error(e)
-        })?;
+        let mut child =
+            crate::executors::sandbox::group_spawn(&mut command, true).map_err(|e| {
+                crate::executor::SpawnContext::from_command(&command, "Gemini")
+                    .with_context(format!(
+                        "Gemini CLI followup execution with context for attempt {}",
+                        self.attempt_id
+                    ))
+                    .spawn_error(e)
+            })?;
 
         self.send_prompt_to_stdin(&mut child, &command, comprehensive_prompt)
             .await?;
@@ -599,8 +635,7 @@ impl Executor for GeminiFollowupExecutor {
         worktree_path: &str,
     ) -> Result<NormalizedConversation, String> {
         // Reuse the same logic as the main GeminiExecutor
-        let main_executor = GeminiExecutor;
-        main_executor.normalize_logs(logs, worktree_path)
+        self.executor.normalize_logs(logs, worktree_path)
     }
 }
 
@@ -608,6 +643,17 @@ impl GeminiExecutor {
     /// Format Gemini CLI output by inserting line breaks where periods are directly
     /// followed by capital letters (common Gemini CLI formatting issue).
     /// Handles both intra-chunk and cross-chunk period-to-capital transitions.
+    /// Convert bytes read to text with secrets redacted, before formatting can split them
+    fn redacted_chunk(
+        redactor: &SecretRedactor,
+        pending: &mut String,
+        bytes: &[u8],
+        accumulated_message: &str,
+    ) -> String {
+        let raw_chunk = redactor.redact_chunk(pending, &String::from_utf8_lossy(bytes));
+        Self::format_gemini_output(&raw_chunk, accumulated_message)
+    }
+
     fn format_gemini_output(content: &str, accumulated_message: &str) -> String {
         let mut result = String::with_capacity(content.len() + 100); // Reserve some extra space for potential newlines
         let chars: Vec<char> = content.chars().collect();
@@ -669,6 +715,10 @@ impl GeminiExecutor {
         let mut read_buf = vec![0u8; chunk_limit.min(max_chunk_size())]; // Use configurable chunk limit, capped for memory efficiency
         let mut last_chunk_emit = Instant::now();
 
+        // Project secrets never reach the WAL or the database
+        let redactor = SecretRedactor::for_process(&pool, execution_process_id).await;
+        let mut pending_redaction = String::new();
+
         // Configuration for WAL and DB management
         let config = GeminiStreamConfig::default();
 
@@ -682,7 +732,12 @@ impl GeminiExecutor {
         loop {
             match reader.read(&mut read_buf).await {
                 Ok(0) => {
-                    // EOF: emit final content and flush to database
+                    // EOF: what was held back can no longer be part of a secret
+                    let rest = Self::format_gemini_output(&pending_redaction, &current_message);
+                    current_message.push_str(&rest);
+                    db_buffer.push_str(&rest);
+
+                    // Emit final content and flush to database
                     Self::emit_final_content(
                         execution_process_id,
                         &current_message,
@@ -695,9 +750,12 @@ impl GeminiExecutor {
                     break;
                 }
                 Ok(n) => {
-                    // Convert bytes to string and apply Gemini-specific formatting
-                    let raw_chunk = String::from_utf8_lossy(&read_buf[..n]);
-                    let formatted_chunk = Self::format_gemini_output(&raw_chunk, &current_message);
+                    let formatted_chunk = Self::redacted_chunk(
+                        &redactor,
+                        &mut pending_redaction,
+                        &read_buf[..n],
+                        &current_message,
+                    );
 
                     // Add to both buffers
                     current_message.push_str(&formatted_chunk);
@@ -780,3 +838,29 @@ impl GeminiExecutor {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets_never_reach_the_stored_chunks() {
+        let redactor = SecretRedactor::new(vec!["sk.Live-123".to_string()]);
+        let output = b"Using key sk.Live-123.Then done.";
+        for chunk_size in 1..output.len() {
+            // As stream_gemini_chunked builds its database buffer
+            let mut pending = String::new();
+            let mut db_buffer = String::new();
+            for bytes in output.chunks(chunk_size) {
+                let chunk =
+                    GeminiExecutor::redacted_chunk(&redactor, &mut pending, bytes, &db_buffer);
+                assert!(!chunk.contains("sk.Live"));
+                db_buffer.push_str(&chunk);
+            }
+            db_buffer.push_str(&GeminiExecutor::format_gemini_output(&pending, &db_buffer));
+
+            assert!(!db_buffer.contains("sk.Live-123"));
+            assert_eq!(db_buffer, "Using key [REDACTED].\nThen done.");
+        }
+    }
+}
@@ -1,17 +1,28 @@
+pub mod aider;
 pub mod amp;
 pub mod ccr;
 pub mod charm_opencode;
+pub mod check_script;
 pub mod claude;
+pub mod codex;
+pub mod custom;
 pub mod dev_server;
 pub mod echo;
 pub mod gemini;
+pub mod sandbox;
 pub mod setup_script;
 pub mod sst_opencode;
+pub mod usage;
+pub mod wal;
 
+pub use aider::{AiderExecutor, AiderFollowupExecutor};
 pub use amp::{AmpExecutor, AmpFollowupExecutor};
 pub use ccr::{CCRExecutor, CCRFollowupExecutor};
 pub use charm_opencode::{CharmOpencodeExecutor, CharmOpencodeFollowupExecutor};
+pub use check_script::CheckScriptExecutor;
 pub use claude::{ClaudeExecutor, ClaudeFollowupExecutor};
+pub use codex::{CodexExecutor, CodexFollowupExecutor};
+pub use custom::{CustomExecutor, CustomFollowupExecutor};
 pub use dev_server::DevServerExecutor;
 pub use echo::EchoExecutor;
 pub use gemini::{GeminiExecutor, GeminiFollowupExecutor};
@@ -0,0 +1,474 @@
+use std::{
+    collections::HashMap,
+    ffi::{OsStr, OsString},
+    future::Future,
+    path::Path,
+    sync::{Mutex, OnceLock},
+};
+
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::models::project::{ContainerRuntime, SandboxSettings};
+
+tokio::task_local! {
+    static SANDBOX: SandboxContext;
+}
+
+/// Where the processes spawned by the current execution run, see `run_sandboxed`
+#[derive(Debug, Clone)]
+pub struct SandboxContext {
+    pub sandbox: ContainerSandbox,
+    pub execution_process_id: Uuid,
+    pub worktree_path: String,
+    /// Git directory of the main repository, which the worktree's `.git` file points into
+    pub git_dir: Option<String>,
+    /// Port of a dev server, reachable on the same port of the host's loopback interface
+    pub published_port: Option<u16>,
+}
+
+/// Runs commands inside a container of the project's image
+#[derive(Debug, Clone)]
+pub struct ContainerSandbox {
+    pub runtime: ContainerRuntime,
+    pub settings: SandboxSettings,
+}
+
+impl ContainerSandbox {
+    /// Sandbox for a project's settings, None when sandboxing is off
+    pub fn for_settings(settings: &SandboxSettings) -> Result<Option<Self>, String> {
+        if !settings.enabled {
+            return Ok(None);
+        }
+        let runtime =
+            ContainerRuntime::detect(settings.runtime).ok_or_else(|| match settings.runtime {
+                Some(runtime) => format!(
+                    "The project sandboxes executions with {}, which is not installed",
+                    runtime.program()
+                ),
+                None => {
+                    "The project sandboxes executions, but neither docker nor podman is installed"
+                        .to_string()
+                }
+            })?;
+        Ok(Some(Self {
+            runtime,
+            settings: settings.clone(),
+        }))
+    }
+}
+
+impl SandboxContext {
+    /// Arguments to the runtime that run `command` in a throwaway container with only the
+    /// worktree and the repository's git directory mounted. Variables set on `command` are
+    /// passed by name only, so their values (often secrets) don't show up in the process list.
+    pub fn container_args(
+        &self,
+        command: &std::process::Command,
+        pipes_stdin: bool,
+        user: Option<(u32, u32)>,
+    ) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec![
+            "run".into(),
+            "--rm".into(),
+            "--init".into(),
+            "--name".into(),
+            container_name(self.execution_process_id).into(),
+        ];
+        if pipes_stdin {
+            args.push("-i".into());
+        }
+
+        let worktree_path = self.worktree_path.as_str();
+        bind_mount(&mut args, worktree_path, false);
+        if let Some(git_dir) = &self.git_dir {
+            // Git commands in the worktree need the repository's objects and refs, but its
+            // hooks and config run commands on the host, so they can't be changed
+            bind_mount(&mut args, git_dir, false);
+            for path in ["config", "hooks"] {
+                let path = Path::new(git_dir).join(path);
+                if path.exists() {
+                    bind_mount(&mut args, &path.to_string_lossy(), true);
+                }
+            }
+        }
+        args.push("--workdir".into());
+        args.push(
+            command
+                .get_current_dir()
+                .map(|dir| dir.as_os_str().to_owned())
+                .unwrap_or_else(|| worktree_path.into()),
+        );
+
+        let settings = &self.sandbox.settings;
+        if settings.isolate_network {
+            args.extend(["--network", "none"].map(OsString::from));
+        }
+        if let Some(port) = self.published_port {
+            args.push("--publish".into());
+            args.push(format!("127.0.0.1:{0}:{0}", port).into());
+        }
+        if let Some(cpus) = settings.cpus {
+            args.push("--cpus".into());
+            args.push(cpus.to_string().into());
+        }
+        if let Some(memory_mb) = settings.memory_mb {
+            args.push("--memory".into());
+            args.push(format!("{}m", memory_mb).into());
+        }
+        match (self.sandbox.runtime, user) {
+            // Rootless podman maps the host user into the container itself
+            (ContainerRuntime::Podman, _) => args.push("--userns=keep-id".into()),
+            (ContainerRuntime::Docker, Some((uid, gid))) => {
+                args.push("--user".into());
+                args.push(format!("{}:{}", uid, gid).into());
+            }
+            (ContainerRuntime::Docker, None) => {}
+        }
+
+        let mut has_home = false;
+        for (name, value) in command.get_envs() {
+            if value.is_some() {
+                has_home |= name == OsStr::new("HOME");
+                args.push("--env".into());
+                args.push(name.to_owned());
+            }
+        }
+        // The host user usually has no home directory in the image
+        if !has_home {
+            args.extend(["--env", "HOME=/tmp"].map(OsString::from));
+        }
+
+        args.push(settings.image.trim().into());
+        args.push(command.get_program().to_owned());
+        args.extend(command.get_args().map(OsStr::to_owned));
+        args
+    }
+}
+
+/// Git directory of the repository `worktree_path` is a worktree of, None when it isn't one.
+/// Its hooks directory is created if missing, so that it can be mounted read-only.
+pub fn repository_git_dir(worktree_path: &str) -> Option<String> {
+    let repo = git2::Repository::open(worktree_path).ok()?;
+    if !repo.is_worktree() {
+        return None;
+    }
+    // The worktree's own git directory names the repository's, relative to itself
+    let common_dir = std::fs::read_to_string(repo.path().join("commondir")).ok()?;
+    let git_dir = repo.path().join(common_dir.trim()).canonicalize().ok()?;
+    if let Err(e) = std::fs::create_dir_all(git_dir.join("hooks")) {
+        tracing::warn!(
+            "Failed to create the hooks directory of {}: {}",
+            worktree_path,
+            e
+        );
+    }
+    Some(git_dir.to_string_lossy().into_owned())
+}
+
+/// Mount `path` of the host at the same path in the container
+fn bind_mount(args: &mut Vec<OsString>, path: &str, read_only: bool) {
+    let mut mount = OsString::from("type=bind,source=");
+    mount.push(path);
+    mount.push(",target=");
+    mount.push(path);
+    if read_only {
+        mount.push(",readonly");
+    }
+    args.push("--mount".into());
+    args.push(mount);
+}
+
+impl ContainerRuntime {
+    /// The runtime to use: `preferred` if it is installed, otherwise docker, then podman
+    pub fn detect(preferred: Option<ContainerRuntime>) -> Option<Self> {
+        match preferred {
+            Some(runtime) => is_installed(runtime.program()).then_some(runtime),
+            None => Self::installed().into_iter().next(),
+        }
+    }
+
+    /// Runtimes found on the PATH
+    pub fn installed() -> Vec<Self> {
+        [ContainerRuntime::Docker, ContainerRuntime::Podman]
+            .into_iter()
+            .filter(|runtime| is_installed(runtime.program()))
+            .collect()
+    }
+}
+
+fn is_installed(program: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|dir| {
+        dir.join(program).is_file()
+            || (cfg!(windows) && dir.join(format!("{}.exe", program)).is_file())
+    })
+}
+
+/// Name of the container an execution process runs in
+pub fn container_name(execution_process_id: Uuid) -> String {
+    format!("vibe-kanban-{}", execution_process_id)
+}
+
+/// Containers of sandboxed execution processes that may still be running
+fn containers() -> &'static Mutex<HashMap<Uuid, ContainerRuntime>> {
+    static CONTAINERS: OnceLock<Mutex<HashMap<Uuid, ContainerRuntime>>> = OnceLock::new();
+    CONTAINERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Run `future`, spawning the processes of the execution inside `context`'s sandbox if any
+pub async fn run_sandboxed<F: Future>(context: Option<SandboxContext>, future: F) -> F::Output {
+    match context {
+        Some(context) => SANDBOX.scope(context, future).await,
+        None => future.await,
+    }
+}
+
+/// Spawn `command` in a new process group, so the whole tree can be killed. Inside
+/// `run_sandboxed` the command runs in a container instead; `pipes_stdin` tells whether the
+/// caller writes to its stdin.
+pub fn group_spawn(command: &mut Command, pipes_stdin: bool) -> std::io::Result<AsyncGroupChild> {
+    let Ok(context) = SANDBOX.try_with(SandboxContext::clone) else {
+        return command.group_spawn();
+    };
+
+    #[cfg(unix)]
+    let user = Some(unsafe { (libc::getuid(), libc::getgid()) });
+    #[cfg(not(unix))]
+    let user = None;
+
+    let std_command = command.as_std();
+    let name = container_name(context.execution_process_id);
+    let mut container = Command::new(context.sandbox.runtime.program());
+    container
+        .kill_on_drop(true)
+        .stdin(if pipes_stdin {
+            std::process::Stdio::piped()
+        } else {
+            std::process::Stdio::null()
+        })
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .args(context.container_args(std_command, pipes_stdin, user))
+        .envs(
+            std_command
+                .get_envs()
+                .filter_map(|(name, value)| Some((name.to_owned(), value?.to_owned()))),
+        );
+
+    tracing::info!(
+        "Running execution process {} in {} container {}",
+        context.execution_process_id,
+        context.sandbox.runtime.program(),
+        name
+    );
+    let child = container.group_spawn()?;
+    containers()
+        .lock()
+        .unwrap()
+        .insert(context.execution_process_id, context.sandbox.runtime);
+    Ok(child)
+}
+
+/// Stop tracking the container of a process that exited on its own
+pub fn forget_container(execution_process_id: Uuid) {
+    containers().lock().unwrap().remove(&execution_process_id);
+}
+
+/// Remove the container of a killed process, which outlives the runtime's CLI when that
+/// is killed before it could stop the container
+pub async fn remove_container(execution_process_id: Uuid) {
+    let Some(runtime) = containers().lock().unwrap().remove(&execution_process_id) else {
+        return;
+    };
+    let name = container_name(execution_process_id);
+    let result = Command::new(runtime.program())
+        .args(["rm", "--force", &name])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .await;
+    if let Err(e) = result {
+        tracing::warn!("Failed to remove container {}: {}", name, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(runtime: ContainerRuntime, settings: SandboxSettings) -> ContainerSandbox {
+        ContainerSandbox {
+            runtime,
+            settings: SandboxSettings {
+                enabled: true,
+                image: "node:20 ".to_string(),
+                ..settings
+            },
+        }
+    }
+
+    fn context(sandbox: ContainerSandbox, worktree_path: &str) -> SandboxContext {
+        SandboxContext {
+            sandbox,
+            execution_process_id: Uuid::nil(),
+            worktree_path: worktree_path.to_string(),
+            git_dir: None,
+            published_port: None,
+        }
+    }
+
+    fn strings(args: Vec<OsString>) -> Vec<String> {
+        args.into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_container_args() {
+        let mut command = std::process::Command::new("bash");
+        command
+            .arg("-c")
+            .arg("npx -y @anthropic-ai/claude-code@latest -p")
+            .current_dir("/tmp/worktrees/vk-1")
+            .env("ANTHROPIC_API_KEY", "sk-secret");
+
+        let docker = sandbox(
+            ContainerRuntime::Docker,
+            SandboxSettings {
+                isolate_network: true,
+                cpus: Some(1.5),
+                memory_mb: Some(2048),
+                ..Default::default()
+            },
+        );
+        let args = strings(context(docker, "/tmp/worktrees/vk-1").container_args(
+            &command,
+            true,
+            Some((1000, 1000)),
+        ));
+        assert_eq!(
+            args,
+            [
+                "run",
+                "--rm",
+                "--init",
+                "--name",
+                "vibe-kanban-00000000-0000-0000-0000-000000000000",
+                "-i",
+                "--mount",
+                "type=bind,source=/tmp/worktrees/vk-1,target=/tmp/worktrees/vk-1",
+                "--workdir",
+                "/tmp/worktrees/vk-1",
+                "--network",
+                "none",
+                "--cpus",
+                "1.5",
+                "--memory",
+                "2048m",
+                "--user",
+                "1000:1000",
+                "--env",
+                "ANTHROPIC_API_KEY",
+                "--env",
+                "HOME=/tmp",
+                "node:20",
+                "bash",
+                "-c",
+                "npx -y @anthropic-ai/claude-code@latest -p",
+            ]
+        );
+        assert!(!args.iter().any(|arg| arg.contains("sk-secret")));
+
+        let podman = sandbox(ContainerRuntime::Podman, SandboxSettings::default());
+        let mut script = std::process::Command::new("sh");
+        script.arg("-c").arg("npm test").env("HOME", "/home/agent");
+        let args = strings(context(podman, "/tmp/worktrees/vk-2").container_args(
+            &script,
+            false,
+            Some((1000, 1000)),
+        ));
+        assert!(!args.contains(&"-i".to_string()));
+        assert!(!args.contains(&"--network".to_string()));
+        assert!(!args.contains(&"--publish".to_string()));
+        assert!(args.contains(&"--userns=keep-id".to_string()));
+        assert!(!args.contains(&"HOME=/tmp".to_string()));
+        // Without a working directory of its own, the command runs in the worktree
+        let workdir = args.iter().position(|arg| arg == "--workdir").unwrap();
+        assert_eq!(args[workdir + 1], "/tmp/worktrees/vk-2");
+    }
+
+    #[test]
+    fn test_worktrees_get_the_repository_git_dir() {
+        let repo_dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(repo_dir.path()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        let worktrees = tempfile::tempdir().unwrap();
+        let worktree_path = worktrees.path().join("vk-1");
+        repo.worktree("vk-1", &worktree_path, None).unwrap();
+
+        let worktree_path = worktree_path.to_string_lossy().to_string();
+        let git_dir = repository_git_dir(&worktree_path).unwrap();
+        assert_eq!(
+            Path::new(&git_dir).canonicalize().unwrap(),
+            repo_dir.path().join(".git").canonicalize().unwrap()
+        );
+        assert_eq!(repository_git_dir(&repo_dir.path().to_string_lossy()), None);
+
+        let sandbox_context = SandboxContext {
+            git_dir: Some(git_dir.clone()),
+            ..context(
+                sandbox(ContainerRuntime::Docker, SandboxSettings::default()),
+                &worktree_path,
+            )
+        };
+        let args = strings(sandbox_context.container_args(
+            &std::process::Command::new("git"),
+            false,
+            None,
+        ));
+        let mounts: Vec<&String> = args
+            .windows(2)
+            .filter(|pair| pair[0] == "--mount")
+            .map(|pair| &pair[1])
+            .collect();
+        assert_eq!(
+            mounts,
+            [
+                &format!("type=bind,source={0},target={0}", worktree_path),
+                &format!("type=bind,source={0},target={0}", git_dir),
+                &format!(
+                    "type=bind,source={0}/config,target={0}/config,readonly",
+                    git_dir
+                ),
+                &format!(
+                    "type=bind,source={0}/hooks,target={0}/hooks,readonly",
+                    git_dir
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dev_server_port_is_published_on_loopback() {
+        let dev_server = SandboxContext {
+            published_port: Some(41234),
+            ..context(
+                sandbox(ContainerRuntime::Docker, SandboxSettings::default()),
+                "/tmp/worktrees/vk-1",
+            )
+        };
+        let args =
+            strings(dev_server.container_args(&std::process::Command::new("npm"), false, None));
+        let publish = args.iter().position(|arg| arg == "--publish").unwrap();
+        assert_eq!(args[publish + 1], "127.0.0.1:41234:41234");
+    }
+}
@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use tokio::process::Command;
 use uuid::Uuid;
 
@@ -12,11 +14,21 @@ use crate::{
 /// Executor for running project setup scripts
 pub struct SetupScriptExecutor {
     pub script: String,
+    pub env_vars: HashMap<String, String>,
 }
 
 impl SetupScriptExecutor {
     pub fn new(script: String) -> Self {
-        Self { script }
+        Self {
+            script,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
     }
 }
 
@@ -45,9 +57,10 @@ impl Executor for SetupScriptExecutor {
             .stderr(std::process::Stdio::piped())
             .arg(shell_arg)
             .arg(&self.script)
-            .current_dir(worktree_path);
+            .current_dir(worktree_path)
+            .envs(&self.env_vars);
 
-        let child = command.group_spawn().map_err(|e| {
+        let child = crate::executors::sandbox::group_spawn(&mut command, false).map_err(|e| {
             crate::executor::SpawnContext::from_command(&command, "SetupScript")
                 .with_task(task_id, Some(task.title.clone()))
                 .with_context("Setup script execution")
@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use serde_json::{json, Value};
 use tokio::{
     io::{AsyncBufReadExt, BufReader},
@@ -9,7 +11,9 @@ use uuid::Uuid;
 
 use crate::{
     executor::{Executor, ExecutorError, NormalizedConversation, NormalizedEntry},
+    executors::wal::NormalizedLogFeed,
     models::{execution_process::ExecutionProcess, executor_session::ExecutorSession, task::Task},
+    services::SecretRedactor,
     utils::shell::get_shell_command,
 };
 
@@ -91,6 +95,8 @@ pub async fn stream_opencode_stderr_to_db(
     let mut reader = BufReader::new(output);
     let mut line = String::new();
     let mut session_extracted = false;
+    let mut log_feed = NormalizedLogFeed::for_process(&pool, execution_process_id).await;
+    let redactor = SecretRedactor::for_process(&pool, execution_process_id).await;
 
     loop {
         line.clear();
@@ -98,7 +104,9 @@ pub async fn stream_opencode_stderr_to_db(
         match reader.read_line(&mut line).await {
             Ok(0) => break, // EOF
             Ok(_) => {
-                line = line.trim_end_matches(['\r', '\n']).to_string();
+                line = redactor
+                    .redact(line.trim_end_matches(['\r', '\n']))
+                    .into_owned();
 
                 let content = process_line_for_content(
                     &line,
@@ -116,6 +124,9 @@ pub async fn stream_opencode_stderr_to_db(
                         stdout,
                         stderr,
                     );
+                    if let (Some(feed), Some(stdout)) = (log_feed.as_mut(), stdout.as_deref()) {
+                        feed.push(stdout);
+                    }
                     if let Err(e) = ExecutionProcess::append_output(
                         &pool,
                         execution_process_id,
@@ -138,6 +149,10 @@ pub async fn stream_opencode_stderr_to_db(
             }
         }
     }
+
+    if let Some(feed) = log_feed {
+        feed.finish();
+    }
 }
 
 /// Format OpenCode clean content as normalized JSON entries for direct database storage
@@ -213,10 +228,14 @@ fn format_opencode_content_as_normalized_json(content: &str, worktree_path: &str
     results.join("\n") + "\n"
 }
 
+/// Default SST Opencode command line
+pub const SST_OPENCODE_COMMAND: &str = "npx -y opencode-ai@latest run --print-logs";
+
 /// An executor that uses SST Opencode CLI to process tasks
 pub struct SstOpencodeExecutor {
     executor_type: String,
     command: String,
+    env_vars: HashMap<String, String>,
 }
 
 impl Default for SstOpencodeExecutor {
@@ -228,11 +247,23 @@ impl Default for SstOpencodeExecutor {
 impl SstOpencodeExecutor {
     /// Create a new SstOpencodeExecutor with default settings
     pub fn new() -> Self {
+        Self::with_command(SST_OPENCODE_COMMAND.to_string())
+    }
+
+    /// Create a new SstOpencodeExecutor around a custom Opencode command
+    pub fn with_command(command: String) -> Self {
         Self {
             executor_type: "SST Opencode".to_string(),
-            command: "npx -y opencode-ai@latest run --print-logs".to_string(),
+            command,
+            env_vars: HashMap::new(),
         }
     }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
 }
 
 /// An executor that resumes an SST Opencode session
@@ -241,18 +272,26 @@ pub struct SstOpencodeFollowupExecutor {
     pub prompt: String,
     executor_type: String,
     command_base: String,
+    env_vars: HashMap<String, String>,
 }
 
 impl SstOpencodeFollowupExecutor {
-    /// Create a new SstOpencodeFollowupExecutor with default settings
-    pub fn new(session_id: String, prompt: String) -> Self {
+    /// Create a new SstOpencodeFollowupExecutor around a custom Opencode command
+    pub fn with_command(session_id: String, prompt: String, command_base: String) -> Self {
         Self {
             session_id,
             prompt,
             executor_type: "SST Opencode".to_string(),
-            command_base: "npx -y opencode-ai@latest run --print-logs".to_string(),
+            command_base,
+            env_vars: HashMap::new(),
         }
     }
+
+    /// Set extra environment variables for the spawned process
+    pub fn with_env_vars(mut self, env_vars: HashMap<String, String>) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
 }
 
 #[async_trait]
@@ -298,10 +337,10 @@ Task title: {}"#,
             .current_dir(worktree_path)
             .arg(shell_arg)
             .arg(opencode_command)
-            .env("NODE_NO_WARNINGS", "1");
+            .env("NODE_NO_WARNINGS", "1")
+            .envs(&self.env_vars);
 
-        let mut child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let mut child = crate::executors::sandbox::group_spawn(&mut command, true) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, &self.executor_type)
                     .with_task(task_id, Some(task.title.clone()))
@@ -421,10 +460,10 @@ impl Executor for SstOpencodeFollowupExecutor {
             .current_dir(worktree_path)
             .arg(shell_arg)
             .arg(&opencode_command)
-            .env("NODE_NO_WARNINGS", "1");
+            .env("NODE_NO_WARNINGS", "1")
+            .envs(&self.env_vars);
 
-        let mut child = command
-            .group_spawn() // Create new process group so we can kill entire tree
+        let mut child = crate::executors::sandbox::group_spawn(&mut command, true) // Create new process group so we can kill entire tree
             .map_err(|e| {
                 crate::executor::SpawnContext::from_command(&command, &self.executor_type)
                     .with_context(format!(
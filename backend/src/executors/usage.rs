@@ -0,0 +1,216 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::Value;
+
+/// Tokens used for one model, split the way providers bill them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenCounts {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_input_tokens: i64,
+    pub cache_creation_input_tokens: i64,
+}
+
+impl TokenCounts {
+    /// Read counts from a usage object, in Claude's snake_case or Amp's camelCase keys
+    fn from_json(usage: &Value) -> Option<Self> {
+        let count = |snake: &str, camel: &str| {
+            usage
+                .get(snake)
+                .or_else(|| usage.get(camel))
+                .and_then(Value::as_i64)
+        };
+        let input = count("input_tokens", "inputTokens");
+        let output = count("output_tokens", "outputTokens");
+        let cache_read = count("cache_read_input_tokens", "cacheReadInputTokens");
+        let cache_creation = count("cache_creation_input_tokens", "cacheCreationInputTokens");
+        if input.is_none() && output.is_none() && cache_read.is_none() && cache_creation.is_none() {
+            return None;
+        }
+        Some(Self {
+            input_tokens: input.unwrap_or(0),
+            output_tokens: output.unwrap_or(0),
+            cache_read_input_tokens: cache_read.unwrap_or(0),
+            cache_creation_input_tokens: cache_creation.unwrap_or(0),
+        })
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_input_tokens += other.cache_read_input_tokens;
+        self.cache_creation_input_tokens += other.cache_creation_input_tokens;
+    }
+}
+
+/// Collects token usage from the JSONL stdout of a coding agent.
+///
+/// Claude repeats a message's usage on every content block of it, so usage is kept per
+/// message id and only the last report counts; its final `result` line carries exact totals
+/// per model and replaces everything seen before. Amp's `token-usage` lines are running
+/// totals, so the latest one per model wins. Executors that print plain text (e.g. Gemini)
+/// report nothing.
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+    usage: HashMap<(String, String), TokenCounts>, // Keyed by (model, message id)
+    dirty: bool,
+}
+
+impl UsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the usage reported on `line`, if any
+    pub fn push_line(&mut self, line: &str) {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            return;
+        }
+        let Ok(json) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+
+        match json.get("type").and_then(Value::as_str) {
+            Some("assistant") => {
+                let Some(message) = json.get("message") else {
+                    return;
+                };
+                let (Some(model), Some(counts)) = (
+                    message.get("model").and_then(Value::as_str),
+                    message.get("usage").and_then(TokenCounts::from_json),
+                ) else {
+                    return;
+                };
+                let id = message
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                self.set(model, id, counts);
+            }
+            Some("result") => {
+                let Some(model_usage) = json.get("modelUsage").and_then(Value::as_object) else {
+                    return;
+                };
+                let totals: Vec<(String, TokenCounts)> = model_usage
+                    .iter()
+                    .filter_map(|(model, usage)| {
+                        TokenCounts::from_json(usage).map(|counts| (model.clone(), counts))
+                    })
+                    .collect();
+                if totals.is_empty() {
+                    return;
+                }
+                self.usage.clear();
+                for (model, counts) in totals {
+                    self.set(&model, "", counts);
+                }
+                self.dirty = true;
+            }
+            Some("token-usage") => {
+                let usage = json.get("usage").unwrap_or(&json);
+                let Some(counts) = TokenCounts::from_json(usage) else {
+                    return;
+                };
+                let model = json
+                    .get("model")
+                    .or_else(|| usage.get("model"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown");
+                self.set(model, "", counts);
+            }
+            _ => {}
+        }
+    }
+
+    fn set(&mut self, model: &str, id: &str, counts: TokenCounts) {
+        let previous = self
+            .usage
+            .insert((model.to_string(), id.to_string()), counts);
+        if previous != Some(counts) {
+            self.dirty = true;
+        }
+    }
+
+    /// Whether usage changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Total usage per model
+    pub fn totals(&self) -> BTreeMap<String, TokenCounts> {
+        let mut totals: BTreeMap<String, TokenCounts> = BTreeMap::new();
+        for ((model, _), counts) in &self.usage {
+            totals.entry(model.clone()).or_default().add(counts);
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claude_usage_counts_each_message_once() {
+        let mut tracker = UsageTracker::new();
+        let block = r#"{"type":"assistant","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","content":[],"usage":{"input_tokens":4,"cache_creation_input_tokens":13497,"cache_read_input_tokens":0,"output_tokens":1}}}"#;
+        tracker.push_line(block);
+        tracker.push_line(block);
+        tracker.push_line(r#"{"type":"assistant","message":{"id":"msg_2","model":"claude-sonnet-4-20250514","content":[],"usage":{"input_tokens":10,"cache_read_input_tokens":13497,"output_tokens":20}}}"#);
+        tracker.push_line(r#"{"type":"user","message":{"content":[]}}"#);
+        tracker.push_line("not json");
+
+        assert!(tracker.take_changed());
+        assert!(!tracker.take_changed());
+        assert_eq!(
+            tracker.totals().get("claude-sonnet-4-20250514"),
+            Some(&TokenCounts {
+                input_tokens: 14,
+                output_tokens: 21,
+                cache_read_input_tokens: 13497,
+                cache_creation_input_tokens: 13497,
+            })
+        );
+
+        // Seeing the same usage again changes nothing
+        tracker.push_line(block);
+        assert!(!tracker.take_changed());
+    }
+
+    #[test]
+    fn test_claude_result_replaces_message_usage() {
+        let mut tracker = UsageTracker::new();
+        tracker.push_line(r#"{"type":"assistant","message":{"id":"msg_1","model":"claude-sonnet-4-20250514","usage":{"input_tokens":4,"output_tokens":1}}}"#);
+        tracker.push_line(r#"{"type":"result","subtype":"success","usage":{"input_tokens":4},"modelUsage":{"claude-sonnet-4-20250514":{"inputTokens":4,"outputTokens":120,"cacheReadInputTokens":50,"cacheCreationInputTokens":7},"claude-3-5-haiku-20241022":{"inputTokens":300,"outputTokens":12}}}"#);
+
+        let totals = tracker.totals();
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals["claude-sonnet-4-20250514"].output_tokens, 120);
+        assert_eq!(
+            totals["claude-sonnet-4-20250514"].cache_read_input_tokens,
+            50
+        );
+        assert_eq!(totals["claude-3-5-haiku-20241022"].input_tokens, 300);
+    }
+
+    #[test]
+    fn test_amp_token_usage_keeps_latest_totals() {
+        let mut tracker = UsageTracker::new();
+        tracker.push_line(r#"{"type":"token-usage","model":"claude-sonnet-4","usage":{"inputTokens":100,"outputTokens":10}}"#);
+        tracker.push_line(r#"{"type":"token-usage","model":"claude-sonnet-4","usage":{"inputTokens":250,"outputTokens":40,"cacheReadInputTokens":90}}"#);
+        tracker.push_line(r#"{"type":"token-usage","inputTokens":5}"#);
+
+        let totals = tracker.totals();
+        assert_eq!(
+            totals["claude-sonnet-4"],
+            TokenCounts {
+                input_tokens: 250,
+                output_tokens: 40,
+                cache_read_input_tokens: 90,
+                cache_creation_input_tokens: 0,
+            }
+        );
+        assert_eq!(totals["unknown"].input_tokens, 5);
+    }
+}
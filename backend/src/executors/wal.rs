@@ -0,0 +1,469 @@
+//! Executor-agnostic streaming log WAL
+//!
+//! Every running execution process gets a Write-Ahead Log of JSON-patch batches
+//! describing how its `NormalizedConversation.entries` evolve. Stream readers
+//! (the SSE endpoint) replay the log from a `batch_id` cursor and then wait for
+//! new batches, so they never need to re-read or re-normalize stdout from SQLite.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use json_patch::{patch, Patch, PatchOperation};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::{
+    executor::{Executor, ExecutorConfig},
+    models::execution_process::ExecutionProcess,
+};
+
+lazy_static::lazy_static! {
+    /// Write-Ahead Log: Maps execution_process_id → WAL state
+    static ref WAL_MAP: Mutex<HashMap<Uuid, WalState>> = Mutex::new(HashMap::new());
+}
+
+/// How long a finished WAL stays around for late or resuming readers
+pub const FINISHED_WAL_RETENTION: Duration = Duration::from_secs(300);
+
+/// A batch of JSON patches for a streaming execution process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchBatch {
+    /// Monotonic batch identifier for cursor-based streaming
+    pub batch_id: u64,
+    /// Array of JSON Patch operations (RFC 6902 format)
+    pub patches: Vec<Value>,
+    /// ISO 8601 timestamp when this batch was created
+    pub timestamp: String,
+    /// Total content length after applying all patches in this batch
+    pub content_length: usize,
+}
+
+/// Configuration for WAL compaction
+#[derive(Debug, Clone)]
+pub struct WalConfig {
+    pub compaction_threshold: usize,
+    pub compaction_interval_ms: u64,
+}
+
+impl Default for WalConfig {
+    fn default() -> Self {
+        Self {
+            compaction_threshold: 40,
+            compaction_interval_ms: 30000,
+        }
+    }
+}
+
+/// WAL state for a single execution process
+#[derive(Debug)]
+pub struct WalState {
+    pub batches: Vec<PatchBatch>,
+    pub total_content_length: usize,
+    pub next_batch_id: u64,
+    pub last_compaction: Instant,
+    pub last_access: Instant,
+    /// Set once the producer has seen EOF; no further batches will be pushed
+    pub finished_at: Option<Instant>,
+    /// Publishes the latest batch_id so readers can wait instead of polling
+    notifier: watch::Sender<u64>,
+}
+
+impl Default for WalState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WalState {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        let (notifier, _) = watch::channel(0);
+        Self {
+            batches: Vec::new(),
+            total_content_length: 0,
+            next_batch_id: 1,
+            last_compaction: now,
+            last_access: now,
+            finished_at: None,
+            notifier,
+        }
+    }
+}
+
+/// Shared WAL utilities
+pub struct ExecutionWal;
+
+impl ExecutionWal {
+    /// Push patches to the WAL of an execution process
+    pub fn push_patch(execution_process_id: Uuid, patches: Vec<Value>, content_length: usize) {
+        let mut wal_map = WAL_MAP.lock().unwrap();
+        let wal_state = wal_map.entry(execution_process_id).or_default();
+        let config = WalConfig::default();
+
+        // Update access time for orphan cleanup
+        wal_state.last_access = Instant::now();
+
+        let batch = PatchBatch {
+            batch_id: wal_state.next_batch_id,
+            patches,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            content_length,
+        };
+
+        wal_state.next_batch_id += 1;
+        wal_state.batches.push(batch);
+        wal_state.total_content_length = content_length;
+
+        if Self::should_compact(wal_state, &config) {
+            Self::compact_wal(wal_state);
+        }
+
+        let latest = wal_state.next_batch_id - 1;
+        wal_state.notifier.send_replace(latest);
+    }
+
+    /// Get WAL batches for an execution process, optionally filtering by cursor
+    pub fn get_wal_batches(
+        execution_process_id: Uuid,
+        after_batch_id: Option<u64>,
+    ) -> Option<Vec<PatchBatch>> {
+        WAL_MAP.lock().ok().and_then(|mut wal_map| {
+            wal_map.get_mut(&execution_process_id).map(|wal_state| {
+                // Update access time when WAL is retrieved
+                wal_state.last_access = Instant::now();
+
+                match after_batch_id {
+                    Some(cursor) => wal_state
+                        .batches
+                        .iter()
+                        .filter(|batch| batch.batch_id > cursor)
+                        .cloned()
+                        .collect(),
+                    None => wal_state.batches.clone(),
+                }
+            })
+        })
+    }
+
+    /// Make sure a WAL exists for the process and subscribe to new batch notifications
+    pub fn subscribe(execution_process_id: Uuid) -> watch::Receiver<u64> {
+        let mut wal_map = WAL_MAP.lock().unwrap();
+        let wal_state = wal_map.entry(execution_process_id).or_default();
+        wal_state.last_access = Instant::now();
+        wal_state.notifier.subscribe()
+    }
+
+    /// Whether a WAL is currently held for the process
+    pub fn exists(execution_process_id: Uuid) -> bool {
+        WAL_MAP
+            .lock()
+            .map(|wal_map| wal_map.contains_key(&execution_process_id))
+            .unwrap_or(false)
+    }
+
+    /// Whether the producer has finished writing to the WAL
+    pub fn is_finished(execution_process_id: Uuid) -> bool {
+        WAL_MAP
+            .lock()
+            .ok()
+            .and_then(|wal_map| {
+                wal_map
+                    .get(&execution_process_id)
+                    .map(|wal_state| wal_state.finished_at.is_some())
+            })
+            .unwrap_or(false)
+    }
+
+    /// Mark the WAL as complete and wake up any waiting readers.
+    /// The WAL is kept for a while so late readers can still replay it.
+    pub fn finish(execution_process_id: Uuid) {
+        if let Ok(mut wal_map) = WAL_MAP.lock() {
+            let wal_state = wal_map.entry(execution_process_id).or_default();
+            wal_state.finished_at = Some(Instant::now());
+            let latest = wal_state.next_batch_id - 1;
+            wal_state.notifier.send_replace(latest);
+        }
+    }
+
+    /// Drop WALs that finished, or were last touched, longer than `retention` ago
+    pub fn purge_expired(retention: Duration) {
+        if let Ok(mut wal_map) = WAL_MAP.lock() {
+            let before = wal_map.len();
+            wal_map.retain(|_, wal_state| match wal_state.finished_at {
+                Some(finished_at) => finished_at.elapsed() < retention,
+                None => wal_state.last_access.elapsed() < retention * 12,
+            });
+            let purged = before - wal_map.len();
+            if purged > 0 {
+                tracing::debug!("Purged {} expired execution WALs", purged);
+            }
+        }
+    }
+
+    /// Check if WAL compaction is needed based on configured thresholds
+    fn should_compact(wal_state: &WalState, config: &WalConfig) -> bool {
+        wal_state.batches.len() >= config.compaction_threshold
+            || wal_state.last_compaction.elapsed().as_millis() as u64
+                >= config.compaction_interval_ms
+    }
+
+    /// Compact WAL by losslessly merging older patches into a snapshot
+    fn compact_wal(wal_state: &mut WalState) {
+        // Need at least a few batches to make compaction worthwhile
+        if wal_state.batches.len() <= 5 {
+            return;
+        }
+
+        // Keep the most recent 3 batches for smooth incremental updates
+        let recent_count = 3;
+        let compact_count = wal_state.batches.len() - recent_count;
+
+        let mut conversation_value = serde_json::json!({ "entries": [] });
+        let mut total_content_length = 0;
+
+        for batch in &wal_state.batches[..compact_count] {
+            let patch_operations: Result<Vec<PatchOperation>, _> = batch
+                .patches
+                .iter()
+                .map(|p| serde_json::from_value(p.clone()))
+                .collect();
+
+            match patch_operations {
+                Ok(ops) => {
+                    if let Err(e) = patch(&mut conversation_value, &Patch(ops)) {
+                        tracing::warn!("Failed to apply patch during compaction: {}, skipping", e);
+                        continue;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to deserialize patch operations: {}, skipping", e);
+                    continue;
+                }
+            }
+            total_content_length = batch.content_length;
+        }
+
+        let final_entries = conversation_value
+            .get("entries")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        // The snapshot takes the id of the newest batch it replaces: readers whose cursor is
+        // behind it get the full state, readers already past it skip it.
+        let snapshot_patch = PatchBatch {
+            batch_id: wal_state.batches[compact_count - 1].batch_id,
+            patches: vec![serde_json::json!({
+                "op": "replace",
+                "path": "/entries",
+                "value": final_entries
+            })],
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            content_length: total_content_length,
+        };
+
+        let mut new_batches = vec![snapshot_patch];
+        new_batches.extend_from_slice(&wal_state.batches[compact_count..]);
+        wal_state.batches = new_batches;
+        wal_state.last_compaction = Instant::now();
+
+        tracing::debug!(
+            "Compacted WAL: {} batches → {} (1 snapshot + {} recent)",
+            compact_count + recent_count,
+            wal_state.batches.len(),
+            recent_count
+        );
+    }
+}
+
+/// Normalizes raw executor output line by line and feeds the resulting entries
+/// into the WAL as JSON patches. The executors' `normalize_logs` implementations
+/// are line-oriented, so only newly completed lines are normalized.
+pub struct NormalizedLogFeed {
+    execution_process_id: Uuid,
+    executor: Box<dyn Executor>,
+    worktree_path: String,
+    /// Trailing output that has not been terminated by a newline yet
+    pending: String,
+    entry_count: usize,
+    content_length: usize,
+}
+
+impl NormalizedLogFeed {
+    pub fn new(
+        execution_process_id: Uuid,
+        executor: Box<dyn Executor>,
+        worktree_path: impl Into<String>,
+    ) -> Self {
+        // Register the WAL up-front so readers attaching early wait on it
+        let _ = ExecutionWal::subscribe(execution_process_id);
+        Self {
+            execution_process_id,
+            executor,
+            worktree_path: worktree_path.into(),
+            pending: String::new(),
+            entry_count: 0,
+            content_length: 0,
+        }
+    }
+
+    /// Build a feed from the execution process record, if its executor can normalize logs
+    pub async fn for_process(pool: &sqlx::SqlitePool, execution_process_id: Uuid) -> Option<Self> {
+        let process = match ExecutionProcess::find_summary_by_id(pool, execution_process_id).await {
+            Ok(Some(process)) => process,
+            Ok(None) => return None,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load execution process {} for log streaming: {}",
+                    execution_process_id,
+                    e
+                );
+                return None;
+            }
+        };
+
+        let config: ExecutorConfig = process.executor_type.as_deref()?.parse().ok()?;
+        Some(Self::new(
+            execution_process_id,
+            config.create_executor(),
+            process.working_directory,
+        ))
+    }
+
+    /// Session id of the agent in a line of its output, if the line has one
+    pub fn parse_session_id(&self, line: &str) -> Option<String> {
+        self.executor.parse_session_id(line)
+    }
+
+    /// Append raw output and publish entries for every completed line
+    pub fn push(&mut self, output: &str) {
+        self.pending.push_str(output);
+
+        let Some(last_newline) = self.pending.rfind('\n') else {
+            return;
+        };
+        let complete: String = self.pending.drain(..=last_newline).collect();
+        self.publish(&complete);
+    }
+
+    /// Flush any unterminated output and mark the feed as complete
+    pub fn finish(mut self) {
+        if !self.pending.trim().is_empty() {
+            let remaining = std::mem::take(&mut self.pending);
+            self.publish(&remaining);
+        }
+        ExecutionWal::finish(self.execution_process_id);
+    }
+
+    fn publish(&mut self, lines: &str) {
+        self.content_length += lines.len();
+
+        let normalized = match self.executor.normalize_logs(lines, &self.worktree_path) {
+            Ok(normalized) => normalized,
+            Err(e) => {
+                tracing::debug!(
+                    "Failed to normalize logs for process {}: {}",
+                    self.execution_process_id,
+                    e
+                );
+                return;
+            }
+        };
+
+        let patches: Vec<Value> = normalized
+            .entries
+            .iter()
+            .map(|entry| {
+                let patch = serde_json::json!({
+                    "op": "add",
+                    "path": format!("/entries/{}", self.entry_count),
+                    "value": entry
+                });
+                self.entry_count += 1;
+                patch
+            })
+            .collect();
+
+        if !patches.is_empty() {
+            ExecutionWal::push_patch(self.execution_process_id, patches, self.content_length);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executors::ClaudeExecutor;
+
+    #[test]
+    fn test_wal_resume_from_cursor_after_compaction() {
+        let process_id = Uuid::new_v4();
+        for i in 0..50 {
+            ExecutionWal::push_patch(
+                process_id,
+                vec![serde_json::json!({
+                    "op": "add",
+                    "path": format!("/entries/{}", i),
+                    "value": {"content": i}
+                })],
+                i,
+            );
+        }
+
+        // A fresh reader reconstructs all 50 entries from the compacted log
+        let mut state = serde_json::json!({ "entries": [] });
+        let batches = ExecutionWal::get_wal_batches(process_id, None).unwrap();
+        for batch in &batches {
+            let ops: Vec<PatchOperation> = batch
+                .patches
+                .iter()
+                .map(|p| serde_json::from_value(p.clone()).unwrap())
+                .collect();
+            patch(&mut state, &Patch(ops)).unwrap();
+        }
+        assert_eq!(state["entries"].as_array().unwrap().len(), 50);
+
+        // A reader resuming near the end only gets newer batches
+        let tail = ExecutionWal::get_wal_batches(process_id, Some(49)).unwrap();
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0].batch_id, 50);
+
+        assert!(!ExecutionWal::is_finished(process_id));
+        ExecutionWal::finish(process_id);
+        assert!(ExecutionWal::is_finished(process_id));
+    }
+
+    #[test]
+    fn test_normalized_log_feed_streams_claude_output() {
+        let process_id = Uuid::new_v4();
+        let mut feed =
+            NormalizedLogFeed::new(process_id, Box::new(ClaudeExecutor::new()), "/tmp/worktree");
+
+        feed.push("{\"type\":\"system\",\"subtype\":\"init\",\"session_id\":\"abc\",");
+        // Nothing is published until the line is complete
+        assert!(ExecutionWal::get_wal_batches(process_id, None)
+            .unwrap()
+            .is_empty());
+        feed.push("\"model\":\"claude-sonnet-4-20250514\"}\n");
+        feed.push(r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Hello"}]},"session_id":"abc"}"#);
+        feed.finish();
+
+        let batches = ExecutionWal::get_wal_batches(process_id, None).unwrap();
+        let paths: Vec<&Value> = batches
+            .iter()
+            .flat_map(|batch| batch.patches.iter())
+            .map(|p| &p["path"])
+            .collect();
+        assert_eq!(paths, vec!["/entries/0", "/entries/1"]);
+        assert_eq!(
+            batches[1].patches[0]["value"]["content"],
+            serde_json::json!("Hello")
+        );
+        assert!(ExecutionWal::is_finished(process_id));
+    }
+}
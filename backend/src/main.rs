@@ -28,9 +28,11 @@ mod utils;
 
 use app_state::AppState;
 use execution_monitor::execution_monitor;
-use models::{ApiResponse, Config};
+use models::{executor_profile::ExecutorProfile, ApiResponse, Config};
 use routes::{
-    auth, config, filesystem, health, projects, stream, task_attempts, task_templates, tasks,
+    audit, auth, config, executor_profiles, filesystem, health, preview, project_checks,
+    project_env_vars, project_members, projects, stream, task_attempts, task_templates, tasks,
+    usage, users, webhooks,
 };
 use services::PrMonitorService;
 
@@ -160,6 +162,9 @@ fn main() -> anyhow::Result<()> {
             let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
             let pool = SqlitePool::connect_with(options).await?;
             sqlx::migrate!("./migrations").run(&pool).await?;
+            if let Err(e) = ExecutorProfile::encrypt_stored_env_vars(&pool).await {
+                tracing::error!("Failed to encrypt executor profile variables: {}", e);
+            }
 
             // Load configuration
             let config_path = utils::config_path();
@@ -190,9 +195,10 @@ fn main() -> anyhow::Result<()> {
             // Public routes (no auth required)
             let public_routes = Router::new()
                 .route("/api/health", get(health::health_check))
-                .route("/api/echo", post(echo_handler));
+                .route("/api/echo", post(echo_handler))
+                .nest("/api", auth::session_router());
 
-            // All routes (no auth required)
+            // All other routes, which need a signed in user in multi-user mode
             let app_routes = Router::new()
                 .nest(
                     "/api",
@@ -202,22 +208,62 @@ fn main() -> anyhow::Result<()> {
                         .merge(task_attempts::task_attempts_router())
                         .merge(stream::stream_router())
                         .merge(task_templates::templates_router())
+                        .merge(executor_profiles::executor_profiles_router())
+                        .merge(webhooks::webhooks_router())
+                        .merge(project_checks::project_checks_router())
+                        .merge(project_env_vars::project_env_vars_router())
+                        .merge(project_members::project_members_router())
+                        .merge(usage::usage_router())
                         .merge(filesystem::filesystem_router())
                         .merge(config::config_router())
                         .merge(auth::auth_router())
+                        .merge(users::users_router())
+                        .merge(audit::audit_router())
                         .route("/sounds/:filename", get(serve_sound_file))
-                        .layer(from_fn_with_state(app_state.clone(), auth::sentry_user_context_middleware)),
+                        .layer(from_fn_with_state(app_state.clone(), auth::sentry_user_context_middleware))
+                        .layer(from_fn_with_state(app_state.clone(), auth::require_user_middleware)),
                 );
+            // Running dev servers of attempts
+            let preview_routes = preview::preview_router()
+                .layer(from_fn_with_state(app_state.clone(), auth::require_user_middleware));
 
-            let app = Router::new()
+            let multi_user = app_state.multi_user();
+            let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+            let app_routes = if multi_user {
+                // On a shared instance the code of a dev server must not run on the API's
+                // origin, where its scripts could call the API as the viewer
+                let preview_port = std::env::var(preview::PREVIEW_PORT_ENV)
+                    .ok()
+                    .and_then(|s| s.trim().parse::<u16>().ok())
+                    .unwrap_or(0);
+                let preview_listener =
+                    tokio::net::TcpListener::bind(format!("{host}:{preview_port}")).await?;
+                let actual_preview_port = preview_listener.local_addr()?.port();
+                app_state.set_preview_port(actual_preview_port);
+                tracing::info!("Previews served on http://{host}:{actual_preview_port}");
+                let preview_app = preview_routes.with_state(app_state.clone());
+                tokio::spawn(async move {
+                    if let Err(e) = axum::serve(preview_listener, preview_app).await {
+                        tracing::error!("Preview server stopped: {}", e);
+                    }
+                });
+                app_routes
+            } else {
+                app_routes.merge(preview_routes)
+            };
+
+            let mut app = Router::new()
                 .merge(public_routes)
                 .merge(app_routes)
                 // Static file serving routes
                 .route("/", get(index_handler))
                 .route("/*path", get(static_handler))
-                .with_state(app_state)
-                .layer(CorsLayer::permissive())
-                .layer(NewSentryLayer::new_from_top());
+                .with_state(app_state);
+            // Other origins must not use the session cookie of a shared instance
+            if !multi_user {
+                app = app.layer(CorsLayer::permissive());
+            }
+            let app = app.layer(NewSentryLayer::new_from_top());
 
             let port = std::env::var("BACKEND_PORT")
                 .or_else(|_| std::env::var("PORT"))
@@ -233,11 +279,17 @@ fn main() -> anyhow::Result<()> {
                     0
                 }); // Use 0 to find free port if no specific port provided
 
-            let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
             let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
             let actual_port = listener.local_addr()?.port(); // get → 53427 (example)
 
             tracing::info!("Server running on http://{host}:{actual_port}");
+            if multi_user {
+                tracing::info!("Multi-user mode is on, requests need a signed in user or an API token");
+            }
+
+            if let Err(e) = std::fs::write(utils::server_port_path(), actual_port.to_string()) {
+                tracing::warn!("Failed to write server port file: {}", e);
+            }
 
             if !cfg!(debug_assertions) {
                 tracing::info!("Opening browser...");
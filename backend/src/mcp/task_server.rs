@@ -7,14 +7,25 @@ use rmcp::{
     },
     schemars, tool, tool_handler, tool_router, Error as RmcpError, ServerHandler,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
-use crate::models::{
-    project::Project,
-    task::{CreateTask, Task, TaskStatus},
+use crate::{
+    models::{
+        api_token::ApiToken,
+        audit_event::{AuditSource, NewAuditEvent},
+        project::Project,
+        task::{CreateTask, Task, TaskStatus},
+        task_attempt::{BranchStatus, MergeResult, TaskAttempt, WorktreeDiff},
+        user::User,
+        webhook::WebhookEvent,
+        ApiResponse,
+    },
+    routes::audit::{Auditor, AUDIT_SOURCE_HEADER},
+    services::WebhookService,
+    utils::{client_api_token, server_port_path},
 };
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -191,9 +202,137 @@ pub struct GetTaskResponse {
     pub project_name: Option<String>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StartExecutionRequest {
+    #[schemars(description = "The ID of the project containing the task")]
+    pub project_id: String,
+    #[schemars(description = "The ID of the task to start an attempt for")]
+    pub task_id: String,
+    #[schemars(
+        description = "Optional executor to run, e.g. 'claude', 'amp', 'gemini' (defaults to the configured executor)"
+    )]
+    pub executor: Option<String>,
+    #[schemars(
+        description = "Optional base branch for the attempt (defaults to the current branch)"
+    )]
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct StartExecutionResponse {
+    pub success: bool,
+    pub message: String,
+    pub attempt_id: String,
+    pub branch: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct TaskAttemptRequest {
+    #[schemars(description = "The ID of the project containing the task")]
+    pub project_id: String,
+    #[schemars(description = "The ID of the task the attempt belongs to")]
+    pub task_id: String,
+    #[schemars(description = "The ID of the task attempt")]
+    pub attempt_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetDiffResponse {
+    pub success: bool,
+    pub attempt_id: String,
+    pub diff: WorktreeDiff,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetBranchStatusResponse {
+    pub success: bool,
+    pub attempt_id: String,
+    pub branch_status: BranchStatus,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StartFollowUpExecutionRequest {
+    #[schemars(description = "The ID of the project containing the task")]
+    pub project_id: String,
+    #[schemars(description = "The ID of the task the attempt belongs to")]
+    pub task_id: String,
+    #[schemars(description = "The ID of the task attempt to continue")]
+    pub attempt_id: String,
+    #[schemars(description = "The follow-up instructions for the coding agent")]
+    pub prompt: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FollowUpResult {
+    actual_attempt_id: Uuid,
+    created_new_attempt: bool,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct StartFollowUpExecutionResponse {
+    pub success: bool,
+    pub message: String,
+    pub attempt_id: String,
+    pub created_new_attempt: bool,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct MergeChangesRequest {
+    #[schemars(description = "The ID of the project containing the task")]
+    pub project_id: String,
+    #[schemars(description = "The ID of the task the attempt belongs to")]
+    pub task_id: String,
+    #[schemars(description = "The ID of the task attempt to merge")]
+    pub attempt_id: String,
+    #[schemars(
+        description = "Optional merge strategy: 'squash', 'merge', 'rebase' or 'keep-commits' (defaults to the project's strategy)"
+    )]
+    pub strategy: Option<String>,
+    #[schemars(
+        description = "Merge even if the project's required checks have not passed on this attempt (defaults to false)"
+    )]
+    pub skip_checks: Option<bool>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct MergeChangesResponse {
+    pub success: bool,
+    pub message: String,
+    pub attempt_id: String,
+    pub merge_commit: Option<String>,
+}
+
+fn error_result(error_response: serde_json::Value) -> CallToolResult {
+    CallToolResult::error(vec![Content::text(
+        serde_json::to_string_pretty(&error_response).unwrap(),
+    )])
+}
+
+fn parse_id(value: &str, name: &str) -> Result<Uuid, CallToolResult> {
+    Uuid::parse_str(value).map_err(|_| {
+        error_result(serde_json::json!({
+            "success": false,
+            "error": format!("Invalid {} format. Must be a valid UUID.", name),
+            "provided_id": value
+        }))
+    })
+}
+
+/// Base URL of the running vibe-kanban server, from `VIBE_KANBAN_URL` or the port file the
+/// server writes on startup
+fn server_url() -> Option<String> {
+    if let Ok(url) = std::env::var("VIBE_KANBAN_URL") {
+        return Some(url.trim_end_matches('/').to_string());
+    }
+    std::fs::read_to_string(server_port_path())
+        .ok()
+        .map(|port| format!("http://127.0.0.1:{}", port.trim()))
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     pub pool: SqlitePool,
+    http: reqwest::Client,
     tool_router: ToolRouter<TaskServer>,
 }
 
@@ -202,9 +341,91 @@ impl TaskServer {
     pub fn new(pool: SqlitePool) -> Self {
         Self {
             pool,
+            http: reqwest::Client::new(),
             tool_router: Self::tool_router(),
         }
     }
+
+    /// Executions are owned by the server process, which tracks and monitors them, so tools
+    /// that start or merge work go through its API instead of touching the database directly.
+    async fn post_to_server<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+    ) -> Result<ApiResponse<T>, String> {
+        let base_url =
+            server_url().ok_or_else(|| "The vibe-kanban server is not running".to_string())?;
+
+        let mut request = self
+            .http
+            .post(format!("{}/api{}", base_url, path))
+            .json(&body);
+        if let Some(token) = client_api_token() {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .header(AUDIT_SOURCE_HEADER, "mcp")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach the vibe-kanban server: {}", e))?;
+        let status = response.status();
+
+        match response.json::<ApiResponse<T>>().await {
+            Ok(api_response) if api_response.success => Ok(api_response),
+            Ok(api_response) => Err(api_response
+                .message
+                .unwrap_or_else(|| format!("Request failed with {}", status))),
+            Err(_) if !status.is_success() => Err(format!("Request failed with {}", status)),
+            Err(e) => Err(format!("Unexpected response from the server: {}", e)),
+        }
+    }
+
+    /// User of the API token in `VIBE_KANBAN_TOKEN`, to attribute the changes made here
+    async fn token_user(&self) -> Option<User> {
+        let token = client_api_token()?;
+        match ApiToken::find_user_by_secret(&self.pool, &token).await {
+            Ok(user) => user,
+            Err(e) => {
+                tracing::warn!("Failed to look up the user of VIBE_KANBAN_TOKEN: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Records the changes tools make on the database directly, the server recording those
+    /// made through its API
+    async fn auditor(&self) -> Auditor {
+        Auditor::new(self.pool.clone(), self.token_user().await, AuditSource::Mcp)
+    }
+
+    /// Check that the attempt belongs to the task and project, returning the parsed ids
+    async fn resolve_attempt(
+        &self,
+        project_id: &str,
+        task_id: &str,
+        attempt_id: &str,
+    ) -> Result<(Uuid, Uuid, Uuid), CallToolResult> {
+        let project_uuid = parse_id(project_id, "project ID")?;
+        let task_uuid = parse_id(task_id, "task ID")?;
+        let attempt_uuid = parse_id(attempt_id, "attempt ID")?;
+
+        match TaskAttempt::exists_for_task(&self.pool, attempt_uuid, task_uuid, project_uuid).await
+        {
+            Ok(true) => Ok((project_uuid, task_uuid, attempt_uuid)),
+            Ok(false) => Err(error_result(serde_json::json!({
+                "success": false,
+                "error": "Task attempt not found in the specified task and project",
+                "attempt_id": attempt_id,
+                "task_id": task_id,
+                "project_id": project_id
+            }))),
+            Err(e) => Err(error_result(serde_json::json!({
+                "success": false,
+                "error": "Failed to check task attempt existence",
+                "details": e.to_string()
+            }))),
+        }
+    }
 }
 
 #[tool_router]
@@ -272,8 +493,28 @@ impl TaskServer {
             parent_task_attempt: None,
         };
 
-        match Task::create(&self.pool, &create_task_data, task_id).await {
-            Ok(_task) => {
+        let auditor = self.auditor().await;
+        let created_by = auditor.actor_id();
+        match Task::create(&self.pool, &create_task_data, task_id, created_by).await {
+            Ok(task) => {
+                auditor
+                    .record(
+                        NewAuditEvent::new("task.create")
+                            .project(project_uuid)
+                            .task(task.id)
+                            .after(&task),
+                    )
+                    .await;
+                WebhookService::dispatch(
+                    &self.pool,
+                    WebhookEvent::TaskCreated,
+                    project_uuid,
+                    task.id,
+                    None,
+                    serde_json::json!({ "task": task }),
+                )
+                .await;
+
                 let success_response = CreateTaskResponse {
                     success: true,
                     task_id: task_id.to_string(),
@@ -571,6 +812,7 @@ impl TaskServer {
                 }
             };
 
+        let before = current_task.clone();
         let new_title = title.unwrap_or(current_task.title);
         let new_description = description.or(current_task.description);
         let new_status = status_enum.unwrap_or(current_task.status);
@@ -588,6 +830,16 @@ impl TaskServer {
         .await
         {
             Ok(updated_task) => {
+                self.auditor()
+                    .await
+                    .record(
+                        NewAuditEvent::new("task.update")
+                            .project(project_uuid)
+                            .task(task_uuid)
+                            .before(&before)
+                            .after(&updated_task),
+                    )
+                    .await;
                 let task_summary = TaskSummary {
                     id: updated_task.id.to_string(),
                     title: updated_task.title,
@@ -661,10 +913,23 @@ impl TaskServer {
 
         match Task::exists(&self.pool, task_uuid, project_uuid).await {
             Ok(true) => {
+                let before = Task::find_by_id_and_project_id(&self.pool, task_uuid, project_uuid)
+                    .await
+                    .ok()
+                    .flatten();
                 // Delete the task
                 match Task::delete(&self.pool, task_uuid, project_uuid).await {
                     Ok(rows_affected) => {
                         if rows_affected > 0 {
+                            self.auditor()
+                                .await
+                                .record(
+                                    NewAuditEvent::new("task.delete")
+                                        .project(project_uuid)
+                                        .task(task_uuid)
+                                        .before(&before),
+                                )
+                                .await;
                             let response = DeleteTaskResponse {
                                 success: true,
                                 message: "Task deleted successfully".to_string(),
@@ -802,6 +1067,276 @@ impl TaskServer {
             }
         }
     }
+
+    #[tool(
+        description = "Start working on a task: creates a new attempt with its own git worktree and branch, and runs the setup script and coding agent in it. `project_id` and `task_id` are required! Returns the `attempt_id` used by the other attempt tools."
+    )]
+    async fn start_execution(
+        &self,
+        Parameters(StartExecutionRequest {
+            project_id,
+            task_id,
+            executor,
+            base_branch,
+        }): Parameters<StartExecutionRequest>,
+    ) -> Result<CallToolResult, RmcpError> {
+        let project_uuid = match parse_id(&project_id, "project ID") {
+            Ok(uuid) => uuid,
+            Err(error) => return Ok(error),
+        };
+        let task_uuid = match parse_id(&task_id, "task ID") {
+            Ok(uuid) => uuid,
+            Err(error) => return Ok(error),
+        };
+
+        match Task::exists(&self.pool, task_uuid, project_uuid).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return Ok(error_result(serde_json::json!({
+                    "success": false,
+                    "error": "Task not found in the specified project",
+                    "task_id": task_id,
+                    "project_id": project_id
+                })));
+            }
+            Err(e) => {
+                return Ok(error_result(serde_json::json!({
+                    "success": false,
+                    "error": "Failed to check task existence",
+                    "details": e.to_string()
+                })));
+            }
+        }
+
+        let body = serde_json::json!({
+            "executor": executor,
+            "base_branch": base_branch,
+            "executor_profile_id": null,
+        });
+        match self
+            .post_to_server::<TaskAttempt>(
+                &format!("/projects/{}/tasks/{}/attempts", project_uuid, task_uuid),
+                body,
+            )
+            .await
+        {
+            Ok(ApiResponse {
+                data: Some(attempt),
+                ..
+            }) => {
+                let response = StartExecutionResponse {
+                    success: true,
+                    message: "Task attempt created and execution started".to_string(),
+                    attempt_id: attempt.id.to_string(),
+                    branch: attempt.branch,
+                };
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&response).unwrap(),
+                )]))
+            }
+            Ok(_) => Ok(error_result(serde_json::json!({
+                "success": false,
+                "error": "Failed to start execution",
+                "details": "The server returned no task attempt"
+            }))),
+            Err(e) => Ok(error_result(serde_json::json!({
+                "success": false,
+                "error": "Failed to start execution",
+                "details": e
+            }))),
+        }
+    }
+
+    #[tool(
+        description = "Get the changes a task attempt made relative to its base branch, as a list of files with their diff chunks. `project_id`, `task_id` and `attempt_id` are required!"
+    )]
+    async fn get_diff(
+        &self,
+        Parameters(TaskAttemptRequest {
+            project_id,
+            task_id,
+            attempt_id,
+        }): Parameters<TaskAttemptRequest>,
+    ) -> Result<CallToolResult, RmcpError> {
+        let (project_uuid, task_uuid, attempt_uuid) = match self
+            .resolve_attempt(&project_id, &task_id, &attempt_id)
+            .await
+        {
+            Ok(ids) => ids,
+            Err(error) => return Ok(error),
+        };
+
+        match TaskAttempt::get_diff(&self.pool, attempt_uuid, task_uuid, project_uuid).await {
+            Ok(diff) => {
+                let response = GetDiffResponse {
+                    success: true,
+                    attempt_id,
+                    diff,
+                };
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&response).unwrap(),
+                )]))
+            }
+            Err(e) => Ok(error_result(serde_json::json!({
+                "success": false,
+                "error": "Failed to get diff",
+                "details": e.to_string()
+            }))),
+        }
+    }
+
+    #[tool(
+        description = "Get the git status of a task attempt's branch: commits ahead of and behind its base branch, uncommitted changes and whether it has been merged. `project_id`, `task_id` and `attempt_id` are required!"
+    )]
+    async fn get_branch_status(
+        &self,
+        Parameters(TaskAttemptRequest {
+            project_id,
+            task_id,
+            attempt_id,
+        }): Parameters<TaskAttemptRequest>,
+    ) -> Result<CallToolResult, RmcpError> {
+        let (project_uuid, task_uuid, attempt_uuid) = match self
+            .resolve_attempt(&project_id, &task_id, &attempt_id)
+            .await
+        {
+            Ok(ids) => ids,
+            Err(error) => return Ok(error),
+        };
+
+        match TaskAttempt::get_branch_status(&self.pool, attempt_uuid, task_uuid, project_uuid)
+            .await
+        {
+            Ok(branch_status) => {
+                let response = GetBranchStatusResponse {
+                    success: true,
+                    attempt_id,
+                    branch_status,
+                };
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&response).unwrap(),
+                )]))
+            }
+            Err(e) => Ok(error_result(serde_json::json!({
+                "success": false,
+                "error": "Failed to get branch status",
+                "details": e.to_string()
+            }))),
+        }
+    }
+
+    #[tool(
+        description = "Send follow-up instructions to the coding agent of a task attempt, continuing its previous session. `project_id`, `task_id`, `attempt_id` and `prompt` are required!"
+    )]
+    async fn start_followup_execution(
+        &self,
+        Parameters(StartFollowUpExecutionRequest {
+            project_id,
+            task_id,
+            attempt_id,
+            prompt,
+        }): Parameters<StartFollowUpExecutionRequest>,
+    ) -> Result<CallToolResult, RmcpError> {
+        let (project_uuid, task_uuid, attempt_uuid) = match self
+            .resolve_attempt(&project_id, &task_id, &attempt_id)
+            .await
+        {
+            Ok(ids) => ids,
+            Err(error) => return Ok(error),
+        };
+
+        match self
+            .post_to_server::<FollowUpResult>(
+                &format!(
+                    "/projects/{}/tasks/{}/attempts/{}/follow-up",
+                    project_uuid, task_uuid, attempt_uuid
+                ),
+                serde_json::json!({ "prompt": prompt }),
+            )
+            .await
+        {
+            Ok(ApiResponse {
+                data: Some(result),
+                message,
+                ..
+            }) => {
+                let response = StartFollowUpExecutionResponse {
+                    success: true,
+                    message: message.unwrap_or_else(|| "Follow-up execution started".to_string()),
+                    attempt_id: result.actual_attempt_id.to_string(),
+                    created_new_attempt: result.created_new_attempt,
+                };
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&response).unwrap(),
+                )]))
+            }
+            Ok(_) => Ok(error_result(serde_json::json!({
+                "success": false,
+                "error": "Failed to start follow-up execution",
+                "details": "The server returned no follow-up result"
+            }))),
+            Err(e) => Ok(error_result(serde_json::json!({
+                "success": false,
+                "error": "Failed to start follow-up execution",
+                "details": e
+            }))),
+        }
+    }
+
+    #[tool(
+        description = "Merge a task attempt's branch into its base branch and mark the task as done. `project_id`, `task_id` and `attempt_id` are required! Check `get_branch_status` first if unsure whether the branch is ready."
+    )]
+    async fn merge_changes(
+        &self,
+        Parameters(MergeChangesRequest {
+            project_id,
+            task_id,
+            attempt_id,
+            strategy,
+            skip_checks,
+        }): Parameters<MergeChangesRequest>,
+    ) -> Result<CallToolResult, RmcpError> {
+        let (project_uuid, task_uuid, attempt_uuid) = match self
+            .resolve_attempt(&project_id, &task_id, &attempt_id)
+            .await
+        {
+            Ok(ids) => ids,
+            Err(error) => return Ok(error),
+        };
+
+        match self
+            .post_to_server::<MergeResult>(
+                &format!(
+                    "/projects/{}/tasks/{}/attempts/{}/merge",
+                    project_uuid, task_uuid, attempt_uuid
+                ),
+                serde_json::json!({
+                    "strategy": strategy,
+                    "skip_checks": skip_checks.unwrap_or(false),
+                }),
+            )
+            .await
+        {
+            Ok(api_response) => {
+                let response = MergeChangesResponse {
+                    success: true,
+                    message: api_response
+                        .message
+                        .unwrap_or_else(|| "Changes merged successfully".to_string()),
+                    attempt_id,
+                    merge_commit: api_response.data.and_then(|result| result.merge_commit),
+                };
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&response).unwrap(),
+                )]))
+            }
+            Err(e) => Ok(error_result(serde_json::json!({
+                "success": false,
+                "error": "Failed to merge changes",
+                "details": e
+            }))),
+        }
+    }
 }
 
 #[tool_handler]
@@ -816,7 +1351,7 @@ impl ServerHandler for TaskServer {
                 name: "vibe-kanban".to_string(),
                 version: "1.0.0".to_string(),
             },
-            instructions: Some("A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. This should be provided to you. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'get_task', 'update_task', 'delete_task'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string()),
+            instructions: Some("A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. This should be provided to you. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'get_task', 'update_task', 'delete_task', 'start_execution', 'get_diff', 'get_branch_status', 'start_followup_execution', 'merge_changes'. Use `start_execution` to have a coding agent work on a task, then inspect its work with `get_diff` and `get_branch_status`, refine it with `start_followup_execution` and finally `merge_changes`. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string()),
         }
     }
 }
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::user::User;
+use crate::services::AuthService;
+
+#[derive(Debug, Clone, Copy, Type, PartialEq)]
+#[sqlx(type_name = "token_kind", rename_all = "lowercase")]
+pub enum TokenKind {
+    Session, // Set as a cookie by the login form
+    Api,     // Created by a user for the CLI, the MCP server or scripts
+}
+
+/// A token a user authenticates with, without its secret
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateApiToken {
+    pub name: String,
+    pub expires_in_days: Option<u32>, // Never expires when omitted
+}
+
+/// A new token with its secret, which is only ever returned here
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct CreatedApiToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
+
+impl ApiToken {
+    /// API tokens of a user, sessions excluded
+    pub async fn find_by_user_id(
+        pool: &SqlitePool,
+        user_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApiToken,
+            r#"SELECT id as "id!: Uuid", user_id as "user_id!: Uuid", name, created_at as "created_at!: DateTime<Utc>", last_used_at as "last_used_at: DateTime<Utc>", expires_at as "expires_at: DateTime<Utc>"
+               FROM api_tokens
+               WHERE user_id = $1 AND kind = $2
+               ORDER BY created_at DESC"#,
+            user_id,
+            TokenKind::Api
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Store a token for `secret`, from `AuthService::generate_token`
+    pub async fn create(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        kind: TokenKind,
+        name: &str,
+        secret: &str,
+        expires_in_days: Option<u32>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let token_hash = AuthService::hash_token(secret);
+        sqlx::query_as!(
+            ApiToken,
+            r#"INSERT INTO api_tokens (id, user_id, kind, name, token_hash, expires_at)
+               VALUES ($1, $2, $3, $4, $5, datetime('now', 'subsec', '+' || $6 || ' days'))
+               RETURNING id as "id!: Uuid", user_id as "user_id!: Uuid", name, created_at as "created_at!: DateTime<Utc>", last_used_at as "last_used_at: DateTime<Utc>", expires_at as "expires_at: DateTime<Utc>""#,
+            id,
+            user_id,
+            kind,
+            name,
+            token_hash,
+            expires_in_days
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// The user a token secret belongs to, if the token exists and has not expired
+    pub async fn find_user_by_secret(
+        pool: &SqlitePool,
+        secret: &str,
+    ) -> Result<Option<User>, sqlx::Error> {
+        let token_hash = AuthService::hash_token(secret);
+        let user = sqlx::query_as!(
+            User,
+            r#"SELECT u.id as "id!: Uuid", u.username, u.is_admin as "is_admin!: bool", u.created_at as "created_at!: DateTime<Utc>", u.updated_at as "updated_at!: DateTime<Utc>"
+               FROM api_tokens t
+               JOIN users u ON u.id = t.user_id
+               WHERE t.token_hash = $1 AND (t.expires_at IS NULL OR t.expires_at > datetime('now', 'subsec'))"#,
+            token_hash
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        if user.is_some() {
+            // At most once a minute, so authenticated requests don't all write
+            sqlx::query!(
+                r#"UPDATE api_tokens SET last_used_at = datetime('now', 'subsec')
+                   WHERE token_hash = $1 AND (last_used_at IS NULL OR last_used_at < datetime('now', '-1 minute'))"#,
+                token_hash
+            )
+            .execute(pool)
+            .await?;
+        }
+        Ok(user)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM api_tokens WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_by_secret(pool: &SqlitePool, secret: &str) -> Result<(), sqlx::Error> {
+        let token_hash = AuthService::hash_token(secret);
+        sqlx::query!("DELETE FROM api_tokens WHERE token_hash = $1", token_hash)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Sign a user out everywhere, except with the session `keep_secret` if given
+    pub async fn delete_sessions(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        keep_secret: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let keep_hash = keep_secret.map(AuthService::hash_token);
+        sqlx::query!(
+            "DELETE FROM api_tokens WHERE user_id = $1 AND kind = $2 AND token_hash IS NOT $3",
+            user_id,
+            TokenKind::Session,
+            keep_hash
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
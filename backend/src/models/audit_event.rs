@@ -0,0 +1,231 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::user::User;
+
+/// Where a change was made from
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, TS, PartialEq)]
+#[sqlx(type_name = "audit_source", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum AuditSource {
+    Api, // The web UI, the CLI or scripts
+    Mcp, // A tool of the MCP task server
+}
+
+/// A recorded change, which is never updated or deleted
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    pub source: AuditSource,
+    pub action: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub attempt_id: Option<Uuid>,
+    pub target: Option<String>,
+    #[ts(type = "unknown")]
+    pub before: Option<Json<Value>>,
+    #[ts(type = "unknown")]
+    pub after: Option<Json<Value>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An event to record, e.g. `NewAuditEvent::new("task.update").task(..).before(&old).after(&new)`
+#[derive(Debug, Clone, Default)]
+pub struct NewAuditEvent {
+    pub action: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub attempt_id: Option<Uuid>,
+    pub target: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl NewAuditEvent {
+    pub fn new(action: &str) -> Self {
+        Self {
+            action: action.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn project(mut self, project_id: Uuid) -> Self {
+        self.project_id = Some(project_id);
+        self
+    }
+
+    pub fn task(mut self, task_id: Uuid) -> Self {
+        self.task_id = Some(task_id);
+        self
+    }
+
+    pub fn attempt(mut self, attempt_id: Uuid) -> Self {
+        self.attempt_id = Some(attempt_id);
+        self
+    }
+
+    pub fn target(mut self, target: impl ToString) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    pub fn before(mut self, value: &impl Serialize) -> Self {
+        self.before = serde_json::to_value(value).ok();
+        self
+    }
+
+    pub fn after(mut self, value: &impl Serialize) -> Self {
+        self.after = serde_json::to_value(value).ok();
+        self
+    }
+}
+
+/// Filters of `AuditEvent::find`, all optional
+#[derive(Debug, Default, Deserialize, TS)]
+#[ts(export)]
+pub struct AuditEventQuery {
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub attempt_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub source: Option<AuditSource>,
+    pub action: Option<String>, // An action, or a prefix ending in '.' such as "task."
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,  // Defaults to 50, at most 200
+    pub offset: Option<u32>, // Events to skip, newest first
+}
+
+/// A page of events, newest first
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct AuditEventPage {
+    pub events: Vec<AuditEvent>,
+    pub has_more: bool,
+}
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 200;
+
+impl AuditEventQuery {
+    fn page_size(&self) -> u32 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+
+    /// Prefix the action has to start with, or the exact action to match
+    fn action_filter(&self) -> (Option<&str>, Option<&str>) {
+        match self.action.as_deref() {
+            Some(action) if action.ends_with('.') => (Some(action), None),
+            action => (None, action),
+        }
+    }
+}
+
+impl AuditEvent {
+    pub async fn create(
+        pool: &SqlitePool,
+        actor: Option<&User>,
+        source: AuditSource,
+        event: NewAuditEvent,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        let actor_id = actor.map(|user| user.id);
+        let actor_name = actor.map(|user| user.username.as_str());
+        let before = event.before.map(Json);
+        let after = event.after.map(Json);
+        sqlx::query!(
+            r#"INSERT INTO audit_events (id, actor_id, actor_name, source, action, project_id, task_id, attempt_id, target, before, after)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"#,
+            id,
+            actor_id,
+            actor_name,
+            source,
+            event.action,
+            event.project_id,
+            event.task_id,
+            event.attempt_id,
+            event.target,
+            before,
+            after
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find(
+        pool: &SqlitePool,
+        query: &AuditEventQuery,
+    ) -> Result<AuditEventPage, sqlx::Error> {
+        let page_size = query.page_size();
+        // One more than asked for, to tell whether there is another page
+        let limit = page_size + 1;
+        let offset = query.offset.unwrap_or(0);
+        let (action_prefix, action) = query.action_filter();
+        let mut events = sqlx::query_as!(
+            AuditEvent,
+            r#"SELECT id as "id!: Uuid", actor_id as "actor_id: Uuid", actor_name, source as "source!: AuditSource", action, project_id as "project_id: Uuid", task_id as "task_id: Uuid", attempt_id as "attempt_id: Uuid", target, before as "before: Json<Value>", after as "after: Json<Value>", created_at as "created_at!: DateTime<Utc>"
+               FROM audit_events
+               WHERE ($1 IS NULL OR project_id = $1)
+                 AND ($2 IS NULL OR task_id = $2)
+                 AND ($3 IS NULL OR attempt_id = $3)
+                 AND ($4 IS NULL OR actor_id = $4)
+                 AND ($5 IS NULL OR source = $5)
+                 AND ($6 IS NULL OR action = $6)
+                 AND ($7 IS NULL OR substr(action, 1, length($7)) = $7)
+                 AND ($8 IS NULL OR created_at >= datetime($8, 'subsec'))
+                 AND ($9 IS NULL OR created_at < datetime($9, 'subsec'))
+               ORDER BY created_at DESC, rowid DESC
+               LIMIT $10 OFFSET $11"#,
+            query.project_id,
+            query.task_id,
+            query.attempt_id,
+            query.actor_id,
+            query.source,
+            action,
+            action_prefix,
+            query.since,
+            query.until,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let has_more = events.len() > page_size as usize;
+        events.truncate(page_size as usize);
+        Ok(AuditEventPage { events, has_more })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_page_size_and_action_filter() {
+        let mut query = AuditEventQuery::default();
+        assert_eq!(query.page_size(), DEFAULT_PAGE_SIZE);
+        assert_eq!(query.action_filter(), (None, None));
+
+        query.limit = Some(10_000);
+        query.action = Some("task.".to_string());
+        assert_eq!(query.page_size(), MAX_PAGE_SIZE);
+        assert_eq!(query.action_filter(), (Some("task."), None));
+
+        query.limit = Some(0);
+        query.action = Some("attempt.merge".to_string());
+        assert_eq!(query.page_size(), 1);
+        assert_eq!(query.action_filter(), (None, Some("attempt.merge")));
+    }
+}
@@ -1,9 +1,9 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use crate::executor::ExecutorConfig;
+use crate::{executor::ExecutorConfig, executors::usage::TokenCounts};
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -20,6 +20,37 @@ pub struct Config {
     pub editor: EditorConfig,
     pub github: GitHubConfig,
     pub analytics_enabled: Option<bool>,
+    pub max_concurrent_agents: Option<u32>, // Coding agents allowed to run at once, None = unlimited
+    pub auto_start_unblocked_tasks: bool,   // Start dependent tasks once all their blockers finish
+    pub execution_timeouts: ExecutionTimeoutConfig,
+    pub model_pricing: HashMap<String, ModelPricing>, // Keyed by model name prefix, e.g. "claude-sonnet-4"
+    pub git_hosts: Vec<GitHostConfig>, // Servers other than github.com to open pull requests on
+}
+
+/// Limits after which a running coding agent is killed
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExecutionTimeoutConfig {
+    pub max_runtime_minutes: Option<u32>, // Wall-clock limit, None = unlimited
+    pub idle_minutes: Option<u32>,        // Limit without any output, None = unlimited
+    pub executors: HashMap<String, ExecutorTimeouts>, // Overrides keyed by executor, e.g. "claude"
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExecutorTimeouts {
+    pub max_runtime_minutes: Option<u32>,
+    pub idle_minutes: Option<u32>,
+}
+
+/// Price of a model in USD per million tokens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_write: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -53,6 +84,26 @@ pub struct GitHubConfig {
     pub default_pr_base: Option<String>,
 }
 
+/// Kind of service a git repository is hosted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "lowercase")]
+pub enum GitProviderKind {
+    GitHub,
+    GitLab,
+    Gitea,
+}
+
+/// A self-hosted server to push branches to and open pull or merge requests on
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitHostConfig {
+    pub provider: GitProviderKind,
+    pub base_url: String, // Web address of the server, e.g. "https://gitlab.example.com"
+    pub token: Option<String>, // Personal access token with API and repository write access
+    pub username: Option<String>, // Sent with the token when pushing, defaults per provider
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 #[serde(rename_all = "lowercase")]
@@ -168,7 +219,104 @@ impl Default for Config {
             editor: EditorConfig::default(),
             github: GitHubConfig::default(),
             analytics_enabled: None,
+            max_concurrent_agents: None,
+            auto_start_unblocked_tasks: false,
+            execution_timeouts: ExecutionTimeoutConfig::default(),
+            model_pricing: ModelPricing::defaults(),
+            git_hosts: Vec::new(),
+        }
+    }
+}
+
+impl Default for ExecutionTimeoutConfig {
+    /// No limits, so an agent thinking quietly for a long time isn't killed unless asked for
+    fn default() -> Self {
+        Self {
+            max_runtime_minutes: None,
+            idle_minutes: None,
+            executors: HashMap::new(),
+        }
+    }
+}
+
+impl ExecutionTimeoutConfig {
+    /// Timeouts for `executor`, with its overrides applied over the defaults
+    pub fn for_executor(&self, executor: Option<&str>) -> ExecutorTimeouts {
+        let overrides = executor
+            .and_then(|e| self.executors.get(e))
+            .copied()
+            .unwrap_or_default();
+        ExecutorTimeouts {
+            max_runtime_minutes: overrides.max_runtime_minutes.or(self.max_runtime_minutes),
+            idle_minutes: overrides.idle_minutes.or(self.idle_minutes),
+        }
+    }
+}
+
+impl ExecutorTimeouts {
+    /// Why a process started at `started_at` that last wrote output at `last_output_at`
+    /// should be killed at `now`, if it should
+    pub fn exceeded(
+        &self,
+        started_at: chrono::DateTime<chrono::Utc>,
+        last_output_at: Option<chrono::DateTime<chrono::Utc>>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<String> {
+        if let Some(limit) = self.max_runtime_minutes {
+            if now - started_at >= chrono::Duration::minutes(i64::from(limit)) {
+                return Some(format!("Exceeded the {} minute time limit", limit));
+            }
         }
+        if let Some(limit) = self.idle_minutes {
+            let last_activity = last_output_at.unwrap_or(started_at).max(started_at);
+            if now - last_activity >= chrono::Duration::minutes(i64::from(limit)) {
+                return Some(format!("No output for {} minutes", limit));
+            }
+        }
+        None
+    }
+}
+
+impl ModelPricing {
+    /// List prices of the Claude models the built-in executors use
+    pub fn defaults() -> HashMap<String, ModelPricing> {
+        [
+            ("claude-opus-4", 15.0, 75.0, 1.5, 18.75),
+            ("claude-sonnet-4", 3.0, 15.0, 0.3, 3.75),
+            ("claude-3-7-sonnet", 3.0, 15.0, 0.3, 3.75),
+            ("claude-3-5-sonnet", 3.0, 15.0, 0.3, 3.75),
+            ("claude-3-5-haiku", 0.8, 4.0, 0.08, 1.0),
+        ]
+        .into_iter()
+        .map(|(model, input, output, cache_read, cache_write)| {
+            (
+                model.to_string(),
+                ModelPricing {
+                    input,
+                    output,
+                    cache_read,
+                    cache_write,
+                },
+            )
+        })
+        .collect()
+    }
+
+    /// Pricing of `model`, from the entry with the longest key that `model` starts with
+    pub fn lookup(pricing: &HashMap<String, ModelPricing>, model: &str) -> Option<ModelPricing> {
+        pricing
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| *price)
+    }
+
+    pub fn cost_usd(&self, counts: &TokenCounts) -> f64 {
+        (counts.input_tokens as f64 * self.input
+            + counts.output_tokens as f64 * self.output
+            + counts.cache_read_input_tokens as f64 * self.cache_read
+            + counts.cache_creation_input_tokens as f64 * self.cache_write)
+            / 1_000_000.0
     }
 }
 
@@ -193,6 +341,16 @@ impl Default for GitHubConfig {
     }
 }
 
+impl std::fmt::Display for GitProviderKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitProviderKind::GitHub => write!(f, "GitHub"),
+            GitProviderKind::GitLab => write!(f, "GitLab"),
+            GitProviderKind::Gitea => write!(f, "Gitea"),
+        }
+    }
+}
+
 impl EditorConfig {
     pub fn get_command(&self) -> Vec<String> {
         match &self.editor_type {
@@ -339,4 +497,154 @@ impl Config {
         std::fs::write(config_path, content)?;
         Ok(())
     }
+
+    /// The config as shown to users who aren't admins of a shared server, with access tokens
+    /// swapped for a placeholder so it still shows which ones are set
+    pub fn redacted(&self) -> Self {
+        let redact = |token: &Option<String>| token.as_ref().map(|_| REDACTED.to_string());
+        let mut config = self.clone();
+        config.github.pat = redact(&self.github.pat);
+        config.github.token = redact(&self.github.token);
+        for host in &mut config.git_hosts {
+            host.token = redact(&host.token);
+        }
+        config
+    }
+}
+
+const REDACTED: &str = "********";
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::*;
+
+    #[test]
+    fn test_redacted_hides_tokens() {
+        let mut config = Config::default();
+        config.github.token = Some("gho_secret".to_string());
+        config.git_hosts.push(GitHostConfig {
+            provider: GitProviderKind::GitLab,
+            base_url: "https://gitlab.example.com".to_string(),
+            token: Some("glpat-secret".to_string()),
+            username: None,
+        });
+
+        let redacted = config.redacted();
+        assert_eq!(redacted.github.token.as_deref(), Some(REDACTED));
+        assert_eq!(redacted.github.pat, None);
+        assert_eq!(redacted.git_hosts[0].token.as_deref(), Some(REDACTED));
+        assert_eq!(redacted.git_hosts[0].base_url, "https://gitlab.example.com");
+    }
+
+    #[test]
+    fn test_execution_timeouts_for_executor() {
+        let mut config = ExecutionTimeoutConfig {
+            max_runtime_minutes: Some(120),
+            idle_minutes: Some(30),
+            executors: HashMap::new(),
+        };
+        config.executors.insert(
+            "amp".to_string(),
+            ExecutorTimeouts {
+                max_runtime_minutes: None,
+                idle_minutes: Some(5),
+            },
+        );
+
+        assert_eq!(
+            config.for_executor(Some("amp")),
+            ExecutorTimeouts {
+                max_runtime_minutes: Some(120),
+                idle_minutes: Some(5),
+            }
+        );
+        assert_eq!(
+            config.for_executor(Some("claude")),
+            ExecutorTimeouts {
+                max_runtime_minutes: Some(120),
+                idle_minutes: Some(30),
+            }
+        );
+        assert_eq!(
+            config.for_executor(None),
+            config.for_executor(Some("claude"))
+        );
+        assert_eq!(
+            ExecutionTimeoutConfig::default().for_executor(Some("claude")),
+            ExecutorTimeouts::default()
+        );
+    }
+
+    #[test]
+    fn test_executor_timeouts_exceeded() {
+        let timeouts = ExecutorTimeouts {
+            max_runtime_minutes: Some(60),
+            idle_minutes: Some(10),
+        };
+        let now = Utc::now();
+
+        // Busy and within the time limit
+        assert_eq!(
+            timeouts.exceeded(now - Duration::minutes(30), Some(now), now),
+            None
+        );
+        // Over the wall-clock limit even though it keeps writing output
+        assert_eq!(
+            timeouts.exceeded(now - Duration::minutes(61), Some(now), now),
+            Some("Exceeded the 60 minute time limit".to_string())
+        );
+        // Silent for too long
+        assert_eq!(
+            timeouts.exceeded(
+                now - Duration::minutes(30),
+                Some(now - Duration::minutes(11)),
+                now
+            ),
+            Some("No output for 10 minutes".to_string())
+        );
+        // Never wrote anything: idle time counts from the start
+        assert_eq!(
+            timeouts.exceeded(now - Duration::minutes(11), None, now),
+            Some("No output for 10 minutes".to_string())
+        );
+        assert_eq!(
+            ExecutorTimeouts::default().exceeded(now - Duration::days(1), None, now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_model_pricing_lookup_and_cost() {
+        let pricing = ModelPricing::defaults();
+        let sonnet = ModelPricing::lookup(&pricing, "claude-sonnet-4-20250514").unwrap();
+        assert_eq!(sonnet.output, 15.0);
+        assert_eq!(ModelPricing::lookup(&pricing, "gpt-4o"), None);
+
+        // The most specific prefix wins
+        let mut pricing = HashMap::new();
+        pricing.insert("claude".to_string(), ModelPricing::default());
+        pricing.insert(
+            "claude-opus-4".to_string(),
+            ModelPricing {
+                input: 15.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            ModelPricing::lookup(&pricing, "claude-opus-4-20250514")
+                .unwrap()
+                .input,
+            15.0
+        );
+
+        let counts = TokenCounts {
+            input_tokens: 2_000_000,
+            output_tokens: 100_000,
+            cache_read_input_tokens: 1_000_000,
+            cache_creation_input_tokens: 0,
+        };
+        assert!((sonnet.cost_usd(&counts) - 7.8).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "log_stream_type", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum LogStreamType {
+    Stdout,
+    Stderr,
+}
+
+/// A piece of output from an execution process, stored append-only in the order it arrived
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExecutionLogChunk {
+    pub id: i64,
+    pub execution_process_id: Uuid,
+    pub seq: i64,
+    pub stream: LogStreamType,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionLogChunk {
+    /// Append a chunk of output, numbered after the last chunk of the process
+    pub async fn append(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        stream: LogStreamType,
+        content: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_log_chunks (execution_process_id, seq, stream, content)
+               VALUES (
+                   $1,
+                   (SELECT COALESCE(MAX(seq) + 1, 0) FROM execution_log_chunks WHERE execution_process_id = $1),
+                   $2,
+                   $3
+               )"#,
+            execution_process_id,
+            stream,
+            content
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Find the chunks of one stream of an execution process, in order
+    pub async fn find_by_execution_process_id_and_stream(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        stream: LogStreamType,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionLogChunk,
+            r#"SELECT
+                id as "id!: i64",
+                execution_process_id as "execution_process_id!: Uuid",
+                seq,
+                stream as "stream!: LogStreamType",
+                content,
+                created_at as "created_at!: DateTime<Utc>"
+               FROM execution_log_chunks
+               WHERE execution_process_id = $1 AND stream = $2
+               ORDER BY seq ASC"#,
+            execution_process_id,
+            stream
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -1,32 +1,18 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::execution_log_chunk::{ExecutionLogChunk, LogStreamType};
 use crate::app_state::ExecutionType;
 
-/// Filter out stderr boundary markers from output
-fn filter_stderr_boundary_markers(stderr: &Option<String>) -> Option<String> {
-    stderr
-        .as_ref()
-        .map(|s| s.replace("---STDERR_CHUNK_BOUNDARY---", ""))
-}
-
-/// Custom serializer for stderr field that filters out boundary markers
-fn serialize_filtered_stderr<S>(stderr: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    let filtered = filter_stderr_boundary_markers(stderr);
-    filtered.serialize(serializer)
-}
-
 #[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
 #[sqlx(type_name = "execution_process_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 #[ts(export)]
 pub enum ExecutionProcessStatus {
+    Queued,
     Running,
     Completed,
     Failed,
@@ -41,6 +27,7 @@ pub enum ExecutionProcessType {
     SetupScript,
     CodingAgent,
     DevServer,
+    CheckScript,
 }
 
 impl From<ExecutionType> for ExecutionProcessType {
@@ -49,6 +36,7 @@ impl From<ExecutionType> for ExecutionProcessType {
             ExecutionType::SetupScript => ExecutionProcessType::SetupScript,
             ExecutionType::CodingAgent => ExecutionProcessType::CodingAgent,
             ExecutionType::DevServer => ExecutionProcessType::DevServer,
+            ExecutionType::CheckScript => ExecutionProcessType::CheckScript,
         }
     }
 }
@@ -59,6 +47,7 @@ impl From<ExecutionProcessType> for ExecutionType {
             ExecutionProcessType::SetupScript => ExecutionType::SetupScript,
             ExecutionProcessType::CodingAgent => ExecutionType::CodingAgent,
             ExecutionProcessType::DevServer => ExecutionType::DevServer,
+            ExecutionProcessType::CheckScript => ExecutionType::CheckScript,
         }
     }
 }
@@ -74,16 +63,28 @@ pub struct ExecutionProcess {
     pub command: String,
     pub args: Option<String>, // JSON array of arguments
     pub working_directory: String,
-    pub stdout: Option<String>,
-    #[serde(serialize_with = "serialize_filtered_stderr")]
-    pub stderr: Option<String>,
+    pub stdout: Option<String>, // Reassembled from execution_log_chunks
+    pub stderr: Option<String>, // Reassembled from execution_log_chunks
     pub exit_code: Option<i64>,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub last_output_at: Option<DateTime<Utc>>,
+    pub kill_reason: Option<String>, // Set when the watchdog or a timeout killed the process
+    pub auto_fix_of_process_id: Option<Uuid>, // Failed process this auto-fix follow-up was started for
+    pub auto_fix_iteration: Option<i64>,      // 1-based count of consecutive auto-fix follow-ups
+    pub port: Option<i64>,                    // Port allocated to a dev server
+    pub ready_at: Option<DateTime<Utc>>,      // When a dev server's output showed it was serving
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// The failed process an auto-fix follow-up is started for
+#[derive(Debug, Clone, Copy)]
+pub struct AutoFixOf {
+    pub process_id: Uuid,
+    pub iteration: i64, // 1-based count of consecutive auto-fix follow-ups
+}
+
 #[derive(Debug, Deserialize, TS)]
 #[ts(export)]
 pub struct CreateExecutionProcess {
@@ -93,6 +94,11 @@ pub struct CreateExecutionProcess {
     pub command: String,
     pub args: Option<String>,
     pub working_directory: String,
+    pub project_check_id: Option<Uuid>, // Only for CheckScript processes
+    pub head_commit: Option<String>,    // Only for CheckScript processes
+    pub port: Option<i64>,              // Only for DevServer processes
+    pub auto_fix_of_process_id: Option<Uuid>, // Only for auto-fix follow-ups
+    pub auto_fix_iteration: Option<i64>, // Only for auto-fix follow-ups
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -118,10 +124,29 @@ pub struct ExecutionProcessSummary {
     pub exit_code: Option<i64>,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub last_output_at: Option<DateTime<Utc>>,
+    pub kill_reason: Option<String>, // Set when the watchdog or a timeout killed the process
+    pub auto_fix_of_process_id: Option<Uuid>, // Failed process this auto-fix follow-up was started for
+    pub auto_fix_iteration: Option<i64>,      // 1-based count of consecutive auto-fix follow-ups
+    pub port: Option<i64>,                    // Port allocated to a dev server
+    pub ready_at: Option<DateTime<Utc>>,      // When a dev server's output showed it was serving
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// A coding agent execution waiting for a free concurrency slot
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct QueuedExecution {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub executor_type: Option<String>,
+    pub position: i64, // 1-based position in the global queue
+    pub queued_at: DateTime<Utc>,
+}
+
 impl ExecutionProcess {
     /// Find execution process by ID
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
@@ -136,11 +161,52 @@ impl ExecutionProcess {
                 command, 
                 args, 
                 working_directory, 
-                stdout, 
-                stderr, 
+                (SELECT group_concat(content, '' ORDER BY seq) FROM execution_log_chunks WHERE execution_process_id = execution_processes.id AND stream = 'stdout') as "stdout?: String", 
+                (SELECT group_concat(content, '' ORDER BY seq) FROM execution_log_chunks WHERE execution_process_id = execution_processes.id AND stream = 'stderr') as "stderr?: String", 
                 exit_code,
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                last_output_at as "last_output_at?: DateTime<Utc>",
+                kill_reason,
+                auto_fix_of_process_id as "auto_fix_of_process_id?: Uuid",
+                auto_fix_iteration,
+                port,
+                ready_at as "ready_at?: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>", 
+                updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes 
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Find execution process summary by ID (excluding stdio)
+    pub async fn find_summary_by_id(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Option<ExecutionProcessSummary>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessSummary,
+            r#"SELECT 
+                id as "id!: Uuid", 
+                task_attempt_id as "task_attempt_id!: Uuid", 
+                process_type as "process_type!: ExecutionProcessType",
+                executor_type,
+                status as "status!: ExecutionProcessStatus",
+                command, 
+                args, 
+                working_directory, 
+                exit_code,
+                started_at as "started_at!: DateTime<Utc>",
+                completed_at as "completed_at?: DateTime<Utc>",
+                last_output_at as "last_output_at?: DateTime<Utc>",
+                kill_reason,
+                auto_fix_of_process_id as "auto_fix_of_process_id?: Uuid",
+                auto_fix_iteration,
+                port,
+                ready_at as "ready_at?: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -167,11 +233,17 @@ impl ExecutionProcess {
                 command, 
                 args, 
                 working_directory, 
-                stdout, 
-                stderr, 
+                (SELECT group_concat(content, '' ORDER BY seq) FROM execution_log_chunks WHERE execution_process_id = execution_processes.id AND stream = 'stdout') as "stdout?: String", 
+                (SELECT group_concat(content, '' ORDER BY seq) FROM execution_log_chunks WHERE execution_process_id = execution_processes.id AND stream = 'stderr') as "stderr?: String", 
                 exit_code,
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                last_output_at as "last_output_at?: DateTime<Utc>",
+                kill_reason,
+                auto_fix_of_process_id as "auto_fix_of_process_id?: Uuid",
+                auto_fix_iteration,
+                port,
+                ready_at as "ready_at?: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -202,6 +274,12 @@ impl ExecutionProcess {
                 exit_code,
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                last_output_at as "last_output_at?: DateTime<Utc>",
+                kill_reason,
+                auto_fix_of_process_id as "auto_fix_of_process_id?: Uuid",
+                auto_fix_iteration,
+                port,
+                ready_at as "ready_at?: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -213,10 +291,12 @@ impl ExecutionProcess {
         .await
     }
 
-    /// Find running execution processes
-    pub async fn find_running(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+    /// Find running execution processes (excluding stdio)
+    pub async fn find_running(
+        pool: &SqlitePool,
+    ) -> Result<Vec<ExecutionProcessSummary>, sqlx::Error> {
         sqlx::query_as!(
-            ExecutionProcess,
+            ExecutionProcessSummary,
             r#"SELECT 
                 id as "id!: Uuid", 
                 task_attempt_id as "task_attempt_id!: Uuid", 
@@ -226,11 +306,15 @@ impl ExecutionProcess {
                 command, 
                 args, 
                 working_directory, 
-                stdout, 
-                stderr, 
                 exit_code,
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                last_output_at as "last_output_at?: DateTime<Utc>",
+                kill_reason,
+                auto_fix_of_process_id as "auto_fix_of_process_id?: Uuid",
+                auto_fix_iteration,
+                port,
+                ready_at as "ready_at?: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes 
@@ -241,13 +325,13 @@ impl ExecutionProcess {
         .await
     }
 
-    /// Find running dev servers for a specific project
+    /// Find running dev servers for a specific project (excluding stdio)
     pub async fn find_running_dev_servers_by_project(
         pool: &SqlitePool,
         project_id: Uuid,
-    ) -> Result<Vec<Self>, sqlx::Error> {
+    ) -> Result<Vec<ExecutionProcessSummary>, sqlx::Error> {
         sqlx::query_as!(
-            ExecutionProcess,
+            ExecutionProcessSummary,
             r#"SELECT 
                 ep.id as "id!: Uuid", 
                 ep.task_attempt_id as "task_attempt_id!: Uuid", 
@@ -257,11 +341,15 @@ impl ExecutionProcess {
                 ep.command, 
                 ep.args, 
                 ep.working_directory, 
-                ep.stdout, 
-                ep.stderr, 
                 ep.exit_code,
                 ep.started_at as "started_at!: DateTime<Utc>",
                 ep.completed_at as "completed_at?: DateTime<Utc>",
+                ep.last_output_at as "last_output_at?: DateTime<Utc>",
+                ep.kill_reason,
+                ep.auto_fix_of_process_id as "auto_fix_of_process_id?: Uuid",
+                ep.auto_fix_iteration,
+                ep.port,
+                ep.ready_at as "ready_at?: DateTime<Utc>",
                 ep.created_at as "created_at!: DateTime<Utc>", 
                 ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
@@ -277,11 +365,21 @@ impl ExecutionProcess {
         .await
     }
 
-    /// Create a new execution process
+    /// Create a new running execution process
     pub async fn create(
         pool: &SqlitePool,
         data: &CreateExecutionProcess,
         process_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        Self::create_with_status(pool, data, process_id, ExecutionProcessStatus::Running).await
+    }
+
+    /// Create a new execution process in the given initial status
+    pub async fn create_with_status(
+        pool: &SqlitePool,
+        data: &CreateExecutionProcess,
+        process_id: Uuid,
+        status: ExecutionProcessStatus,
     ) -> Result<Self, sqlx::Error> {
         let now = Utc::now();
 
@@ -289,10 +387,11 @@ impl ExecutionProcess {
             ExecutionProcess,
             r#"INSERT INTO execution_processes (
                 id, task_attempt_id, process_type, executor_type, status, command, args, 
-                working_directory, stdout, stderr, exit_code, started_at, 
-                completed_at, created_at, updated_at
+                working_directory, exit_code, started_at, 
+                completed_at, created_at, updated_at, project_check_id, head_commit, port,
+                auto_fix_of_process_id, auto_fix_iteration
                ) 
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15) 
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18) 
                RETURNING 
                 id as "id!: Uuid", 
                 task_attempt_id as "task_attempt_id!: Uuid", 
@@ -302,28 +401,37 @@ impl ExecutionProcess {
                 command, 
                 args, 
                 working_directory, 
-                stdout, 
-                stderr, 
+                NULL as "stdout?: String", 
+                NULL as "stderr?: String", 
                 exit_code,
                 started_at as "started_at!: DateTime<Utc>",
                 completed_at as "completed_at?: DateTime<Utc>",
+                last_output_at as "last_output_at?: DateTime<Utc>",
+                kill_reason,
+                auto_fix_of_process_id as "auto_fix_of_process_id?: Uuid",
+                auto_fix_iteration,
+                port,
+                ready_at as "ready_at?: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>", 
                 updated_at as "updated_at!: DateTime<Utc>""#,
             process_id,
             data.task_attempt_id,
             data.process_type,
             data.executor_type,
-            ExecutionProcessStatus::Running,
+            status,
             data.command,
             data.args,
             data.working_directory,
-            None::<String>,        // stdout
-            None::<String>,        // stderr
             None::<i64>,           // exit_code
             now,                   // started_at
             None::<DateTime<Utc>>, // completed_at
             now,                   // created_at
-            now                    // updated_at
+            now,                   // updated_at
+            data.project_check_id,
+            data.head_commit,
+            data.port,
+            data.auto_fix_of_process_id,
+            data.auto_fix_iteration
         )
         .fetch_one(pool)
         .await
@@ -357,16 +465,15 @@ impl ExecutionProcess {
         Ok(())
     }
 
-    /// Append to stdout for this execution process (for streaming updates)
-    pub async fn append_stdout(
-        pool: &SqlitePool,
-        id: Uuid,
-        stdout_append: &str,
-    ) -> Result<(), sqlx::Error> {
+    /// Record that a dev server's output showed it was ready to serve
+    pub async fn mark_ready(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
         sqlx::query!(
-            "UPDATE execution_processes SET stdout = COALESCE(stdout, '') || $1, updated_at = datetime('now') WHERE id = $2",
-            stdout_append,
-            id
+            r#"UPDATE execution_processes
+               SET ready_at = $2
+               WHERE id = $1 AND ready_at IS NULL"#,
+            id,
+            now
         )
         .execute(pool)
         .await?;
@@ -374,15 +481,130 @@ impl ExecutionProcess {
         Ok(())
     }
 
-    /// Append to stderr for this execution process (for streaming updates)
-    pub async fn append_stderr(
+    /// Ports allocated to dev servers that are still running
+    pub async fn find_running_dev_server_ports(pool: &SqlitePool) -> Result<Vec<i64>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT port as "port!: i64"
+               FROM execution_processes
+               WHERE process_type = 'devserver' AND status = 'running' AND port IS NOT NULL"#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records.into_iter().map(|r| r.port).collect())
+    }
+
+    /// The newest running dev server of a task attempt that was allocated a port
+    pub async fn find_running_dev_server_for_attempt(
         pool: &SqlitePool,
-        id: Uuid,
-        stderr_append: &str,
-    ) -> Result<(), sqlx::Error> {
+        task_attempt_id: Uuid,
+    ) -> Result<Option<(Uuid, i64)>, sqlx::Error> {
+        let record = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", port as "port!: i64"
+               FROM execution_processes
+               WHERE task_attempt_id = $1 AND process_type = 'devserver'
+                 AND status = 'running' AND port IS NOT NULL
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            task_attempt_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record.map(|r| (r.id, r.port)))
+    }
+
+    /// Find queued coding agent executions in the order they were queued
+    pub async fn find_queued(pool: &SqlitePool) -> Result<Vec<QueuedExecution>, sqlx::Error> {
+        sqlx::query_as!(
+            QueuedExecution,
+            r#"SELECT
+                ep.id as "id!: Uuid",
+                ep.task_attempt_id as "task_attempt_id!: Uuid",
+                ta.task_id as "task_id!: Uuid",
+                t.project_id as "project_id!: Uuid",
+                ep.executor_type,
+                ROW_NUMBER() OVER (ORDER BY ep.created_at ASC) as "position!: i64",
+                ep.created_at as "queued_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ep.task_attempt_id = ta.id
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE ep.status = 'queued'
+               ORDER BY ep.created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Count running coding agent executions, optionally limited to one project
+    pub async fn count_running_coding_agents(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ep.task_attempt_id = ta.id
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE ep.status = 'running'
+               AND ep.process_type = 'codingagent'
+               AND ($1 IS NULL OR t.project_id = $1)"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.count)
+    }
+
+    /// Move a queued execution process to running. Returns false if it was no longer queued.
+    pub async fn mark_started(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET status = 'running', started_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'queued'"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Remove a queued execution process from the queue by marking it killed.
+    /// Returns false if it was not queued.
+    pub async fn cancel_queued(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET status = 'killed', completed_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'queued'"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark a running execution process as killed by vibe-kanban, recording why
+    pub async fn mark_killed(pool: &SqlitePool, id: Uuid, reason: &str) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "UPDATE execution_processes SET stderr = COALESCE(stderr, '') || $1, updated_at = datetime('now') WHERE id = $2",
-            stderr_append,
+            r#"UPDATE execution_processes
+               SET status = 'killed', kill_reason = $2, completed_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            reason
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record that the process just produced output
+    async fn touch_last_output(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE execution_processes SET last_output_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1",
             id
         )
         .execute(pool)
@@ -391,6 +613,26 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    /// Append to stdout for this execution process (for streaming updates)
+    pub async fn append_stdout(
+        pool: &SqlitePool,
+        id: Uuid,
+        stdout_append: &str,
+    ) -> Result<(), sqlx::Error> {
+        ExecutionLogChunk::append(pool, id, LogStreamType::Stdout, stdout_append).await?;
+        Self::touch_last_output(pool, id).await
+    }
+
+    /// Append to stderr for this execution process (for streaming updates)
+    pub async fn append_stderr(
+        pool: &SqlitePool,
+        id: Uuid,
+        stderr_append: &str,
+    ) -> Result<(), sqlx::Error> {
+        ExecutionLogChunk::append(pool, id, LogStreamType::Stderr, stderr_append).await?;
+        Self::touch_last_output(pool, id).await
+    }
+
     /// Append to both stdout and stderr for this execution process
     pub async fn append_output(
         pool: &SqlitePool,
@@ -0,0 +1,270 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::config::ModelPricing;
+use crate::executors::usage::TokenCounts;
+
+/// Tokens of one model used by the execution processes of a task on a given day
+#[derive(Debug, Clone)]
+pub struct UsageRow {
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub day: String, // YYYY-MM-DD (UTC) the execution process started
+    pub model: String,
+    pub counts: TokenCounts,
+}
+
+/// Token usage and its cost, summed over some group of execution processes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UsageRollup {
+    pub key: String, // Task id, day (YYYY-MM-DD) or model, depending on the rollup
+    pub label: Option<String>, // Task title for per-task rollups
+    #[ts(type = "number")]
+    pub input_tokens: i64,
+    #[ts(type = "number")]
+    pub output_tokens: i64,
+    #[ts(type = "number")]
+    pub cache_read_input_tokens: i64,
+    #[ts(type = "number")]
+    pub cache_creation_input_tokens: i64,
+    pub cost_usd: f64, // Leaves out models without configured pricing
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UsageReport {
+    pub total: UsageRollup,
+    pub by_model: Vec<UsageRollup>,
+    pub by_task: Vec<UsageRollup>,
+    pub by_day: Vec<UsageRollup>,
+    pub unpriced_models: Vec<String>, // Models used that have no entry in `model_pricing`
+}
+
+pub struct ExecutionUsage;
+
+impl ExecutionUsage {
+    /// Store the running usage totals of an execution process, one row per model
+    pub async fn record(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        totals: &BTreeMap<String, TokenCounts>,
+    ) -> Result<(), sqlx::Error> {
+        for (model, counts) in totals {
+            sqlx::query!(
+                r#"INSERT INTO execution_usage (
+                       execution_process_id, model, input_tokens, output_tokens,
+                       cache_read_input_tokens, cache_creation_input_tokens
+                   )
+                   VALUES ($1, $2, $3, $4, $5, $6)
+                   ON CONFLICT (execution_process_id, model) DO UPDATE SET
+                       input_tokens = excluded.input_tokens,
+                       output_tokens = excluded.output_tokens,
+                       cache_read_input_tokens = excluded.cache_read_input_tokens,
+                       cache_creation_input_tokens = excluded.cache_creation_input_tokens,
+                       updated_at = datetime('now', 'subsec')"#,
+                execution_process_id,
+                model,
+                counts.input_tokens,
+                counts.output_tokens,
+                counts.cache_read_input_tokens,
+                counts.cache_creation_input_tokens
+            )
+            .execute(pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Usage of a project, narrowed down to one task or execution process when given
+    pub async fn find_rows(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        task_id: Option<Uuid>,
+        execution_process_id: Option<Uuid>,
+    ) -> Result<Vec<UsageRow>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT
+                t.id as "task_id!: Uuid",
+                t.title as "task_title!: String",
+                date(ep.started_at) as "day!: String",
+                eu.model as "model!: String",
+                SUM(eu.input_tokens) as "input_tokens!: i64",
+                SUM(eu.output_tokens) as "output_tokens!: i64",
+                SUM(eu.cache_read_input_tokens) as "cache_read_input_tokens!: i64",
+                SUM(eu.cache_creation_input_tokens) as "cache_creation_input_tokens!: i64"
+               FROM execution_usage eu
+               JOIN execution_processes ep ON ep.id = eu.execution_process_id
+               JOIN task_attempts ta ON ta.id = ep.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE t.project_id = $1
+                 AND ($2 IS NULL OR t.id = $2)
+                 AND ($3 IS NULL OR ep.id = $3)
+               GROUP BY t.id, date(ep.started_at), eu.model
+               ORDER BY date(ep.started_at) ASC"#,
+            project_id,
+            task_id,
+            execution_process_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| UsageRow {
+                task_id: r.task_id,
+                task_title: r.task_title,
+                day: r.day,
+                model: r.model,
+                counts: TokenCounts {
+                    input_tokens: r.input_tokens,
+                    output_tokens: r.output_tokens,
+                    cache_read_input_tokens: r.cache_read_input_tokens,
+                    cache_creation_input_tokens: r.cache_creation_input_tokens,
+                },
+            })
+            .collect())
+    }
+}
+
+impl UsageRollup {
+    fn add(&mut self, counts: &TokenCounts, cost_usd: f64) {
+        self.input_tokens += counts.input_tokens;
+        self.output_tokens += counts.output_tokens;
+        self.cache_read_input_tokens += counts.cache_read_input_tokens;
+        self.cache_creation_input_tokens += counts.cache_creation_input_tokens;
+        self.cost_usd += cost_usd;
+    }
+}
+
+impl UsageReport {
+    /// Roll usage rows up per model, task and day, priced with `pricing`
+    pub fn from_rows(rows: &[UsageRow], pricing: &HashMap<String, ModelPricing>) -> Self {
+        let mut total = UsageRollup {
+            key: "total".to_string(),
+            ..Default::default()
+        };
+        let mut by_model: BTreeMap<&str, UsageRollup> = BTreeMap::new();
+        let mut by_task: BTreeMap<Uuid, UsageRollup> = BTreeMap::new();
+        let mut by_day: BTreeMap<&str, UsageRollup> = BTreeMap::new();
+        let mut unpriced_models = BTreeSet::new();
+
+        for row in rows {
+            let cost_usd = match ModelPricing::lookup(pricing, &row.model) {
+                Some(price) => price.cost_usd(&row.counts),
+                None => {
+                    unpriced_models.insert(row.model.clone());
+                    0.0
+                }
+            };
+
+            total.add(&row.counts, cost_usd);
+            by_model
+                .entry(&row.model)
+                .or_insert_with(|| UsageRollup {
+                    key: row.model.clone(),
+                    ..Default::default()
+                })
+                .add(&row.counts, cost_usd);
+            by_task
+                .entry(row.task_id)
+                .or_insert_with(|| UsageRollup {
+                    key: row.task_id.to_string(),
+                    label: Some(row.task_title.clone()),
+                    ..Default::default()
+                })
+                .add(&row.counts, cost_usd);
+            by_day
+                .entry(&row.day)
+                .or_insert_with(|| UsageRollup {
+                    key: row.day.clone(),
+                    ..Default::default()
+                })
+                .add(&row.counts, cost_usd);
+        }
+
+        let mut by_task: Vec<UsageRollup> = by_task.into_values().collect();
+        by_task.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+
+        Self {
+            total,
+            by_model: by_model.into_values().collect(),
+            by_task,
+            by_day: by_day.into_values().collect(),
+            unpriced_models: unpriced_models.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(task_id: Uuid, day: &str, model: &str, input: i64, output: i64) -> UsageRow {
+        UsageRow {
+            task_id,
+            task_title: format!("Task {}", &task_id.to_string()[..4]),
+            day: day.to_string(),
+            model: model.to_string(),
+            counts: TokenCounts {
+                input_tokens: input,
+                output_tokens: output,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_usage_report_from_rows() {
+        let mut pricing = HashMap::new();
+        pricing.insert(
+            "claude-sonnet-4".to_string(),
+            ModelPricing {
+                input: 3.0,
+                output: 15.0,
+                cache_read: 0.3,
+                cache_write: 3.75,
+            },
+        );
+        let task_a = Uuid::new_v4();
+        let task_b = Uuid::new_v4();
+        let rows = vec![
+            row(
+                task_a,
+                "2025-07-01",
+                "claude-sonnet-4-20250514",
+                1_000_000,
+                0,
+            ),
+            row(
+                task_a,
+                "2025-07-02",
+                "claude-sonnet-4-20250514",
+                0,
+                1_000_000,
+            ),
+            row(task_b, "2025-07-02", "gpt-4o", 500, 50),
+        ];
+
+        let report = UsageReport::from_rows(&rows, &pricing);
+
+        assert_eq!(report.total.input_tokens, 1_000_500);
+        assert_eq!(report.total.output_tokens, 1_000_050);
+        assert!((report.total.cost_usd - 18.0).abs() < 1e-9);
+        assert_eq!(report.unpriced_models, vec!["gpt-4o".to_string()]);
+
+        assert_eq!(report.by_model.len(), 2);
+        assert_eq!(report.by_day.len(), 2);
+        assert_eq!(report.by_day[0].key, "2025-07-01");
+        assert!((report.by_day[1].cost_usd - 15.0).abs() < 1e-9);
+
+        // Most expensive task first
+        assert_eq!(report.by_task[0].key, task_a.to_string());
+        assert!((report.by_task[0].cost_usd - 18.0).abs() < 1e-9);
+        assert_eq!(report.by_task[1].output_tokens, 50);
+    }
+}
@@ -0,0 +1,510 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::{
+    executor::ExecutorConfig,
+    services::{SecretError, SecretRedactor, SecretService},
+};
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExecutorProfile {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub executor: String, // Base executor type, e.g. "claude"
+    pub command: Option<String>,
+    pub extra_args: Option<String>,
+    #[ts(type = "Record<string, string>")]
+    pub env_vars: Json<HashMap<String, String>>, // Encrypted, masked when sent back
+    pub model: Option<String>,
+    pub npm_version: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateExecutorProfile {
+    pub name: String,
+    pub executor: String,
+    pub command: Option<String>,
+    pub extra_args: Option<String>,
+    pub env_vars: Option<HashMap<String, String>>,
+    pub model: Option<String>,
+    pub npm_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdateExecutorProfile {
+    pub name: Option<String>,
+    pub executor: Option<String>,
+    pub command: Option<String>,
+    pub extra_args: Option<String>,
+    pub env_vars: Option<HashMap<String, String>>, // Masked values keep the current ones
+    pub model: Option<String>,
+    pub npm_version: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ExecutorProfileError {
+    Database(sqlx::Error),
+    Secret(SecretError),
+}
+
+impl std::fmt::Display for ExecutorProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecutorProfileError::Database(e) => write!(f, "Database error: {}", e),
+            ExecutorProfileError::Secret(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExecutorProfileError {}
+
+impl From<sqlx::Error> for ExecutorProfileError {
+    fn from(err: sqlx::Error) -> Self {
+        ExecutorProfileError::Database(err)
+    }
+}
+
+impl From<SecretError> for ExecutorProfileError {
+    fn from(err: SecretError) -> Self {
+        ExecutorProfileError::Secret(err)
+    }
+}
+
+/// Variables as stored, each value encrypted as they usually hold API keys. A value sent back
+/// masked keeps what `existing` has stored for the variable.
+fn stored_env_vars(
+    env_vars: &HashMap<String, String>,
+    existing: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, SecretError> {
+    env_vars
+        .iter()
+        .map(|(name, value)| {
+            let stored = match existing.get(name) {
+                Some(stored) if value == SecretRedactor::PLACEHOLDER => stored.clone(),
+                _ => SecretService::encrypt(value)?,
+            };
+            Ok((name.clone(), stored))
+        })
+        .collect()
+}
+
+impl ExecutorProfile {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutorProfile,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, executor, command, extra_args, env_vars as "env_vars!: Json<HashMap<String, String>>", model, npm_version, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM executor_profiles
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id_and_project_id(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutorProfile,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, executor, command, extra_args, env_vars as "env_vars!: Json<HashMap<String, String>>", model, npm_version, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM executor_profiles
+               WHERE id = $1 AND project_id = $2"#,
+            id,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Find the profile a task attempt was started with, if any
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutorProfile,
+            r#"SELECT ep.id as "id!: Uuid", ep.project_id as "project_id!: Uuid", ep.name, ep.executor, ep.command, ep.extra_args, ep.env_vars as "env_vars!: Json<HashMap<String, String>>", ep.model, ep.npm_version, ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
+               FROM executor_profiles ep
+               JOIN task_attempts ta ON ta.executor_profile_id = ep.id
+               WHERE ta.id = $1"#,
+            attempt_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateExecutorProfile,
+    ) -> Result<Self, ExecutorProfileError> {
+        let id = Uuid::new_v4();
+        let env_vars = Json(stored_env_vars(
+            &data.env_vars.clone().unwrap_or_default(),
+            &HashMap::new(),
+        )?);
+        Ok(sqlx::query_as!(
+            ExecutorProfile,
+            r#"INSERT INTO executor_profiles (id, project_id, name, executor, command, extra_args, env_vars, model, npm_version)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, executor, command, extra_args, env_vars as "env_vars!: Json<HashMap<String, String>>", model, npm_version, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.executor,
+            data.command,
+            data.extra_args,
+            env_vars,
+            data.model,
+            data.npm_version
+        )
+        .fetch_one(pool)
+        .await?)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+        data: &UpdateExecutorProfile,
+    ) -> Result<Self, ExecutorProfileError> {
+        let existing = Self::find_by_id_and_project_id(pool, id, project_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let executor = data.executor.as_ref().unwrap_or(&existing.executor);
+        let command = data.command.as_ref().or(existing.command.as_ref());
+        let extra_args = data.extra_args.as_ref().or(existing.extra_args.as_ref());
+        let env_vars = Json(match &data.env_vars {
+            Some(env_vars) => stored_env_vars(env_vars, &existing.env_vars)?,
+            None => existing.env_vars.0.clone(),
+        });
+        let model = data.model.as_ref().or(existing.model.as_ref());
+        let npm_version = data.npm_version.as_ref().or(existing.npm_version.as_ref());
+
+        Ok(sqlx::query_as!(
+            ExecutorProfile,
+            r#"UPDATE executor_profiles
+               SET name = $3, executor = $4, command = $5, extra_args = $6, env_vars = $7, model = $8, npm_version = $9, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND project_id = $2
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, executor, command, extra_args, env_vars as "env_vars!: Json<HashMap<String, String>>", model, npm_version, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            executor,
+            command,
+            extra_args,
+            env_vars,
+            model,
+            npm_version
+        )
+        .fetch_one(pool)
+        .await?)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM executor_profiles WHERE id = $1 AND project_id = $2",
+            id,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Check that `executor` names a coding agent a profile can be applied to
+    pub fn validate_executor(executor: &str) -> Result<ExecutorConfig, String> {
+        match executor.parse::<ExecutorConfig>()? {
+            ExecutorConfig::Echo | ExecutorConfig::SetupScript { .. } => Err(format!(
+                "Executor profiles are not supported for '{}'",
+                executor
+            )),
+            config => Ok(config),
+        }
+    }
+
+    /// Build the command line for an executor from its default command:
+    /// the profile command (or the default with the npm version pinned),
+    /// followed by the model flag and any extra arguments.
+    pub fn resolve_command(&self, default_command: &str) -> String {
+        let mut command = match &self.command {
+            Some(command) if !command.trim().is_empty() => command.trim().to_string(),
+            _ => match &self.npm_version {
+                Some(version) if !version.trim().is_empty() => {
+                    pin_npx_package_version(default_command, version.trim())
+                }
+                _ => default_command.to_string(),
+            },
+        };
+
+        if let Some(model) = self.model.as_deref().filter(|m| !m.trim().is_empty()) {
+            command.push_str(&format!(" --model {}", model.trim()));
+        }
+        if let Some(extra_args) = self.extra_args.as_deref().filter(|a| !a.trim().is_empty()) {
+            command.push(' ');
+            command.push_str(extra_args.trim());
+        }
+
+        command
+    }
+
+    /// The profile's variables decrypted. Variables that can't be are left out, as their
+    /// encrypted values would be useless to the agent.
+    pub fn env_vars(&self) -> HashMap<String, String> {
+        self.env_vars
+            .iter()
+            .filter_map(|(name, value)| {
+                if !SecretService::is_sealed(value) {
+                    // Stored before values were encrypted, until `encrypt_stored_env_vars` runs
+                    return Some((name.clone(), value.clone()));
+                }
+                match SecretService::decrypt(value) {
+                    Ok(value) => Some((name.clone(), value)),
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to decrypt {} of executor profile {}: {}",
+                            name,
+                            self.id,
+                            e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// The profile as sent back to clients, with the values of its variables masked
+    pub fn masked(mut self) -> Self {
+        for value in self.env_vars.values_mut() {
+            *value = SecretRedactor::PLACEHOLDER.to_string();
+        }
+        self
+    }
+
+    /// Variable values of the profile the attempt of an execution process was started with,
+    /// to redact from its output
+    pub async fn find_secrets_for_process(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let profile = sqlx::query_as!(
+            ExecutorProfile,
+            r#"SELECT p.id as "id!: Uuid", p.project_id as "project_id!: Uuid", p.name, p.executor, p.command, p.extra_args, p.env_vars as "env_vars!: Json<HashMap<String, String>>", p.model, p.npm_version, p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
+               FROM executor_profiles p
+               JOIN task_attempts ta ON ta.executor_profile_id = p.id
+               JOIN execution_processes ep ON ep.task_attempt_id = ta.id
+               WHERE ep.id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(profile
+            .map(|profile| profile.env_vars().into_values().collect())
+            .unwrap_or_default())
+    }
+
+    /// Encrypt the variables of profiles saved before their values were encrypted
+    pub async fn encrypt_stored_env_vars(pool: &SqlitePool) -> Result<(), ExecutorProfileError> {
+        let profiles = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", env_vars as "env_vars!: Json<HashMap<String, String>>"
+               FROM executor_profiles"#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        for profile in profiles {
+            if profile
+                .env_vars
+                .values()
+                .all(|v| SecretService::is_sealed(v))
+            {
+                continue;
+            }
+            let env_vars = Json(
+                profile
+                    .env_vars
+                    .0
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let value = if SecretService::is_sealed(&value) {
+                            value
+                        } else {
+                            SecretService::encrypt(&value)?
+                        };
+                        Ok((name, value))
+                    })
+                    .collect::<Result<HashMap<_, _>, SecretError>>()?,
+            );
+            sqlx::query!(
+                "UPDATE executor_profiles SET env_vars = $2 WHERE id = $1",
+                profile.id,
+                env_vars
+            )
+            .execute(pool)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Replace the version of the package run by `npx` in `command`,
+/// e.g. `npx -y @anthropic-ai/claude-code@latest -p` → `npx -y @anthropic-ai/claude-code@1.0.51 -p`.
+/// Commands that don't go through npx are returned unchanged.
+fn pin_npx_package_version(command: &str, version: &str) -> String {
+    let mut tokens: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+    let Some(npx_index) = tokens.iter().position(|t| t == "npx") else {
+        return command.to_string();
+    };
+    let Some(package_index) = tokens
+        .iter()
+        .skip(npx_index + 1)
+        .position(|t| !t.starts_with('-'))
+        .map(|offset| npx_index + 1 + offset)
+    else {
+        return command.to_string();
+    };
+
+    let package = &tokens[package_index];
+    // Scoped packages start with '@', so the version separator is the next '@'
+    let name_end = if let Some(scoped) = package.strip_prefix('@') {
+        scoped.find('@').map(|i| i + 1)
+    } else {
+        package.find('@')
+    };
+    let name = match name_end {
+        Some(end) => &package[..end],
+        None => package.as_str(),
+    };
+    tokens[package_index] = format!("{}@{}", name, version);
+    tokens.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> ExecutorProfile {
+        ExecutorProfile {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            name: "pinned".to_string(),
+            executor: "claude".to_string(),
+            command: None,
+            extra_args: None,
+            env_vars: Json(HashMap::new()),
+            model: None,
+            npm_version: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_pin_npx_package_version() {
+        assert_eq!(
+            pin_npx_package_version(
+                "npx -y @anthropic-ai/claude-code@latest -p --verbose",
+                "1.0.51"
+            ),
+            "npx -y @anthropic-ai/claude-code@1.0.51 -p --verbose"
+        );
+        assert_eq!(
+            pin_npx_package_version("npx -y opencode-ai@latest run", "0.3.0"),
+            "npx -y opencode-ai@0.3.0 run"
+        );
+        assert_eq!(
+            pin_npx_package_version("npx -y @musistudio/claude-code-router code", "1.0.0"),
+            "npx -y @musistudio/claude-code-router@1.0.0 code"
+        );
+        assert_eq!(
+            pin_npx_package_version("opencode -p", "1.0.0"),
+            "opencode -p"
+        );
+    }
+
+    #[test]
+    fn test_resolve_command() {
+        let default = "npx -y @anthropic-ai/claude-code@latest -p --output-format=stream-json";
+
+        assert_eq!(profile().resolve_command(default), default);
+
+        let pinned = ExecutorProfile {
+            npm_version: Some("1.0.51".to_string()),
+            model: Some("opus".to_string()),
+            extra_args: Some("--max-turns 20".to_string()),
+            ..profile()
+        };
+        assert_eq!(
+            pinned.resolve_command(default),
+            "npx -y @anthropic-ai/claude-code@1.0.51 -p --output-format=stream-json --model opus --max-turns 20"
+        );
+
+        // A custom command replaces the default entirely (version pin doesn't apply)
+        let custom = ExecutorProfile {
+            command: Some("claude -p --output-format=stream-json".to_string()),
+            npm_version: Some("1.0.51".to_string()),
+            ..profile()
+        };
+        assert_eq!(
+            custom.resolve_command(default),
+            "claude -p --output-format=stream-json"
+        );
+    }
+
+    #[test]
+    fn test_masked_hides_env_var_values() {
+        let profile = ExecutorProfile {
+            env_vars: Json(HashMap::from([(
+                "ANTHROPIC_API_KEY".to_string(),
+                "v1:abcdef".to_string(),
+            )])),
+            ..profile()
+        };
+        assert_eq!(
+            profile.masked().env_vars.0,
+            HashMap::from([(
+                "ANTHROPIC_API_KEY".to_string(),
+                SecretRedactor::PLACEHOLDER.to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_masked_values_keep_the_stored_ones() {
+        let existing = HashMap::from([("TOKEN".to_string(), "v1:stored".to_string())]);
+        let update =
+            HashMap::from([("TOKEN".to_string(), SecretRedactor::PLACEHOLDER.to_string())]);
+        assert_eq!(stored_env_vars(&update, &existing).unwrap(), existing);
+        assert!(stored_env_vars(&HashMap::new(), &existing)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_validate_executor() {
+        assert!(ExecutorProfile::validate_executor("claude").is_ok());
+        assert!(ExecutorProfile::validate_executor("echo").is_err());
+        assert!(ExecutorProfile::validate_executor("setup-script").is_err());
+        assert!(ExecutorProfile::validate_executor("nope").is_err());
+    }
+}
@@ -1,10 +1,21 @@
 pub mod api_response;
+pub mod api_token;
+pub mod audit_event;
 pub mod config;
+pub mod execution_log_chunk;
 pub mod execution_process;
+pub mod execution_usage;
+pub mod executor_profile;
 pub mod executor_session;
 pub mod project;
+pub mod project_check;
+pub mod project_env_var;
+pub mod project_member;
 pub mod task;
 pub mod task_attempt;
+pub mod task_dependency;
+pub mod user;
+pub mod webhook;
 
 pub mod task_template;
 
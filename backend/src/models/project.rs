@@ -1,10 +1,277 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use git2::{BranchType, Repository};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{types::Json, FromRow, SqlitePool, Type};
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::project_env_var::is_valid_env_var_name;
+
+/// Upper bound for `auto_fix_max_iterations`, so a command that keeps failing can't keep
+/// an agent busy forever
+pub const MAX_AUTO_FIX_ITERATIONS: i64 = 10;
+
+/// How an attempt's branch is brought into its base branch
+#[derive(Debug, Clone, Copy, Default, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "merge_strategy", rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+#[ts(export)]
+pub enum MergeStrategy {
+    /// One commit on top of the base branch with all of the attempt's changes
+    #[default]
+    Squash,
+    /// A merge commit joining the base branch and the attempt branch
+    Merge,
+    /// Replay the attempt's commits on top of the base branch, then fast-forward it
+    Rebase,
+    /// Fast-forward to the attempt's commits as they are, failing if the base branch has
+    /// moved on until the attempt is rebased
+    KeepCommits,
+}
+
+/// Whether each coding agent execution gets its own commit
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export)]
+pub enum CommitMode {
+    /// A new commit for the changes of every execution
+    #[default]
+    PerExecution,
+    /// Amend the attempt's last commit, so the branch holds a single commit
+    Amend,
+}
+
+/// How changes left behind by coding agents are committed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct CommitSettings {
+    /// Supports {task_title}, {task_id}, {attempt_id}, {executor} and {summary}.
+    /// Defaults to the agent's summary.
+    pub message_template: Option<String>,
+    pub conventional_prefix: Option<String>, // e.g. "feat" or "chore(agent)"
+    pub author_name: Option<String>,         // Also used as committer, defaults to the git config
+    pub author_email: Option<String>,
+    pub co_authors: Vec<String>, // "Name <email>", added as Co-authored-by trailers
+    pub mode: CommitMode,
+}
+
+/// How dev servers of the project's attempts are started and watched
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct DevServerSettings {
+    /// Set next to `PORT`. Values support {port}, {attempt_id} and {preview_path}, e.g.
+    /// `"VITE_BASE": "{preview_path}"` for apps served under the preview proxy. The proxy
+    /// only rewrites redirects, so an app linking to absolute paths like `/assets/app.js`
+    /// without such a base loads them from vibe-kanban instead of its dev server.
+    pub env: HashMap<String, String>,
+    /// Regex matched against output lines to tell the server is ready. Defaults to a line
+    /// mentioning the allocated port or saying it is ready or listening.
+    pub ready_pattern: Option<String>,
+}
+
+impl DevServerSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        for name in self.env.keys() {
+            if name == "PORT" {
+                return Err("PORT is allocated per attempt and can't be overridden".to_string());
+            }
+            if !is_valid_env_var_name(name) {
+                return Err(format!("Invalid environment variable name '{}'", name));
+            }
+        }
+        if let Some(pattern) = self.ready_pattern.as_deref() {
+            regex::Regex::new(pattern)
+                .map_err(|e| format!("Invalid ready pattern '{}': {}", pattern, e))?;
+        }
+        Ok(())
+    }
+
+    /// Environment of a dev server listening on `port` for `attempt_id`
+    pub fn render_env(&self, port: u16, attempt_id: Uuid) -> HashMap<String, String> {
+        let preview_path = format!("/preview/{}/", attempt_id);
+        let mut env: HashMap<String, String> = self
+            .env
+            .iter()
+            .map(|(name, value)| {
+                let value = value
+                    .replace("{port}", &port.to_string())
+                    .replace("{attempt_id}", &attempt_id.to_string())
+                    .replace("{preview_path}", &preview_path);
+                (name.clone(), value)
+            })
+            .collect();
+        env.insert("PORT".to_string(), port.to_string());
+        env
+    }
+}
+
+/// Container engine used to sandbox executions
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    pub fn program(&self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+}
+
+/// Whether and how coding agents, scripts and checks of the project run inside a container
+/// instead of directly on the host. Dev servers get their port published on the host's
+/// loopback interface for the preview proxy, so they can't run with the network isolated.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct SandboxSettings {
+    pub enabled: bool,
+    /// Defaults to docker, or podman when docker isn't installed
+    pub runtime: Option<ContainerRuntime>,
+    /// Must provide the executor's CLI (e.g. node for npx-based agents). Only the attempt's
+    /// worktree is mounted, so credentials are passed through project environment variables.
+    pub image: String,
+    pub isolate_network: bool, // No network access at all, which most agents need
+    pub cpus: Option<f64>,
+    #[ts(type = "number | null")]
+    pub memory_mb: Option<i64>,
+}
+
+impl SandboxSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        let image = self.image.trim();
+        if image.starts_with('-') || image.contains(char::is_whitespace) {
+            return Err(format!("Invalid container image '{}'", self.image));
+        }
+        if self.enabled && image.is_empty() {
+            return Err("Choose a container image to sandbox executions in".to_string());
+        }
+        if self.cpus.is_some_and(|cpus| cpus.is_nan() || cpus <= 0.0) {
+            return Err("The CPU limit must be greater than 0".to_string());
+        }
+        if self.memory_mb.is_some_and(|memory| memory < 6) {
+            return Err("The memory limit must be at least 6 MB".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Values available to commit message templates
+pub struct CommitMessageContext<'a> {
+    pub task_title: &'a str,
+    pub task_id: Uuid,
+    pub attempt_id: Uuid,
+    pub executor: Option<&'a str>,
+    pub summary: Option<&'a str>,
+}
+
+impl CommitSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        for co_author in &self.co_authors {
+            let valid = co_author
+                .trim()
+                .strip_suffix('>')
+                .and_then(|rest| rest.split_once(" <"))
+                .is_some_and(|(name, email)| !name.trim().is_empty() && email.contains('@'));
+            if !valid {
+                return Err(format!(
+                    "Co-author '{}' must look like 'Name <email>'",
+                    co_author
+                ));
+            }
+        }
+        if let Some(email) = self.author_email.as_deref() {
+            if !email.trim().is_empty() && !email.contains('@') {
+                return Err(format!("Invalid author email '{}'", email));
+            }
+        }
+        Ok(())
+    }
+
+    /// The full commit message: the rendered template with the conventional prefix on its
+    /// first line, followed by the Co-authored-by trailers
+    pub fn render_message(&self, ctx: &CommitMessageContext) -> String {
+        let summary = ctx.summary.map(str::trim).unwrap_or_default();
+        let mut message = match self.message_template.as_deref() {
+            Some(template) if !template.trim().is_empty() => template
+                .replace("{task_title}", ctx.task_title)
+                .replace("{task_id}", &ctx.task_id.to_string())
+                .replace("{attempt_id}", &ctx.attempt_id.to_string())
+                .replace("{executor}", ctx.executor.unwrap_or("unknown"))
+                .replace("{summary}", summary)
+                .trim()
+                .to_string(),
+            _ => summary.to_string(),
+        };
+        if message.is_empty() {
+            message = format!("Task attempt {} - Final changes", ctx.attempt_id);
+        }
+
+        if let Some(prefix) = self
+            .conventional_prefix
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            let prefix = format!("{}: ", prefix.trim_end_matches(':'));
+            if !message.starts_with(&prefix) {
+                message.insert_str(0, &prefix);
+            }
+        }
+
+        let trailers: Vec<String> = self
+            .co_authors
+            .iter()
+            .map(|co_author| format!("Co-authored-by: {}", co_author.trim()))
+            .filter(|trailer| !message.contains(trailer.as_str()))
+            .collect();
+        if !trailers.is_empty() {
+            message.push_str("\n\n");
+            message.push_str(&trailers.join("\n"));
+        }
+
+        message
+    }
+
+    /// Author and committer for agent commits, falling back to the repository's git config
+    pub fn signature(&self, repo: &Repository) -> Result<git2::Signature<'static>, git2::Error> {
+        let name = self
+            .author_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty());
+        let email = self
+            .author_email
+            .as_deref()
+            .map(str::trim)
+            .filter(|e| !e.is_empty());
+        if name.is_none() && email.is_none() {
+            return repo.signature();
+        }
+
+        let default = repo.signature().ok();
+        let name = name
+            .map(str::to_string)
+            .or_else(|| default.as_ref().and_then(|s| s.name().map(str::to_string)));
+        let email = email
+            .map(str::to_string)
+            .or_else(|| default.as_ref().and_then(|s| s.email().map(str::to_string)));
+        match (name, email) {
+            (Some(name), Some(email)) => git2::Signature::now(&name, &email),
+            _ => repo.signature(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct Project {
@@ -13,6 +280,16 @@ pub struct Project {
     pub git_repo_path: String,
     pub setup_script: Option<String>,
     pub dev_script: Option<String>,
+    pub max_concurrent_agents: Option<i64>, // Coding agents allowed to run at once, None = unlimited
+    pub merge_strategy: MergeStrategy,
+    #[ts(type = "CommitSettings")]
+    pub commit_settings: Json<CommitSettings>,
+    #[ts(type = "number")]
+    pub auto_fix_max_iterations: i64, // Follow-ups started for a failing command, 0 = off
+    #[ts(type = "DevServerSettings")]
+    pub dev_server_settings: Json<DevServerSettings>,
+    #[ts(type = "SandboxSettings")]
+    pub sandbox_settings: Json<SandboxSettings>,
 
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
@@ -37,6 +314,13 @@ pub struct UpdateProject {
     pub git_repo_path: Option<String>,
     pub setup_script: Option<String>,
     pub dev_script: Option<String>,
+    pub max_concurrent_agents: Option<i64>,
+    pub merge_strategy: Option<MergeStrategy>,
+    pub commit_settings: Option<CommitSettings>,
+    #[ts(type = "number | null")]
+    pub auto_fix_max_iterations: Option<i64>,
+    pub dev_server_settings: Option<DevServerSettings>,
+    pub sandbox_settings: Option<SandboxSettings>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -47,6 +331,13 @@ pub struct ProjectWithBranch {
     pub git_repo_path: String,
     pub setup_script: Option<String>,
     pub dev_script: Option<String>,
+    pub max_concurrent_agents: Option<i64>,
+    pub merge_strategy: MergeStrategy,
+    pub commit_settings: CommitSettings,
+    #[ts(type = "number")]
+    pub auto_fix_max_iterations: i64,
+    pub dev_server_settings: DevServerSettings,
+    pub sandbox_settings: SandboxSettings,
     pub current_branch: Option<String>,
 
     #[ts(type = "Date")]
@@ -92,7 +383,25 @@ impl Project {
     pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, max_concurrent_agents, merge_strategy as "merge_strategy!: MergeStrategy", commit_settings as "commit_settings!: Json<CommitSettings>", auto_fix_max_iterations, dev_server_settings as "dev_server_settings!: Json<DevServerSettings>", sandbox_settings as "sandbox_settings!: Json<SandboxSettings>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Projects a user is a member of, whatever their role
+    pub async fn find_by_member(
+        pool: &SqlitePool,
+        user_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT p.id as "id!: Uuid", p.name, p.git_repo_path, p.setup_script, p.dev_script, p.max_concurrent_agents, p.merge_strategy as "merge_strategy!: MergeStrategy", p.commit_settings as "commit_settings!: Json<CommitSettings>", p.auto_fix_max_iterations, p.dev_server_settings as "dev_server_settings!: Json<DevServerSettings>", p.sandbox_settings as "sandbox_settings!: Json<SandboxSettings>", p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects p
+               JOIN project_members m ON m.project_id = p.id
+               WHERE m.user_id = $1
+               ORDER BY p.created_at DESC"#,
+            user_id
         )
         .fetch_all(pool)
         .await
@@ -101,7 +410,7 @@ impl Project {
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, max_concurrent_agents, merge_strategy as "merge_strategy!: MergeStrategy", commit_settings as "commit_settings!: Json<CommitSettings>", auto_fix_max_iterations, dev_server_settings as "dev_server_settings!: Json<DevServerSettings>", sandbox_settings as "sandbox_settings!: Json<SandboxSettings>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
             id
         )
         .fetch_optional(pool)
@@ -114,7 +423,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, max_concurrent_agents, merge_strategy as "merge_strategy!: MergeStrategy", commit_settings as "commit_settings!: Json<CommitSettings>", auto_fix_max_iterations, dev_server_settings as "dev_server_settings!: Json<DevServerSettings>", sandbox_settings as "sandbox_settings!: Json<SandboxSettings>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
             git_repo_path
         )
         .fetch_optional(pool)
@@ -128,7 +437,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, max_concurrent_agents, merge_strategy as "merge_strategy!: MergeStrategy", commit_settings as "commit_settings!: Json<CommitSettings>", auto_fix_max_iterations, dev_server_settings as "dev_server_settings!: Json<DevServerSettings>", sandbox_settings as "sandbox_settings!: Json<SandboxSettings>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
             git_repo_path,
             exclude_id
         )
@@ -143,7 +452,7 @@ impl Project {
     ) -> Result<Self, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script) VALUES ($1, $2, $3, $4, $5) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script) VALUES ($1, $2, $3, $4, $5) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, max_concurrent_agents, merge_strategy as "merge_strategy!: MergeStrategy", commit_settings as "commit_settings!: Json<CommitSettings>", auto_fix_max_iterations, dev_server_settings as "dev_server_settings!: Json<DevServerSettings>", sandbox_settings as "sandbox_settings!: Json<SandboxSettings>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
             data.name,
             data.git_repo_path,
@@ -154,6 +463,7 @@ impl Project {
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &SqlitePool,
         id: Uuid,
@@ -161,15 +471,30 @@ impl Project {
         git_repo_path: String,
         setup_script: Option<String>,
         dev_script: Option<String>,
+        max_concurrent_agents: Option<i64>,
+        merge_strategy: MergeStrategy,
+        commit_settings: CommitSettings,
+        auto_fix_max_iterations: i64,
+        dev_server_settings: DevServerSettings,
+        sandbox_settings: SandboxSettings,
     ) -> Result<Self, sqlx::Error> {
+        let commit_settings = Json(commit_settings);
+        let dev_server_settings = Json(dev_server_settings);
+        let sandbox_settings = Json(sandbox_settings);
         sqlx::query_as!(
             Project,
-            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5, max_concurrent_agents = $6, merge_strategy = $7, commit_settings = $8, auto_fix_max_iterations = $9, dev_server_settings = $10, sandbox_settings = $11 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, max_concurrent_agents, merge_strategy as "merge_strategy!: MergeStrategy", commit_settings as "commit_settings!: Json<CommitSettings>", auto_fix_max_iterations, dev_server_settings as "dev_server_settings!: Json<DevServerSettings>", sandbox_settings as "sandbox_settings!: Json<SandboxSettings>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             name,
             git_repo_path,
             setup_script,
-            dev_script
+            dev_script,
+            max_concurrent_agents,
+            merge_strategy,
+            commit_settings,
+            auto_fix_max_iterations,
+            dev_server_settings,
+            sandbox_settings
         )
         .fetch_one(pool)
         .await
@@ -217,6 +542,12 @@ impl Project {
             git_repo_path: self.git_repo_path,
             setup_script: self.setup_script,
             dev_script: self.dev_script,
+            max_concurrent_agents: self.max_concurrent_agents,
+            merge_strategy: self.merge_strategy,
+            commit_settings: self.commit_settings.0,
+            auto_fix_max_iterations: self.auto_fix_max_iterations,
+            dev_server_settings: self.dev_server_settings.0,
+            sandbox_settings: self.sandbox_settings.0,
             current_branch,
             created_at: self.created_at,
             updated_at: self.updated_at,
@@ -341,3 +672,129 @@ impl Project {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(summary: Option<&'a str>) -> CommitMessageContext<'a> {
+        CommitMessageContext {
+            task_title: "Add login page",
+            task_id: Uuid::nil(),
+            attempt_id: Uuid::nil(),
+            executor: Some("claude"),
+            summary,
+        }
+    }
+
+    #[test]
+    fn test_render_commit_message() {
+        let defaults = CommitSettings::default();
+        assert_eq!(
+            defaults.render_message(&context(Some(" Added the page \n"))),
+            "Added the page"
+        );
+        assert_eq!(
+            defaults.render_message(&context(None)),
+            format!("Task attempt {} - Final changes", Uuid::nil())
+        );
+
+        let settings = CommitSettings {
+            message_template: Some("{task_title} ({executor})\n\n{summary}".to_string()),
+            conventional_prefix: Some("feat".to_string()),
+            co_authors: vec!["Jane Doe <jane@example.com>".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.render_message(&context(Some("Added the page"))),
+            "feat: Add login page (claude)\n\nAdded the page\n\nCo-authored-by: Jane Doe <jane@example.com>"
+        );
+
+        // An existing prefix isn't repeated
+        let settings = CommitSettings {
+            conventional_prefix: Some("fix:".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.render_message(&context(Some("fix: typo"))),
+            "fix: typo"
+        );
+    }
+
+    #[test]
+    fn test_validate_commit_settings() {
+        let mut settings = CommitSettings {
+            co_authors: vec!["Jane Doe <jane@example.com>".to_string()],
+            author_email: Some("bot@example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(settings.validate().is_ok());
+
+        settings.co_authors.push("jane@example.com".to_string());
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_commit_settings_deserialize_with_defaults() {
+        let settings: CommitSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, CommitSettings::default());
+
+        let settings: CommitSettings =
+            serde_json::from_str(r#"{"author_name":"vibe-bot","mode":"amend"}"#).unwrap();
+        assert_eq!(settings.author_name.as_deref(), Some("vibe-bot"));
+        assert_eq!(settings.mode, CommitMode::Amend);
+    }
+
+    #[test]
+    fn test_dev_server_settings() {
+        let mut settings = DevServerSettings::default();
+        settings
+            .env
+            .insert("VITE_BASE".to_string(), "{preview_path}".to_string());
+        settings.env.insert(
+            "API_URL".to_string(),
+            "http://localhost:{port}/api".to_string(),
+        );
+        assert!(settings.validate().is_ok());
+
+        let env = settings.render_env(5173, Uuid::nil());
+        assert_eq!(env["PORT"], "5173");
+        assert_eq!(env["API_URL"], "http://localhost:5173/api");
+        assert_eq!(
+            env["VITE_BASE"],
+            "/preview/00000000-0000-0000-0000-000000000000/"
+        );
+
+        settings.env.insert("PORT".to_string(), "3000".to_string());
+        assert!(settings.validate().is_err());
+        settings.env.remove("PORT");
+        settings.env.insert("1BAD".to_string(), String::new());
+        assert!(settings.validate().is_err());
+        settings.env.remove("1BAD");
+        settings.ready_pattern = Some("(unclosed".to_string());
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_sandbox_settings() {
+        let settings: SandboxSettings = serde_json::from_str("{}").unwrap();
+        assert!(!settings.enabled);
+        assert!(settings.validate().is_ok());
+
+        let mut settings: SandboxSettings =
+            serde_json::from_str(r#"{"enabled":true,"runtime":"podman","memory_mb":4096}"#)
+                .unwrap();
+        assert_eq!(settings.runtime, Some(ContainerRuntime::Podman));
+        // Enabled without an image
+        assert!(settings.validate().is_err());
+
+        settings.image = "ghcr.io/acme/agents:latest".to_string();
+        assert!(settings.validate().is_ok());
+
+        settings.image = "--privileged".to_string();
+        assert!(settings.validate().is_err());
+        settings.image = "node:20".to_string();
+        settings.cpus = Some(0.0);
+        assert!(settings.validate().is_err());
+    }
+}
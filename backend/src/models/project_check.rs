@@ -0,0 +1,449 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    execution_process::ExecutionProcessStatus, project::Project, task_attempt::TaskAttempt,
+};
+use crate::services::GitService;
+
+/// A script (tests, lint, typecheck...) run in an attempt's worktree before it is merged
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProjectCheck {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub script: String,
+    pub required: bool, // A failing or missing run blocks merge and PR creation
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateProjectCheck {
+    pub name: String,
+    pub script: String,
+    pub required: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdateProjectCheck {
+    pub name: Option<String>,
+    pub script: Option<String>,
+    pub required: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export)]
+pub enum CheckStatus {
+    NotRun,
+    Running,
+    Passed,
+    Failed,
+}
+
+/// The latest run of one check in an attempt
+#[derive(Debug, Clone, FromRow)]
+pub struct CheckRun {
+    pub execution_process_id: Uuid,
+    pub project_check_id: Uuid,
+    pub status: ExecutionProcessStatus,
+    pub exit_code: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub head_commit: Option<String>, // Commit of the attempt's branch the check ran on
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CheckResult {
+    pub check_id: Uuid,
+    pub name: String,
+    pub required: bool,
+    pub status: CheckStatus,
+    pub outdated: bool, // The branch, the worktree or the check changed since the run
+    pub execution_process_id: Option<Uuid>,
+    pub exit_code: Option<i64>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl CheckResult {
+    /// Evaluate `check` from its latest run, given when the coding agent last touched the worktree
+    /// and the commit the attempt's branch points to now
+    pub fn evaluate(
+        check: &ProjectCheck,
+        run: Option<&CheckRun>,
+        last_agent_activity: Option<DateTime<Utc>>,
+        head_commit: Option<&str>,
+    ) -> Self {
+        let status = match run.map(|run| &run.status) {
+            None => CheckStatus::NotRun,
+            Some(ExecutionProcessStatus::Queued | ExecutionProcessStatus::Running) => {
+                CheckStatus::Running
+            }
+            Some(ExecutionProcessStatus::Completed) => CheckStatus::Passed,
+            Some(ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed) => {
+                CheckStatus::Failed
+            }
+        };
+        let outdated = run.is_some_and(|run| {
+            check.updated_at > run.started_at
+                || last_agent_activity.is_some_and(|activity| activity > run.started_at)
+                // Rebases and merges of the base branch rewrite the branch without the agent
+                || matches!(
+                    (run.head_commit.as_deref(), head_commit),
+                    (Some(ran_on), Some(head)) if ran_on != head
+                )
+        });
+
+        Self {
+            check_id: check.id,
+            name: check.name.clone(),
+            required: check.required,
+            status,
+            outdated,
+            execution_process_id: run.map(|run| run.execution_process_id),
+            exit_code: run.and_then(|run| run.exit_code),
+            started_at: run.map(|run| run.started_at),
+            completed_at: run.and_then(|run| run.completed_at),
+        }
+    }
+
+    /// Whether this result keeps the attempt from being merged
+    pub fn blocks_merge(&self) -> bool {
+        self.required && (self.status != CheckStatus::Passed || self.outdated)
+    }
+}
+
+/// Why merging is blocked by `results`, or None when every required check passed on the current code
+pub fn blocking_message(results: &[CheckResult]) -> Option<String> {
+    let blocking: Vec<String> = results
+        .iter()
+        .filter(|result| result.blocks_merge())
+        .map(|result| {
+            let reason = match result.status {
+                CheckStatus::NotRun => "not run",
+                CheckStatus::Running => "still running",
+                CheckStatus::Failed => "failed",
+                CheckStatus::Passed => "outdated",
+            };
+            format!("{} ({})", result.name, reason)
+        })
+        .collect();
+
+    if blocking.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "Required checks have not passed: {}. Run the checks again or skip them to continue.",
+            blocking.join(", ")
+        ))
+    }
+}
+
+impl ProjectCheck {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectCheck,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, script, required as "required!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_checks
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id_and_project_id(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectCheck,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, script, required as "required!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_checks
+               WHERE id = $1 AND project_id = $2"#,
+            id,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectCheck,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let required = data.required.unwrap_or(true);
+        sqlx::query_as!(
+            ProjectCheck,
+            r#"INSERT INTO project_checks (id, project_id, name, script, required)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, script, required as "required!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.script,
+            required
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+        data: &UpdateProjectCheck,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id_and_project_id(pool, id, project_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let script = data.script.as_ref().unwrap_or(&existing.script);
+        let required = data.required.unwrap_or(existing.required);
+
+        sqlx::query_as!(
+            ProjectCheck,
+            r#"UPDATE project_checks
+               SET name = $3, script = $4, required = $5, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND project_id = $2
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, script, required as "required!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            script,
+            required
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_checks WHERE id = $1 AND project_id = $2",
+            id,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Check that the given name and script are not blank
+    pub fn validate(name: Option<&str>, script: Option<&str>) -> Result<(), String> {
+        if name.is_some_and(|name| name.trim().is_empty()) {
+            return Err("Check name cannot be empty".to_string());
+        }
+        if script.is_some_and(|script| script.trim().is_empty()) {
+            return Err("Check script cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Results of every check of the project on an attempt, from the latest run of each
+    pub async fn find_results_for_attempt(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        attempt_id: Uuid,
+    ) -> Result<Vec<CheckResult>, sqlx::Error> {
+        let checks = Self::find_by_project_id(pool, project_id).await?;
+
+        let runs = sqlx::query_as!(
+            CheckRun,
+            r#"SELECT
+                id as "execution_process_id!: Uuid",
+                project_check_id as "project_check_id!: Uuid",
+                status as "status!: ExecutionProcessStatus",
+                exit_code,
+                started_at as "started_at!: DateTime<Utc>",
+                completed_at as "completed_at?: DateTime<Utc>",
+                head_commit
+               FROM execution_processes
+               WHERE task_attempt_id = $1 AND process_type = 'checkscript' AND project_check_id IS NOT NULL
+               ORDER BY started_at ASC"#,
+            attempt_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        // A running agent counts as touching the worktree right now
+        let agents = sqlx::query!(
+            r#"SELECT completed_at as "completed_at?: DateTime<Utc>"
+               FROM execution_processes
+               WHERE task_attempt_id = $1 AND process_type = 'codingagent' AND status != 'queued'"#,
+            attempt_id
+        )
+        .fetch_all(pool)
+        .await?;
+        let last_agent_activity = agents
+            .iter()
+            .map(|agent| agent.completed_at.unwrap_or_else(Utc::now))
+            .max();
+        let head_commit = Self::attempt_head_commit(pool, project_id, attempt_id).await?;
+
+        Ok(checks
+            .iter()
+            .map(|check| {
+                let latest_run = runs
+                    .iter()
+                    .rev()
+                    .find(|run| run.project_check_id == check.id);
+                CheckResult::evaluate(
+                    check,
+                    latest_run,
+                    last_agent_activity,
+                    head_commit.as_deref(),
+                )
+            })
+            .collect())
+    }
+
+    /// Commit the attempt's branch points to, None if it can't be resolved
+    pub async fn attempt_head_commit(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        attempt_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let (Some(attempt), Some(project)) = (
+            TaskAttempt::find_by_id(pool, attempt_id).await?,
+            Project::find_by_id(pool, project_id).await?,
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(GitService::new(&project.git_repo_path)
+            .and_then(|git| git.get_branch_head_commit(&attempt.branch))
+            .map_err(|e| {
+                tracing::debug!(
+                    "Failed to resolve the head of branch {} of attempt {}: {}",
+                    attempt.branch,
+                    attempt_id,
+                    e
+                )
+            })
+            .ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn check(required: bool, updated_at: DateTime<Utc>) -> ProjectCheck {
+        ProjectCheck {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            name: "tests".to_string(),
+            script: "cargo test".to_string(),
+            required,
+            created_at: updated_at,
+            updated_at,
+        }
+    }
+
+    fn run(check: &ProjectCheck, status: ExecutionProcessStatus, at: DateTime<Utc>) -> CheckRun {
+        CheckRun {
+            execution_process_id: Uuid::new_v4(),
+            project_check_id: check.id,
+            status,
+            exit_code: None,
+            started_at: at,
+            completed_at: None,
+            head_commit: Some("a1b2c3".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_check_result() {
+        let now = Utc::now();
+        let earlier = now - Duration::minutes(10);
+        let later = now + Duration::minutes(10);
+        let check = check(true, earlier);
+
+        let result = CheckResult::evaluate(&check, None, None, None);
+        assert_eq!(result.status, CheckStatus::NotRun);
+        assert!(!result.outdated);
+        assert!(result.blocks_merge());
+
+        let passed = run(&check, ExecutionProcessStatus::Completed, now);
+        let result = CheckResult::evaluate(&check, Some(&passed), Some(earlier), None);
+        assert_eq!(result.status, CheckStatus::Passed);
+        assert!(!result.outdated);
+        assert!(!result.blocks_merge());
+
+        // The agent ran again after the check
+        let result = CheckResult::evaluate(&check, Some(&passed), Some(later), None);
+        assert!(result.outdated);
+        assert!(result.blocks_merge());
+
+        // The check script was edited after the run
+        let edited = ProjectCheck {
+            updated_at: later,
+            ..check.clone()
+        };
+        assert!(CheckResult::evaluate(&edited, Some(&passed), None, None).outdated);
+
+        // The branch was rebased after the run
+        assert!(!CheckResult::evaluate(&check, Some(&passed), None, Some("a1b2c3")).outdated);
+        assert!(CheckResult::evaluate(&check, Some(&passed), None, Some("d4e5f6")).outdated);
+
+        let killed = run(&check, ExecutionProcessStatus::Killed, now);
+        let result = CheckResult::evaluate(&check, Some(&killed), None, None);
+        assert_eq!(result.status, CheckStatus::Failed);
+        assert!(result.blocks_merge());
+
+        let optional = ProjectCheck {
+            required: false,
+            ..check.clone()
+        };
+        assert!(!CheckResult::evaluate(&optional, Some(&killed), None, None).blocks_merge());
+    }
+
+    #[test]
+    fn test_blocking_message() {
+        let now = Utc::now();
+        let lint = ProjectCheck {
+            name: "lint".to_string(),
+            ..check(true, now)
+        };
+        let tests = check(true, now);
+        let optional = ProjectCheck {
+            name: "bench".to_string(),
+            ..check(false, now)
+        };
+
+        let failed = run(&tests, ExecutionProcessStatus::Failed, now);
+        let passed = run(&lint, ExecutionProcessStatus::Completed, now);
+
+        let results = vec![
+            CheckResult::evaluate(&lint, Some(&passed), None, None),
+            CheckResult::evaluate(&tests, Some(&failed), None, None),
+            CheckResult::evaluate(&optional, None, None, None),
+        ];
+        assert_eq!(
+            blocking_message(&results).unwrap(),
+            "Required checks have not passed: tests (failed). Run the checks again or skip them to continue."
+        );
+
+        assert!(blocking_message(&results[..1]).is_none());
+        assert!(blocking_message(&[]).is_none());
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{SecretError, SecretService};
+
+/// An environment variable set on every process spawned for the project's tasks
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProjectEnvVar {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub value: Option<String>, // None for secrets, which are never sent back
+    pub is_secret: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateProjectEnvVar {
+    pub name: String,
+    pub value: String,
+    pub is_secret: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdateProjectEnvVar {
+    pub name: Option<String>,
+    pub value: Option<String>, // Keeps the current value when omitted
+    pub is_secret: Option<bool>,
+}
+
+/// A variable as stored, with secrets still encrypted
+#[derive(Debug, Clone, FromRow)]
+struct StoredEnvVar {
+    name: String,
+    value: String,
+    is_secret: bool,
+}
+
+impl StoredEnvVar {
+    fn plaintext(self) -> Result<(String, String), SecretError> {
+        let value = if self.is_secret {
+            SecretService::decrypt(&self.value)?
+        } else {
+            self.value
+        };
+        Ok((self.name, value))
+    }
+}
+
+#[derive(Debug)]
+pub enum ProjectEnvVarError {
+    Database(sqlx::Error),
+    Secret(SecretError),
+    Validation(String),
+}
+
+impl std::fmt::Display for ProjectEnvVarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectEnvVarError::Database(e) => write!(f, "Database error: {}", e),
+            ProjectEnvVarError::Secret(e) => write!(f, "{}", e),
+            ProjectEnvVarError::Validation(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ProjectEnvVarError {}
+
+impl From<sqlx::Error> for ProjectEnvVarError {
+    fn from(err: sqlx::Error) -> Self {
+        ProjectEnvVarError::Database(err)
+    }
+}
+
+impl From<SecretError> for ProjectEnvVarError {
+    fn from(err: SecretError) -> Self {
+        ProjectEnvVarError::Secret(err)
+    }
+}
+
+/// Whether `name` can be used as an environment variable name in any shell
+pub fn is_valid_env_var_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn stored_value(value: &str, is_secret: bool) -> Result<String, SecretError> {
+    if is_secret {
+        SecretService::encrypt(value)
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+impl ProjectEnvVar {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, CASE WHEN is_secret THEN NULL ELSE value END as "value?: String", is_secret as "is_secret!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_env_vars
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id_and_project_id(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, CASE WHEN is_secret THEN NULL ELSE value END as "value?: String", is_secret as "is_secret!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_env_vars
+               WHERE id = $1 AND project_id = $2"#,
+            id,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectEnvVar,
+    ) -> Result<Self, ProjectEnvVarError> {
+        let id = Uuid::new_v4();
+        let is_secret = data.is_secret.unwrap_or(false);
+        let value = stored_value(&data.value, is_secret)?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_env_vars (id, project_id, name, value, is_secret)
+               VALUES ($1, $2, $3, $4, $5)"#,
+            id,
+            project_id,
+            data.name,
+            value,
+            is_secret
+        )
+        .execute(pool)
+        .await?;
+
+        Self::find_by_id_and_project_id(pool, id, project_id)
+            .await?
+            .ok_or(ProjectEnvVarError::Database(sqlx::Error::RowNotFound))
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+        data: &UpdateProjectEnvVar,
+    ) -> Result<Self, ProjectEnvVarError> {
+        let existing = Self::find_by_id_and_project_id(pool, id, project_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let is_secret = data.is_secret.unwrap_or(existing.is_secret);
+        let value = match data.value.as_deref() {
+            Some(value) => Some(stored_value(value, is_secret)?),
+            None if is_secret != existing.is_secret => {
+                return Err(ProjectEnvVarError::Validation(
+                    "Enter the value again to change whether the variable is secret".to_string(),
+                ))
+            }
+            None => None,
+        };
+
+        sqlx::query!(
+            r#"UPDATE project_env_vars
+               SET name = $3, value = COALESCE($4, value), is_secret = $5, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND project_id = $2"#,
+            id,
+            project_id,
+            name,
+            value,
+            is_secret
+        )
+        .execute(pool)
+        .await?;
+
+        Self::find_by_id_and_project_id(pool, id, project_id)
+            .await?
+            .ok_or(ProjectEnvVarError::Database(sqlx::Error::RowNotFound))
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_env_vars WHERE id = $1 AND project_id = $2",
+            id,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Check that the given name is usable, if any
+    pub fn validate(name: Option<&str>) -> Result<(), String> {
+        match name {
+            Some(name) if !is_valid_env_var_name(name) => Err(format!(
+                "Invalid environment variable name '{}'. Use letters, digits and underscores, not starting with a digit.",
+                name
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Environment of the project a task belongs to, with secrets decrypted
+    pub async fn resolve_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<HashMap<String, String>, ProjectEnvVarError> {
+        let vars = sqlx::query_as!(
+            StoredEnvVar,
+            r#"SELECT v.name, v.value, v.is_secret as "is_secret!: bool"
+               FROM project_env_vars v
+               JOIN tasks t ON t.project_id = v.project_id
+               WHERE t.id = $1"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        vars.into_iter()
+            .map(|var| var.plaintext().map_err(ProjectEnvVarError::from))
+            .collect()
+    }
+
+    /// Decrypted secret values of the project an execution process runs for
+    pub async fn find_secrets_for_process(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<String>, ProjectEnvVarError> {
+        let vars = sqlx::query_as!(
+            StoredEnvVar,
+            r#"SELECT v.name, v.value, v.is_secret as "is_secret!: bool"
+               FROM project_env_vars v
+               JOIN tasks t ON t.project_id = v.project_id
+               JOIN task_attempts ta ON ta.task_id = t.id
+               JOIN execution_processes ep ON ep.task_attempt_id = ta.id
+               WHERE ep.id = $1 AND v.is_secret = TRUE"#,
+            execution_process_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        vars.into_iter().map(|var| Ok(var.plaintext()?.1)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_var_names() {
+        assert!(is_valid_env_var_name("DATABASE_URL"));
+        assert!(is_valid_env_var_name("_private2"));
+        assert!(!is_valid_env_var_name(""));
+        assert!(!is_valid_env_var_name("2FA_SECRET"));
+        assert!(!is_valid_env_var_name("API-KEY"));
+        assert!(!is_valid_env_var_name("A B"));
+
+        assert!(ProjectEnvVar::validate(None).is_ok());
+        assert!(ProjectEnvVar::validate(Some("API KEY")).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// What a member can do on a project, each role allowing everything the ones before it do
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, TS, PartialEq, Eq, PartialOrd, Ord)]
+#[sqlx(type_name = "project_role", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ProjectRole {
+    Viewer,      // Sees the board, attempts and their logs
+    Contributor, // Creates and edits tasks, and starts and stops attempts
+    Maintainer,  // Merges attempts, opens pull requests and changes the project's settings
+}
+
+impl ProjectRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectRole::Viewer => "viewer",
+            ProjectRole::Contributor => "contributor",
+            ProjectRole::Maintainer => "maintainer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProjectMember {
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub role: ProjectRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct SetProjectMember {
+    pub role: ProjectRole,
+}
+
+impl ProjectMember {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMember,
+            r#"SELECT m.project_id as "project_id!: Uuid", m.user_id as "user_id!: Uuid", u.username, m.role as "role!: ProjectRole", m.created_at as "created_at!: DateTime<Utc>", m.updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_members m
+               JOIN users u ON u.id = m.user_id
+               WHERE m.project_id = $1
+               ORDER BY u.username COLLATE NOCASE"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Role of a user on a project, None when they are not a member
+    pub async fn find_role(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ProjectRole>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT role as "role!: ProjectRole" FROM project_members WHERE project_id = $1 AND user_id = $2"#,
+            project_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add a user to a project, or change the role they have on it
+    pub async fn set(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        user_id: Uuid,
+        role: ProjectRole,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO project_members (project_id, user_id, role)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (project_id, user_id) DO UPDATE
+               SET role = excluded.role, updated_at = datetime('now', 'subsec')"#,
+            project_id,
+            user_id,
+            role
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_members WHERE project_id = $1 AND user_id = $2",
+            project_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_are_ordered_by_what_they_allow() {
+        assert!(ProjectRole::Viewer < ProjectRole::Contributor);
+        assert!(ProjectRole::Contributor < ProjectRole::Maintainer);
+        assert_eq!(
+            serde_json::to_string(&ProjectRole::Maintainer).unwrap(),
+            "\"maintainer\""
+        );
+    }
+}
@@ -8,10 +8,17 @@ use tracing::info;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::{project::Project, task::Task};
+use super::{
+    execution_process::{
+        ExecutionProcess, ExecutionProcessStatus, ExecutionProcessSummary, ExecutionProcessType,
+    },
+    executor_profile::ExecutorProfile,
+    project::{MergeStrategy, Project},
+    project_check::{CheckResult, ProjectCheck},
+    task::Task,
+};
 use crate::services::{
-    CreatePrRequest, GitHubRepoInfo, GitHubService, GitHubServiceError, GitService,
-    GitServiceError, ProcessService,
+    CreatePrRequest, GitProviderError, GitService, GitServiceError, HostedRepo, ProcessService,
 };
 
 // Constants for git diff operations
@@ -23,7 +30,7 @@ pub enum TaskAttemptError {
     Database(sqlx::Error),
     Git(GitError),
     GitService(GitServiceError),
-    GitHubService(GitHubServiceError),
+    GitProvider(GitProviderError),
     TaskNotFound,
     ProjectNotFound,
     ValidationError(String),
@@ -36,7 +43,7 @@ impl std::fmt::Display for TaskAttemptError {
             TaskAttemptError::Database(e) => write!(f, "Database error: {}", e),
             TaskAttemptError::Git(e) => write!(f, "Git error: {}", e),
             TaskAttemptError::GitService(e) => write!(f, "Git service error: {}", e),
-            TaskAttemptError::GitHubService(e) => write!(f, "GitHub service error: {}", e),
+            TaskAttemptError::GitProvider(e) => write!(f, "Git provider error: {}", e),
             TaskAttemptError::TaskNotFound => write!(f, "Task not found"),
             TaskAttemptError::ProjectNotFound => write!(f, "Project not found"),
             TaskAttemptError::ValidationError(e) => write!(f, "Validation error: {}", e),
@@ -65,9 +72,9 @@ impl From<GitServiceError> for TaskAttemptError {
     }
 }
 
-impl From<GitHubServiceError> for TaskAttemptError {
-    fn from(err: GitHubServiceError) -> Self {
-        TaskAttemptError::GitHubService(err)
+impl From<GitProviderError> for TaskAttemptError {
+    fn from(err: GitProviderError) -> Self {
+        TaskAttemptError::GitProvider(err)
     }
 }
 
@@ -94,12 +101,14 @@ pub struct TaskAttempt {
     pub base_branch: String, // Base branch this attempt is based on
     pub merge_commit: Option<String>,
     pub executor: Option<String>,  // Name of the executor to use
-    pub pr_url: Option<String>,    // GitHub PR URL
-    pub pr_number: Option<i64>,    // GitHub PR number
+    pub pr_url: Option<String>,    // Pull or merge request URL
+    pub pr_number: Option<i64>,    // Pull or merge request number on its host
     pub pr_status: Option<String>, // open, closed, merged
     pub pr_merged_at: Option<DateTime<Utc>>, // When PR was merged
     pub worktree_deleted: bool,    // Flag indicating if worktree has been cleaned up
     pub setup_completed_at: Option<DateTime<Utc>>, // When setup script was last completed
+    pub executor_profile_id: Option<Uuid>, // Project executor profile the attempt runs with
+    pub started_by: Option<Uuid>,  // User who started the attempt, in multi-user mode
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -109,6 +118,23 @@ pub struct TaskAttempt {
 pub struct CreateTaskAttempt {
     pub executor: Option<String>, // Optional executor name (defaults to "echo")
     pub base_branch: Option<String>, // Optional base branch to checkout (defaults to current HEAD)
+    pub executor_profile_id: Option<Uuid>, // Optional project executor profile (overrides executor)
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct MergeTaskAttempt {
+    pub strategy: Option<MergeStrategy>, // Overrides the project's merge strategy
+    #[serde(default)]
+    pub skip_checks: bool, // Merge even if required checks have not passed
+}
+
+/// Outcome of a merge: the new base branch commit, or the files that conflict
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MergeResult {
+    pub merge_commit: Option<String>,
+    pub conflicted_files: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -117,12 +143,12 @@ pub struct UpdateTaskAttempt {
     // Currently no updateable fields, but keeping struct for API compatibility
 }
 
-/// GitHub PR creation parameters
+/// Pull request creation parameters
 pub struct CreatePrParams<'a> {
     pub attempt_id: Uuid,
     pub task_id: Uuid,
     pub project_id: Uuid,
-    pub github_token: &'a str,
+    pub repo: &'a HostedRepo, // Where to push and open the pull request
     pub title: &'a str,
     pub body: Option<&'a str>,
     pub base_branch: Option<&'a str>,
@@ -162,6 +188,130 @@ pub struct WorktreeDiff {
     pub files: Vec<FileDiff>,
 }
 
+/// Size of a diff, in files and lines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DiffStats {
+    pub files_changed: usize,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+impl WorktreeDiff {
+    pub fn stats(&self) -> DiffStats {
+        let mut stats = DiffStats {
+            files_changed: self.files.len(),
+            ..Default::default()
+        };
+        for chunk in self.files.iter().flat_map(|file| &file.chunks) {
+            let lines = chunk.content.lines().count().max(1);
+            match chunk.chunk_type {
+                DiffChunkType::Insert => stats.lines_added += lines,
+                DiffChunkType::Delete => stats.lines_removed += lines,
+                DiffChunkType::Equal => {}
+            }
+        }
+        stats
+    }
+}
+
+/// One attempt of a task next to its siblings, for picking the one to merge
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AttemptComparison {
+    pub attempt: TaskAttempt,
+    pub diff_stats: Option<DiffStats>, // None if the diff could not be computed
+    pub agent_status: Option<ExecutionProcessStatus>, // Of the latest coding agent execution
+    pub exit_code: Option<i64>,
+    pub agent_duration_seconds: Option<i64>, // Summed over all coding agent executions
+    pub checks: Vec<CheckResult>,
+}
+
+/// Git operation in an attempt's worktree that stopped on conflicts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ConflictOperation {
+    /// Rebasing the attempt branch onto its base branch
+    Rebase,
+    /// Merging the base branch into the attempt branch
+    Merge,
+}
+
+/// One conflicting region of a file. `ours` is always the attempt's side and `theirs` the base
+/// branch's side, whichever operation is running.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export)]
+pub struct ConflictHunk {
+    pub start_line: usize, // 1-based line of the hunk in `merged_content`
+    pub ours: String,
+    pub base: String,
+    pub theirs: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ConflictFile {
+    pub path: String,
+    pub ours: Option<String>, // None if the attempt deleted the file or it is binary
+    pub base: Option<String>, // None if both sides added the file
+    pub theirs: Option<String>, // None if the base branch deleted the file or it is binary
+    pub merged_content: Option<String>, // Both sides merged, with diff3 conflict markers
+    pub hunks: Vec<ConflictHunk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ConflictReport {
+    pub operation: ConflictOperation,
+    pub base_branch: String,
+    pub files: Vec<ConflictFile>, // Files still unresolved, empty once all are staged
+}
+
+impl ConflictReport {
+    /// Follow-up prompt asking the attempt's coding agent to resolve the conflicts in its
+    /// worktree, where they are left with diff3 style markers
+    pub fn agent_prompt(&self) -> String {
+        let operation = match self.operation {
+            ConflictOperation::Rebase => "Rebasing this branch onto",
+            ConflictOperation::Merge => "Merging",
+        };
+        let mut prompt = format!(
+            "{} the `{}` branch stopped on conflicts in these files:\n",
+            operation, self.base_branch
+        );
+        for file in &self.files {
+            prompt.push_str(&format!("- {}\n", file.path));
+        }
+        prompt.push_str(
+            "\nResolve each conflict by editing the files so they keep the intent of both this \
+             branch's changes and the changes on the base branch, and remove every conflict \
+             marker (<<<<<<<, |||||||, =======, >>>>>>>). Do not commit, stage, rebase or merge; \
+             the operation is continued once no conflict markers are left.",
+        );
+        prompt
+    }
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct StartConflictResolution {
+    pub operation: ConflictOperation,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export)]
+pub struct ConflictResolution {
+    pub path: String,
+    pub content: Option<String>, // None resolves the conflict by deleting the file
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct ResolveConflicts {
+    pub resolutions: Vec<ConflictResolution>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct BranchStatus {
@@ -182,6 +332,7 @@ pub enum ExecutionState {
     SetupComplete,
     SetupFailed,
     SetupStopped,
+    CodingAgentQueued,
     CodingAgentRunning,
     CodingAgentComplete,
     CodingAgentFailed,
@@ -237,6 +388,8 @@ impl TaskAttempt {
                        ta.pr_merged_at      AS "pr_merged_at: DateTime<Utc>",
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       ta.executor_profile_id AS "executor_profile_id?: Uuid",
+                       ta.started_by        AS "started_by: Uuid",
                        ta.created_at        AS "created_at!: DateTime<Utc>",
                        ta.updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts ta
@@ -317,6 +470,8 @@ impl TaskAttempt {
                        pr_merged_at      AS "pr_merged_at: DateTime<Utc>",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       executor_profile_id AS "executor_profile_id?: Uuid",
+                       started_by        AS "started_by: Uuid",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -346,6 +501,8 @@ impl TaskAttempt {
                        pr_merged_at      AS "pr_merged_at: DateTime<Utc>",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       executor_profile_id AS "executor_profile_id?: Uuid",
+                       started_by        AS "started_by: Uuid",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -357,6 +514,22 @@ impl TaskAttempt {
         .await
     }
 
+    /// Id of the project an attempt belongs to
+    pub async fn find_project_id(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT t.project_id as "project_id!: Uuid"
+               FROM task_attempts ta
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE ta.id = $1"#,
+            attempt_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Find task attempts by task_id with project git repo path for cleanup operations
     pub async fn find_by_task_id_with_project(
         pool: &SqlitePool,
@@ -434,6 +607,7 @@ impl TaskAttempt {
         pool: &SqlitePool,
         data: &CreateTaskAttempt,
         task_id: Uuid,
+        started_by: Option<Uuid>,
     ) -> Result<Self, TaskAttemptError> {
         let attempt_id = Uuid::new_v4();
         // let prefixed_id = format!("vibe-kanban-{}", attempt_id);
@@ -460,6 +634,23 @@ impl TaskAttempt {
             .await?
             .ok_or(TaskAttemptError::ProjectNotFound)?;
 
+        // A profile decides which executor the attempt runs with
+        let executor = match data.executor_profile_id {
+            Some(profile_id) => {
+                let profile =
+                    ExecutorProfile::find_by_id_and_project_id(pool, profile_id, project.id)
+                        .await?
+                        .ok_or_else(|| {
+                            TaskAttemptError::ValidationError(format!(
+                                "Executor profile {} not found in project",
+                                profile_id
+                            ))
+                        })?;
+                Some(profile.executor)
+            }
+            None => data.executor.clone(),
+        };
+
         // Create GitService instance
         let git_service = GitService::new(&project.git_repo_path)?;
 
@@ -481,22 +672,24 @@ impl TaskAttempt {
         // Insert the record into the database
         Ok(sqlx::query_as!(
             TaskAttempt,
-            r#"INSERT INTO task_attempts (id, task_id, worktree_path, branch, base_branch, merge_commit, executor, pr_url, pr_number, pr_status, pr_merged_at, worktree_deleted, setup_completed_at)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", worktree_path, branch, base_branch, merge_commit, executor, pr_url, pr_number, pr_status, pr_merged_at as "pr_merged_at: DateTime<Utc>", worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO task_attempts (id, task_id, worktree_path, branch, base_branch, merge_commit, executor, pr_url, pr_number, pr_status, pr_merged_at, worktree_deleted, setup_completed_at, executor_profile_id, started_by)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", worktree_path, branch, base_branch, merge_commit, executor, pr_url, pr_number, pr_status, pr_merged_at as "pr_merged_at: DateTime<Utc>", worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", executor_profile_id as "executor_profile_id?: Uuid", started_by as "started_by: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             attempt_id,
             task_id,
             worktree_path_str,
             task_attempt_branch,
             resolved_base_branch,
             Option::<String>::None, // merge_commit is always None during creation
-            data.executor,
+            executor,
             Option::<String>::None, // pr_url is None during creation
             Option::<i64>::None, // pr_number is None during creation
             Option::<String>::None, // pr_status is None during creation
             Option::<DateTime<Utc>>::None, // pr_merged_at is None during creation
             false, // worktree_deleted is false during creation
-            Option::<DateTime<Utc>>::None // setup_completed_at is None during creation
+            Option::<DateTime<Utc>>::None, // setup_completed_at is None during creation
+            data.executor_profile_id,
+            started_by
         )
         .fetch_one(pool)
         .await?)
@@ -522,6 +715,7 @@ impl TaskAttempt {
     }
 
     /// Perform the actual merge operation using GitService
+    #[allow(clippy::too_many_arguments)]
     fn perform_merge_operation(
         worktree_path: &str,
         main_repo_path: &str,
@@ -530,6 +724,7 @@ impl TaskAttempt {
         task_title: &str,
         task_description: &Option<String>,
         task_id: Uuid,
+        strategy: MergeStrategy,
     ) -> Result<String, TaskAttemptError> {
         let git_service = GitService::new(main_repo_path)?;
         let worktree_path = Path::new(worktree_path);
@@ -550,7 +745,13 @@ impl TaskAttempt {
         }
 
         git_service
-            .merge_changes(worktree_path, branch_name, base_branch, &commit_message)
+            .merge_changes(
+                worktree_path,
+                branch_name,
+                base_branch,
+                &commit_message,
+                strategy,
+            )
             .map_err(TaskAttemptError::from)
     }
 
@@ -568,12 +769,14 @@ impl TaskAttempt {
             .map_err(TaskAttemptError::from)
     }
 
-    /// Merge the worktree changes back to the main repository
+    /// Merge the worktree changes back to the main repository, with the project's merge
+    /// strategy unless `strategy` overrides it
     pub async fn merge_changes(
         pool: &SqlitePool,
         attempt_id: Uuid,
         task_id: Uuid,
         project_id: Uuid,
+        strategy: Option<MergeStrategy>,
     ) -> Result<String, TaskAttemptError> {
         // Load context with full validation
         let ctx = TaskAttempt::load_context(pool, attempt_id, task_id, project_id).await?;
@@ -591,6 +794,7 @@ impl TaskAttempt {
             &ctx.task.title,
             &ctx.task.description,
             ctx.task.id,
+            strategy.unwrap_or(ctx.project.merge_strategy),
         )?;
 
         // Update the task attempt with the merge commit
@@ -627,6 +831,17 @@ impl TaskAttempt {
         ProcessService::start_dev_server(pool, app_state, attempt_id, task_id, project_id).await
     }
 
+    /// Run the project's check scripts in the attempt's worktree
+    pub async fn start_checks(
+        pool: &SqlitePool,
+        app_state: &crate::app_state::AppState,
+        attempt_id: Uuid,
+        task_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<(), TaskAttemptError> {
+        ProcessService::start_checks(pool, app_state, attempt_id, task_id, project_id).await
+    }
+
     /// Start a follow-up execution using the same executor type as the first process
     /// Returns the attempt_id that was actually used (always the original attempt_id for session continuity)
     pub async fn start_followup_execution(
@@ -742,6 +957,50 @@ impl TaskAttempt {
         }
     }
 
+    /// Diff size, agent outcome and check results of every attempt of a task, oldest first
+    pub async fn compare_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<Vec<AttemptComparison>, TaskAttemptError> {
+        let mut attempts = Self::find_by_task_id(pool, task_id).await?;
+        attempts.sort_by_key(|attempt| attempt.created_at);
+
+        let mut comparisons = Vec::with_capacity(attempts.len());
+        for attempt in attempts {
+            let diff_stats = match Self::get_diff(pool, attempt.id, task_id, project_id).await {
+                Ok(diff) => Some(diff.stats()),
+                Err(e) => {
+                    tracing::warn!("Failed to get diff for task attempt {}: {}", attempt.id, e);
+                    None
+                }
+            };
+
+            let agents: Vec<ExecutionProcessSummary> =
+                ExecutionProcess::find_summaries_by_task_attempt_id(pool, attempt.id)
+                    .await?
+                    .into_iter()
+                    .filter(|p| {
+                        p.process_type == ExecutionProcessType::CodingAgent
+                            && p.status != ExecutionProcessStatus::Queued
+                    })
+                    .collect();
+            let latest_agent = agents.last();
+
+            comparisons.push(AttemptComparison {
+                diff_stats,
+                agent_status: latest_agent.map(|agent| agent.status.clone()),
+                exit_code: latest_agent.and_then(|agent| agent.exit_code),
+                agent_duration_seconds: agent_duration_seconds(&agents, Utc::now()),
+                checks: ProjectCheck::find_results_for_attempt(pool, project_id, attempt.id)
+                    .await?,
+                attempt,
+            });
+        }
+
+        Ok(comparisons)
+    }
+
     /// Get the branch status for this task attempt
     pub async fn get_branch_status(
         pool: &SqlitePool,
@@ -840,11 +1099,21 @@ impl TaskAttempt {
             Self::ensure_worktree_exists(pool, attempt_id, project_id, "rebase").await?;
 
         // Perform the git rebase operations (synchronous)
-        let new_base_commit = Self::perform_rebase_operation(
+        let result = Self::perform_rebase_operation(
             &worktree_path,
             &ctx.project.git_repo_path,
             effective_base_branch.clone(),
-        )?;
+        );
+
+        // A rebase stopped on conflicts is still onto the new base, so record it too
+        if let Err(e) = &result {
+            if !matches!(
+                e,
+                TaskAttemptError::GitService(GitServiceError::ConflictingFiles(_))
+            ) {
+                return result;
+            }
+        }
 
         // Update the database with the new base branch if it was changed
         if let Some(new_base_branch) = &effective_base_branch {
@@ -866,7 +1135,103 @@ impl TaskAttempt {
             }
         }
 
-        Ok(new_base_commit)
+        result
+    }
+
+    /// The conflicts of the rebase or merge with the base branch in progress in the attempt's
+    /// worktree, or None if there is none
+    pub async fn get_conflicts(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        task_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<Option<ConflictReport>, TaskAttemptError> {
+        let ctx = TaskAttempt::load_context(pool, attempt_id, task_id, project_id).await?;
+        let worktree_path =
+            Self::ensure_worktree_exists(pool, attempt_id, project_id, "get conflicts").await?;
+
+        let git_service = GitService::new(&ctx.project.git_repo_path)?;
+        Ok(git_service
+            .get_conflict_report(Path::new(&worktree_path), &ctx.task_attempt.base_branch)?)
+    }
+
+    /// Bring the base branch into the attempt's branch by rebasing onto it or merging it in.
+    /// Returns the conflicts if the operation stopped on any, or None if it completed.
+    pub async fn start_conflict_resolution(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        task_id: Uuid,
+        project_id: Uuid,
+        operation: ConflictOperation,
+    ) -> Result<Option<ConflictReport>, TaskAttemptError> {
+        let result = match operation {
+            ConflictOperation::Rebase => {
+                Self::rebase_attempt(pool, attempt_id, task_id, project_id, None)
+                    .await
+                    .map(|_| ())
+            }
+            ConflictOperation::Merge => {
+                let ctx = TaskAttempt::load_context(pool, attempt_id, task_id, project_id).await?;
+                let worktree_path =
+                    Self::ensure_worktree_exists(pool, attempt_id, project_id, "merge base")
+                        .await?;
+                GitService::new(&ctx.project.git_repo_path)?
+                    .merge_base_into_branch(
+                        Path::new(&worktree_path),
+                        &ctx.task_attempt.base_branch,
+                    )
+                    .map_err(TaskAttemptError::from)
+            }
+        };
+
+        match result {
+            Ok(()) => Ok(None),
+            Err(TaskAttemptError::GitService(GitServiceError::ConflictingFiles(_))) => {
+                Self::get_conflicts(pool, attempt_id, task_id, project_id).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Apply conflict resolutions and continue the rebase or merge in progress. Returns the
+    /// conflicts still left, or None once the operation completed.
+    pub async fn resolve_conflicts(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        task_id: Uuid,
+        project_id: Uuid,
+        resolutions: &[ConflictResolution],
+    ) -> Result<Option<ConflictReport>, TaskAttemptError> {
+        let ctx = TaskAttempt::load_context(pool, attempt_id, task_id, project_id).await?;
+        let worktree_path =
+            Self::ensure_worktree_exists(pool, attempt_id, project_id, "resolve conflicts").await?;
+
+        let git_service = GitService::new(&ctx.project.git_repo_path)?;
+        let worktree_path = Path::new(&worktree_path);
+        if git_service.resolve_conflicts(
+            worktree_path,
+            &ctx.task_attempt.base_branch,
+            resolutions,
+        )? {
+            return Ok(None);
+        }
+        Ok(git_service.get_conflict_report(worktree_path, &ctx.task_attempt.base_branch)?)
+    }
+
+    /// Abort the rebase or merge in progress, restoring the attempt's branch as it was
+    pub async fn abort_conflicts(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        task_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<(), TaskAttemptError> {
+        let ctx = TaskAttempt::load_context(pool, attempt_id, task_id, project_id).await?;
+        let worktree_path =
+            Self::ensure_worktree_exists(pool, attempt_id, project_id, "abort conflicts").await?;
+
+        GitService::new(&ctx.project.git_repo_path)?
+            .abort_conflict_resolution(Path::new(&worktree_path))?;
+        Ok(())
     }
 
     /// Delete a file from the worktree and commit the change
@@ -894,8 +1259,8 @@ impl TaskAttempt {
         Ok(commit_id)
     }
 
-    /// Create a GitHub PR for this task attempt
-    pub async fn create_github_pr(
+    /// Push the attempt's branch and open a pull request for it on the repository's host
+    pub async fn create_pr(
         pool: &SqlitePool,
         params: CreatePrParams<'_>,
     ) -> Result<String, TaskAttemptError> {
@@ -906,28 +1271,20 @@ impl TaskAttempt {
 
         // Ensure worktree exists (recreate if needed for cold task support)
         let worktree_path =
-            Self::ensure_worktree_exists(pool, params.attempt_id, params.project_id, "GitHub PR")
+            Self::ensure_worktree_exists(pool, params.attempt_id, params.project_id, "create PR")
                 .await?;
 
-        // Create GitHub service instance
-        let github_service = GitHubService::new(params.github_token)?;
-
-        // Use GitService to get the remote URL, then create GitHubRepoInfo
-        let git_service = GitService::new(&ctx.project.git_repo_path)?;
-        let (owner, repo_name) = git_service
-            .get_github_repo_info()
-            .map_err(|e| TaskAttemptError::ValidationError(e.to_string()))?;
-        let repo_info = GitHubRepoInfo { owner, repo_name };
-
-        // Push the branch to GitHub first
-        Self::push_branch_to_github(
-            &ctx.project.git_repo_path,
-            &worktree_path,
+        // Push the branch first
+        let HostedRepo { provider, remote } = params.repo;
+        let (username, token) = provider.push_credentials();
+        GitService::new(&ctx.project.git_repo_path)?.push_branch(
+            Path::new(&worktree_path),
             &ctx.task_attempt.branch,
-            params.github_token,
+            &provider.push_url(remote),
+            username,
+            token,
         )?;
 
-        // Create the PR using GitHub service
         let pr_request = CreatePrRequest {
             title: params.title.to_string(),
             body: params.body.map(|s| s.to_string()),
@@ -935,7 +1292,7 @@ impl TaskAttempt {
             base_branch: params.base_branch.unwrap_or("main").to_string(),
         };
 
-        let pr_info = github_service.create_pr(&repo_info, &pr_request).await?;
+        let pr_info = provider.create_pull_request(remote, &pr_request).await?;
 
         // Update the task attempt with PR information
         sqlx::query!(
@@ -951,20 +1308,6 @@ impl TaskAttempt {
         Ok(pr_info.url)
     }
 
-    /// Push the branch to GitHub remote
-    fn push_branch_to_github(
-        git_repo_path: &str,
-        worktree_path: &str,
-        branch_name: &str,
-        github_token: &str,
-    ) -> Result<(), TaskAttemptError> {
-        // Use GitService to push to GitHub
-        let git_service = GitService::new(git_repo_path)?;
-        git_service
-            .push_to_github(Path::new(worktree_path), branch_name, github_token)
-            .map_err(TaskAttemptError::from)
-    }
-
     /// Update PR status and merge commit
     pub async fn update_pr_status(
         pool: &SqlitePool,
@@ -1005,7 +1348,7 @@ impl TaskAttempt {
 
         // Get all execution processes for this attempt, ordered by created_at
         let processes =
-            crate::models::execution_process::ExecutionProcess::find_by_task_attempt_id(
+            crate::models::execution_process::ExecutionProcess::find_summaries_by_task_attempt_id(
                 pool, attempt_id,
             )
             .await?;
@@ -1028,12 +1371,19 @@ impl TaskAttempt {
         // Determine execution state based on processes
         let execution_state = if let Some(setup) = setup_process {
             match setup.status {
+                // Setup scripts aren't queued, but treat one as not yet started if it is
+                crate::models::execution_process::ExecutionProcessStatus::Queued => {
+                    ExecutionState::NotStarted
+                }
                 crate::models::execution_process::ExecutionProcessStatus::Running => {
                     ExecutionState::SetupRunning
                 }
                 crate::models::execution_process::ExecutionProcessStatus::Completed => {
                     if let Some(agent) = coding_agent_process {
                         match agent.status {
+                            crate::models::execution_process::ExecutionProcessStatus::Queued => {
+                                ExecutionState::CodingAgentQueued
+                            }
                             crate::models::execution_process::ExecutionProcessStatus::Running => {
                                 ExecutionState::CodingAgentRunning
                             }
@@ -1061,6 +1411,9 @@ impl TaskAttempt {
         } else if let Some(agent) = coding_agent_process {
             // No setup script, only coding agent
             match agent.status {
+                crate::models::execution_process::ExecutionProcessStatus::Queued => {
+                    ExecutionState::CodingAgentQueued
+                }
                 crate::models::execution_process::ExecutionProcessStatus::Running => {
                     ExecutionState::CodingAgentRunning
                 }
@@ -1229,3 +1582,124 @@ impl TaskAttempt {
         })
     }
 }
+
+/// Time the coding agents spent running, with a still running one counted up to `now`
+fn agent_duration_seconds(agents: &[ExecutionProcessSummary], now: DateTime<Utc>) -> Option<i64> {
+    if agents.is_empty() {
+        return None;
+    }
+    Some(
+        agents
+            .iter()
+            .map(|agent| (agent.completed_at.unwrap_or(now) - agent.started_at).num_seconds())
+            .sum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(
+        started_at: DateTime<Utc>,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> ExecutionProcessSummary {
+        ExecutionProcessSummary {
+            id: Uuid::new_v4(),
+            task_attempt_id: Uuid::new_v4(),
+            process_type: ExecutionProcessType::CodingAgent,
+            executor_type: Some("echo".to_string()),
+            status: if completed_at.is_some() {
+                ExecutionProcessStatus::Completed
+            } else {
+                ExecutionProcessStatus::Running
+            },
+            command: "echo".to_string(),
+            args: None,
+            working_directory: "/tmp".to_string(),
+            exit_code: None,
+            started_at,
+            completed_at,
+            last_output_at: None,
+            kill_reason: None,
+            auto_fix_of_process_id: None,
+            auto_fix_iteration: None,
+            port: None,
+            ready_at: None,
+            created_at: started_at,
+            updated_at: started_at,
+        }
+    }
+
+    #[test]
+    fn test_agent_duration_seconds() {
+        let now = Utc::now();
+        let minutes_ago = |m: i64| now - chrono::Duration::minutes(m);
+
+        assert_eq!(agent_duration_seconds(&[], now), None);
+        // A finished 5 minute run plus a follow-up that has been running for 2 minutes
+        let agents = vec![
+            agent(minutes_ago(30), Some(minutes_ago(25))),
+            agent(minutes_ago(2), None),
+        ];
+        assert_eq!(agent_duration_seconds(&agents, now), Some(7 * 60));
+    }
+
+    #[test]
+    fn test_diff_stats() {
+        let chunk = |chunk_type, content: &str| DiffChunk {
+            chunk_type,
+            content: content.to_string(),
+        };
+        let diff = WorktreeDiff {
+            files: vec![
+                FileDiff {
+                    path: "src/lib.rs".to_string(),
+                    chunks: vec![
+                        chunk(DiffChunkType::Equal, "fn main() {\n"),
+                        chunk(DiffChunkType::Delete, "    old();\n"),
+                        chunk(DiffChunkType::Insert, "    new();\n"),
+                        chunk(DiffChunkType::Insert, "    more();\n"),
+                    ],
+                },
+                FileDiff {
+                    path: "NOTES.md".to_string(),
+                    chunks: vec![chunk(DiffChunkType::Insert, "a\nb\nc")],
+                },
+            ],
+        };
+
+        assert_eq!(
+            diff.stats(),
+            DiffStats {
+                files_changed: 2,
+                lines_added: 5,
+                lines_removed: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_conflict_report_agent_prompt() {
+        let report = ConflictReport {
+            operation: ConflictOperation::Rebase,
+            base_branch: "main".to_string(),
+            files: ["src/lib.rs", "README.md"]
+                .iter()
+                .map(|path| ConflictFile {
+                    path: path.to_string(),
+                    ours: None,
+                    base: None,
+                    theirs: None,
+                    merged_content: None,
+                    hunks: Vec::new(),
+                })
+                .collect(),
+        };
+
+        let prompt = report.agent_prompt();
+        assert!(prompt.starts_with("Rebasing this branch onto the `main` branch"));
+        assert!(prompt.contains("- src/lib.rs\n- README.md\n"));
+        assert!(prompt.contains("Do not commit"));
+    }
+}
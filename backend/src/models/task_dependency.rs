@@ -0,0 +1,233 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskStatus};
+
+/// `task_id` can't start until `blocked_by_task_id` is merged or done
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TaskDependency {
+    pub task_id: Uuid,
+    pub blocked_by_task_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateTaskDependency {
+    pub blocked_by_task_id: Uuid,
+}
+
+/// Both directions of the dependency graph around one task
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct TaskDependencies {
+    pub blocked_by: Vec<Task>,
+    pub blocking: Vec<Task>,
+    pub is_blocked: bool, // Some blocker is neither merged nor done
+}
+
+impl TaskDependency {
+    /// All dependency edges between tasks of a project
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskDependency,
+            r#"SELECT td.task_id as "task_id!: Uuid", td.blocked_by_task_id as "blocked_by_task_id!: Uuid", td.created_at as "created_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.task_id
+               WHERE t.project_id = $1
+               ORDER BY td.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks that `task_id` waits on
+    pub async fn find_blockers(pool: &SqlitePool, task_id: Uuid) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.created_by as "created_by: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.blocked_by_task_id
+               WHERE td.task_id = $1
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks waiting on `task_id`
+    pub async fn find_dependents(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.created_by as "created_by: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.task_id
+               WHERE td.blocked_by_task_id = $1
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Whether any blocker of `task_id` is neither done nor merged
+    pub async fn is_blocked(pool: &SqlitePool, task_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM task_dependencies td
+               JOIN tasks b ON b.id = td.blocked_by_task_id
+               WHERE td.task_id = $1
+               AND b.status != 'done'
+               AND NOT EXISTS (
+                   SELECT 1 FROM task_attempts ta
+                   WHERE ta.task_id = b.id AND ta.merge_commit IS NOT NULL
+               )"#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.count > 0)
+    }
+
+    /// Dependents of `task_id` that are still in todo, have never been attempted and
+    /// whose blockers are now all merged or done
+    pub async fn find_unblocked_dependents(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.created_by as "created_by: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.task_id
+               WHERE td.blocked_by_task_id = $1
+               AND t.status = 'todo'
+               AND NOT EXISTS (SELECT 1 FROM task_attempts ta WHERE ta.task_id = t.id)
+               AND NOT EXISTS (
+                   SELECT 1 FROM task_dependencies other
+                   JOIN tasks b ON b.id = other.blocked_by_task_id
+                   WHERE other.task_id = t.id
+                   AND b.status != 'done'
+                   AND NOT EXISTS (
+                       SELECT 1 FROM task_attempts ba
+                       WHERE ba.task_id = b.id AND ba.merge_commit IS NOT NULL
+                   )
+               )
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        blocked_by_task_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskDependency,
+            r#"INSERT INTO task_dependencies (task_id, blocked_by_task_id)
+               VALUES ($1, $2)
+               RETURNING task_id as "task_id!: Uuid", blocked_by_task_id as "blocked_by_task_id!: Uuid", created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            blocked_by_task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        blocked_by_task_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_dependencies WHERE task_id = $1 AND blocked_by_task_id = $2",
+            task_id,
+            blocked_by_task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Whether adding "`task_id` is blocked by `blocked_by_task_id`" to `existing` would close a
+/// cycle, i.e. `task_id` is already reachable from `blocked_by_task_id` through blockers.
+pub fn would_create_cycle(
+    existing: &[TaskDependency],
+    task_id: Uuid,
+    blocked_by_task_id: Uuid,
+) -> bool {
+    let mut blockers: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for dependency in existing {
+        blockers
+            .entry(dependency.task_id)
+            .or_default()
+            .push(dependency.blocked_by_task_id);
+    }
+
+    let mut visited = HashSet::new();
+    let mut stack = vec![blocked_by_task_id];
+    while let Some(current) = stack.pop() {
+        if current == task_id {
+            return true;
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        if let Some(next) = blockers.get(&current) {
+            stack.extend(next.iter().copied());
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(task_id: Uuid, blocked_by_task_id: Uuid) -> TaskDependency {
+        TaskDependency {
+            task_id,
+            blocked_by_task_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_would_create_cycle() {
+        let (a, b, c, d) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        // c is blocked by b, b is blocked by a
+        let existing = vec![edge(c, b), edge(b, a)];
+
+        assert!(would_create_cycle(&existing, a, c));
+        assert!(would_create_cycle(&existing, a, b));
+        assert!(would_create_cycle(&existing, a, a));
+        assert!(!would_create_cycle(&existing, c, a));
+        assert!(!would_create_cycle(&existing, d, c));
+        assert!(!would_create_cycle(&[], a, b));
+    }
+}
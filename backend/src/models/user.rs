@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An account on a shared instance, see `AppState::multi_user`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct User {
+    pub id: Uuid,
+    pub username: String,
+    pub is_admin: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateUser {
+    pub username: String,
+    pub password: String,
+    #[serde(default)]
+    pub is_admin: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct ChangePassword {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Who is signed in, and whether the instance needs signing in at all
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct AuthSession {
+    pub multi_user: bool,
+    pub needs_setup: bool, // No account exists yet, the first one becomes an admin
+    pub user: Option<User>,
+    pub preview_port: Option<u16>, // Previews are served from this port rather than the app's
+}
+
+#[derive(Debug, FromRow)]
+struct StoredCredentials {
+    id: Uuid,
+    password_hash: String,
+}
+
+impl User {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", username, is_admin as "is_admin!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               ORDER BY username ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", username, is_admin as "is_admin!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM users"#)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Id and password hash of the user with the given name, ignoring case
+    pub async fn find_credentials(
+        pool: &SqlitePool,
+        username: &str,
+    ) -> Result<Option<(Uuid, String)>, sqlx::Error> {
+        let credentials = sqlx::query_as!(
+            StoredCredentials,
+            r#"SELECT id as "id!: Uuid", password_hash FROM users WHERE username = $1"#,
+            username
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(credentials.map(|c| (c.id, c.password_hash)))
+    }
+
+    pub async fn password_hash(pool: &SqlitePool, id: Uuid) -> Result<String, sqlx::Error> {
+        sqlx::query_scalar!("SELECT password_hash FROM users WHERE id = $1", id)
+            .fetch_one(pool)
+            .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        username: &str,
+        password_hash: &str,
+        is_admin: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (id, username, password_hash, is_admin)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", username, is_admin as "is_admin!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            username,
+            password_hash,
+            is_admin
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Create the first account as an admin, None if someone else got there first
+    pub async fn create_first_admin(
+        pool: &SqlitePool,
+        username: &str,
+        password_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (id, username, password_hash, is_admin)
+               SELECT $1, $2, $3, TRUE
+               WHERE NOT EXISTS (SELECT 1 FROM users)
+               RETURNING id as "id!: Uuid", username, is_admin as "is_admin!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            username,
+            password_hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn update_password(
+        pool: &SqlitePool,
+        id: Uuid,
+        password_hash: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE users SET password_hash = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            password_hash
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete a user, whose tasks and attempts stay but lose their attribution
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM users WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
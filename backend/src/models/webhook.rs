@@ -0,0 +1,209 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Lifecycle events a webhook can subscribe to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export)]
+pub enum WebhookEvent {
+    #[serde(rename = "task.created")]
+    TaskCreated,
+    #[serde(rename = "attempt.started")]
+    AttemptStarted,
+    #[serde(rename = "agent.finished")]
+    AgentFinished,
+    #[serde(rename = "agent.failed")]
+    AgentFailed,
+    #[serde(rename = "pr.opened")]
+    PrOpened,
+    #[serde(rename = "pr.merged")]
+    PrMerged,
+}
+
+impl WebhookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::TaskCreated => "task.created",
+            WebhookEvent::AttemptStarted => "attempt.started",
+            WebhookEvent::AgentFinished => "agent.finished",
+            WebhookEvent::AgentFailed => "agent.failed",
+            WebhookEvent::PrOpened => "pr.opened",
+            WebhookEvent::PrMerged => "pr.merged",
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub url: String,
+    pub secret: String,
+    #[ts(type = "Array<WebhookEvent>")]
+    pub events: Json<Vec<WebhookEvent>>, // Empty subscribes to every event
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateWebhook {
+    pub url: String,
+    pub secret: Option<String>, // Generated if not given
+    pub events: Option<Vec<WebhookEvent>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdateWebhook {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub events: Option<Vec<WebhookEvent>>,
+    pub enabled: Option<bool>,
+}
+
+impl Webhook {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", url, secret, events as "events!: Json<Vec<WebhookEvent>>", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id_and_project_id(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", url, secret, events as "events!: Json<Vec<WebhookEvent>>", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               WHERE id = $1 AND project_id = $2"#,
+            id,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enabled webhooks of a project subscribed to `event`
+    pub async fn find_subscribed(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        event: WebhookEvent,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let webhooks = Self::find_by_project_id(pool, project_id).await?;
+        Ok(webhooks
+            .into_iter()
+            .filter(|webhook| webhook.enabled && webhook.subscribes_to(event))
+            .collect())
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateWebhook,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let secret = data.secret.clone().unwrap_or_else(generate_secret);
+        let events = Json(data.events.clone().unwrap_or_default());
+        let enabled = data.enabled.unwrap_or(true);
+        sqlx::query_as!(
+            Webhook,
+            r#"INSERT INTO webhooks (id, project_id, url, secret, events, enabled)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", url, secret, events as "events!: Json<Vec<WebhookEvent>>", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.url,
+            secret,
+            events,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+        data: &UpdateWebhook,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id_and_project_id(pool, id, project_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let url = data.url.as_ref().unwrap_or(&existing.url);
+        let secret = data.secret.as_ref().unwrap_or(&existing.secret);
+        let events = Json(data.events.clone().unwrap_or(existing.events.0));
+        let enabled = data.enabled.unwrap_or(existing.enabled);
+
+        sqlx::query_as!(
+            Webhook,
+            r#"UPDATE webhooks
+               SET url = $3, secret = $4, events = $5, enabled = $6, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND project_id = $2
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", url, secret, events as "events!: Json<Vec<WebhookEvent>>", enabled as "enabled!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            url,
+            secret,
+            events,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM webhooks WHERE id = $1 AND project_id = $2",
+            id,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub fn subscribes_to(&self, event: WebhookEvent) -> bool {
+        self.events.0.is_empty() || self.events.0.contains(&event)
+    }
+
+    /// The webhook with its signing secret left out, for the audit log
+    pub fn without_secret(&self) -> Self {
+        Self {
+            secret: String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Check that `url` is an absolute http(s) URL
+    pub fn validate_url(url: &str) -> Result<(), String> {
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+            Ok(_) => Err("Webhook URL must use http or https".to_string()),
+            Err(e) => Err(format!("Invalid webhook URL: {}", e)),
+        }
+    }
+}
+
+fn generate_secret() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
@@ -0,0 +1,115 @@
+use std::convert::Infallible;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Query, State},
+    http::{request::Parts, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Extension, Json, Router,
+};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        audit_event::{AuditEvent, AuditEventQuery, AuditSource, NewAuditEvent},
+        project_member::ProjectRole,
+        user::User,
+        ApiResponse,
+    },
+    routes::auth::{authorize_project, CurrentUser},
+};
+
+/// Header the MCP task server marks its requests with, so their events are told apart
+pub const AUDIT_SOURCE_HEADER: &str = "x-vibe-kanban-source";
+
+/// A handler argument recording the changes a request makes in the audit log, attributed to
+/// the signed in user
+pub struct Auditor {
+    pool: SqlitePool,
+    actor: Option<User>,
+    source: AuditSource,
+}
+
+impl Auditor {
+    pub fn new(pool: SqlitePool, actor: Option<User>, source: AuditSource) -> Self {
+        Self {
+            pool,
+            actor,
+            source,
+        }
+    }
+
+    pub fn actor_id(&self) -> Option<Uuid> {
+        self.actor.as_ref().map(|user| user.id)
+    }
+
+    /// Record an event, logging rather than failing the request when that goes wrong as the
+    /// change itself has already been made
+    pub async fn record(&self, event: NewAuditEvent) {
+        let action = event.action.clone();
+        if let Err(e) =
+            AuditEvent::create(&self.pool, self.actor.as_ref(), self.source, event).await
+        {
+            tracing::error!("Failed to record audit event {}: {}", action, e);
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for Auditor {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let actor = parts
+            .extensions
+            .get::<CurrentUser>()
+            .map(|CurrentUser(user)| user.clone());
+        let source = match parts.headers.get(AUDIT_SOURCE_HEADER) {
+            Some(value) if value == "mcp" => AuditSource::Mcp,
+            _ => AuditSource::Api,
+        };
+        Ok(Self::new(state.db_pool.clone(), actor, source))
+    }
+}
+
+/// GET /audit-events - admins see every event, and maintainers those of their projects
+pub async fn list_audit_events(
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Query(query): Query<AuditEventQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    if let Some(Extension(CurrentUser(user))) = &user {
+        if !user.is_admin {
+            let project_id = query.project_id.ok_or_else(|| {
+                (
+                    StatusCode::FORBIDDEN,
+                    Json(ApiResponse::error(
+                        "Only admins can see events of every project, filter by a project",
+                    )),
+                )
+            })?;
+            authorize_project(&state, Some(user), project_id, ProjectRole::Maintainer).await?;
+        }
+    }
+
+    match AuditEvent::find(&state.db_pool, &query).await {
+        Ok(page) => Ok(Json(ApiResponse::success(page))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to fetch audit events: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub fn audit_router() -> Router<AppState> {
+    Router::new().route("/audit-events", get(list_audit_events))
+}
@@ -1,13 +1,34 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use async_trait::async_trait;
 use axum::{
-    extract::{Request, State},
+    extract::{FromRequestParts, Path, Request, State},
+    http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode},
     middleware::Next,
-    response::{Json as ResponseJson, Response},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, post},
     Json, Router,
 };
 use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        api_token::{ApiToken, TokenKind},
+        audit_event::{AuditSource, NewAuditEvent},
+        project_member::{ProjectMember, ProjectRole},
+        task_attempt::TaskAttempt,
+        user::{AuthSession, LoginRequest, User},
+        ApiResponse,
+    },
+    routes::audit::Auditor,
+    services::AuthService,
+};
 
-use crate::{app_state::AppState, models::ApiResponse};
+/// Cookie holding the secret of a browser session
+pub const SESSION_COOKIE: &str = "vk_session";
+const SESSION_DAYS: u32 = 30;
 
 pub fn auth_router() -> Router<AppState> {
     Router::new()
@@ -16,6 +37,15 @@ pub fn auth_router() -> Router<AppState> {
         .route("/auth/github/check", get(github_check_token))
 }
 
+/// Signing in and out, reachable without being signed in
+pub fn session_router() -> Router<AppState> {
+    Router::new()
+        .route("/auth/session", get(get_session))
+        .route("/auth/login", post(login))
+        .route("/auth/logout", post(logout))
+        .route("/auth/setup", post(setup))
+}
+
 #[derive(serde::Deserialize)]
 struct DeviceStartRequest {}
 
@@ -35,7 +65,18 @@ struct DevicePollRequest {
 }
 
 /// POST /auth/github/device/start
-async fn device_start() -> ResponseJson<ApiResponse<DeviceStartResponse>> {
+async fn device_start(
+    user: Option<axum::Extension<CurrentUser>>,
+) -> ResponseJson<ApiResponse<DeviceStartResponse>> {
+    // The token is saved in the config the whole server shares
+    if let Err((_, ResponseJson(response))) = require_admin(&user, "sign in to GitHub") {
+        return ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: response.message,
+        });
+    }
+
     let client_id = option_env!("GITHUB_CLIENT_ID").unwrap_or("Ov23li9bxz3kKfPOIsGm");
 
     let params = [("client_id", client_id), ("scope", "user:email,repo")];
@@ -101,9 +142,19 @@ async fn device_start() -> ResponseJson<ApiResponse<DeviceStartResponse>> {
 
 /// POST /auth/github/device/poll
 async fn device_poll(
+    audit: Auditor,
     State(app_state): State<AppState>,
+    user: Option<axum::Extension<CurrentUser>>,
     Json(payload): Json<DevicePollRequest>,
 ) -> ResponseJson<ApiResponse<String>> {
+    if let Err((_, ResponseJson(response))) = require_admin(&user, "sign in to GitHub") {
+        return ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: response.message,
+        });
+    }
+
     let client_id = option_env!("GITHUB_CLIENT_ID").unwrap_or("Ov23li9bxz3kKfPOIsGm");
 
     let params = [
@@ -234,6 +285,12 @@ async fn device_poll(
             }
         }
         app_state.update_sentry_scope().await;
+        audit
+            .record(
+                NewAuditEvent::new("github.login")
+                    .after(&serde_json::json!({ "username": username })),
+            )
+            .await;
         // Identify user in PostHog
         let mut props = serde_json::Map::new();
         if let Some(ref username) = username {
@@ -312,3 +369,442 @@ pub async fn sentry_user_context_middleware(
     app_state.update_sentry_scope().await;
     next.run(req).await
 }
+
+/// The signed in user a request comes from, set by `require_user_middleware` in multi-user
+/// mode. Handlers take an `Option<Extension<CurrentUser>>`, which is None in single-user mode.
+#[derive(Debug, Clone)]
+pub struct CurrentUser(pub User);
+
+/// Id of the user behind a request, to attribute what it creates
+pub fn current_user_id(user: &Option<axum::Extension<CurrentUser>>) -> Option<Uuid> {
+    user.as_ref()
+        .map(|axum::Extension(CurrentUser(user))| user.id)
+}
+
+/// Check that the user behind a request is an admin, as everyone is in single-user mode.
+/// `action` completes "Only admins can ...".
+pub fn require_admin(
+    user: &Option<axum::Extension<CurrentUser>>,
+    action: &str,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    match user {
+        Some(axum::Extension(CurrentUser(user))) if !user.is_admin => Err(auth_error(
+            StatusCode::FORBIDDEN,
+            &format!("Only admins can {}", action),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Role `user` has on a project, None when they are not a member. Admins, and everyone in
+/// single-user mode where there is no user, maintain every project.
+pub async fn project_role(
+    state: &AppState,
+    user: Option<&User>,
+    project_id: Uuid,
+) -> Result<Option<ProjectRole>, (StatusCode, Json<ApiResponse<()>>)> {
+    match user {
+        None => Ok(Some(ProjectRole::Maintainer)),
+        Some(user) if user.is_admin => Ok(Some(ProjectRole::Maintainer)),
+        Some(user) => ProjectMember::find_role(&state.db_pool, project_id, user.id)
+            .await
+            .map_err(|e| {
+                auth_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    &format!("Failed to check project permissions: {}", e),
+                )
+            }),
+    }
+}
+
+/// Check that `user` has at least the `required` role on a project. Projects a user is not a
+/// member of are reported as not found, so their existence doesn't leak.
+pub async fn authorize_project(
+    state: &AppState,
+    user: Option<&User>,
+    project_id: Uuid,
+    required: ProjectRole,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    match project_role(state, user, project_id).await? {
+        Some(role) if role >= required => Ok(()),
+        Some(_) => Err(auth_error(
+            StatusCode::FORBIDDEN,
+            &format!("This needs the {} role on the project", required.as_str()),
+        )),
+        None => Err(auth_error(StatusCode::NOT_FOUND, "Project not found")),
+    }
+}
+
+/// Roles to parameterize `ProjectAccess` with
+pub mod role {
+    use crate::models::project_member::ProjectRole;
+
+    pub trait RequiredRole: Send + Sync {
+        const ROLE: ProjectRole;
+    }
+
+    pub struct Viewer;
+    pub struct Contributor;
+    pub struct Maintainer;
+
+    impl RequiredRole for Viewer {
+        const ROLE: ProjectRole = ProjectRole::Viewer;
+    }
+
+    impl RequiredRole for Contributor {
+        const ROLE: ProjectRole = ProjectRole::Contributor;
+    }
+
+    impl RequiredRole for Maintainer {
+        const ROLE: ProjectRole = ProjectRole::Maintainer;
+    }
+}
+
+/// A handler argument rejecting requests from users without at least the role `R` on the
+/// project in the path, e.g. `_: ProjectAccess<role::Maintainer>`
+pub struct ProjectAccess<R>(PhantomData<R>);
+
+fn uuid_param(params: &HashMap<String, String>, name: &str) -> Option<Uuid> {
+    params.get(name).and_then(|id| Uuid::parse_str(id).ok())
+}
+
+/// The project a path is about: its `:project_id`, `:id` under /projects, or else the project
+/// of its `:attempt_id`
+async fn path_project_id(
+    state: &AppState,
+    params: &HashMap<String, String>,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    if let Some(project_id) = uuid_param(params, "project_id").or(uuid_param(params, "id")) {
+        return Ok(Some(project_id));
+    }
+    match uuid_param(params, "attempt_id") {
+        Some(attempt_id) => TaskAttempt::find_project_id(&state.db_pool, attempt_id).await,
+        None => Ok(None),
+    }
+}
+
+#[async_trait]
+impl<R: role::RequiredRole> FromRequestParts<AppState> for ProjectAccess<R> {
+    type Rejection = (StatusCode, Json<ApiResponse<()>>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(CurrentUser(user)) = parts.extensions.get::<CurrentUser>().cloned() else {
+            return Ok(Self(PhantomData));
+        };
+        if user.is_admin {
+            return Ok(Self(PhantomData));
+        }
+
+        let not_found = || auth_error(StatusCode::NOT_FOUND, "Project not found");
+        let Path(params) = Path::<HashMap<String, String>>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| not_found())?;
+        let project_id = path_project_id(state, &params)
+            .await
+            .map_err(|e| {
+                auth_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    &format!("Failed to check project permissions: {}", e),
+                )
+            })?
+            .ok_or_else(not_found)?;
+        authorize_project(state, Some(&user), project_id, R::ROLE).await?;
+        Ok(Self(PhantomData))
+    }
+}
+
+/// Secret a request authenticates with: a bearer token, or else the session cookie
+pub fn request_token(headers: &HeaderMap) -> Option<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if let Some(token) = bearer {
+        return Some(token.trim().to_string());
+    }
+
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value.to_string())
+}
+
+/// Middleware rejecting requests without a valid token in multi-user mode, and otherwise
+/// recording the user they come from as a `CurrentUser` extension
+pub async fn require_user_middleware(
+    State(app_state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    if !app_state.multi_user() {
+        return next.run(req).await;
+    }
+
+    let user = match request_token(req.headers()) {
+        Some(secret) => ApiToken::find_user_by_secret(&app_state.db_pool, &secret).await,
+        None => Ok(None),
+    };
+    match user {
+        Ok(Some(user)) => {
+            req.extensions_mut().insert(CurrentUser(user));
+            next.run(req).await
+        }
+        Ok(None) => (
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::<()>::error(
+                "Sign in, or pass an API token as a bearer token",
+            )),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to check the token of a request: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error("Failed to check credentials")),
+            )
+                .into_response()
+        }
+    }
+}
+
+fn session_cookie(secret: &str, max_age_secs: u32) -> HeaderValue {
+    HeaderValue::from_str(&format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}",
+        SESSION_COOKIE, secret, max_age_secs
+    ))
+    .expect("token secrets are hex")
+}
+
+fn auth_error(status: StatusCode, message: &str) -> (StatusCode, Json<ApiResponse<()>>) {
+    (status, Json(ApiResponse::error(message)))
+}
+
+fn require_multi_user(state: &AppState) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    if state.multi_user() {
+        Ok(())
+    } else {
+        Err(auth_error(
+            StatusCode::BAD_REQUEST,
+            "Accounts are only used in multi-user mode",
+        ))
+    }
+}
+
+/// Hash a password off the async runtime, PBKDF2 being slow on purpose
+pub async fn hash_password(
+    password: String,
+) -> Result<String, (StatusCode, Json<ApiResponse<()>>)> {
+    tokio::task::spawn_blocking(move || AuthService::hash_password(&password))
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .ok_or_else(|| {
+            auth_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to hash the password",
+            )
+        })
+}
+
+pub async fn verify_password(password: String, stored: String) -> bool {
+    tokio::task::spawn_blocking(move || AuthService::verify_password(&password, &stored))
+        .await
+        .unwrap_or(false)
+}
+
+/// Create a session for `user`, answering with the user and the session cookie
+async fn start_session(
+    state: &AppState,
+    user: User,
+) -> Result<Response, (StatusCode, Json<ApiResponse<()>>)> {
+    let secret = AuthService::generate_token().map_err(|_| {
+        auth_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to generate a session",
+        )
+    })?;
+    ApiToken::create(
+        &state.db_pool,
+        user.id,
+        TokenKind::Session,
+        "Browser session",
+        &secret,
+        Some(SESSION_DAYS),
+    )
+    .await
+    .map_err(|e| {
+        auth_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to create a session: {}", e),
+        )
+    })?;
+
+    Ok((
+        [(
+            header::SET_COOKIE,
+            session_cookie(&secret, SESSION_DAYS * 24 * 60 * 60),
+        )],
+        Json(ApiResponse::success(user)),
+    )
+        .into_response())
+}
+
+/// GET /auth/session
+async fn get_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<AuthSession>>, (StatusCode, Json<ApiResponse<()>>)> {
+    if !state.multi_user() {
+        return Ok(Json(ApiResponse::success(AuthSession {
+            multi_user: false,
+            needs_setup: false,
+            user: None,
+            preview_port: None,
+        })));
+    }
+
+    let db_error = |e: sqlx::Error| {
+        auth_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to load the session: {}", e),
+        )
+    };
+    let user = match request_token(&headers) {
+        Some(secret) => ApiToken::find_user_by_secret(&state.db_pool, &secret)
+            .await
+            .map_err(db_error)?,
+        None => None,
+    };
+    let needs_setup = user.is_none() && User::count(&state.db_pool).await.map_err(db_error)? == 0;
+    Ok(Json(ApiResponse::success(AuthSession {
+        multi_user: true,
+        needs_setup,
+        user,
+        preview_port: state.preview_port(),
+    })))
+}
+
+/// POST /auth/login
+async fn login(
+    State(state): State<AppState>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Response, (StatusCode, Json<ApiResponse<()>>)> {
+    require_multi_user(&state)?;
+
+    let invalid = || auth_error(StatusCode::UNAUTHORIZED, "Invalid user name or password");
+    let (user_id, password_hash) = User::find_credentials(&state.db_pool, payload.username.trim())
+        .await
+        .map_err(|e| {
+            auth_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("Failed to sign in: {}", e),
+            )
+        })?
+        .ok_or_else(invalid)?;
+    if !verify_password(payload.password, password_hash).await {
+        return Err(invalid());
+    }
+
+    let user = User::find_by_id(&state.db_pool, user_id)
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(invalid)?;
+    start_session(&state, user).await
+}
+
+/// POST /auth/logout
+async fn logout(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Some(secret) = request_token(&headers) {
+        if let Err(e) = ApiToken::delete_by_secret(&state.db_pool, &secret).await {
+            tracing::error!("Failed to delete a session: {}", e);
+        }
+    }
+    (
+        [(header::SET_COOKIE, session_cookie("", 0))],
+        Json(ApiResponse::success(())),
+    )
+        .into_response()
+}
+
+/// POST /auth/setup - create the first account, as an admin
+async fn setup(
+    State(state): State<AppState>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Response, (StatusCode, Json<ApiResponse<()>>)> {
+    require_multi_user(&state)?;
+    let username = payload.username.trim();
+    AuthService::validate_credentials(username, &payload.password)
+        .map_err(|e| auth_error(StatusCode::BAD_REQUEST, &e))?;
+
+    let password_hash = hash_password(payload.password).await?;
+    match User::create_first_admin(&state.db_pool, username, &password_hash).await {
+        Ok(Some(user)) => {
+            tracing::info!("Created the first user {}, as an admin", user.username);
+            Auditor::new(state.db_pool.clone(), Some(user.clone()), AuditSource::Api)
+                .record(
+                    NewAuditEvent::new("user.setup")
+                        .target(user.id)
+                        .after(&user),
+                )
+                .await;
+            start_session(&state, user).await
+        }
+        Ok(None) => Err(auth_error(
+            StatusCode::CONFLICT,
+            "The server is already set up, sign in instead",
+        )),
+        Err(e) => Err(auth_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to create the user: {}", e),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_token() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(request_token(&headers), None);
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; vk_session=vk_abc; other=1"),
+        );
+        assert_eq!(request_token(&headers).as_deref(), Some("vk_abc"));
+
+        // A bearer token wins over the cookie
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer vk_def"),
+        );
+        assert_eq!(request_token(&headers).as_deref(), Some("vk_def"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, HeaderValue::from_static("vk_sessions=nope"));
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Basic abc"));
+        assert_eq!(request_token(&headers), None);
+    }
+
+    #[test]
+    fn test_uuid_param() {
+        let id = Uuid::new_v4();
+        let params = HashMap::from([
+            ("project_id".to_string(), id.to_string()),
+            ("task_id".to_string(), "not-a-uuid".to_string()),
+        ]);
+        assert_eq!(uuid_param(&params, "project_id"), Some(id));
+        assert_eq!(uuid_param(&params, "task_id"), None);
+        assert_eq!(uuid_param(&params, "id"), None);
+    }
+}
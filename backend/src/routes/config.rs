@@ -2,9 +2,10 @@ use std::collections::HashMap;
 
 use axum::{
     extract::{Query, State},
+    http::StatusCode,
     response::Json as ResponseJson,
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -13,11 +14,17 @@ use ts_rs::TS;
 
 use crate::{
     app_state::AppState,
-    executor::ExecutorConfig,
+    executor::{ExecutorConfig, ExecutorOption},
     models::{
+        audit_event::NewAuditEvent,
         config::{Config, EditorConstants, SoundConstants},
+        project::ContainerRuntime,
         ApiResponse,
     },
+    routes::{
+        audit::Auditor,
+        auth::{require_admin, CurrentUser},
+    },
     utils,
 };
 
@@ -30,27 +37,47 @@ pub fn config_router() -> Router<AppState> {
         .route("/mcp-servers", post(update_mcp_servers))
 }
 
-async fn get_config(State(app_state): State<AppState>) -> ResponseJson<ApiResponse<Config>> {
+/// GET /config - without access tokens for users other than admins, in multi-user mode
+async fn get_config(
+    State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+) -> ResponseJson<ApiResponse<Config>> {
     let config = app_state.get_config().read().await;
+    let config = match require_admin(&user, "see access tokens") {
+        Ok(()) => config.clone(),
+        Err(_) => config.redacted(),
+    };
     ResponseJson(ApiResponse {
         success: true,
-        data: Some(config.clone()),
+        data: Some(config),
         message: Some("Config retrieved successfully".to_string()),
     })
 }
 
 async fn update_config(
+    audit: Auditor,
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Json(new_config): Json<Config>,
-) -> ResponseJson<ApiResponse<Config>> {
+) -> Result<ResponseJson<ApiResponse<Config>>, (StatusCode, Json<ApiResponse<()>>)> {
+    require_admin(&user, "change the server settings")?;
     let config_path = utils::config_path();
 
-    match new_config.save(&config_path) {
+    Ok(match new_config.save(&config_path) {
         Ok(_) => {
             let mut config = app_state.get_config().write().await;
-            *config = new_config.clone();
+            let before = std::mem::replace(&mut *config, new_config.clone());
             drop(config);
 
+            // Redacted, as the audit log is shown to project maintainers
+            audit
+                .record(
+                    NewAuditEvent::new("config.update")
+                        .before(&before.redacted())
+                        .after(&new_config.redacted()),
+                )
+                .await;
+
             app_state
                 .update_analytics_config(new_config.analytics_enabled.unwrap_or(true))
                 .await;
@@ -66,7 +93,7 @@ async fn update_config(
             data: None,
             message: Some(format!("Failed to save config: {}", e)),
         }),
-    }
+    })
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -74,12 +101,16 @@ async fn update_config(
 pub struct ConfigConstants {
     pub editor: EditorConstants,
     pub sound: SoundConstants,
+    pub container_runtimes: Vec<ContainerRuntime>, // Installed runtimes projects can sandbox with
+    pub executors: Vec<ExecutorOption>, // Built-in coding agents and those in executors.json
 }
 
 async fn get_config_constants() -> ResponseJson<ApiResponse<ConfigConstants>> {
     let constants = ConfigConstants {
         editor: EditorConstants::new(),
         sound: SoundConstants::new(),
+        container_runtimes: ContainerRuntime::installed(),
+        executors: ExecutorConfig::options(),
     };
 
     ResponseJson(ApiResponse {
@@ -118,8 +149,12 @@ fn resolve_executor_config(
 
 async fn get_mcp_servers(
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Query(query): Query<McpServerQuery>,
-) -> ResponseJson<ApiResponse<Value>> {
+) -> Result<ResponseJson<ApiResponse<Value>>, (StatusCode, Json<ApiResponse<()>>)> {
+    // The servers' env blocks usually hold tokens, and the config files live on the host
+    require_admin(&user, "view MCP servers")?;
+
     let saved_config = {
         let config = app_state.get_config().read().await;
         config.executor.clone()
@@ -128,11 +163,11 @@ async fn get_mcp_servers(
     let executor_config = match resolve_executor_config(query.executor, &saved_config) {
         Ok(config) => config,
         Err(message) => {
-            return ResponseJson(ApiResponse {
+            return Ok(ResponseJson(ApiResponse {
                 success: false,
                 data: None,
                 message: Some(message),
-            });
+            }));
         }
     };
 
@@ -140,39 +175,46 @@ async fn get_mcp_servers(
     let config_path = match executor_config.config_path() {
         Some(path) => path,
         None => {
-            return ResponseJson(ApiResponse {
+            return Ok(ResponseJson(ApiResponse {
                 success: false,
                 data: None,
                 message: Some("Could not determine config file path".to_string()),
-            });
+            }));
         }
     };
 
-    match read_mcp_servers_from_config(&config_path, &executor_config).await {
-        Ok(servers) => {
-            let response_data = serde_json::json!({
-                "servers": servers,
-                "config_path": config_path.to_string_lossy().to_string()
-            });
-            ResponseJson(ApiResponse {
-                success: true,
-                data: Some(response_data),
-                message: Some("MCP servers retrieved successfully".to_string()),
-            })
-        }
-        Err(e) => ResponseJson(ApiResponse {
-            success: false,
-            data: None,
-            message: Some(format!("Failed to read MCP servers: {}", e)),
-        }),
-    }
+    Ok(
+        match read_mcp_servers_from_config(&config_path, &executor_config).await {
+            Ok(servers) => {
+                let response_data = serde_json::json!({
+                    "servers": servers,
+                    "config_path": config_path.to_string_lossy().to_string()
+                });
+                ResponseJson(ApiResponse {
+                    success: true,
+                    data: Some(response_data),
+                    message: Some("MCP servers retrieved successfully".to_string()),
+                })
+            }
+            Err(e) => ResponseJson(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(format!("Failed to read MCP servers: {}", e)),
+            }),
+        },
+    )
 }
 
 async fn update_mcp_servers(
+    audit: Auditor,
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Query(query): Query<McpServerQuery>,
     Json(new_servers): Json<HashMap<String, Value>>,
-) -> ResponseJson<ApiResponse<String>> {
+) -> Result<ResponseJson<ApiResponse<String>>, (StatusCode, Json<ApiResponse<()>>)> {
+    // The executors' config files are shared by everyone using the server
+    require_admin(&user, "change MCP servers")?;
+
     let saved_config = {
         let config = app_state.get_config().read().await;
         config.executor.clone()
@@ -181,11 +223,11 @@ async fn update_mcp_servers(
     let executor_config = match resolve_executor_config(query.executor, &saved_config) {
         Ok(config) => config,
         Err(message) => {
-            return ResponseJson(ApiResponse {
+            return Ok(ResponseJson(ApiResponse {
                 success: false,
                 data: None,
                 message: Some(message),
-            });
+            }));
         }
     };
 
@@ -193,26 +235,44 @@ async fn update_mcp_servers(
     let config_path = match executor_config.config_path() {
         Some(path) => path,
         None => {
-            return ResponseJson(ApiResponse {
+            return Ok(ResponseJson(ApiResponse {
                 success: false,
                 data: None,
                 message: Some("Could not determine config file path".to_string()),
-            });
+            }));
         }
     };
 
-    match update_mcp_servers_in_config(&config_path, &executor_config, new_servers).await {
-        Ok(message) => ResponseJson(ApiResponse {
-            success: true,
-            data: Some(message.clone()),
-            message: Some(message),
-        }),
-        Err(e) => ResponseJson(ApiResponse {
-            success: false,
-            data: None,
-            message: Some(format!("Failed to update MCP servers: {}", e)),
-        }),
-    }
+    // Only the names, as servers are often configured with API keys
+    let mut server_names: Vec<String> = new_servers.keys().cloned().collect();
+    server_names.sort();
+
+    Ok(
+        match update_mcp_servers_in_config(&config_path, &executor_config, new_servers).await {
+            Ok(message) => {
+                audit
+                    .record(
+                        NewAuditEvent::new("mcp_servers.update")
+                            .target(executor_config.to_string())
+                            .after(&serde_json::json!({
+                                "config_path": config_path,
+                                "servers": server_names,
+                            })),
+                    )
+                    .await;
+                ResponseJson(ApiResponse {
+                    success: true,
+                    data: Some(message.clone()),
+                    message: Some(message),
+                })
+            }
+            Err(e) => ResponseJson(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(format!("Failed to update MCP servers: {}", e)),
+            }),
+        },
+    )
 }
 
 async fn update_mcp_servers_in_config(
@@ -225,23 +285,33 @@ async fn update_mcp_servers_in_config(
         fs::create_dir_all(parent).await?;
     }
 
-    // Read existing config file or create empty object if it doesn't exist
-    let file_content = fs::read_to_string(file_path)
-        .await
-        .unwrap_or_else(|_| "{}".to_string());
-    let mut config: Value = serde_json::from_str(&file_content)?;
-
     // Get the attribute path for MCP servers
     let mcp_path = executor_config.mcp_attribute_path().unwrap();
 
-    // Get the current server count for comparison
-    let old_servers = get_mcp_servers_from_config_path(&config, &mcp_path).len();
+    // Read existing config file or create an empty one if it doesn't exist
+    let file_content = fs::read_to_string(file_path).await.ok();
+
+    let (old_servers, updated_content) = if is_toml_config(file_path) {
+        // Edit the TOML document in place to keep its comments and formatting
+        let mut document: toml_edit::DocumentMut =
+            file_content.as_deref().unwrap_or_default().parse()?;
+        let old_servers =
+            get_mcp_servers_from_config_path(&utils::toml::document_to_json(&document), &mcp_path)
+                .len();
+        utils::toml::set_tables(&mut document, &mcp_path, &new_servers)?;
+        (old_servers, document.to_string())
+    } else {
+        let mut config: Value = serde_json::from_str(file_content.as_deref().unwrap_or("{}"))?;
 
-    // Set the MCP servers using the correct attribute path
-    set_mcp_servers_in_config_path(&mut config, &mcp_path, &new_servers)?;
+        // Get the current server count for comparison
+        let old_servers = get_mcp_servers_from_config_path(&config, &mcp_path).len();
+
+        // Set the MCP servers using the correct attribute path
+        set_mcp_servers_in_config_path(&mut config, &mcp_path, &new_servers)?;
+        (old_servers, serde_json::to_string_pretty(&config)?)
+    };
 
     // Write the updated config back to file
-    let updated_content = serde_json::to_string_pretty(&config)?;
     fs::write(file_path, updated_content).await?;
 
     let new_count = new_servers.len();
@@ -263,10 +333,14 @@ async fn read_mcp_servers_from_config(
     executor_config: &ExecutorConfig,
 ) -> Result<HashMap<String, Value>, Box<dyn std::error::Error + Send + Sync>> {
     // Read the config file, return empty if it doesn't exist
-    let file_content = fs::read_to_string(file_path)
-        .await
-        .unwrap_or_else(|_| "{}".to_string());
-    let config: Value = serde_json::from_str(&file_content)?;
+    let file_content = fs::read_to_string(file_path).await.ok();
+    let config: Value = if is_toml_config(file_path) {
+        let document: toml_edit::DocumentMut =
+            file_content.as_deref().unwrap_or_default().parse()?;
+        utils::toml::document_to_json(&document)
+    } else {
+        serde_json::from_str(file_content.as_deref().unwrap_or("{}"))?
+    };
 
     // Get the attribute path for MCP servers
     let mcp_path = executor_config.mcp_attribute_path().unwrap();
@@ -277,6 +351,13 @@ async fn read_mcp_servers_from_config(
     Ok(servers)
 }
 
+/// Whether an executor's config file is TOML rather than JSON, as Codex's is
+fn is_toml_config(file_path: &std::path::Path) -> bool {
+    file_path
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+}
+
 /// Helper function to get MCP servers from config using a path
 fn get_mcp_servers_from_config_path(config: &Value, path: &[&str]) -> HashMap<String, Value> {
     // Special handling for AMP - use flat key structure
@@ -0,0 +1,224 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, put},
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        api_response::ApiResponse,
+        audit_event::NewAuditEvent,
+        executor_profile::{
+            CreateExecutorProfile, ExecutorProfile, ExecutorProfileError, UpdateExecutorProfile,
+        },
+        project::Project,
+    },
+    routes::{
+        audit::Auditor,
+        auth::{role, ProjectAccess},
+    },
+};
+
+async fn ensure_project_exists(
+    state: &AppState,
+    project_id: Uuid,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    match Project::exists(&state.db_pool, project_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Project not found")),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to check project existence: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+fn validate_executor(executor: &str) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    ExecutorProfile::validate_executor(executor)
+        .map(|_| ())
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&e))))
+}
+
+pub async fn list_executor_profiles(
+    _: ProjectAccess<role::Viewer>,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_project_exists(&state, project_id).await?;
+
+    match ExecutorProfile::find_by_project_id(&state.db_pool, project_id).await {
+        Ok(profiles) => Ok(Json(ApiResponse::success(
+            profiles
+                .into_iter()
+                .map(ExecutorProfile::masked)
+                .collect::<Vec<_>>(),
+        ))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to fetch executor profiles: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub async fn create_executor_profile(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateExecutorProfile>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_project_exists(&state, project_id).await?;
+    validate_executor(&payload.executor)?;
+
+    match ExecutorProfile::create(&state.db_pool, project_id, &payload).await {
+        Ok(profile) => {
+            // The audit log is shown to project maintainers, so it never gets the variables
+            let profile = profile.masked();
+            audit
+                .record(
+                    NewAuditEvent::new("executor_profile.create")
+                        .project(project_id)
+                        .target(profile.id)
+                        .after(&profile),
+                )
+                .await;
+            Ok((StatusCode::CREATED, Json(ApiResponse::success(profile))))
+        }
+        Err(e) => {
+            if e.to_string().contains("UNIQUE constraint failed") {
+                Err((
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::error(
+                        "An executor profile with this name already exists in this project",
+                    )),
+                ))
+            } else {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(&format!(
+                        "Failed to create executor profile: {}",
+                        e
+                    ))),
+                ))
+            }
+        }
+    }
+}
+
+pub async fn update_executor_profile(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, profile_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateExecutorProfile>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    if let Some(executor) = &payload.executor {
+        validate_executor(executor)?;
+    }
+
+    let before = ExecutorProfile::find_by_id_and_project_id(&state.db_pool, profile_id, project_id)
+        .await
+        .ok()
+        .flatten()
+        .map(ExecutorProfile::masked);
+    match ExecutorProfile::update(&state.db_pool, profile_id, project_id, &payload).await {
+        Ok(profile) => {
+            let profile = profile.masked();
+            audit
+                .record(
+                    NewAuditEvent::new("executor_profile.update")
+                        .project(project_id)
+                        .target(profile_id)
+                        .before(&before)
+                        .after(&profile),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(profile)))
+        }
+        Err(e) => {
+            if matches!(e, ExecutorProfileError::Database(sqlx::Error::RowNotFound)) {
+                Err((
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error("Executor profile not found")),
+                ))
+            } else if e.to_string().contains("UNIQUE constraint failed") {
+                Err((
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::error(
+                        "An executor profile with this name already exists in this project",
+                    )),
+                ))
+            } else {
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(&format!(
+                        "Failed to update executor profile: {}",
+                        e
+                    ))),
+                ))
+            }
+        }
+    }
+}
+
+pub async fn delete_executor_profile(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, profile_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let before = ExecutorProfile::find_by_id_and_project_id(&state.db_pool, profile_id, project_id)
+        .await
+        .ok()
+        .flatten()
+        .map(ExecutorProfile::masked);
+    match ExecutorProfile::delete(&state.db_pool, profile_id, project_id).await {
+        Ok(0) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Executor profile not found")),
+        )),
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("executor_profile.delete")
+                        .project(project_id)
+                        .target(profile_id)
+                        .before(&before),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to delete executor profile: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub fn executor_profiles_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/:project_id/executor-profiles",
+            get(list_executor_profiles).post(create_executor_profile),
+        )
+        .route(
+            "/projects/:project_id/executor-profiles/:profile_id",
+            put(update_executor_profile).delete(delete_executor_profile),
+        )
+}
@@ -4,12 +4,20 @@ use std::{
 };
 
 use axum::{
-    extract::Query, http::StatusCode, response::Json as ResponseJson, routing::get, Router,
+    extract::Query, http::StatusCode, response::Json as ResponseJson, routing::get, Extension,
+    Router,
 };
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use crate::{app_state::AppState, models::ApiResponse};
+use crate::{
+    app_state::AppState,
+    models::{audit_event::NewAuditEvent, ApiResponse},
+    routes::{
+        audit::Auditor,
+        auth::{require_admin, CurrentUser},
+    },
+};
 
 #[derive(Debug, Serialize, TS)]
 #[ts(export)]
@@ -33,8 +41,17 @@ pub struct ListDirectoryQuery {
 }
 
 pub async fn list_directory(
+    user: Option<Extension<CurrentUser>>,
     Query(query): Query<ListDirectoryQuery>,
 ) -> Result<ResponseJson<ApiResponse<DirectoryListResponse>>, StatusCode> {
+    // The host's filesystem isn't for every user of a shared instance to browse
+    if let Err((_, ResponseJson(response))) = require_admin(&user, "browse the server's files") {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: response.message,
+        }));
+    }
     let path_str = query.path.unwrap_or_else(|| {
         // Default to user's home directory
         dirs::home_dir()
@@ -129,8 +146,17 @@ pub async fn list_directory(
 }
 
 pub async fn validate_git_path(
+    user: Option<Extension<CurrentUser>>,
     Query(query): Query<ListDirectoryQuery>,
 ) -> Result<ResponseJson<ApiResponse<bool>>, StatusCode> {
+    // The host's filesystem isn't for every user of a shared instance to browse
+    if let Err((_, ResponseJson(response))) = require_admin(&user, "browse the server's files") {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: response.message,
+        }));
+    }
     let path_str = query.path.ok_or(StatusCode::BAD_REQUEST)?;
     let path = Path::new(&path_str);
 
@@ -149,8 +175,20 @@ pub async fn validate_git_path(
 }
 
 pub async fn create_git_repo(
+    audit: Auditor,
+    user: Option<Extension<CurrentUser>>,
     Query(query): Query<ListDirectoryQuery>,
 ) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+    // The host's filesystem isn't for every user of a shared instance to browse
+    if let Err((_, ResponseJson(response))) =
+        require_admin(&user, "create repositories on the server")
+    {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: response.message,
+        }));
+    }
     let path_str = query.path.ok_or(StatusCode::BAD_REQUEST)?;
     let path = Path::new(&path_str);
 
@@ -183,6 +221,9 @@ pub async fn create_git_repo(
     {
         Ok(output) => {
             if output.status.success() {
+                audit
+                    .record(NewAuditEvent::new("git_repo.create").target(&path_str))
+                    .await;
                 Ok(ResponseJson(ApiResponse {
                     success: true,
                     data: Some(()),
@@ -1,9 +1,18 @@
+pub mod audit;
 pub mod auth;
 pub mod config;
+pub mod executor_profiles;
 pub mod filesystem;
 pub mod health;
+pub mod preview;
+pub mod project_checks;
+pub mod project_env_vars;
+pub mod project_members;
 pub mod projects;
 pub mod stream;
 pub mod task_attempts;
 pub mod task_templates;
 pub mod tasks;
+pub mod usage;
+pub mod users;
+pub mod webhooks;
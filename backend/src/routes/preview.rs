@@ -0,0 +1,533 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use axum::{
+    body::Body,
+    extract::{Path, Request, State},
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    routing::any,
+    Router,
+};
+use hyper_util::rt::TokioIo;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::execution_process::ExecutionProcess,
+    routes::auth::{role, ProjectAccess, SESSION_COOKIE},
+};
+
+/// Largest request body passed on to a dev server
+const MAX_REQUEST_BODY: usize = 32 * 1024 * 1024;
+
+/// Headers that only apply to a single connection, never forwarded either way
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "host",
+];
+
+/// Largest response head of a dev server switching protocols
+const MAX_UPGRADE_RESPONSE_HEAD: usize = 64 * 1024;
+
+/// Environment variable setting the port previews are served from in multi-user mode,
+/// picked automatically when unset
+pub const PREVIEW_PORT_ENV: &str = "PREVIEW_PORT";
+
+fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build preview proxy client")
+    })
+}
+
+fn preview_path(attempt_id: Uuid) -> String {
+    format!("/preview/{}/", attempt_id)
+}
+
+/// URL of `path` on the dev server listening on `port`
+pub fn preview_upstream_url(port: i64, path: &str, query: Option<&str>) -> String {
+    let mut url = format!("http://127.0.0.1:{}/{}", port, path.trim_start_matches('/'));
+    if let Some(query) = query.filter(|q| !q.is_empty()) {
+        url.push('?');
+        url.push_str(query);
+    }
+    url
+}
+
+/// Point a redirect from the dev server back through the preview proxy
+pub fn rewrite_location(location: &str, attempt_id: Uuid, port: i64) -> String {
+    let prefix = preview_path(attempt_id);
+    for origin in [
+        format!("http://localhost:{}", port),
+        format!("http://127.0.0.1:{}", port),
+        format!("http://0.0.0.0:{}", port),
+    ] {
+        if let Some(rest) = location.strip_prefix(&origin) {
+            return format!("{}{}", prefix, rest.trim_start_matches('/'));
+        }
+    }
+    if location.starts_with('/')
+        && !location.starts_with("//")
+        && !location.starts_with(prefix.as_str())
+    {
+        return format!("{}{}", prefix, location.trim_start_matches('/'));
+    }
+    location.to_string()
+}
+
+/// The `Cookie` header to send to the dev server, without the session cookie which would let
+/// the code the agent wrote act as the viewer. None when no other cookie is left.
+pub fn forwarded_cookies(cookies: &str) -> Option<String> {
+    let kept: Vec<&str> = cookies
+        .split(';')
+        .map(str::trim)
+        .filter(|cookie| {
+            !cookie.is_empty()
+                && cookie
+                    .split_once('=')
+                    .is_none_or(|(name, _)| name.trim() != SESSION_COOKIE)
+        })
+        .collect();
+    (!kept.is_empty()).then(|| kept.join("; "))
+}
+
+/// Whether a `Set-Cookie` of the dev server would replace the session cookie
+fn sets_session_cookie(set_cookie: &str) -> bool {
+    set_cookie
+        .split_once('=')
+        .is_some_and(|(name, _)| name.trim() == SESSION_COOKIE)
+}
+
+/// Request head asking the dev server on `port` to switch protocols, e.g. to the WebSocket
+/// of hot module reloading. Credentials are dropped as for every other request.
+pub fn upgrade_request_head(request: &Request, port: i64, path: &str, attempt_id: Uuid) -> String {
+    let mut head = format!("{} /{}", request.method(), path.trim_start_matches('/'));
+    if let Some(query) = request.uri().query().filter(|q| !q.is_empty()) {
+        head.push('?');
+        head.push_str(query);
+    }
+    head.push_str(&format!(" HTTP/1.1\r\nHost: 127.0.0.1:{}\r\n", port));
+    head.push_str("Connection: Upgrade\r\n");
+
+    for (name, value) in request.headers() {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        if name == header::UPGRADE {
+            head.push_str(&format!("Upgrade: {}\r\n", value));
+        } else if name == header::COOKIE {
+            if let Some(cookies) = forwarded_cookies(value) {
+                head.push_str(&format!("Cookie: {}\r\n", cookies));
+            }
+        } else if !HOP_BY_HOP_HEADERS.contains(&name.as_str()) && name != header::AUTHORIZATION {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+    }
+    head.push_str(&format!(
+        "x-forwarded-prefix: /preview/{}\r\n\r\n",
+        attempt_id
+    ));
+    head
+}
+
+/// Status and headers of a response head ending with an empty line
+pub fn parse_response_head(head: &str) -> Option<(StatusCode, Vec<(String, String)>)> {
+    let mut lines = head.split("\r\n");
+    let status = lines.next()?.split(' ').nth(1)?.parse::<u16>().ok()?;
+    let headers = lines
+        .take_while(|line| !line.is_empty())
+        .map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((StatusCode::from_u16(status).ok()?, headers))
+}
+
+/// Pass a protocol switch on to the dev server, then relay both ways until either side closes
+async fn proxy_upgrade(mut request: Request, port: i64, path: &str, attempt_id: Uuid) -> Response {
+    let head = upgrade_request_head(&request, port, path, attempt_id);
+    let on_upgrade = hyper::upgrade::on(&mut request);
+
+    let not_responding = |e: std::io::Error| {
+        error_response(
+            StatusCode::BAD_GATEWAY,
+            format!("Dev server on port {} is not responding: {}", port, e),
+        )
+    };
+    let mut upstream = match TcpStream::connect(("127.0.0.1", port as u16)).await {
+        Ok(upstream) => upstream,
+        Err(e) => return not_responding(e),
+    };
+    if let Err(e) = upstream.write_all(head.as_bytes()).await {
+        return not_responding(e);
+    }
+
+    // Read up to the end of the response head, keeping whatever the server sent after it
+    let mut buffer = Vec::new();
+    let head_end = loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+        if buffer.len() > MAX_UPGRADE_RESPONSE_HEAD {
+            return error_response(
+                StatusCode::BAD_GATEWAY,
+                "Dev server sent an oversized response head".to_string(),
+            );
+        }
+        let mut chunk = [0; 4096];
+        match upstream.read(&mut chunk).await {
+            Ok(0) => return StatusCode::BAD_GATEWAY.into_response(),
+            Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+            Err(e) => return not_responding(e),
+        }
+    };
+    let Some((status, headers)) = std::str::from_utf8(&buffer[..head_end])
+        .ok()
+        .and_then(parse_response_head)
+    else {
+        return error_response(
+            StatusCode::BAD_GATEWAY,
+            "Dev server sent an invalid response".to_string(),
+        );
+    };
+
+    let mut builder = Response::builder().status(status);
+    for (name, value) in &headers {
+        let name = name.to_ascii_lowercase();
+        if (HOP_BY_HOP_HEADERS.contains(&name.as_str())
+            && name != "connection"
+            && name != "upgrade")
+            || (name == "set-cookie" && sets_session_cookie(value))
+        {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            builder = builder.header(name, value);
+        }
+    }
+    if status != StatusCode::SWITCHING_PROTOCOLS {
+        // The dev server turned the switch down, its body is of no use to the client
+        return builder
+            .header(header::CONTENT_LENGTH, 0)
+            .body(Body::empty())
+            .unwrap_or_else(|_| StatusCode::BAD_GATEWAY.into_response());
+    }
+
+    let early_data = buffer.split_off(head_end);
+    tokio::spawn(async move {
+        let client = match on_upgrade.await {
+            Ok(upgraded) => upgraded,
+            Err(e) => {
+                tracing::warn!("Failed to upgrade preview connection: {}", e);
+                return;
+            }
+        };
+        let mut client = TokioIo::new(client);
+        if client.write_all(&early_data).await.is_ok() {
+            let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+        }
+    });
+
+    builder
+        .body(Body::empty())
+        .unwrap_or_else(|_| StatusCode::BAD_GATEWAY.into_response())
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    (status, message).into_response()
+}
+
+/// GET/POST/... /preview/:attempt_id/*path
+/// Reverse-proxies to the running dev server of the attempt, WebSockets included
+pub async fn proxy_preview(
+    _: ProjectAccess<role::Viewer>,
+    Path(params): Path<HashMap<String, String>>,
+    State(app_state): State<AppState>,
+    request: Request,
+) -> Response {
+    let Some(attempt_id) = params
+        .get("attempt_id")
+        .and_then(|id| Uuid::parse_str(id).ok())
+    else {
+        return error_response(StatusCode::NOT_FOUND, "Invalid attempt id".to_string());
+    };
+    let path = params.get("path").map(String::as_str).unwrap_or_default();
+
+    let port =
+        match ExecutionProcess::find_running_dev_server_for_attempt(&app_state.db_pool, attempt_id)
+            .await
+        {
+            Ok(Some((_, port))) => port,
+            Ok(None) => {
+                return error_response(
+                    StatusCode::NOT_FOUND,
+                    "No dev server is running for this attempt".to_string(),
+                )
+            }
+            Err(e) => {
+                tracing::error!("Failed to find dev server of attempt {}: {}", attempt_id, e);
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+
+    if request.headers().contains_key(header::UPGRADE) {
+        return proxy_upgrade(request, port, path, attempt_id).await;
+    }
+
+    let url = preview_upstream_url(port, path, request.uri().query());
+    let Ok(method) = reqwest::Method::from_bytes(request.method().as_str().as_bytes()) else {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    };
+    let mut upstream = client().request(method, &url);
+    for (name, value) in request.headers() {
+        if HOP_BY_HOP_HEADERS.contains(&name.as_str()) || name == header::AUTHORIZATION {
+            continue;
+        }
+        if name == header::COOKIE {
+            if let Some(cookies) = value.to_str().ok().and_then(forwarded_cookies) {
+                upstream = upstream.header(header::COOKIE.as_str(), cookies);
+            }
+            continue;
+        }
+        upstream = upstream.header(name.as_str(), value.as_bytes());
+    }
+    upstream = upstream.header("x-forwarded-prefix", format!("/preview/{}", attempt_id));
+
+    let body = match axum::body::to_bytes(request.into_body(), MAX_REQUEST_BODY).await {
+        Ok(body) => body,
+        Err(e) => {
+            return error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Failed to read request body: {}", e),
+            )
+        }
+    };
+
+    let response = match upstream.body(body.to_vec()).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_GATEWAY,
+                format!("Dev server on port {} is not responding: {}", port, e),
+            )
+        }
+    };
+
+    let mut builder = Response::builder().status(response.status().as_u16());
+    for (name, value) in response.headers() {
+        if HOP_BY_HOP_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        if name.as_str() == "set-cookie" && value.to_str().is_ok_and(sets_session_cookie) {
+            continue;
+        }
+        if name.as_str() == "location" {
+            if let Some(location) = value
+                .to_str()
+                .ok()
+                .map(|location| rewrite_location(location, attempt_id, port))
+                .and_then(|location| HeaderValue::from_str(&location).ok())
+            {
+                builder = builder.header(header::LOCATION, location);
+                continue;
+            }
+        }
+        builder = builder.header(name.as_str(), value.as_bytes());
+    }
+
+    builder
+        .body(Body::from_stream(response.bytes_stream()))
+        .unwrap_or_else(|_| StatusCode::BAD_GATEWAY.into_response())
+}
+
+/// Relative asset URLs only resolve under the trailing slash
+async fn redirect_to_preview_root(Path(attempt_id): Path<Uuid>) -> Redirect {
+    Redirect::permanent(&preview_path(attempt_id))
+}
+
+pub fn preview_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/preview/:attempt_id",
+            axum::routing::get(redirect_to_preview_root),
+        )
+        .route("/preview/:attempt_id/", any(proxy_preview))
+        .route("/preview/:attempt_id/*path", any(proxy_preview))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_upstream_url() {
+        assert_eq!(
+            preview_upstream_url(5173, "", None),
+            "http://127.0.0.1:5173/"
+        );
+        assert_eq!(
+            preview_upstream_url(5173, "assets/app.js", Some("v=1")),
+            "http://127.0.0.1:5173/assets/app.js?v=1"
+        );
+        assert_eq!(
+            preview_upstream_url(5173, "/api", Some("")),
+            "http://127.0.0.1:5173/api"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_location() {
+        let attempt_id = Uuid::nil();
+        let prefix = "/preview/00000000-0000-0000-0000-000000000000/";
+        assert_eq!(
+            rewrite_location("/login", attempt_id, 5173),
+            format!("{}login", prefix)
+        );
+        assert_eq!(
+            rewrite_location("http://localhost:5173/login?next=/", attempt_id, 5173),
+            format!("{}login?next=/", prefix)
+        );
+        assert_eq!(
+            rewrite_location(&format!("{}login", prefix), attempt_id, 5173),
+            format!("{}login", prefix)
+        );
+        assert_eq!(
+            rewrite_location("https://example.com/oauth", attempt_id, 5173),
+            "https://example.com/oauth"
+        );
+        assert_eq!(
+            rewrite_location("//cdn.example.com/x", attempt_id, 5173),
+            "//cdn.example.com/x"
+        );
+        assert_eq!(rewrite_location("next", attempt_id, 5173), "next");
+    }
+
+    #[test]
+    fn test_session_cookie_is_not_forwarded() {
+        assert_eq!(
+            forwarded_cookies("theme=dark; vk_session=vk_abc; other=1"),
+            Some("theme=dark; other=1".to_string())
+        );
+        assert_eq!(forwarded_cookies("vk_session=vk_abc"), None);
+        assert_eq!(
+            forwarded_cookies("vk_sessions=kept"),
+            Some("vk_sessions=kept".to_string())
+        );
+        assert!(sets_session_cookie("vk_session=stolen; Path=/"));
+        assert!(!sets_session_cookie("app_session=1; Path=/"));
+    }
+
+    #[test]
+    fn test_upgrade_request_head() {
+        let request = Request::builder()
+            .uri("/preview/00000000-0000-0000-0000-000000000000/?token=1")
+            .header(header::HOST, "localhost:3000")
+            .header(header::CONNECTION, "keep-alive, Upgrade")
+            .header(header::UPGRADE, "websocket")
+            .header("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ==")
+            .header(header::AUTHORIZATION, "Bearer vk_token")
+            .header(header::COOKIE, "vk_session=vk_abc; theme=dark")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(
+            upgrade_request_head(&request, 5173, "", Uuid::nil()),
+            "GET /?token=1 HTTP/1.1\r\n\
+             Host: 127.0.0.1:5173\r\n\
+             Connection: Upgrade\r\n\
+             Upgrade: websocket\r\n\
+             sec-websocket-key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+             Cookie: theme=dark\r\n\
+             x-forwarded-prefix: /preview/00000000-0000-0000-0000-000000000000\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_response_head() {
+        let (status, headers) = parse_response_head(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(status, StatusCode::SWITCHING_PROTOCOLS);
+        assert_eq!(
+            headers[2],
+            (
+                "Sec-WebSocket-Accept".to_string(),
+                "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=".to_string()
+            )
+        );
+        assert!(parse_response_head("garbage\r\n\r\n").is_none());
+    }
+
+    /// Reads from `stream` up to the end of a request or response head
+    async fn read_head(stream: &mut TcpStream) -> String {
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        String::from_utf8(head).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_websockets_are_relayed() {
+        // A dev server switching protocols, then echoing what it receives
+        let dev_server = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let dev_port = dev_server.local_addr().unwrap().port() as i64;
+        tokio::spawn(async move {
+            let (mut stream, _) = dev_server.accept().await.unwrap();
+            let head = read_head(&mut stream).await;
+            assert!(head.starts_with("GET /hmr HTTP/1.1\r\n"));
+            assert!(!head.contains("vk_session"));
+            stream
+                .write_all(b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\r\nhello")
+                .await
+                .unwrap();
+            let mut buffer = [0; 4];
+            stream.read_exact(&mut buffer).await.unwrap();
+            stream.write_all(&buffer).await.unwrap();
+        });
+
+        let app = Router::new().route(
+            "/hmr",
+            any(move |request: Request| proxy_upgrade(request, dev_port, "hmr", Uuid::nil())),
+        );
+        let proxy = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_port = proxy.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(proxy, app).await.unwrap() });
+
+        let mut client = TcpStream::connect(("127.0.0.1", proxy_port)).await.unwrap();
+        client
+            .write_all(b"GET /hmr HTTP/1.1\r\nHost: localhost\r\nConnection: Upgrade\r\nUpgrade: websocket\r\nCookie: vk_session=vk_abc\r\n\r\n")
+            .await
+            .unwrap();
+        let head = read_head(&mut client).await;
+        assert!(head.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+
+        let mut greeting = [0; 5];
+        client.read_exact(&mut greeting).await.unwrap();
+        assert_eq!(&greeting, b"hello");
+        client.write_all(b"ping").await.unwrap();
+        let mut echo = [0; 4];
+        client.read_exact(&mut echo).await.unwrap();
+        assert_eq!(&echo, b"ping");
+    }
+}
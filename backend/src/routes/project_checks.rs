@@ -0,0 +1,189 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, put},
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        api_response::ApiResponse,
+        audit_event::NewAuditEvent,
+        project::Project,
+        project_check::{CreateProjectCheck, ProjectCheck, UpdateProjectCheck},
+    },
+    routes::{
+        audit::Auditor,
+        auth::{role, ProjectAccess},
+    },
+};
+
+async fn ensure_project_exists(
+    state: &AppState,
+    project_id: Uuid,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    match Project::exists(&state.db_pool, project_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Project not found")),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to check project existence: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+fn validate_check(
+    name: Option<&str>,
+    script: Option<&str>,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    ProjectCheck::validate(name, script)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&e))))
+}
+
+pub async fn list_checks(
+    _: ProjectAccess<role::Viewer>,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_project_exists(&state, project_id).await?;
+
+    match ProjectCheck::find_by_project_id(&state.db_pool, project_id).await {
+        Ok(checks) => Ok(Json(ApiResponse::success(checks))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to fetch checks: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub async fn create_check(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateProjectCheck>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_project_exists(&state, project_id).await?;
+    validate_check(Some(&payload.name), Some(&payload.script))?;
+
+    match ProjectCheck::create(&state.db_pool, project_id, &payload).await {
+        Ok(check) => {
+            audit
+                .record(
+                    NewAuditEvent::new("check.create")
+                        .project(project_id)
+                        .target(check.id)
+                        .after(&check),
+                )
+                .await;
+            Ok((StatusCode::CREATED, Json(ApiResponse::success(check))))
+        }
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to create check: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub async fn update_check(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, check_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectCheck>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    validate_check(payload.name.as_deref(), payload.script.as_deref())?;
+
+    let before = ProjectCheck::find_by_id_and_project_id(&state.db_pool, check_id, project_id)
+        .await
+        .ok()
+        .flatten();
+    match ProjectCheck::update(&state.db_pool, check_id, project_id, &payload).await {
+        Ok(check) => {
+            audit
+                .record(
+                    NewAuditEvent::new("check.update")
+                        .project(project_id)
+                        .target(check_id)
+                        .before(&before)
+                        .after(&check),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(check)))
+        }
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Check not found")),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to update check: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub async fn delete_check(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, check_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let before = ProjectCheck::find_by_id_and_project_id(&state.db_pool, check_id, project_id)
+        .await
+        .ok()
+        .flatten();
+    match ProjectCheck::delete(&state.db_pool, check_id, project_id).await {
+        Ok(0) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Check not found")),
+        )),
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("check.delete")
+                        .project(project_id)
+                        .target(check_id)
+                        .before(&before),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to delete check: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub fn project_checks_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/:project_id/checks",
+            get(list_checks).post(create_check),
+        )
+        .route(
+            "/projects/:project_id/checks/:check_id",
+            put(update_check).delete(delete_check),
+        )
+}
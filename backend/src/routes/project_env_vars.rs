@@ -0,0 +1,207 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, put},
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        api_response::ApiResponse,
+        audit_event::NewAuditEvent,
+        project::Project,
+        project_env_var::{
+            CreateProjectEnvVar, ProjectEnvVar, ProjectEnvVarError, UpdateProjectEnvVar,
+        },
+    },
+    routes::{
+        audit::Auditor,
+        auth::{role, ProjectAccess},
+    },
+};
+
+async fn ensure_project_exists(
+    state: &AppState,
+    project_id: Uuid,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    match Project::exists(&state.db_pool, project_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Project not found")),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to check project existence: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+fn validate_env_var(name: Option<&str>) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    ProjectEnvVar::validate(name)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&e))))
+}
+
+/// Map a failed create or update to a response, `action` being e.g. "create"
+fn env_var_error(
+    e: ProjectEnvVarError,
+    name: Option<&str>,
+    action: &str,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    match e {
+        ProjectEnvVarError::Database(sqlx::Error::RowNotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Environment variable not found")),
+        ),
+        ProjectEnvVarError::Database(sqlx::Error::Database(db_error))
+            if db_error.is_unique_violation() =>
+        {
+            (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(&format!(
+                    "The project already has a variable named '{}'",
+                    name.unwrap_or_default()
+                ))),
+            )
+        }
+        ProjectEnvVarError::Validation(message) => {
+            (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&message)))
+        }
+        e => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to {} environment variable: {}",
+                action, e
+            ))),
+        ),
+    }
+}
+
+pub async fn list_env_vars(
+    _: ProjectAccess<role::Viewer>,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_project_exists(&state, project_id).await?;
+
+    match ProjectEnvVar::find_by_project_id(&state.db_pool, project_id).await {
+        Ok(vars) => Ok(Json(ApiResponse::success(vars))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to fetch environment variables: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub async fn create_env_var(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateProjectEnvVar>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_project_exists(&state, project_id).await?;
+    validate_env_var(Some(&payload.name))?;
+
+    match ProjectEnvVar::create(&state.db_pool, project_id, &payload).await {
+        Ok(var) => {
+            audit
+                .record(
+                    NewAuditEvent::new("env_var.create")
+                        .project(project_id)
+                        .target(var.id)
+                        .after(&var),
+                )
+                .await;
+            Ok((StatusCode::CREATED, Json(ApiResponse::success(var))))
+        }
+        Err(e) => Err(env_var_error(e, Some(&payload.name), "create")),
+    }
+}
+
+pub async fn update_env_var(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, env_var_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectEnvVar>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    validate_env_var(payload.name.as_deref())?;
+
+    let before = ProjectEnvVar::find_by_id_and_project_id(&state.db_pool, env_var_id, project_id)
+        .await
+        .ok()
+        .flatten();
+    match ProjectEnvVar::update(&state.db_pool, env_var_id, project_id, &payload).await {
+        Ok(var) => {
+            audit
+                .record(
+                    NewAuditEvent::new("env_var.update")
+                        .project(project_id)
+                        .target(env_var_id)
+                        .before(&before)
+                        .after(&var),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(var)))
+        }
+        Err(e) => Err(env_var_error(e, payload.name.as_deref(), "update")),
+    }
+}
+
+pub async fn delete_env_var(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, env_var_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let before = ProjectEnvVar::find_by_id_and_project_id(&state.db_pool, env_var_id, project_id)
+        .await
+        .ok()
+        .flatten();
+    match ProjectEnvVar::delete(&state.db_pool, env_var_id, project_id).await {
+        Ok(0) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Environment variable not found")),
+        )),
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("env_var.delete")
+                        .project(project_id)
+                        .target(env_var_id)
+                        .before(&before),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to delete environment variable: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub fn project_env_vars_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/:project_id/env-vars",
+            get(list_env_vars).post(create_env_var),
+        )
+        .route(
+            "/projects/:project_id/env-vars/:env_var_id",
+            put(update_env_var).delete(delete_env_var),
+        )
+}
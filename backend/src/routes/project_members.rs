@@ -0,0 +1,141 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, put},
+    Extension, Json, Router,
+};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        api_response::ApiResponse,
+        audit_event::NewAuditEvent,
+        project_member::{ProjectMember, SetProjectMember},
+    },
+    routes::{
+        audit::Auditor,
+        auth::{project_role, role, CurrentUser, ProjectAccess},
+    },
+};
+
+type ApiError = (StatusCode, Json<ApiResponse<()>>);
+
+fn error(status: StatusCode, message: &str) -> ApiError {
+    (status, Json(ApiResponse::error(message)))
+}
+
+fn require_multi_user(state: &AppState) -> Result<(), ApiError> {
+    if state.multi_user() {
+        Ok(())
+    } else {
+        Err(error(
+            StatusCode::BAD_REQUEST,
+            "Project members are only used in multi-user mode",
+        ))
+    }
+}
+
+pub async fn list_members(
+    _: ProjectAccess<role::Viewer>,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    match ProjectMember::find_by_project_id(&state.db_pool, project_id).await {
+        Ok(members) => Ok(Json(ApiResponse::success(members))),
+        Err(e) => Err(error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to fetch project members: {}", e),
+        )),
+    }
+}
+
+/// GET /projects/:project_id/role - what the signed in user can do on the project
+pub async fn get_my_role(
+    _: ProjectAccess<role::Viewer>,
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = user.as_ref().map(|Extension(CurrentUser(user))| user);
+    let role = project_role(&state, user, project_id).await?;
+    Ok(Json(ApiResponse::success(role)))
+}
+
+/// PUT /projects/:project_id/members/:user_id - add a member, or change their role
+pub async fn set_member(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<SetProjectMember>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_multi_user(&state)?;
+
+    let before = ProjectMember::find_role(&state.db_pool, project_id, user_id)
+        .await
+        .ok()
+        .flatten();
+    match ProjectMember::set(&state.db_pool, project_id, user_id, payload.role).await {
+        Ok(()) => {
+            audit
+                .record(
+                    NewAuditEvent::new("member.set")
+                        .project(project_id)
+                        .target(user_id)
+                        .before(&before)
+                        .after(&payload.role),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
+        Err(sqlx::Error::Database(db_error)) if db_error.is_foreign_key_violation() => {
+            Err(error(StatusCode::NOT_FOUND, "User not found"))
+        }
+        Err(e) => Err(error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to set project member: {}", e),
+        )),
+    }
+}
+
+pub async fn delete_member(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let before = ProjectMember::find_role(&state.db_pool, project_id, user_id)
+        .await
+        .ok()
+        .flatten();
+    match ProjectMember::delete(&state.db_pool, project_id, user_id).await {
+        Ok(0) => Err(error(StatusCode::NOT_FOUND, "Project member not found")),
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("member.remove")
+                        .project(project_id)
+                        .target(user_id)
+                        .before(&before),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
+        Err(e) => Err(error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to remove project member: {}", e),
+        )),
+    }
+}
+
+pub fn project_members_router() -> Router<AppState> {
+    Router::new()
+        .route("/projects/:project_id/members", get(list_members))
+        .route(
+            "/projects/:project_id/members/:user_id",
+            put(set_member).delete(delete_member),
+        )
+        .route("/projects/:project_id/role", get(get_my_role))
+}
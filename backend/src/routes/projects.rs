@@ -5,25 +5,40 @@ use axum::{
     http::StatusCode,
     response::Json as ResponseJson,
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use uuid::Uuid;
 
 use crate::{
     app_state::AppState,
     models::{
+        audit_event::NewAuditEvent,
         project::{
-            CreateBranch, CreateProject, GitBranch, Project, ProjectWithBranch, SearchMatchType,
-            SearchResult, UpdateProject,
+            CommitSettings, CreateBranch, CreateProject, DevServerSettings, GitBranch, Project,
+            ProjectWithBranch, SandboxSettings, SearchMatchType, SearchResult, UpdateProject,
+            MAX_AUTO_FIX_ITERATIONS,
         },
+        project_member::{ProjectMember, ProjectRole},
         ApiResponse,
     },
+    routes::{
+        audit::Auditor,
+        auth::{current_user_id, require_admin, role, CurrentUser, ProjectAccess},
+    },
 };
 
+/// GET /projects - in multi-user mode, only the projects the user is a member of unless an admin
 pub async fn get_projects(
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, StatusCode> {
-    match Project::find_all(&app_state.db_pool).await {
+    let projects = match user {
+        Some(Extension(CurrentUser(user))) if !user.is_admin => {
+            Project::find_by_member(&app_state.db_pool, user.id).await
+        }
+        _ => Project::find_all(&app_state.db_pool).await,
+    };
+    match projects {
         Ok(projects) => Ok(ResponseJson(ApiResponse {
             success: true,
             data: Some(projects),
@@ -37,6 +52,7 @@ pub async fn get_projects(
 }
 
 pub async fn get_project(
+    _: ProjectAccess<role::Viewer>,
     Path(id): Path<Uuid>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, StatusCode> {
@@ -55,6 +71,7 @@ pub async fn get_project(
 }
 
 pub async fn get_project_with_branch(
+    _: ProjectAccess<role::Viewer>,
     Path(id): Path<Uuid>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<ProjectWithBranch>>, StatusCode> {
@@ -73,6 +90,7 @@ pub async fn get_project_with_branch(
 }
 
 pub async fn get_project_branches(
+    _: ProjectAccess<role::Viewer>,
     Path(id): Path<Uuid>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<Vec<GitBranch>>>, StatusCode> {
@@ -97,6 +115,8 @@ pub async fn get_project_branches(
 }
 
 pub async fn create_project_branch(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path(id): Path<Uuid>,
     State(app_state): State<AppState>,
     Json(payload): Json<CreateBranch>,
@@ -122,11 +142,21 @@ pub async fn create_project_branch(
     match Project::find_by_id(&app_state.db_pool, id).await {
         Ok(Some(project)) => {
             match project.create_branch(&payload.name, payload.base_branch.as_deref()) {
-                Ok(branch) => Ok(ResponseJson(ApiResponse {
-                    success: true,
-                    data: Some(branch),
-                    message: Some(format!("Branch '{}' created successfully", payload.name)),
-                })),
+                Ok(branch) => {
+                    audit
+                        .record(
+                            NewAuditEvent::new("project.branch_create")
+                                .project(id)
+                                .target(&branch.name)
+                                .after(&branch),
+                        )
+                        .await;
+                    Ok(ResponseJson(ApiResponse {
+                        success: true,
+                        data: Some(branch),
+                        message: Some(format!("Branch '{}' created successfully", payload.name)),
+                    }))
+                }
                 Err(e) => {
                     tracing::error!(
                         "Failed to create branch '{}' for project {}: {}",
@@ -152,8 +182,19 @@ pub async fn create_project_branch(
 
 pub async fn create_project(
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    audit: Auditor,
     Json(payload): Json<CreateProject>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, StatusCode> {
+    // A project runs its scripts in any repository of the host, and its creator maintains it
+    if let Err((_, ResponseJson(response))) = require_admin(&user, "create projects") {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: response.message,
+        }));
+    }
+
     let id = Uuid::new_v4();
 
     tracing::debug!("Creating project '{}'", payload.name);
@@ -251,6 +292,27 @@ pub async fn create_project(
 
     match Project::create(&app_state.db_pool, &payload, id).await {
         Ok(project) => {
+            // Whoever creates a project maintains it
+            if let Some(user_id) = current_user_id(&user) {
+                if let Err(e) = ProjectMember::set(
+                    &app_state.db_pool,
+                    project.id,
+                    user_id,
+                    ProjectRole::Maintainer,
+                )
+                .await
+                {
+                    tracing::error!("Failed to add the creator to project {}: {}", project.id, e);
+                }
+            }
+            audit
+                .record(
+                    NewAuditEvent::new("project.create")
+                        .project(project.id)
+                        .after(&project),
+                )
+                .await;
+
             // Track project creation event
             app_state
                 .track_analytics_event(
@@ -278,8 +340,11 @@ pub async fn create_project(
 }
 
 pub async fn update_project(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
     Path(id): Path<Uuid>,
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Json(payload): Json<UpdateProject>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, StatusCode> {
     // Check if project exists first
@@ -292,9 +357,24 @@ pub async fn update_project(
         }
     };
 
+    let event = NewAuditEvent::new("project.update")
+        .project(id)
+        .before(&existing_project);
+
     // If git_repo_path is being changed, check if the new path is already used by another project
     if let Some(new_git_repo_path) = &payload.git_repo_path {
         if new_git_repo_path != &existing_project.git_repo_path {
+            // Pointing a project at another repository is creating it anew
+            if let Err((_, ResponseJson(response))) =
+                require_admin(&user, "move projects to another repository")
+            {
+                return Ok(ResponseJson(ApiResponse {
+                    success: false,
+                    data: None,
+                    message: response.message,
+                }));
+            }
+
             match Project::find_by_git_repo_path_excluding_id(
                 &app_state.db_pool,
                 new_git_repo_path,
@@ -322,6 +402,62 @@ pub async fn update_project(
         }
     }
 
+    if let Some(Err(e)) = payload
+        .commit_settings
+        .as_ref()
+        .map(CommitSettings::validate)
+    {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: Some(e),
+        }));
+    }
+
+    if let Some(Err(e)) = payload
+        .dev_server_settings
+        .as_ref()
+        .map(DevServerSettings::validate)
+    {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: Some(e),
+        }));
+    }
+
+    if let Some(Err(e)) = payload
+        .sandbox_settings
+        .as_ref()
+        .map(SandboxSettings::validate)
+    {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: Some(e),
+        }));
+    }
+
+    if matches!(payload.max_concurrent_agents, Some(limit) if limit < 1) {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: Some("Concurrent agent limit must be at least 1".to_string()),
+        }));
+    }
+
+    if matches!(payload.auto_fix_max_iterations, Some(max) if !(0..=MAX_AUTO_FIX_ITERATIONS).contains(&max))
+    {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: Some(format!(
+                "Auto-fix iterations must be between 0 and {}",
+                MAX_AUTO_FIX_ITERATIONS
+            )),
+        }));
+    }
+
     // Destructure payload to handle field updates.
     // This allows us to treat `None` from the payload as an explicit `null` to clear a field,
     // as the frontend currently sends all fields on update.
@@ -330,10 +466,22 @@ pub async fn update_project(
         git_repo_path,
         setup_script,
         dev_script,
+        max_concurrent_agents,
+        merge_strategy,
+        commit_settings,
+        auto_fix_max_iterations,
+        dev_server_settings,
+        sandbox_settings,
     } = payload;
 
     let name = name.unwrap_or(existing_project.name);
     let git_repo_path = git_repo_path.unwrap_or(existing_project.git_repo_path);
+    let merge_strategy = merge_strategy.unwrap_or(existing_project.merge_strategy);
+    let commit_settings = commit_settings.unwrap_or(existing_project.commit_settings.0);
+    let auto_fix_max_iterations =
+        auto_fix_max_iterations.unwrap_or(existing_project.auto_fix_max_iterations);
+    let dev_server_settings = dev_server_settings.unwrap_or(existing_project.dev_server_settings.0);
+    let sandbox_settings = sandbox_settings.unwrap_or(existing_project.sandbox_settings.0);
 
     match Project::update(
         &app_state.db_pool,
@@ -342,14 +490,23 @@ pub async fn update_project(
         git_repo_path,
         setup_script,
         dev_script,
+        max_concurrent_agents,
+        merge_strategy,
+        commit_settings,
+        auto_fix_max_iterations,
+        dev_server_settings,
+        sandbox_settings,
     )
     .await
     {
-        Ok(project) => Ok(ResponseJson(ApiResponse {
-            success: true,
-            data: Some(project),
-            message: Some("Project updated successfully".to_string()),
-        })),
+        Ok(project) => {
+            audit.record(event.after(&project)).await;
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: Some(project),
+                message: Some("Project updated successfully".to_string()),
+            }))
+        }
         Err(e) => {
             tracing::error!("Failed to update project: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -358,14 +515,27 @@ pub async fn update_project(
 }
 
 pub async fn delete_project(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
     Path(id): Path<Uuid>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+    let existing_project = Project::find_by_id(&app_state.db_pool, id)
+        .await
+        .ok()
+        .flatten();
     match Project::delete(&app_state.db_pool, id).await {
         Ok(rows_affected) => {
             if rows_affected == 0 {
                 Err(StatusCode::NOT_FOUND)
             } else {
+                audit
+                    .record(
+                        NewAuditEvent::new("project.delete")
+                            .project(id)
+                            .before(&existing_project),
+                    )
+                    .await;
                 Ok(ResponseJson(ApiResponse {
                     success: true,
                     data: None,
@@ -386,6 +556,7 @@ pub struct OpenEditorRequest {
 }
 
 pub async fn open_project_in_editor(
+    _: ProjectAccess<role::Contributor>,
     Path(id): Path<Uuid>,
     State(app_state): State<AppState>,
     Json(payload): Json<Option<OpenEditorRequest>>,
@@ -463,6 +634,7 @@ pub async fn open_project_in_editor(
 }
 
 pub async fn search_project_files(
+    _: ProjectAccess<role::Viewer>,
     Path(id): Path<Uuid>,
     Query(params): Query<HashMap<String, String>>,
     State(app_state): State<AppState>,
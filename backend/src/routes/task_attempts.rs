@@ -3,7 +3,7 @@ use axum::{
     http::StatusCode,
     response::Json as ResponseJson,
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
@@ -16,17 +16,28 @@ use crate::{
         ActionType, ExecutorConfig, NormalizedConversation, NormalizedEntry, NormalizedEntryType,
     },
     models::{
-        config::Config,
+        audit_event::NewAuditEvent,
+        config::{Config, GitProviderKind},
         execution_process::{
-            ExecutionProcess, ExecutionProcessStatus, ExecutionProcessSummary, ExecutionProcessType,
+            ExecutionProcess, ExecutionProcessStatus, ExecutionProcessSummary,
+            ExecutionProcessType, QueuedExecution,
         },
+        project::Project,
+        project_check::{blocking_message, CheckResult, ProjectCheck},
         task::{Task, TaskStatus},
         task_attempt::{
-            BranchStatus, CreateFollowUpAttempt, CreatePrParams, CreateTaskAttempt, TaskAttempt,
-            TaskAttemptState, WorktreeDiff,
+            AttemptComparison, BranchStatus, ConflictReport, CreateFollowUpAttempt, CreatePrParams,
+            CreateTaskAttempt, MergeResult, MergeTaskAttempt, ResolveConflicts,
+            StartConflictResolution, TaskAttempt, TaskAttemptError, TaskAttemptState, WorktreeDiff,
         },
+        webhook::WebhookEvent,
         ApiResponse,
     },
+    routes::{
+        audit::Auditor,
+        auth::{current_user_id, role, CurrentUser, ProjectAccess},
+    },
+    services::{GitProviderError, GitServiceError, HostedRepo, WebhookService},
 };
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -39,6 +50,8 @@ pub struct CreateGitHubPRRequest {
     pub title: String,
     pub body: Option<String>,
     pub base_branch: Option<String>,
+    #[serde(default)]
+    pub skip_checks: bool, // Open the PR even if required checks have not passed
 }
 
 #[derive(Debug, Serialize)]
@@ -65,7 +78,9 @@ async fn normalize_process_logs(
     process: &ExecutionProcess,
 ) -> NormalizedConversation {
     use crate::models::{
-        execution_process::ExecutionProcessType, executor_session::ExecutorSession,
+        execution_log_chunk::{ExecutionLogChunk, LogStreamType},
+        execution_process::ExecutionProcessType,
+        executor_session::ExecutorSession,
     };
     let executor_session = ExecutorSession::find_by_execution_process_id(db_pool, process.id)
         .await
@@ -132,25 +147,25 @@ async fn normalize_process_logs(
             }
         }
     }
-    // Parse stderr chunks separated by boundary markers
+    // Each stored stderr chunk becomes one error entry
     let mut stderr_entries = Vec::new();
-    if let Some(stderr) = &process.stderr {
-        let trimmed = stderr.trim();
-        if !trimmed.is_empty() {
-            let chunks: Vec<&str> = trimmed.split("---STDERR_CHUNK_BOUNDARY---").collect();
-            for chunk in chunks {
-                let chunk_trimmed = chunk.trim();
-                if !chunk_trimmed.is_empty() {
-                    let filtered_content = chunk_trimmed.replace("---STDERR_CHUNK_BOUNDARY---", "");
-                    if !filtered_content.trim().is_empty() {
-                        stderr_entries.push(NormalizedEntry {
-                            timestamp: Some(chrono::Utc::now().to_rfc3339()),
-                            entry_type: NormalizedEntryType::ErrorMessage,
-                            content: filtered_content.trim().to_string(),
-                            metadata: None,
-                        });
-                    }
-                }
+    if has_stderr {
+        let chunks = ExecutionLogChunk::find_by_execution_process_id_and_stream(
+            db_pool,
+            process.id,
+            LogStreamType::Stderr,
+        )
+        .await
+        .unwrap_or_default();
+        for chunk in chunks {
+            let content = chunk.content.trim();
+            if !content.is_empty() {
+                stderr_entries.push(NormalizedEntry {
+                    timestamp: Some(chunk.created_at.to_rfc3339()),
+                    entry_type: NormalizedEntryType::ErrorMessage,
+                    content: content.to_string(),
+                    metadata: None,
+                });
             }
         }
     }
@@ -182,6 +197,7 @@ async fn normalize_process_logs(
 
 /// Get all normalized logs for all execution processes of a task attempt
 pub async fn get_task_attempt_all_logs(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<Json<ApiResponse<Vec<ProcessLogsResponse>>>, StatusCode> {
@@ -219,6 +235,7 @@ pub async fn get_task_attempt_all_logs(
 }
 
 pub async fn get_task_attempts(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, task_id)): Path<(Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<Vec<TaskAttempt>>>, StatusCode> {
@@ -245,9 +262,44 @@ pub async fn get_task_attempts(
     }
 }
 
+pub async fn compare_task_attempts(
+    _: ProjectAccess<role::Viewer>,
+    Path((project_id, task_id)): Path<(Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptComparison>>>, StatusCode> {
+    // Verify task exists in project first
+    match Task::exists(&app_state.db_pool, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    match TaskAttempt::compare_for_task(&app_state.db_pool, task_id, project_id).await {
+        Ok(comparisons) => Ok(ResponseJson(ApiResponse {
+            success: true,
+            data: Some(comparisons),
+            message: None,
+        })),
+        Err(e) => {
+            tracing::error!(
+                "Failed to compare task attempts for task {}: {}",
+                task_id,
+                e
+            );
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 pub async fn create_task_attempt(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id)): Path<(Uuid, Uuid)>,
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Json(payload): Json<CreateTaskAttempt>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, StatusCode> {
     // Verify task exists in project first
@@ -262,7 +314,14 @@ pub async fn create_task_attempt(
 
     let executor_string = payload.executor.as_ref().map(|exec| exec.to_string());
 
-    match TaskAttempt::create(&app_state.db_pool, &payload, task_id).await {
+    match TaskAttempt::create(
+        &app_state.db_pool,
+        &payload,
+        task_id,
+        current_user_id(&user),
+    )
+    .await
+    {
         Ok(attempt) => {
             app_state
                 .track_analytics_event(
@@ -274,6 +333,15 @@ pub async fn create_task_attempt(
                     })),
                 )
                 .await;
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.create")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(attempt.id)
+                        .after(&attempt),
+                )
+                .await;
 
             // Start execution asynchronously (don't block the response)
             let app_state_clone = app_state.clone();
@@ -302,6 +370,10 @@ pub async fn create_task_attempt(
                 message: Some("Task attempt created successfully".to_string()),
             }))
         }
+        Err(crate::models::task_attempt::TaskAttemptError::ValidationError(e)) => {
+            tracing::warn!("Invalid task attempt request: {}", e);
+            Err(StatusCode::BAD_REQUEST)
+        }
         Err(e) => {
             tracing::error!("Failed to create task attempt: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -310,6 +382,7 @@ pub async fn create_task_attempt(
 }
 
 pub async fn get_task_attempt_diff(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<WorktreeDiff>>, StatusCode> {
@@ -338,9 +411,12 @@ pub async fn get_task_attempt_diff(
 
 #[axum::debug_handler]
 pub async fn merge_task_attempt(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
-) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+    Json(payload): Json<MergeTaskAttempt>,
+) -> Result<ResponseJson<ApiResponse<MergeResult>>, StatusCode> {
     // Verify task attempt exists and belongs to the correct task
     match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
         Ok(false) => return Err(StatusCode::NOT_FOUND),
@@ -351,8 +427,26 @@ pub async fn merge_task_attempt(
         Ok(true) => {}
     }
 
-    match TaskAttempt::merge_changes(&app_state.db_pool, attempt_id, task_id, project_id).await {
-        Ok(_) => {
+    if !payload.skip_checks {
+        if let Some(message) = failing_checks_message(&app_state, project_id, attempt_id).await? {
+            return Ok(ResponseJson(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(message),
+            }));
+        }
+    }
+
+    match TaskAttempt::merge_changes(
+        &app_state.db_pool,
+        attempt_id,
+        task_id,
+        project_id,
+        payload.strategy,
+    )
+    .await
+    {
+        Ok(merge_commit) => {
             // Update task status to Done
             if let Err(e) = Task::update_status(
                 &app_state.db_pool,
@@ -378,12 +472,42 @@ pub async fn merge_task_attempt(
                 )
                 .await;
 
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.merge")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(attempt_id)
+                        .after(&serde_json::json!({
+                            "merge_commit": merge_commit,
+                            "strategy": payload.strategy,
+                            "skip_checks": payload.skip_checks,
+                        })),
+                )
+                .await;
+
             Ok(ResponseJson(ApiResponse {
                 success: true,
-                data: None,
+                data: Some(MergeResult {
+                    merge_commit: Some(merge_commit),
+                    conflicted_files: Vec::new(),
+                }),
                 message: Some("Changes merged successfully".to_string()),
             }))
         }
+        Err(TaskAttemptError::GitService(GitServiceError::ConflictingFiles(files))) => {
+            Ok(ResponseJson(ApiResponse {
+                success: false,
+                message: Some(format!(
+                    "Failed to merge: the base branch has conflicting changes in {}",
+                    files.join(", ")
+                )),
+                data: Some(MergeResult {
+                    merge_commit: None,
+                    conflicted_files: files,
+                }),
+            }))
+        }
         Err(e) => {
             tracing::error!("Failed to merge task attempt {}: {}", attempt_id, e);
             Ok(ResponseJson(ApiResponse {
@@ -395,7 +519,24 @@ pub async fn merge_task_attempt(
     }
 }
 
-pub async fn create_github_pr(
+/// Why required checks block merging or opening a PR for the attempt, if they do
+async fn failing_checks_message(
+    app_state: &AppState,
+    project_id: Uuid,
+    attempt_id: Uuid,
+) -> Result<Option<String>, StatusCode> {
+    match ProjectCheck::find_results_for_attempt(&app_state.db_pool, project_id, attempt_id).await {
+        Ok(results) => Ok(blocking_message(&results)),
+        Err(e) => {
+            tracing::error!("Failed to load check results for {}: {}", attempt_id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn create_pr(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
     Json(request): Json<CreateGitHubPRRequest>,
@@ -410,7 +551,17 @@ pub async fn create_github_pr(
         Ok(true) => {}
     }
 
-    // Load the user's GitHub configuration
+    if !request.skip_checks {
+        if let Some(message) = failing_checks_message(&app_state, project_id, attempt_id).await? {
+            return Ok(ResponseJson(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(message),
+            }));
+        }
+    }
+
+    // Load the credentials of the git hosts
     let config = match Config::load(&crate::utils::config_path()) {
         Ok(config) => config,
         Err(e) => {
@@ -419,18 +570,26 @@ pub async fn create_github_pr(
         }
     };
 
-    let github_token = match config.github.token {
-        Some(token) => token,
-        None => {
+    let project = match Project::find_by_id(&app_state.db_pool, project_id).await {
+        Ok(Some(project)) => project,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to fetch project {}: {}", project_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let repo = match HostedRepo::for_repo(&config, &project.git_repo_path) {
+        Ok(repo) => repo,
+        Err(e) => {
             return Ok(ResponseJson(ApiResponse {
                 success: false,
                 data: None,
-                message: Some(
-                    "GitHub authentication not configured. Please sign in with GitHub.".to_string(),
-                ),
+                message: Some(e.to_string()),
             }));
         }
     };
+    let provider = repo.provider.kind();
 
     // Get the task attempt to access the stored base branch
     let attempt = match TaskAttempt::find_by_id(&app_state.db_pool, attempt_id).await {
@@ -455,13 +614,13 @@ pub async fn create_github_pr(
         }
     });
 
-    match TaskAttempt::create_github_pr(
+    match TaskAttempt::create_pr(
         &app_state.db_pool,
         CreatePrParams {
             attempt_id,
             task_id,
             project_id,
-            github_token: &config.github.pat.unwrap_or(github_token),
+            repo: &repo,
             title: &request.title,
             body: request.body.as_deref(),
             base_branch: Some(&base_branch),
@@ -477,61 +636,101 @@ pub async fn create_github_pr(
                         "task_id": task_id.to_string(),
                         "project_id": project_id.to_string(),
                         "attempt_id": attempt_id.to_string(),
+                        "provider": provider,
                     })),
                 )
                 .await;
 
+            WebhookService::dispatch(
+                &app_state.db_pool,
+                WebhookEvent::PrOpened,
+                project_id,
+                task_id,
+                Some(attempt_id),
+                serde_json::json!({ "pr_url": pr_url, "base_branch": base_branch }),
+            )
+            .await;
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.create_pr")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(attempt_id)
+                        .target(&pr_url)
+                        .after(&serde_json::json!({
+                            "pr_url": pr_url,
+                            "base_branch": base_branch,
+                            "title": request.title,
+                            "skip_checks": request.skip_checks,
+                        })),
+                )
+                .await;
+
             Ok(ResponseJson(ApiResponse {
                 success: true,
                 data: Some(pr_url),
-                message: Some("GitHub PR created successfully".to_string()),
+                message: Some(format!("{} pull request created successfully", provider)),
             }))
         }
         Err(e) => {
             tracing::error!(
-                "Failed to create GitHub PR for attempt {}: {}",
+                "Failed to create {} PR for attempt {}: {}",
+                provider,
                 attempt_id,
                 e
             );
-            let message = match &e {
-                crate::models::task_attempt::TaskAttemptError::GitHubService(
-                    crate::services::GitHubServiceError::TokenInvalid,
-                ) => Some("github_token_invalid".to_string()),
-                crate::models::task_attempt::TaskAttemptError::GitService(
-                    crate::services::git_service::GitServiceError::Git(err),
-                ) if err
-                    .message()
-                    .contains("too many redirects or authentication replays") =>
-                {
-                    Some("insufficient_github_permissions".to_string()) // PAT is invalid
-                }
-                crate::models::task_attempt::TaskAttemptError::GitService(
-                    crate::services::git_service::GitServiceError::Git(err),
-                ) if err.message().contains("status code: 403") => {
-                    Some("insufficient_github_permissions".to_string())
-                }
-                crate::models::task_attempt::TaskAttemptError::GitService(
-                    crate::services::git_service::GitServiceError::Git(err),
-                ) if err.message().contains("status code: 404") => {
-                    Some("github_repo_not_found_or_no_access".to_string())
-                }
-                _ => Some(format!("Failed to create PR: {}", e)),
-            };
             Ok(ResponseJson(ApiResponse {
                 success: false,
                 data: None,
-                message,
+                message: Some(create_pr_error_message(&e, provider)),
             }))
         }
     }
 }
 
+/// Message for a failed PR creation. GitHub failures the frontend can help with, by
+/// asking for a sign in or a token, are reported as codes.
+fn create_pr_error_message(e: &TaskAttemptError, provider: GitProviderKind) -> String {
+    let push_error = match e {
+        TaskAttemptError::GitService(GitServiceError::Git(err)) => Some(err.message()),
+        _ => None,
+    };
+    match (provider, e, push_error) {
+        (_, TaskAttemptError::GitProvider(GitProviderError::TokenInvalid(kind)), _)
+            if *kind == GitProviderKind::GitHub =>
+        {
+            "github_token_invalid".to_string()
+        }
+        (GitProviderKind::GitHub, _, Some(message))
+            if message.contains("too many redirects or authentication replays")
+                || message.contains("status code: 403") =>
+        {
+            "insufficient_github_permissions".to_string() // PAT is invalid
+        }
+        (GitProviderKind::GitHub, _, Some(message)) if message.contains("status code: 404") => {
+            "github_repo_not_found_or_no_access".to_string()
+        }
+        (_, _, Some(message))
+            if message.contains("too many redirects or authentication replays")
+                || message.contains("status code: 401")
+                || message.contains("status code: 403") =>
+        {
+            format!(
+                "{} rejected the push. Check that the token in the settings can write to the repository.",
+                provider
+            )
+        }
+        _ => format!("Failed to create PR: {}", e),
+    }
+}
+
 #[derive(serde::Deserialize)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
 }
 
 pub async fn open_task_attempt_in_editor(
+    _: ProjectAccess<role::Contributor>,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
     Json(payload): Json<Option<OpenEditorRequest>>,
@@ -619,6 +818,7 @@ pub async fn open_task_attempt_in_editor(
 }
 
 pub async fn get_task_attempt_branch_status(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<BranchStatus>>, StatusCode> {
@@ -652,10 +852,12 @@ pub async fn get_task_attempt_branch_status(
 
 #[axum::debug_handler]
 pub async fn rebase_task_attempt(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
     request_body: Option<Json<RebaseTaskAttemptRequest>>,
-) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+) -> Result<ResponseJson<ApiResponse<ConflictReport>>, StatusCode> {
     // Verify task attempt exists and belongs to the correct task
     match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
         Ok(false) => return Err(StatusCode::NOT_FOUND),
@@ -668,21 +870,54 @@ pub async fn rebase_task_attempt(
 
     // Extract new base branch from request body if provided
     let new_base_branch = request_body.and_then(|body| body.new_base_branch.clone());
+    let event = NewAuditEvent::new("attempt.rebase")
+        .project(project_id)
+        .task(task_id)
+        .attempt(attempt_id)
+        .after(&serde_json::json!({ "new_base_branch": new_base_branch }));
 
-    match TaskAttempt::rebase_attempt(
+    let result = TaskAttempt::rebase_attempt(
         &app_state.db_pool,
         attempt_id,
         task_id,
         project_id,
         new_base_branch,
     )
-    .await
-    {
+    .await;
+    if matches!(
+        result,
+        Ok(_)
+            | Err(TaskAttemptError::GitService(
+                GitServiceError::ConflictingFiles(_)
+            ))
+    ) {
+        audit.record(event).await;
+    }
+
+    match result {
         Ok(_new_base_commit) => Ok(ResponseJson(ApiResponse {
             success: true,
             data: None,
             message: Some("Branch rebased successfully".to_string()),
         })),
+        Err(TaskAttemptError::GitService(GitServiceError::ConflictingFiles(files))) => {
+            // The rebase is left in progress so the conflicts can be resolved
+            let report =
+                TaskAttempt::get_conflicts(&app_state.db_pool, attempt_id, task_id, project_id)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("Failed to get conflicts for {}: {}", attempt_id, e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })?;
+            Ok(ResponseJson(ApiResponse {
+                success: false,
+                data: report,
+                message: Some(format!(
+                    "Rebase stopped on conflicts in {}",
+                    files.join(", ")
+                )),
+            }))
+        }
         Err(e) => {
             tracing::error!("Failed to rebase task attempt {}: {}", attempt_id, e);
             Ok(ResponseJson(ApiResponse {
@@ -694,7 +929,283 @@ pub async fn rebase_task_attempt(
     }
 }
 
+pub async fn get_task_attempt_conflicts(
+    _: ProjectAccess<role::Viewer>,
+    Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<Option<ConflictReport>>>, StatusCode> {
+    // Verify task attempt exists and belongs to the correct task
+    match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task attempt existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    match TaskAttempt::get_conflicts(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(report) => Ok(ResponseJson(ApiResponse {
+            success: true,
+            data: Some(report),
+            message: None,
+        })),
+        Err(e) => {
+            tracing::error!(
+                "Failed to get conflicts for task attempt {}: {}",
+                attempt_id,
+                e
+            );
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Response of the conflict endpoints: the conflicts left, none once the operation completed
+fn conflict_response(
+    result: Result<Option<ConflictReport>, TaskAttemptError>,
+    attempt_id: Uuid,
+    action: &str,
+) -> ResponseJson<ApiResponse<ConflictReport>> {
+    match result {
+        Ok(None) => ResponseJson(ApiResponse {
+            success: true,
+            data: None,
+            message: Some("Branch is up to date with its base branch".to_string()),
+        }),
+        Ok(Some(report)) => {
+            let message = format!("{} conflicting file(s) left to resolve", report.files.len());
+            ResponseJson(ApiResponse {
+                success: true,
+                data: Some(report),
+                message: Some(message),
+            })
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to {} for task attempt {}: {}",
+                action,
+                attempt_id,
+                e
+            );
+            ResponseJson(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(format!("Failed to {}: {}", action, e)),
+            })
+        }
+    }
+}
+
+pub async fn start_task_attempt_conflict_resolution(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
+    Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+    Json(payload): Json<StartConflictResolution>,
+) -> Result<ResponseJson<ApiResponse<ConflictReport>>, StatusCode> {
+    // Verify task attempt exists and belongs to the correct task
+    match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task attempt existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    let result = TaskAttempt::start_conflict_resolution(
+        &app_state.db_pool,
+        attempt_id,
+        task_id,
+        project_id,
+        payload.operation,
+    )
+    .await;
+    if result.is_ok() {
+        audit
+            .record(
+                NewAuditEvent::new("attempt.conflicts_start")
+                    .project(project_id)
+                    .task(task_id)
+                    .attempt(attempt_id)
+                    .after(&payload.operation),
+            )
+            .await;
+    }
+    Ok(conflict_response(result, attempt_id, "update branch"))
+}
+
+pub async fn resolve_task_attempt_conflicts(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
+    Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+    Json(payload): Json<ResolveConflicts>,
+) -> Result<ResponseJson<ApiResponse<ConflictReport>>, StatusCode> {
+    // Verify task attempt exists and belongs to the correct task
+    match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task attempt existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    let result = TaskAttempt::resolve_conflicts(
+        &app_state.db_pool,
+        attempt_id,
+        task_id,
+        project_id,
+        &payload.resolutions,
+    )
+    .await;
+    if result.is_ok() {
+        // Only the paths, the resolved contents are in the attempt's branch
+        let paths: Vec<&str> = payload
+            .resolutions
+            .iter()
+            .map(|resolution| resolution.path.as_str())
+            .collect();
+        audit
+            .record(
+                NewAuditEvent::new("attempt.conflicts_resolve")
+                    .project(project_id)
+                    .task(task_id)
+                    .attempt(attempt_id)
+                    .after(&paths),
+            )
+            .await;
+    }
+    Ok(conflict_response(result, attempt_id, "resolve conflicts"))
+}
+
+pub async fn abort_task_attempt_conflicts(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
+    Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+    // Verify task attempt exists and belongs to the correct task
+    match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task attempt existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    match TaskAttempt::abort_conflicts(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(()) => {
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.conflicts_abort")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(attempt_id),
+                )
+                .await;
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: None,
+                message: Some("Conflict resolution aborted".to_string()),
+            }))
+        }
+        Err(e) => {
+            tracing::error!("Failed to abort conflicts for {}: {}", attempt_id, e);
+            Ok(ResponseJson(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(format!("Failed to abort: {}", e)),
+            }))
+        }
+    }
+}
+
+/// Hand the conflicts in progress to the attempt's coding agent as a follow-up. The rebase or
+/// merge is continued once the agent finishes without leaving conflict markers behind.
+pub async fn resolve_task_attempt_conflicts_with_agent(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
+    Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<FollowUpResponse>>, StatusCode> {
+    // Verify task attempt exists and belongs to the correct task
+    match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task attempt existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    let report =
+        match TaskAttempt::get_conflicts(&app_state.db_pool, attempt_id, task_id, project_id).await
+        {
+            Ok(Some(report)) if !report.files.is_empty() => report,
+            Ok(_) => {
+                return Ok(ResponseJson(ApiResponse {
+                    success: false,
+                    data: None,
+                    message: Some("No conflicts to resolve".to_string()),
+                }))
+            }
+            Err(e) => {
+                tracing::error!("Failed to get conflicts for {}: {}", attempt_id, e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
+
+    match TaskAttempt::start_followup_execution(
+        &app_state.db_pool,
+        &app_state,
+        attempt_id,
+        task_id,
+        project_id,
+        &report.agent_prompt(),
+    )
+    .await
+    {
+        Ok(actual_attempt_id) => {
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.conflicts_agent")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(actual_attempt_id)
+                        .after(&serde_json::json!({
+                            "operation": report.operation,
+                            "paths": report.files.iter().map(|file| &file.path).collect::<Vec<_>>(),
+                        })),
+                )
+                .await;
+            let message = "Coding agent started resolving the conflicts".to_string();
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: Some(FollowUpResponse {
+                    message: message.clone(),
+                    actual_attempt_id,
+                    created_new_attempt: actual_attempt_id != attempt_id,
+                }),
+                message: Some(message),
+            }))
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to start conflict resolution follow-up for task attempt {}: {}",
+                attempt_id,
+                e
+            );
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 pub async fn get_task_attempt_execution_processes(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<Vec<ExecutionProcessSummary>>>, StatusCode> {
@@ -726,7 +1237,42 @@ pub async fn get_task_attempt_execution_processes(
     }
 }
 
+/// Queued coding agent executions of an attempt, with their position in the global queue
+pub async fn get_task_attempt_queue(
+    _: ProjectAccess<role::Viewer>,
+    Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<Vec<QueuedExecution>>>, StatusCode> {
+    // Verify task attempt exists and belongs to the correct task
+    match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task attempt existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    match ExecutionProcess::find_queued(&app_state.db_pool).await {
+        Ok(queued) => Ok(ResponseJson(ApiResponse {
+            success: true,
+            data: Some(
+                queued
+                    .into_iter()
+                    .filter(|q| q.task_attempt_id == attempt_id)
+                    .collect(),
+            ),
+            message: None,
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch execution queue: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 pub async fn get_execution_process(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, process_id)): Path<(Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, StatusCode> {
@@ -768,6 +1314,8 @@ pub async fn get_execution_process(
 
 #[axum::debug_handler]
 pub async fn stop_all_execution_processes(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
@@ -798,8 +1346,20 @@ pub async fn stop_all_execution_processes(
     let mut stopped_count = 0;
     let mut errors = Vec::new();
 
-    // Stop all running processes
+    // Stop all running processes and drop queued ones from the queue
     for process in processes {
+        if process.status == ExecutionProcessStatus::Queued {
+            match ExecutionProcess::cancel_queued(&app_state.db_pool, process.id).await {
+                Ok(true) => stopped_count += 1,
+                Ok(false) => {} // Started or finished in the meantime
+                Err(e) => {
+                    tracing::error!("Failed to cancel queued process {}: {}", process.id, e);
+                    errors.push(format!("Failed to cancel queued process {}", process.id));
+                }
+            }
+            continue;
+        }
+
         match app_state.stop_running_execution_by_id(process.id).await {
             Ok(true) => {
                 stopped_count += 1;
@@ -829,6 +1389,18 @@ pub async fn stop_all_execution_processes(
         }
     }
 
+    if stopped_count > 0 {
+        audit
+            .record(
+                NewAuditEvent::new("attempt.stop")
+                    .project(project_id)
+                    .task(task_id)
+                    .attempt(attempt_id)
+                    .after(&serde_json::json!({ "stopped_count": stopped_count })),
+            )
+            .await;
+    }
+
     if !errors.is_empty() {
         return Ok(ResponseJson(ApiResponse {
             success: false,
@@ -861,6 +1433,8 @@ pub async fn stop_all_execution_processes(
 
 #[axum::debug_handler]
 pub async fn stop_execution_process(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id, process_id)): Path<(Uuid, Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
@@ -875,7 +1449,7 @@ pub async fn stop_execution_process(
     }
 
     // Verify execution process exists and belongs to the task attempt
-    match ExecutionProcess::find_by_id(&app_state.db_pool, process_id).await {
+    let process = match ExecutionProcess::find_summary_by_id(&app_state.db_pool, process_id).await {
         Ok(Some(process)) if process.task_attempt_id == attempt_id => process,
         Ok(Some(_)) => return Err(StatusCode::NOT_FOUND), // Process exists but wrong attempt
         Ok(None) => return Err(StatusCode::NOT_FOUND),
@@ -885,6 +1459,32 @@ pub async fn stop_execution_process(
         }
     };
 
+    let event = NewAuditEvent::new("attempt.process_stop")
+        .project(project_id)
+        .task(task_id)
+        .attempt(attempt_id)
+        .target(process_id)
+        .before(&process.status);
+
+    // A queued process only has to leave the queue
+    if process.status == ExecutionProcessStatus::Queued {
+        match ExecutionProcess::cancel_queued(&app_state.db_pool, process_id).await {
+            Ok(true) => {
+                audit.record(event).await;
+                return Ok(ResponseJson(ApiResponse {
+                    success: true,
+                    data: None,
+                    message: Some(format!("Queued execution process {} cancelled", process_id)),
+                }));
+            }
+            Ok(false) => {} // Started in the meantime, stop it below
+            Err(e) => {
+                tracing::error!("Failed to cancel queued process {}: {}", process_id, e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+
     // Stop the specific execution process
     let stopped = match app_state.stop_running_execution_by_id(process_id).await {
         Ok(stopped) => stopped,
@@ -916,6 +1516,7 @@ pub async fn stop_execution_process(
     }
 
     // Process stopped successfully
+    audit.record(event).await;
 
     Ok(ResponseJson(ApiResponse {
         success: true,
@@ -934,6 +1535,8 @@ pub struct DeleteFileQuery {
 
 #[axum::debug_handler]
 pub async fn delete_task_attempt_file(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     Query(query): Query<DeleteFileQuery>,
     State(app_state): State<AppState>,
@@ -957,11 +1560,23 @@ pub async fn delete_task_attempt_file(
     )
     .await
     {
-        Ok(_commit_id) => Ok(ResponseJson(ApiResponse {
-            success: true,
-            data: None,
-            message: Some(format!("File '{}' deleted successfully", query.file_path)),
-        })),
+        Ok(commit_id) => {
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.delete_file")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(attempt_id)
+                        .target(&query.file_path)
+                        .after(&serde_json::json!({ "commit": commit_id })),
+                )
+                .await;
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: None,
+                message: Some(format!("File '{}' deleted successfully", query.file_path)),
+            }))
+        }
         Err(e) => {
             tracing::error!(
                 "Failed to delete file '{}' from task attempt {}: {}",
@@ -979,6 +1594,8 @@ pub async fn delete_task_attempt_file(
 }
 
 pub async fn create_followup_attempt(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
     Json(payload): Json<CreateFollowUpAttempt>,
@@ -1006,6 +1623,15 @@ pub async fn create_followup_attempt(
     .await
     {
         Ok(actual_attempt_id) => {
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.follow_up")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(actual_attempt_id)
+                        .after(&serde_json::json!({ "prompt": payload.prompt })),
+                )
+                .await;
             let created_new_attempt = actual_attempt_id != attempt_id;
             let message = if created_new_attempt {
                 format!(
@@ -1038,6 +1664,8 @@ pub async fn create_followup_attempt(
 }
 
 pub async fn start_dev_server(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
@@ -1106,14 +1734,105 @@ pub async fn start_dev_server(
     )
     .await
     {
-        Ok(_) => Ok(ResponseJson(ApiResponse {
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.dev_server_start")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(attempt_id),
+                )
+                .await;
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: None,
+                message: Some("Dev server started successfully".to_string()),
+            }))
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to start dev server for task attempt {}: {}",
+                attempt_id,
+                e
+            );
+            Ok(ResponseJson(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(e.to_string()),
+            }))
+        }
+    }
+}
+
+pub async fn get_task_attempt_checks(
+    _: ProjectAccess<role::Viewer>,
+    Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<Vec<CheckResult>>>, StatusCode> {
+    match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task attempt existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    match ProjectCheck::find_results_for_attempt(&app_state.db_pool, project_id, attempt_id).await {
+        Ok(results) => Ok(ResponseJson(ApiResponse {
             success: true,
-            data: None,
-            message: Some("Dev server started successfully".to_string()),
+            data: Some(results),
+            message: None,
         })),
+        Err(e) => {
+            tracing::error!("Failed to load check results for {}: {}", attempt_id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn run_task_attempt_checks(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
+    Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+    match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task attempt existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    match TaskAttempt::start_checks(
+        &app_state.db_pool,
+        &app_state,
+        attempt_id,
+        task_id,
+        project_id,
+    )
+    .await
+    {
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("attempt.run_checks")
+                        .project(project_id)
+                        .task(task_id)
+                        .attempt(attempt_id),
+                )
+                .await;
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: None,
+                message: Some("Checks started successfully".to_string()),
+            }))
+        }
         Err(e) => {
             tracing::error!(
-                "Failed to start dev server for task attempt {}: {}",
+                "Failed to start checks for task attempt {}: {}",
                 attempt_id,
                 e
             );
@@ -1127,6 +1846,7 @@ pub async fn start_dev_server(
 }
 
 pub async fn get_task_attempt_execution_state(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttemptState>>, StatusCode> {
@@ -1237,8 +1957,11 @@ async fn find_plan_content_with_context(
 }
 
 pub async fn approve_plan(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
 ) -> Result<ResponseJson<ApiResponse<FollowUpResponse>>, StatusCode> {
     // Verify task attempt exists and belongs to the correct task
     match TaskAttempt::exists_for_task(&app_state.db_pool, attempt_id, task_id, project_id).await {
@@ -1270,7 +1993,14 @@ pub async fn approve_plan(
         parent_task_attempt: Some(attempt_id),
     };
 
-    let new_task = match Task::create(&app_state.db_pool, &create_task_data, new_task_id).await {
+    let new_task = match Task::create(
+        &app_state.db_pool,
+        &create_task_data,
+        new_task_id,
+        current_user_id(&user),
+    )
+    .await
+    {
         Ok(task) => task,
         Err(e) => {
             tracing::error!("Failed to create new task: {}", e);
@@ -1291,6 +2021,17 @@ pub async fn approve_plan(
         );
     }
 
+    audit
+        .record(
+            NewAuditEvent::new("attempt.approve_plan")
+                .project(project_id)
+                .task(task_id)
+                .attempt(attempt_id)
+                .before(&serde_json::json!({ "status": current_task.status }))
+                .after(&new_task),
+        )
+        .await;
+
     Ok(ResponseJson(ApiResponse {
         success: true,
         data: Some(FollowUpResponse {
@@ -1303,6 +2044,7 @@ pub async fn approve_plan(
 }
 
 pub async fn get_task_attempt_details(
+    _: ProjectAccess<role::Viewer>,
     Path(attempt_id): Path<Uuid>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, StatusCode> {
@@ -1321,6 +2063,7 @@ pub async fn get_task_attempt_details(
 }
 
 pub async fn get_task_attempt_children(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, task_id, attempt_id)): Path<(Uuid, Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, StatusCode> {
@@ -1359,6 +2102,10 @@ pub fn task_attempts_router() -> Router<AppState> {
             "/projects/:project_id/tasks/:task_id/attempts",
             get(get_task_attempts).post(create_task_attempt),
         )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/compare",
+            get(compare_task_attempts),
+        )
 
 
         .route(
@@ -1377,6 +2124,34 @@ pub fn task_attempts_router() -> Router<AppState> {
             "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/rebase",
             post(rebase_task_attempt),
         )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/checks",
+            get(get_task_attempt_checks),
+        )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/checks/run",
+            post(run_task_attempt_checks),
+        )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/conflicts",
+            get(get_task_attempt_conflicts),
+        )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/conflicts/start",
+            post(start_task_attempt_conflict_resolution),
+        )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/conflicts/resolve",
+            post(resolve_task_attempt_conflicts),
+        )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/conflicts/abort",
+            post(abort_task_attempt_conflicts),
+        )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/conflicts/agent",
+            post(resolve_task_attempt_conflicts_with_agent),
+        )
         .route(
             "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/open-editor",
             post(open_task_attempt_in_editor),
@@ -1387,12 +2162,16 @@ pub fn task_attempts_router() -> Router<AppState> {
         )
         .route(
             "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/create-pr",
-            post(create_github_pr),
+            post(create_pr),
         )
         .route(
             "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/execution-processes",
             get(get_task_attempt_execution_processes),
         )
+        .route(
+            "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/queue",
+            get(get_task_attempt_queue),
+        )
         .route(
             "/projects/:project_id/tasks/:task_id/attempts/:attempt_id/stop",
             post(stop_all_execution_processes),
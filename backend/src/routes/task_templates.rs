@@ -3,7 +3,7 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use uuid::Uuid;
 
@@ -11,10 +11,57 @@ use crate::{
     app_state::AppState,
     models::{
         api_response::ApiResponse,
+        audit_event::NewAuditEvent,
+        project_member::ProjectRole,
         task_template::{CreateTaskTemplate, TaskTemplate, UpdateTaskTemplate},
     },
+    routes::{
+        audit::Auditor,
+        auth::{authorize_project, require_admin, role, CurrentUser, ProjectAccess},
+    },
 };
 
+/// Check that the user can change templates of `project_id`, global ones being shared by
+/// every project and so left to admins
+async fn authorize_template_change(
+    state: &AppState,
+    user: &Option<Extension<CurrentUser>>,
+    project_id: Option<Uuid>,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    match project_id {
+        Some(project_id) => {
+            let user = user.as_ref().map(|Extension(CurrentUser(user))| user);
+            authorize_project(state, user, project_id, ProjectRole::Contributor).await
+        }
+        None => require_admin(user, "change global templates"),
+    }
+}
+
+/// Check that the user can change an existing template, returning it
+async fn authorize_template_id_change(
+    state: &AppState,
+    user: &Option<Extension<CurrentUser>>,
+    template_id: Uuid,
+) -> Result<TaskTemplate, (StatusCode, Json<ApiResponse<()>>)> {
+    match TaskTemplate::find_by_id(&state.db_pool, template_id).await {
+        Ok(Some(template)) => {
+            authorize_template_change(state, user, template.project_id).await?;
+            Ok(template)
+        }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Template not found")),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to fetch template: {}",
+                e
+            ))),
+        )),
+    }
+}
+
 pub async fn list_templates(
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
@@ -31,6 +78,7 @@ pub async fn list_templates(
 }
 
 pub async fn list_project_templates(
+    _: ProjectAccess<role::Viewer>,
     State(state): State<AppState>,
     Path(project_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
@@ -82,11 +130,26 @@ pub async fn get_template(
 }
 
 pub async fn create_template(
+    audit: Auditor,
     State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Json(payload): Json<CreateTaskTemplate>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    authorize_template_change(&state, &user, payload.project_id).await?;
     match TaskTemplate::create(&state.db_pool, &payload).await {
-        Ok(template) => Ok((StatusCode::CREATED, Json(ApiResponse::success(template)))),
+        Ok(template) => {
+            audit
+                .record(
+                    NewAuditEvent {
+                        project_id: template.project_id,
+                        ..NewAuditEvent::new("template.create")
+                    }
+                    .target(template.id)
+                    .after(&template),
+                )
+                .await;
+            Ok((StatusCode::CREATED, Json(ApiResponse::success(template))))
+        }
         Err(e) => {
             if e.to_string().contains("UNIQUE constraint failed") {
                 Err((
@@ -109,12 +172,28 @@ pub async fn create_template(
 }
 
 pub async fn update_template(
+    audit: Auditor,
     State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Path(template_id): Path<Uuid>,
     Json(payload): Json<UpdateTaskTemplate>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let before = authorize_template_id_change(&state, &user, template_id).await?;
     match TaskTemplate::update(&state.db_pool, template_id, &payload).await {
-        Ok(template) => Ok(Json(ApiResponse::success(template))),
+        Ok(template) => {
+            audit
+                .record(
+                    NewAuditEvent {
+                        project_id: template.project_id,
+                        ..NewAuditEvent::new("template.update")
+                    }
+                    .target(template_id)
+                    .before(&before)
+                    .after(&template),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(template)))
+        }
         Err(e) => {
             if matches!(e, sqlx::Error::RowNotFound) {
                 Err((
@@ -142,15 +221,30 @@ pub async fn update_template(
 }
 
 pub async fn delete_template(
+    audit: Auditor,
     State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Path(template_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let before = authorize_template_id_change(&state, &user, template_id).await?;
     match TaskTemplate::delete(&state.db_pool, template_id).await {
         Ok(0) => Err((
             StatusCode::NOT_FOUND,
             Json(ApiResponse::error("Template not found")),
         )),
-        Ok(_) => Ok(Json(ApiResponse::success(()))),
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent {
+                        project_id: before.project_id,
+                        ..NewAuditEvent::new("template.delete")
+                    }
+                    .target(template_id)
+                    .before(&before),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(&format!(
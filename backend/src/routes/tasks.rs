@@ -3,7 +3,7 @@ use axum::{
     http::StatusCode,
     response::Json as ResponseJson,
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use uuid::Uuid;
 
@@ -11,14 +11,25 @@ use crate::{
     app_state::AppState,
     execution_monitor,
     models::{
+        audit_event::NewAuditEvent,
         project::Project,
         task::{CreateTask, CreateTaskAndStart, Task, TaskWithAttemptStatus, UpdateTask},
         task_attempt::{CreateTaskAttempt, TaskAttempt},
+        task_dependency::{
+            would_create_cycle, CreateTaskDependency, TaskDependencies, TaskDependency,
+        },
+        webhook::WebhookEvent,
         ApiResponse,
     },
+    routes::{
+        audit::Auditor,
+        auth::{current_user_id, role, CurrentUser, ProjectAccess},
+    },
+    services::WebhookService,
 };
 
 pub async fn get_project_tasks(
+    _: ProjectAccess<role::Viewer>,
     Path(project_id): Path<Uuid>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, StatusCode> {
@@ -36,6 +47,7 @@ pub async fn get_project_tasks(
 }
 
 pub async fn get_task(
+    _: ProjectAccess<role::Viewer>,
     Path((project_id, task_id)): Path<(Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, StatusCode> {
@@ -59,8 +71,11 @@ pub async fn get_task(
 }
 
 pub async fn create_task(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path(project_id): Path<Uuid>,
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Json(mut payload): Json<CreateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, StatusCode> {
     let id = Uuid::new_v4();
@@ -84,7 +99,7 @@ pub async fn create_task(
         project_id
     );
 
-    match Task::create(&app_state.db_pool, &payload, id).await {
+    match Task::create(&app_state.db_pool, &payload, id, current_user_id(&user)).await {
         Ok(task) => {
             // Track task creation event
             app_state
@@ -98,6 +113,24 @@ pub async fn create_task(
                 )
                 .await;
 
+            WebhookService::dispatch(
+                &app_state.db_pool,
+                WebhookEvent::TaskCreated,
+                project_id,
+                task.id,
+                None,
+                serde_json::json!({ "task": task }),
+            )
+            .await;
+            audit
+                .record(
+                    NewAuditEvent::new("task.create")
+                        .project(project_id)
+                        .task(task.id)
+                        .after(&task),
+                )
+                .await;
+
             Ok(ResponseJson(ApiResponse {
                 success: true,
                 data: Some(task),
@@ -112,8 +145,11 @@ pub async fn create_task(
 }
 
 pub async fn create_task_and_start(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path(project_id): Path<Uuid>,
     State(app_state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
     Json(mut payload): Json<CreateTaskAndStart>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, StatusCode> {
     let task_id = Uuid::new_v4();
@@ -144,7 +180,9 @@ pub async fn create_task_and_start(
         description: payload.description.clone(),
         parent_task_attempt: payload.parent_task_attempt,
     };
-    let task = match Task::create(&app_state.db_pool, &create_task_payload, task_id).await {
+    let user_id = current_user_id(&user);
+    let task = match Task::create(&app_state.db_pool, &create_task_payload, task_id, user_id).await
+    {
         Ok(task) => task,
         Err(e) => {
             tracing::error!("Failed to create task: {}", e);
@@ -152,72 +190,117 @@ pub async fn create_task_and_start(
         }
     };
 
-    // Create task attempt
-    let executor_string = payload.executor.as_ref().map(|exec| exec.to_string());
-    let attempt_payload = CreateTaskAttempt {
-        executor: executor_string.clone(),
-        base_branch: None, // Not supported in task creation endpoint, only in task attempts
+    // One attempt per requested executor, or a single attempt with the default executor
+    let attempt_payloads: Vec<CreateTaskAttempt> = if payload.executors.is_empty() {
+        vec![CreateTaskAttempt {
+            executor: payload.executor.as_ref().map(|exec| exec.to_string()),
+            base_branch: None, // Not supported in task creation endpoint, only in task attempts
+            executor_profile_id: payload.executor_profile_id,
+        }]
+    } else {
+        payload
+            .executors
+            .iter()
+            .map(|exec| CreateTaskAttempt {
+                executor: Some(exec.to_string()),
+                base_branch: None,
+                executor_profile_id: None,
+            })
+            .collect()
     };
 
-    match TaskAttempt::create(&app_state.db_pool, &attempt_payload, task_id).await {
-        Ok(attempt) => {
-            app_state
-                .track_analytics_event(
-                    "task_created",
-                    Some(serde_json::json!({
-                        "task_id": task.id.to_string(),
-                        "project_id": project_id.to_string(),
-                        "has_description": task.description.is_some(),
-                    })),
-                )
-                .await;
+    app_state
+        .track_analytics_event(
+            "task_created",
+            Some(serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project_id.to_string(),
+                "has_description": task.description.is_some(),
+            })),
+        )
+        .await;
 
-            app_state
-                .track_analytics_event(
-                    "task_attempt_started",
-                    Some(serde_json::json!({
-                        "task_id": task.id.to_string(),
-                        "executor_type": executor_string.as_deref().unwrap_or("default"),
-                        "attempt_id": attempt.id.to_string(),
-                    })),
-                )
-                .await;
+    WebhookService::dispatch(
+        &app_state.db_pool,
+        WebhookEvent::TaskCreated,
+        project_id,
+        task.id,
+        None,
+        serde_json::json!({ "task": task }),
+    )
+    .await;
+    audit
+        .record(
+            NewAuditEvent::new("task.create")
+                .project(project_id)
+                .task(task.id)
+                .after(&task),
+        )
+        .await;
 
-            // Start execution asynchronously (don't block the response)
-            let app_state_clone = app_state.clone();
-            let attempt_id = attempt.id;
-            tokio::spawn(async move {
-                if let Err(e) = TaskAttempt::start_execution(
-                    &app_state_clone.db_pool,
-                    &app_state_clone,
-                    attempt_id,
-                    task_id,
-                    project_id,
-                )
-                .await
-                {
-                    tracing::error!(
-                        "Failed to start execution for task attempt {}: {}",
-                        attempt_id,
-                        e
-                    );
+    for attempt_payload in attempt_payloads {
+        let attempt =
+            match TaskAttempt::create(&app_state.db_pool, &attempt_payload, task_id, user_id).await
+            {
+                Ok(attempt) => attempt,
+                Err(e) => {
+                    tracing::error!("Failed to create task attempt: {}", e);
+                    return Err(StatusCode::INTERNAL_SERVER_ERROR);
                 }
-            });
+            };
 
-            Ok(ResponseJson(ApiResponse {
-                success: true,
-                data: Some(task),
-                message: Some("Task created and started successfully".to_string()),
-            }))
-        }
-        Err(e) => {
-            tracing::error!("Failed to create task attempt: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        app_state
+            .track_analytics_event(
+                "task_attempt_started",
+                Some(serde_json::json!({
+                    "task_id": task.id.to_string(),
+                    "executor_type": attempt_payload.executor.as_deref().unwrap_or("default"),
+                    "attempt_id": attempt.id.to_string(),
+                })),
+            )
+            .await;
+        audit
+            .record(
+                NewAuditEvent::new("attempt.create")
+                    .project(project_id)
+                    .task(task_id)
+                    .attempt(attempt.id)
+                    .after(&attempt),
+            )
+            .await;
+
+        // Start execution asynchronously (don't block the response)
+        let app_state_clone = app_state.clone();
+        let attempt_id = attempt.id;
+        tokio::spawn(async move {
+            if let Err(e) = TaskAttempt::start_execution(
+                &app_state_clone.db_pool,
+                &app_state_clone,
+                attempt_id,
+                task_id,
+                project_id,
+            )
+            .await
+            {
+                tracing::error!(
+                    "Failed to start execution for task attempt {}: {}",
+                    attempt_id,
+                    e
+                );
+            }
+        });
     }
+
+    Ok(ResponseJson(ApiResponse {
+        success: true,
+        data: Some(task),
+        message: Some("Task created and started successfully".to_string()),
+    }))
 }
 
 pub async fn update_task(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id)): Path<(Uuid, Uuid)>,
     State(app_state): State<AppState>,
     Json(payload): Json<UpdateTask>,
@@ -233,6 +316,11 @@ pub async fn update_task(
             }
         };
 
+    let event = NewAuditEvent::new("task.update")
+        .project(project_id)
+        .task(task_id)
+        .before(&existing_task);
+
     // Use existing values if not provided in update
     let title = payload.title.unwrap_or(existing_task.title);
     let description = payload.description.or(existing_task.description);
@@ -252,11 +340,15 @@ pub async fn update_task(
     )
     .await
     {
-        Ok(task) => Ok(ResponseJson(ApiResponse {
-            success: true,
-            data: Some(task),
-            message: Some("Task updated successfully".to_string()),
-        })),
+        Ok(task) => {
+            audit.record(event.after(&task)).await;
+
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: Some(task),
+                message: Some("Task updated successfully".to_string()),
+            }))
+        }
         Err(e) => {
             tracing::error!("Failed to update task: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -265,18 +357,21 @@ pub async fn update_task(
 }
 
 pub async fn delete_task(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
     Path((project_id, task_id)): Path<(Uuid, Uuid)>,
     State(app_state): State<AppState>,
 ) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
     // Verify task exists in the specified project
-    match Task::find_by_id_and_project_id(&app_state.db_pool, task_id, project_id).await {
-        Ok(Some(_)) => {} // Task exists, proceed
-        Ok(None) => return Err(StatusCode::NOT_FOUND),
-        Err(e) => {
-            tracing::error!("Failed to check task existence: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    }
+    let existing_task =
+        match Task::find_by_id_and_project_id(&app_state.db_pool, task_id, project_id).await {
+            Ok(Some(task)) => task,
+            Ok(None) => return Err(StatusCode::NOT_FOUND),
+            Err(e) => {
+                tracing::error!("Failed to check task existence: {}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
 
     // Clean up all worktrees for this task before deletion
     if let Err(e) = execution_monitor::cleanup_task_worktrees(&app_state.db_pool, task_id).await {
@@ -320,6 +415,14 @@ pub async fn delete_task(
             if rows_affected == 0 {
                 Err(StatusCode::NOT_FOUND)
             } else {
+                audit
+                    .record(
+                        NewAuditEvent::new("task.delete")
+                            .project(project_id)
+                            .task(task_id)
+                            .before(&existing_task),
+                    )
+                    .await;
                 Ok(ResponseJson(ApiResponse {
                     success: true,
                     data: None,
@@ -334,8 +437,195 @@ pub async fn delete_task(
     }
 }
 
+pub async fn get_project_task_dependencies(
+    _: ProjectAccess<role::Viewer>,
+    Path(project_id): Path<Uuid>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskDependency>>>, StatusCode> {
+    match TaskDependency::find_by_project_id(&app_state.db_pool, project_id).await {
+        Ok(dependencies) => Ok(ResponseJson(ApiResponse {
+            success: true,
+            data: Some(dependencies),
+            message: None,
+        })),
+        Err(e) => {
+            tracing::error!(
+                "Failed to fetch task dependencies for project {}: {}",
+                project_id,
+                e
+            );
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn get_task_dependencies(
+    _: ProjectAccess<role::Viewer>,
+    Path((project_id, task_id)): Path<(Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, StatusCode> {
+    match Task::exists(&app_state.db_pool, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    let pool = &app_state.db_pool;
+    let result = async {
+        Ok::<_, sqlx::Error>(TaskDependencies {
+            blocked_by: TaskDependency::find_blockers(pool, task_id).await?,
+            blocking: TaskDependency::find_dependents(pool, task_id).await?,
+            is_blocked: TaskDependency::is_blocked(pool, task_id).await?,
+        })
+    }
+    .await;
+
+    match result {
+        Ok(dependencies) => Ok(ResponseJson(ApiResponse {
+            success: true,
+            data: Some(dependencies),
+            message: None,
+        })),
+        Err(e) => {
+            tracing::error!("Failed to fetch dependencies of task {}: {}", task_id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn create_task_dependency(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
+    Path((project_id, task_id)): Path<(Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+    Json(payload): Json<CreateTaskDependency>,
+) -> Result<ResponseJson<ApiResponse<TaskDependency>>, StatusCode> {
+    let blocked_by_task_id = payload.blocked_by_task_id;
+
+    match Task::exists(&app_state.db_pool, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    // Blockers must live in the same project
+    match Task::exists(&app_state.db_pool, blocked_by_task_id, project_id).await {
+        Ok(false) => {
+            return Ok(ResponseJson(ApiResponse {
+                success: false,
+                data: None,
+                message: Some("Blocking task not found in this project".to_string()),
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Failed to check task existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    let existing = match TaskDependency::find_by_project_id(&app_state.db_pool, project_id).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            tracing::error!(
+                "Failed to fetch task dependencies for project {}: {}",
+                project_id,
+                e
+            );
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    if existing
+        .iter()
+        .any(|d| d.task_id == task_id && d.blocked_by_task_id == blocked_by_task_id)
+    {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: Some("Task is already blocked by this task".to_string()),
+        }));
+    }
+
+    if would_create_cycle(&existing, task_id, blocked_by_task_id) {
+        return Ok(ResponseJson(ApiResponse {
+            success: false,
+            data: None,
+            message: Some("This dependency would create a cycle".to_string()),
+        }));
+    }
+
+    match TaskDependency::create(&app_state.db_pool, task_id, blocked_by_task_id).await {
+        Ok(dependency) => {
+            audit
+                .record(
+                    NewAuditEvent::new("task.dependency_add")
+                        .project(project_id)
+                        .task(task_id)
+                        .target(blocked_by_task_id)
+                        .after(&dependency),
+                )
+                .await;
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: Some(dependency),
+                message: Some("Task dependency added successfully".to_string()),
+            }))
+        }
+        Err(e) => {
+            tracing::error!("Failed to create task dependency: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn delete_task_dependency(
+    _: ProjectAccess<role::Contributor>,
+    audit: Auditor,
+    Path((project_id, task_id, blocked_by_task_id)): Path<(Uuid, Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+    match Task::exists(&app_state.db_pool, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    match TaskDependency::delete(&app_state.db_pool, task_id, blocked_by_task_id).await {
+        Ok(0) => Err(StatusCode::NOT_FOUND),
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("task.dependency_remove")
+                        .project(project_id)
+                        .task(task_id)
+                        .target(blocked_by_task_id),
+                )
+                .await;
+            Ok(ResponseJson(ApiResponse {
+                success: true,
+                data: None,
+                message: Some("Task dependency removed successfully".to_string()),
+            }))
+        }
+        Err(e) => {
+            tracing::error!("Failed to delete task dependency: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 pub fn tasks_router() -> Router<AppState> {
-    use axum::routing::post;
+    use axum::routing::{delete, post};
 
     Router::new()
         .route(
@@ -350,4 +640,16 @@ pub fn tasks_router() -> Router<AppState> {
             "/projects/:project_id/tasks/:task_id",
             get(get_task).put(update_task).delete(delete_task),
         )
+        .route(
+            "/projects/:project_id/task-dependencies",
+            get(get_project_task_dependencies),
+        )
+        .route(
+            "/projects/:project_id/tasks/:task_id/dependencies",
+            get(get_task_dependencies).post(create_task_dependency),
+        )
+        .route(
+            "/projects/:project_id/tasks/:task_id/dependencies/:blocked_by_task_id",
+            delete(delete_task_dependency),
+        )
 }
@@ -0,0 +1,125 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json as ResponseJson,
+    routing::get,
+    Router,
+};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        execution_process::ExecutionProcess,
+        execution_usage::{ExecutionUsage, UsageReport},
+        project::Project,
+        task::Task,
+        task_attempt::TaskAttempt,
+        ApiResponse,
+    },
+    routes::auth::{role, ProjectAccess},
+};
+
+/// Roll up the project's recorded usage, narrowed down to a task or execution process
+async fn usage_report(
+    app_state: &AppState,
+    project_id: Uuid,
+    task_id: Option<Uuid>,
+    process_id: Option<Uuid>,
+) -> Result<ResponseJson<ApiResponse<UsageReport>>, StatusCode> {
+    let rows = ExecutionUsage::find_rows(&app_state.db_pool, project_id, task_id, process_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch usage of project {}: {}", project_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let pricing = app_state.get_config().read().await.model_pricing.clone();
+
+    Ok(ResponseJson(ApiResponse {
+        success: true,
+        data: Some(UsageReport::from_rows(&rows, &pricing)),
+        message: None,
+    }))
+}
+
+pub async fn get_project_usage(
+    _: ProjectAccess<role::Viewer>,
+    Path(project_id): Path<Uuid>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<UsageReport>>, StatusCode> {
+    match Project::exists(&app_state.db_pool, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check project existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    usage_report(&app_state, project_id, None, None).await
+}
+
+pub async fn get_task_usage(
+    _: ProjectAccess<role::Viewer>,
+    Path((project_id, task_id)): Path<(Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<UsageReport>>, StatusCode> {
+    match Task::exists(&app_state.db_pool, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    usage_report(&app_state, project_id, Some(task_id), None).await
+}
+
+pub async fn get_execution_process_usage(
+    _: ProjectAccess<role::Viewer>,
+    Path((project_id, process_id)): Path<(Uuid, Uuid)>,
+    State(app_state): State<AppState>,
+) -> Result<ResponseJson<ApiResponse<UsageReport>>, StatusCode> {
+    let pool = &app_state.db_pool;
+    let process = match ExecutionProcess::find_summary_by_id(pool, process_id).await {
+        Ok(Some(process)) => process,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to fetch execution process {}: {}", process_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let task_id = match TaskAttempt::find_by_id(pool, process.task_attempt_id).await {
+        Ok(Some(attempt)) => attempt.task_id,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to fetch task attempt: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    // Verify the process belongs to a task of the project
+    match Task::exists(pool, task_id, project_id).await {
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to check task existence: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Ok(true) => {}
+    }
+
+    usage_report(&app_state, project_id, Some(task_id), Some(process_id)).await
+}
+
+pub fn usage_router() -> Router<AppState> {
+    Router::new()
+        .route("/projects/:project_id/usage", get(get_project_usage))
+        .route(
+            "/projects/:project_id/tasks/:task_id/usage",
+            get(get_task_usage),
+        )
+        .route(
+            "/projects/:project_id/execution-processes/:process_id/usage",
+            get(get_execution_process_usage),
+        )
+}
@@ -0,0 +1,260 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{delete, get, put},
+    Extension, Json, Router,
+};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        api_response::ApiResponse,
+        api_token::{ApiToken, CreateApiToken, CreatedApiToken, TokenKind},
+        audit_event::NewAuditEvent,
+        user::{ChangePassword, CreateUser, User},
+    },
+    routes::{
+        audit::Auditor,
+        auth::{hash_password, request_token, verify_password, CurrentUser},
+    },
+    services::AuthService,
+};
+
+type ApiError = (StatusCode, Json<ApiResponse<()>>);
+
+fn error(status: StatusCode, message: &str) -> ApiError {
+    (status, Json(ApiResponse::error(message)))
+}
+
+fn database_error(action: &str) -> impl Fn(sqlx::Error) -> ApiError + '_ {
+    move |e| {
+        error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to {}: {}", action, e),
+        )
+    }
+}
+
+/// The signed in user, these routes only making sense in multi-user mode
+fn signed_in(user: Option<Extension<CurrentUser>>) -> Result<User, ApiError> {
+    user.map(|Extension(CurrentUser(user))| user)
+        .ok_or_else(|| {
+            error(
+                StatusCode::BAD_REQUEST,
+                "Accounts are only used in multi-user mode",
+            )
+        })
+}
+
+fn admin(user: Option<Extension<CurrentUser>>) -> Result<User, ApiError> {
+    let user = signed_in(user)?;
+    if !user.is_admin {
+        return Err(error(StatusCode::FORBIDDEN, "Only admins can manage users"));
+    }
+    Ok(user)
+}
+
+pub async fn list_users(
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+) -> Result<impl IntoResponse, ApiError> {
+    signed_in(user)?;
+    let users = User::find_all(&state.db_pool)
+        .await
+        .map_err(database_error("fetch users"))?;
+    Ok(Json(ApiResponse::success(users)))
+}
+
+pub async fn create_user(
+    audit: Auditor,
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Json(payload): Json<CreateUser>,
+) -> Result<impl IntoResponse, ApiError> {
+    admin(user)?;
+    let username = payload.username.trim();
+    AuthService::validate_credentials(username, &payload.password)
+        .map_err(|e| error(StatusCode::BAD_REQUEST, &e))?;
+
+    let password_hash = hash_password(payload.password).await?;
+    match User::create(&state.db_pool, username, &password_hash, payload.is_admin).await {
+        Ok(user) => {
+            tracing::info!("Created user {}", user.username);
+            audit
+                .record(
+                    NewAuditEvent::new("user.create")
+                        .target(user.id)
+                        .after(&user),
+                )
+                .await;
+            Ok((StatusCode::CREATED, Json(ApiResponse::success(user))))
+        }
+        Err(sqlx::Error::Database(db_error)) if db_error.is_unique_violation() => Err(error(
+            StatusCode::CONFLICT,
+            &format!("The user name '{}' is taken", username),
+        )),
+        Err(e) => Err(database_error("create user")(e)),
+    }
+}
+
+pub async fn delete_user(
+    audit: Auditor,
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Path(user_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let current = admin(user)?;
+    if current.id == user_id {
+        return Err(error(
+            StatusCode::BAD_REQUEST,
+            "You can't delete your own account",
+        ));
+    }
+
+    let before = User::find_by_id(&state.db_pool, user_id)
+        .await
+        .ok()
+        .flatten();
+    match User::delete(&state.db_pool, user_id).await {
+        Ok(0) => Err(error(StatusCode::NOT_FOUND, "User not found")),
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("user.delete")
+                        .target(user_id)
+                        .before(&before),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
+        Err(e) => Err(database_error("delete user")(e)),
+    }
+}
+
+pub async fn get_me(user: Option<Extension<CurrentUser>>) -> Result<impl IntoResponse, ApiError> {
+    Ok(Json(ApiResponse::success(signed_in(user)?)))
+}
+
+/// PUT /users/me/password - also signs out the user's other sessions
+pub async fn change_password(
+    audit: Auditor,
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    headers: HeaderMap,
+    Json(payload): Json<ChangePassword>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = signed_in(user)?;
+    AuthService::validate_password(&payload.new_password)
+        .map_err(|e| error(StatusCode::BAD_REQUEST, &e))?;
+
+    let stored = User::password_hash(&state.db_pool, user.id)
+        .await
+        .map_err(database_error("change password"))?;
+    if !verify_password(payload.current_password, stored).await {
+        return Err(error(
+            StatusCode::FORBIDDEN,
+            "The current password is wrong",
+        ));
+    }
+
+    let password_hash = hash_password(payload.new_password).await?;
+    User::update_password(&state.db_pool, user.id, &password_hash)
+        .await
+        .map_err(database_error("change password"))?;
+    ApiToken::delete_sessions(&state.db_pool, user.id, request_token(&headers).as_deref())
+        .await
+        .map_err(database_error("sign out other sessions"))?;
+    audit
+        .record(NewAuditEvent::new("user.password_change").target(user.id))
+        .await;
+    Ok(Json(ApiResponse::success(())))
+}
+
+pub async fn list_tokens(
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = signed_in(user)?;
+    let tokens = ApiToken::find_by_user_id(&state.db_pool, user.id)
+        .await
+        .map_err(database_error("fetch API tokens"))?;
+    Ok(Json(ApiResponse::success(tokens)))
+}
+
+pub async fn create_token(
+    audit: Auditor,
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Json(payload): Json<CreateApiToken>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = signed_in(user)?;
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(error(StatusCode::BAD_REQUEST, "Name the token"));
+    }
+    if payload.expires_in_days == Some(0) {
+        return Err(error(
+            StatusCode::BAD_REQUEST,
+            "Tokens must be valid for at least a day",
+        ));
+    }
+
+    let secret = AuthService::generate_token().map_err(|_| {
+        error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to generate a token",
+        )
+    })?;
+    let token = ApiToken::create(
+        &state.db_pool,
+        user.id,
+        TokenKind::Api,
+        name,
+        &secret,
+        payload.expires_in_days,
+    )
+    .await
+    .map_err(database_error("create API token"))?;
+    audit
+        .record(
+            NewAuditEvent::new("token.create")
+                .target(token.id)
+                .after(&token),
+        )
+        .await;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success(CreatedApiToken { token, secret })),
+    ))
+}
+
+pub async fn delete_token(
+    audit: Auditor,
+    State(state): State<AppState>,
+    user: Option<Extension<CurrentUser>>,
+    Path(token_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user = signed_in(user)?;
+    match ApiToken::delete(&state.db_pool, token_id, user.id).await {
+        Ok(0) => Err(error(StatusCode::NOT_FOUND, "Token not found")),
+        Ok(_) => {
+            audit
+                .record(NewAuditEvent::new("token.delete").target(token_id))
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
+        Err(e) => Err(database_error("delete API token")(e)),
+    }
+}
+
+pub fn users_router() -> Router<AppState> {
+    Router::new()
+        .route("/users", get(list_users).post(create_user))
+        .route("/users/:user_id", delete(delete_user))
+        .route("/users/me", get(get_me))
+        .route("/users/me/password", put(change_password))
+        .route("/users/me/tokens", get(list_tokens).post(create_token))
+        .route("/users/me/tokens/:token_id", delete(delete_token))
+}
@@ -0,0 +1,187 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, put},
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    models::{
+        api_response::ApiResponse,
+        audit_event::NewAuditEvent,
+        project::Project,
+        webhook::{CreateWebhook, UpdateWebhook, Webhook},
+    },
+    routes::{
+        audit::Auditor,
+        auth::{role, ProjectAccess},
+    },
+};
+
+async fn ensure_project_exists(
+    state: &AppState,
+    project_id: Uuid,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    match Project::exists(&state.db_pool, project_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Project not found")),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to check project existence: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+fn validate_url(url: &str) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    Webhook::validate_url(url).map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiResponse::error(&e))))
+}
+
+pub async fn list_webhooks(
+    _: ProjectAccess<role::Maintainer>,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_project_exists(&state, project_id).await?;
+
+    match Webhook::find_by_project_id(&state.db_pool, project_id).await {
+        Ok(webhooks) => Ok(Json(ApiResponse::success(webhooks))),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to fetch webhooks: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub async fn create_webhook(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateWebhook>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    ensure_project_exists(&state, project_id).await?;
+    validate_url(&payload.url)?;
+
+    match Webhook::create(&state.db_pool, project_id, &payload).await {
+        Ok(webhook) => {
+            audit
+                .record(
+                    NewAuditEvent::new("webhook.create")
+                        .project(project_id)
+                        .target(webhook.id)
+                        .after(&webhook.without_secret()),
+                )
+                .await;
+            Ok((StatusCode::CREATED, Json(ApiResponse::success(webhook))))
+        }
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to create webhook: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub async fn update_webhook(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, webhook_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateWebhook>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    if let Some(url) = &payload.url {
+        validate_url(url)?;
+    }
+
+    let before = Webhook::find_by_id_and_project_id(&state.db_pool, webhook_id, project_id)
+        .await
+        .ok()
+        .flatten();
+    match Webhook::update(&state.db_pool, webhook_id, project_id, &payload).await {
+        Ok(webhook) => {
+            audit
+                .record(
+                    NewAuditEvent::new("webhook.update")
+                        .project(project_id)
+                        .target(webhook_id)
+                        .before(&before.map(|webhook| webhook.without_secret()))
+                        .after(&webhook.without_secret()),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(webhook)))
+        }
+        Err(sqlx::Error::RowNotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Webhook not found")),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to update webhook: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub async fn delete_webhook(
+    _: ProjectAccess<role::Maintainer>,
+    audit: Auditor,
+    State(state): State<AppState>,
+    Path((project_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
+    let before = Webhook::find_by_id_and_project_id(&state.db_pool, webhook_id, project_id)
+        .await
+        .ok()
+        .flatten();
+    match Webhook::delete(&state.db_pool, webhook_id, project_id).await {
+        Ok(0) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error("Webhook not found")),
+        )),
+        Ok(_) => {
+            audit
+                .record(
+                    NewAuditEvent::new("webhook.delete")
+                        .project(project_id)
+                        .target(webhook_id)
+                        .before(&before.map(|webhook| webhook.without_secret())),
+                )
+                .await;
+            Ok(Json(ApiResponse::success(())))
+        }
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(&format!(
+                "Failed to delete webhook: {}",
+                e
+            ))),
+        )),
+    }
+}
+
+pub fn webhooks_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/:project_id/webhooks",
+            get(list_webhooks).post(create_webhook),
+        )
+        .route(
+            "/projects/:project_id/webhooks/:webhook_id",
+            put(update_webhook).delete(delete_webhook),
+        )
+}
@@ -0,0 +1,170 @@
+use std::num::NonZeroU32;
+
+use ring::{
+    pbkdf2,
+    rand::{SecureRandom, SystemRandom},
+};
+use sha2::{Digest, Sha256};
+
+/// Prefix of stored password hashes, so the scheme can change without guessing
+const PASSWORD_SCHEME: &str = "pbkdf2-sha256";
+const PASSWORD_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const HASH_LEN: usize = 32;
+/// Prefix of API and session tokens, which makes them easy to spot in leaked logs
+const TOKEN_PREFIX: &str = "vk_";
+const TOKEN_LEN: usize = 32;
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// Password hashing and the secrets of API and session tokens, for multi-user mode
+pub struct AuthService;
+
+impl AuthService {
+    /// Hash `password` with PBKDF2-HMAC-SHA256 under a fresh random salt
+    pub fn hash_password(password: &str) -> Result<String, ring::error::Unspecified> {
+        Self::hash_password_with_iterations(password, PASSWORD_ITERATIONS)
+    }
+
+    pub fn hash_password_with_iterations(
+        password: &str,
+        iterations: u32,
+    ) -> Result<String, ring::error::Unspecified> {
+        let iterations = NonZeroU32::new(iterations).ok_or(ring::error::Unspecified)?;
+        let mut salt = [0u8; SALT_LEN];
+        SystemRandom::new().fill(&mut salt)?;
+
+        let mut hash = [0u8; HASH_LEN];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            &salt,
+            password.as_bytes(),
+            &mut hash,
+        );
+        Ok(format!(
+            "{}${}${}${}",
+            PASSWORD_SCHEME,
+            iterations,
+            hex::encode(salt),
+            hex::encode(hash)
+        ))
+    }
+
+    /// Whether `password` matches a hash produced by `hash_password`
+    pub fn verify_password(password: &str, stored: &str) -> bool {
+        let mut parts = stored.split('$');
+        let (Some(PASSWORD_SCHEME), Some(iterations), Some(salt), Some(hash), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return false;
+        };
+        let (Some(iterations), Ok(salt), Ok(hash)) = (
+            iterations.parse().ok().and_then(NonZeroU32::new),
+            hex::decode(salt),
+            hex::decode(hash),
+        ) else {
+            return false;
+        };
+        pbkdf2::verify(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            &salt,
+            password.as_bytes(),
+            &hash,
+        )
+        .is_ok()
+    }
+
+    /// A new random token secret, shown to its owner once and stored as `hash_token`
+    pub fn generate_token() -> Result<String, ring::error::Unspecified> {
+        let mut bytes = [0u8; TOKEN_LEN];
+        SystemRandom::new().fill(&mut bytes)?;
+        Ok(format!("{}{}", TOKEN_PREFIX, hex::encode(bytes)))
+    }
+
+    /// Tokens are long and random, so a plain SHA-256 is enough to store them
+    pub fn hash_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Check a new user name and password, returning the error to show
+    pub fn validate_credentials(username: &str, password: &str) -> Result<(), String> {
+        if username.is_empty()
+            || username.len() > 64
+            || !username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(
+                "User names are 1 to 64 letters, digits, dots, dashes or underscores".to_string(),
+            );
+        }
+        Self::validate_password(password)
+    }
+
+    pub fn validate_password(password: &str) -> Result<(), String> {
+        if password.chars().count() < MIN_PASSWORD_LEN {
+            return Err(format!(
+                "Passwords must be at least {} characters long",
+                MIN_PASSWORD_LEN
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_verify_password() {
+        let stored = AuthService::hash_password_with_iterations("correct horse", 1_000).unwrap();
+        assert!(stored.starts_with("pbkdf2-sha256$1000$"));
+        assert!(!stored.contains("correct horse"));
+        // A fresh salt every time
+        assert_ne!(
+            stored,
+            AuthService::hash_password_with_iterations("correct horse", 1_000).unwrap()
+        );
+
+        assert!(AuthService::verify_password("correct horse", &stored));
+        assert!(!AuthService::verify_password("correct horse ", &stored));
+        assert!(!AuthService::verify_password(
+            "correct horse",
+            "correct horse"
+        ));
+        assert!(!AuthService::verify_password(
+            "correct horse",
+            &format!("{}$extra", stored)
+        ));
+        assert!(!AuthService::verify_password(
+            "correct horse",
+            &stored.replace("$1000$", "$0$")
+        ));
+    }
+
+    #[test]
+    fn test_tokens() {
+        let token = AuthService::generate_token().unwrap();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + TOKEN_LEN * 2);
+        assert_ne!(token, AuthService::generate_token().unwrap());
+
+        let hash = AuthService::hash_token(&token);
+        assert_eq!(hash, AuthService::hash_token(&token));
+        assert!(!hash.contains(&token[TOKEN_PREFIX.len()..]));
+    }
+
+    #[test]
+    fn test_validate_credentials() {
+        assert!(AuthService::validate_credentials("alice.smith", "hunter22").is_ok());
+        assert!(AuthService::validate_credentials("", "hunter22").is_err());
+        assert!(AuthService::validate_credentials("alice smith", "hunter22").is_err());
+        assert!(AuthService::validate_credentials("alice", "short").is_err());
+    }
+}
@@ -0,0 +1,357 @@
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    execution_monitor::parse_delegation_context,
+    models::{
+        execution_process::{
+            AutoFixOf, ExecutionProcess, ExecutionProcessStatus, ExecutionProcessSummary,
+            ExecutionProcessType,
+        },
+        project::Project,
+        project_check::{CheckStatus, ProjectCheck},
+        task::{Task, TaskStatus},
+        task_attempt::{TaskAttempt, TaskAttemptError},
+    },
+    services::ProcessService,
+};
+
+/// How much of a failing command's output is passed on to the agent
+const MAX_OUTPUT_LEN: usize = 4000;
+
+/// A command that failed in an attempt's worktree, with the end of its output
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailedCommand {
+    pub label: String, // e.g. "setup script" or "check 'tests'"
+    pub output: String,
+}
+
+/// Opt-in loop that hands the output of a failing setup script, dev server or check back to
+/// the attempt's coding agent as a follow-up, then runs the command again once the agent is
+/// done. Stops after the project's `auto_fix_max_iterations` follow-ups in a row.
+pub struct AutoFixService;
+
+impl AutoFixService {
+    /// Start an auto-fix follow-up for a failed setup script, dev server or check, if the
+    /// project has the loop enabled and iterations are left
+    pub async fn handle_failure(app_state: &AppState, failed: &ExecutionProcess) {
+        let pool = &app_state.db_pool;
+        let Some((task, project)) =
+            Self::load_task_and_project(app_state, failed.task_attempt_id).await
+        else {
+            return;
+        };
+        if project.auto_fix_max_iterations <= 0 {
+            return;
+        }
+
+        let failures = match failed.process_type {
+            ExecutionProcessType::SetupScript => vec![failed_command("setup script", failed)],
+            ExecutionProcessType::DevServer => vec![failed_command("dev server", failed)],
+            ExecutionProcessType::CheckScript => {
+                match Self::failed_checks(app_state, project.id, failed.task_attempt_id).await {
+                    Some(failures) if !failures.is_empty() => failures,
+                    _ => return,
+                }
+            }
+            ExecutionProcessType::CodingAgent => return,
+        };
+
+        let processes =
+            match ExecutionProcess::find_summaries_by_task_attempt_id(pool, failed.task_attempt_id)
+                .await
+            {
+                Ok(processes) => processes,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to load execution processes of attempt {}: {}",
+                        failed.task_attempt_id,
+                        e
+                    );
+                    return;
+                }
+            };
+        let Some(previous_agent) = latest_coding_agent(&processes) else {
+            tracing::info!(
+                "Not auto-fixing attempt {}: there is no coding agent session to follow up",
+                failed.task_attempt_id
+            );
+            return;
+        };
+        if matches!(
+            previous_agent.status,
+            ExecutionProcessStatus::Running | ExecutionProcessStatus::Queued
+        ) {
+            return;
+        }
+
+        let Some(iteration) = next_auto_fix_iteration(
+            previous_agent.auto_fix_iteration,
+            project.auto_fix_max_iterations,
+        ) else {
+            tracing::info!(
+                "Giving up auto-fixing attempt {} after {} iterations",
+                failed.task_attempt_id,
+                project.auto_fix_max_iterations
+            );
+            return;
+        };
+
+        tracing::info!(
+            "Starting auto-fix iteration {} of {} for attempt {}",
+            iteration,
+            project.auto_fix_max_iterations,
+            failed.task_attempt_id
+        );
+
+        if let Err(e) = Task::update_status(pool, task.id, project.id, TaskStatus::InProgress).await
+        {
+            tracing::error!("Failed to update task status for auto-fix: {}", e);
+        }
+
+        let prompt = auto_fix_prompt(&failures, iteration, project.auto_fix_max_iterations);
+        if let Err(e) = ProcessService::start_followup_execution_direct(
+            pool,
+            app_state,
+            failed.task_attempt_id,
+            task.id,
+            project.id,
+            &prompt,
+            Some(AutoFixOf {
+                process_id: failed.id,
+                iteration,
+            }),
+        )
+        .await
+        {
+            tracing::error!(
+                "Failed to start auto-fix follow-up for attempt {}: {}",
+                failed.task_attempt_id,
+                e
+            );
+        }
+    }
+
+    /// Run the command an auto-fix follow-up was started for again, now that the agent
+    /// finished its fix
+    pub async fn rerun_fixed_command(app_state: &AppState, agent: &ExecutionProcess) {
+        let Some(failed_id) = agent.auto_fix_of_process_id else {
+            return;
+        };
+        let failed = match ExecutionProcess::find_summary_by_id(&app_state.db_pool, failed_id).await
+        {
+            Ok(Some(failed)) => failed,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!("Failed to load auto-fixed process {}: {}", failed_id, e);
+                return;
+            }
+        };
+        let Some((task, project)) =
+            Self::load_task_and_project(app_state, failed.task_attempt_id).await
+        else {
+            return;
+        };
+
+        let pool = &app_state.db_pool;
+        let attempt_id = failed.task_attempt_id;
+        let result: Result<(), TaskAttemptError> = match failed.process_type {
+            ExecutionProcessType::CheckScript => {
+                ProcessService::start_checks_direct(
+                    pool, app_state, attempt_id, task.id, project.id,
+                )
+                .await
+            }
+            ExecutionProcessType::DevServer => {
+                ProcessService::start_dev_server_direct(
+                    pool, app_state, attempt_id, task.id, project.id,
+                )
+                .await
+            }
+            ExecutionProcessType::SetupScript => {
+                // Setup is still incomplete, so this runs it again before the operation that
+                // was waiting on it
+                let Some(delegation) = failed.args.as_deref().and_then(parse_delegation_context)
+                else {
+                    return;
+                };
+                ProcessService::auto_setup_and_execute(
+                    pool,
+                    app_state,
+                    attempt_id,
+                    task.id,
+                    project.id,
+                    &delegation.delegate_to,
+                    delegation.operation_params.additional,
+                )
+                .await
+            }
+            ExecutionProcessType::CodingAgent => return,
+        };
+
+        if let Err(e) = result {
+            tracing::error!(
+                "Failed to re-run process {} after auto-fix of attempt {}: {}",
+                failed.id,
+                attempt_id,
+                e
+            );
+        }
+    }
+
+    async fn load_task_and_project(
+        app_state: &AppState,
+        attempt_id: Uuid,
+    ) -> Option<(Task, Project)> {
+        let pool = &app_state.db_pool;
+        let attempt = TaskAttempt::find_by_id(pool, attempt_id)
+            .await
+            .ok()
+            .flatten()?;
+        let task = Task::find_by_id(pool, attempt.task_id)
+            .await
+            .ok()
+            .flatten()?;
+        let project = Project::find_by_id(pool, task.project_id)
+            .await
+            .ok()
+            .flatten()?;
+        Some((task, project))
+    }
+
+    /// Failed checks of the attempt's current run, or None while some are still running so
+    /// only the last check to finish starts the follow-up
+    async fn failed_checks(
+        app_state: &AppState,
+        project_id: Uuid,
+        attempt_id: Uuid,
+    ) -> Option<Vec<FailedCommand>> {
+        let pool = &app_state.db_pool;
+        let results = ProjectCheck::find_results_for_attempt(pool, project_id, attempt_id)
+            .await
+            .ok()?;
+        if results.iter().any(|r| r.status == CheckStatus::Running) {
+            return None;
+        }
+
+        let mut failures = Vec::new();
+        for result in results
+            .iter()
+            .filter(|r| r.status == CheckStatus::Failed && !r.outdated)
+        {
+            let Some(process_id) = result.execution_process_id else {
+                continue;
+            };
+            if let Ok(Some(process)) = ExecutionProcess::find_by_id(pool, process_id).await {
+                failures.push(failed_command(
+                    &format!("check '{}'", result.name),
+                    &process,
+                ));
+            }
+        }
+        Some(failures)
+    }
+}
+
+fn latest_coding_agent(processes: &[ExecutionProcessSummary]) -> Option<&ExecutionProcessSummary> {
+    processes
+        .iter()
+        .rev()
+        .find(|p| p.process_type == ExecutionProcessType::CodingAgent)
+}
+
+/// Stderr of the process, or stdout if it wrote nothing to stderr
+fn failed_command(label: &str, process: &ExecutionProcess) -> FailedCommand {
+    let output = [process.stderr.as_deref(), process.stdout.as_deref()]
+        .into_iter()
+        .flatten()
+        .find(|output| !output.trim().is_empty())
+        .unwrap_or_default();
+    FailedCommand {
+        label: label.to_string(),
+        output: output_tail(output, MAX_OUTPUT_LEN).to_string(),
+    }
+}
+
+/// The iteration an auto-fix follow-up would be, given the iteration of the previous coding
+/// agent execution (None if a person started it), or None once `max_iterations` is used up
+pub fn next_auto_fix_iteration(previous: Option<i64>, max_iterations: i64) -> Option<i64> {
+    let next = previous.unwrap_or(0) + 1;
+    (next <= max_iterations).then_some(next)
+}
+
+/// The last `max_len` bytes of `output`, cut at a character boundary
+pub fn output_tail(output: &str, max_len: usize) -> &str {
+    if output.len() <= max_len {
+        return output;
+    }
+    let mut start = output.len() - max_len;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    &output[start..]
+}
+
+pub fn auto_fix_prompt(failures: &[FailedCommand], iteration: i64, max_iterations: i64) -> String {
+    let labels: Vec<&str> = failures.iter().map(|f| f.label.as_str()).collect();
+    let mut prompt = format!(
+        "The {} failed after your changes (automatic fix attempt {} of {}). \
+         Find the cause in the output below and fix it so the command succeeds.",
+        labels.join(", "),
+        iteration,
+        max_iterations
+    );
+    for failure in failures {
+        let output = failure.output.trim();
+        prompt.push_str(&format!("\n\nOutput of the {}:\n```\n", failure.label));
+        prompt.push_str(if output.is_empty() {
+            "(no output)"
+        } else {
+            output
+        });
+        prompt.push_str("\n```");
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_auto_fix_iteration() {
+        assert_eq!(next_auto_fix_iteration(None, 3), Some(1));
+        assert_eq!(next_auto_fix_iteration(Some(2), 3), Some(3));
+        assert_eq!(next_auto_fix_iteration(Some(3), 3), None);
+        assert_eq!(next_auto_fix_iteration(None, 0), None);
+    }
+
+    #[test]
+    fn test_output_tail() {
+        assert_eq!(output_tail("short", 10), "short");
+        assert_eq!(output_tail("0123456789", 4), "6789");
+        // Never splits a multi-byte character
+        assert_eq!(output_tail("aé", 1), "");
+        assert_eq!(output_tail("aéb", 3), "éb");
+    }
+
+    #[test]
+    fn test_auto_fix_prompt() {
+        let failures = vec![
+            FailedCommand {
+                label: "check 'tests'".to_string(),
+                output: "assertion failed\n".to_string(),
+            },
+            FailedCommand {
+                label: "check 'lint'".to_string(),
+                output: String::new(),
+            },
+        ];
+        let prompt = auto_fix_prompt(&failures, 2, 3);
+        assert!(prompt.starts_with(
+            "The check 'tests', check 'lint' failed after your changes (automatic fix attempt 2 of 3)."
+        ));
+        assert!(prompt.contains("Output of the check 'tests':\n```\nassertion failed\n```"));
+        assert!(prompt.contains("Output of the check 'lint':\n```\n(no output)\n```"));
+    }
+}
@@ -1,14 +1,19 @@
 use std::path::{Path, PathBuf};
 
 use git2::{
-    BranchType, Cred, DiffOptions, Error as GitError, FetchOptions, RebaseOptions, RemoteCallbacks,
-    Repository, WorktreeAddOptions,
+    build::CheckoutBuilder, BranchType, Cred, DiffOptions, Error as GitError, FetchOptions,
+    RebaseOptions, RemoteCallbacks, Repository, RepositoryState, WorktreeAddOptions,
 };
-use regex;
 use tracing::{debug, info};
 
 use crate::{
-    models::task_attempt::{DiffChunk, DiffChunkType, FileDiff, WorktreeDiff},
+    models::{
+        project::MergeStrategy,
+        task_attempt::{
+            ConflictFile, ConflictHunk, ConflictOperation, ConflictReport, ConflictResolution,
+            DiffChunk, DiffChunkType, FileDiff, WorktreeDiff,
+        },
+    },
     utils::worktree_manager::WorktreeManager,
 };
 
@@ -19,9 +24,11 @@ pub enum GitServiceError {
     InvalidRepository(String),
     BranchNotFound(String),
 
-    MergeConflicts(String),
+    ConflictingFiles(Vec<String>),
+    CannotFastForward(String),
     InvalidPath(String),
     WorktreeDirty(String),
+    NoConflictResolution,
 }
 
 impl std::fmt::Display for GitServiceError {
@@ -32,11 +39,21 @@ impl std::fmt::Display for GitServiceError {
             GitServiceError::InvalidRepository(e) => write!(f, "Invalid repository: {}", e),
             GitServiceError::BranchNotFound(e) => write!(f, "Branch not found: {}", e),
 
-            GitServiceError::MergeConflicts(e) => write!(f, "Merge conflicts: {}", e),
+            GitServiceError::ConflictingFiles(files) => {
+                write!(f, "Merge conflicts in {}", files.join(", "))
+            }
+            GitServiceError::CannotFastForward(branch) => write!(
+                f,
+                "Cannot fast-forward {}, it has moved on since the attempt started; rebase the attempt first",
+                branch
+            ),
             GitServiceError::InvalidPath(e) => write!(f, "Invalid path: {}", e),
             GitServiceError::WorktreeDirty(e) => {
                 write!(f, "Worktree has uncommitted changes: {}", e)
             }
+            GitServiceError::NoConflictResolution => {
+                write!(f, "No rebase or merge with conflicts is in progress")
+            }
         }
     }
 }
@@ -176,13 +193,16 @@ impl GitService {
         Ok(())
     }
 
-    /// Merge changes from a worktree branch back to the main repository
+    /// Merge changes from a worktree branch back to the main repository using `strategy`.
+    /// Fails with `ConflictingFiles` before any branch is touched if the attempt's changes
+    /// conflict with changes made on the base branch in the meantime.
     pub fn merge_changes(
         &self,
         worktree_path: &Path,
         branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+        strategy: MergeStrategy,
     ) -> Result<String, GitServiceError> {
         // Open the worktree repository
         let worktree_repo = Repository::open(worktree_path)?;
@@ -207,18 +227,137 @@ impl GitService {
         // Get the signature for the merge commit
         let signature = worktree_repo.signature()?;
 
-        // Perform a squash merge - create a single commit with all changes
-        let squash_commit_id = self.perform_squash_merge(
-            &worktree_repo,
-            &base_commit,
-            &task_commit,
-            &signature,
-            commit_message,
-            base_branch_name,
+        // Three-way merge of both sides, so changes made on the base branch since the attempt
+        // started are kept
+        let mut merged_index = worktree_repo.merge_commits(&base_commit, &task_commit, None)?;
+        if merged_index.has_conflicts() {
+            return Err(GitServiceError::ConflictingFiles(Self::conflicted_paths(
+                &merged_index,
+            )?));
+        }
+        let merged_tree = worktree_repo.find_tree(merged_index.write_tree_to(&worktree_repo)?)?;
+
+        let can_fast_forward = base_commit.id() == task_commit.id()
+            || worktree_repo.graph_descendant_of(task_commit.id(), base_commit.id())?;
+
+        let new_base_id = match strategy {
+            MergeStrategy::Squash => worktree_repo.commit(
+                None,
+                &signature,
+                &signature,
+                commit_message,
+                &merged_tree,
+                &[&base_commit],
+            )?,
+            MergeStrategy::KeepCommits if can_fast_forward => task_commit.id(),
+            MergeStrategy::KeepCommits => {
+                return Err(GitServiceError::CannotFastForward(
+                    base_branch_name.to_string(),
+                ))
+            }
+            MergeStrategy::Merge => worktree_repo.commit(
+                None,
+                &signature,
+                &signature,
+                commit_message,
+                &merged_tree,
+                &[&base_commit, &task_commit],
+            )?,
+            MergeStrategy::Rebase => {
+                let rebased_id =
+                    self.rebase_in_memory(&worktree_repo, &base_commit, &task_commit, &signature)?;
+                // Move the attempt branch along so it matches what was merged
+                if rebased_id != task_commit.id() {
+                    worktree_repo.reference_matching(
+                        &format!("refs/heads/{}", branch_name),
+                        rebased_id,
+                        true,
+                        task_commit.id(),
+                        "Rebase onto base branch before merge",
+                    )?;
+                    let mut checkout = git2::build::CheckoutBuilder::new();
+                    checkout.force();
+                    worktree_repo.checkout_head(Some(&mut checkout))?;
+                }
+                rebased_id
+            }
+        };
+
+        // Only move the base branch if nobody else moved it while we were merging
+        worktree_repo.reference_matching(
+            &format!("refs/heads/{}", base_branch_name),
+            new_base_id,
+            true,
+            base_commit.id(),
+            &format!("Merge {} ({:?})", branch_name, strategy),
         )?;
 
-        info!("Created squash merge commit: {}", squash_commit_id);
-        Ok(squash_commit_id.to_string())
+        info!(
+            "Merged {} into {} with {:?}: {}",
+            branch_name, base_branch_name, strategy, new_base_id
+        );
+        Ok(new_base_id.to_string())
+    }
+
+    /// Replay the commits of `task_commit` that aren't on `base_commit` on top of it without
+    /// touching any branch or working tree, returning the last rebased commit
+    fn rebase_in_memory(
+        &self,
+        repo: &Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+        signature: &git2::Signature,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let task_annotated = repo.find_annotated_commit(task_commit.id())?;
+        let base_annotated = repo.find_annotated_commit(base_commit.id())?;
+
+        let mut rebase_opts = RebaseOptions::new();
+        rebase_opts.inmemory(true);
+        let mut rebase = repo.rebase(
+            Some(&task_annotated),
+            Some(&base_annotated),
+            None,
+            Some(&mut rebase_opts),
+        )?;
+
+        let mut head = base_commit.id();
+        while let Some(operation) = rebase.next() {
+            operation?;
+
+            let index = rebase.inmemory_index()?;
+            if index.has_conflicts() {
+                let paths = Self::conflicted_paths(&index)?;
+                rebase.abort()?;
+                return Err(GitServiceError::ConflictingFiles(paths));
+            }
+
+            match rebase.commit(None, signature, None) {
+                Ok(commit_id) => head = commit_id,
+                // The change is already on the base branch, nothing to replay
+                Err(e) if e.code() == git2::ErrorCode::Applied => {}
+                Err(e) => {
+                    rebase.abort()?;
+                    return Err(e.into());
+                }
+            }
+        }
+        rebase.finish(None)?;
+
+        Ok(head)
+    }
+
+    /// Paths with conflicts in an index, sorted and without duplicates
+    fn conflicted_paths(index: &git2::Index) -> Result<Vec<String>, GitServiceError> {
+        let mut paths = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+                paths.push(String::from_utf8_lossy(&entry.path).to_string());
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
     }
 
     /// Check if the worktree is clean (no uncommitted changes to tracked files)
@@ -260,33 +399,6 @@ impl GitService {
         Ok(())
     }
 
-    /// Perform a squash merge of task branch into base branch
-    fn perform_squash_merge(
-        &self,
-        repo: &Repository,
-        base_commit: &git2::Commit,
-        task_commit: &git2::Commit,
-        signature: &git2::Signature,
-        commit_message: &str,
-        base_branch_name: &str,
-    ) -> Result<git2::Oid, GitServiceError> {
-        // Create a single commit that squashes all changes from task branch
-        let squash_commit_id = repo.commit(
-            None,                 // Don't update any reference yet
-            signature,            // Author
-            signature,            // Committer
-            commit_message,       // Custom message
-            &task_commit.tree()?, // Use the tree from task branch (all changes)
-            &[base_commit],       // Single parent: base branch commit
-        )?;
-
-        // Update the base branch reference to point to the new commit
-        let refname = format!("refs/heads/{}", base_branch_name);
-        repo.reference(&refname, squash_commit_id, true, "Squash merge")?;
-
-        Ok(squash_commit_id)
-    }
-
     /// Rebase a worktree branch onto a new base
     pub fn rebase_branch(
         &self,
@@ -296,17 +408,10 @@ impl GitService {
         let worktree_repo = Repository::open(worktree_path)?;
         let main_repo = self.open_repo()?;
 
-        // Check if there's an existing rebase in progress and abort it
-        let state = worktree_repo.state();
-        if state == git2::RepositoryState::Rebase
-            || state == git2::RepositoryState::RebaseInteractive
-            || state == git2::RepositoryState::RebaseMerge
-        {
-            tracing::warn!("Existing rebase in progress, aborting it first");
-            // Try to abort the existing rebase
-            if let Ok(mut existing_rebase) = worktree_repo.open_rebase(None) {
-                let _ = existing_rebase.abort();
-            }
+        // Check if there's an existing rebase or merge in progress and abort it
+        if worktree_repo.state() != RepositoryState::Clean {
+            tracing::warn!("Existing rebase or merge in progress, aborting it first");
+            self.abort_conflict_resolution(worktree_path)?;
         }
 
         // Get the target base branch reference
@@ -367,7 +472,7 @@ impl GitService {
         let head = worktree_repo.head()?;
 
         // Set up rebase
-        let mut rebase_opts = RebaseOptions::new();
+        let mut rebase_opts = Self::rebase_options();
         let signature = worktree_repo.signature()?;
 
         // Start the rebase
@@ -381,34 +486,300 @@ impl GitService {
             Some(&mut rebase_opts),
         )?;
 
-        // Process each rebase operation
+        // Process each rebase operation. On conflicts the rebase stays in progress so they can
+        // be resolved and the rebase continued with `resolve_conflicts`.
+        Self::continue_rebase(&worktree_repo, &mut rebase, &signature)?;
+
+        // Get the final commit ID after rebase
+        let final_head = worktree_repo.head()?;
+        let final_commit = final_head.peel_to_commit()?;
+
+        info!("Rebase completed. New HEAD: {}", final_commit.id());
+        Ok(final_commit.id().to_string())
+    }
+
+    /// Rebase options that leave diff3 style conflict markers in the worktree
+    fn rebase_options<'cb>() -> RebaseOptions<'cb> {
+        let mut checkout = CheckoutBuilder::new();
+        checkout.conflict_style_diff3(true);
+        let mut rebase_opts = RebaseOptions::new();
+        rebase_opts.checkout_options(checkout);
+        rebase_opts
+    }
+
+    /// Apply the remaining operations of a rebase and finish it. Stops with `ConflictingFiles`
+    /// if an operation conflicts, leaving the rebase in progress.
+    fn continue_rebase(
+        repo: &Repository,
+        rebase: &mut git2::Rebase,
+        signature: &git2::Signature,
+    ) -> Result<(), GitServiceError> {
         while let Some(operation) = rebase.next() {
-            let _operation = operation?;
+            operation?;
 
-            // Check for conflicts
-            let index = worktree_repo.index()?;
+            let index = repo.index()?;
             if index.has_conflicts() {
-                // For now, abort the rebase on conflicts
-                rebase.abort()?;
-                return Err(GitServiceError::MergeConflicts(
-                    "Rebase failed due to conflicts. Please resolve conflicts manually."
-                        .to_string(),
-                ));
+                return Err(GitServiceError::ConflictingFiles(Self::conflicted_paths(
+                    &index,
+                )?));
             }
 
-            // Commit the rebased operation
-            rebase.commit(None, &signature, None)?;
+            Self::commit_rebase_operation(rebase, signature)?;
         }
 
-        // Finish the rebase
         rebase.finish(None)?;
+        Ok(())
+    }
 
-        // Get the final commit ID after rebase
-        let final_head = worktree_repo.head()?;
-        let final_commit = final_head.peel_to_commit()?;
+    fn commit_rebase_operation(
+        rebase: &mut git2::Rebase,
+        signature: &git2::Signature,
+    ) -> Result<(), GitServiceError> {
+        match rebase.commit(None, signature, None) {
+            Ok(_) => Ok(()),
+            // The change is already on the new base, nothing to commit
+            Err(e) if e.code() == git2::ErrorCode::Applied => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
 
-        info!("Rebase completed. New HEAD: {}", final_commit.id());
-        Ok(final_commit.id().to_string())
+    /// Merge the base branch into the worktree's branch. Stops with `ConflictingFiles` if they
+    /// conflict, leaving the merge in progress so the conflicts can be resolved.
+    pub fn merge_base_into_branch(
+        &self,
+        worktree_path: &Path,
+        base_branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let worktree_repo = Repository::open(worktree_path)?;
+        if worktree_repo.state() != RepositoryState::Clean {
+            self.abort_conflict_resolution(worktree_path)?;
+        }
+        self.check_worktree_clean(&worktree_repo)?;
+
+        let base_commit = worktree_repo
+            .find_branch(base_branch_name, BranchType::Local)
+            .map_err(|_| GitServiceError::BranchNotFound(base_branch_name.to_string()))?
+            .get()
+            .peel_to_commit()?;
+        let base_annotated = worktree_repo.find_annotated_commit(base_commit.id())?;
+
+        let (analysis, _) = worktree_repo.merge_analysis(&[&base_annotated])?;
+        if analysis.is_up_to_date() {
+            return Ok(());
+        }
+
+        let mut checkout = CheckoutBuilder::new();
+        checkout.conflict_style_diff3(true);
+        worktree_repo.merge(&[&base_annotated], None, Some(&mut checkout))?;
+
+        let index = worktree_repo.index()?;
+        if index.has_conflicts() {
+            return Err(GitServiceError::ConflictingFiles(Self::conflicted_paths(
+                &index,
+            )?));
+        }
+
+        Self::commit_merge(&worktree_repo, base_branch_name)
+    }
+
+    /// Commit an in-progress merge of the base branch with the staged result
+    fn commit_merge(repo: &Repository, base_branch_name: &str) -> Result<(), GitServiceError> {
+        let mut index = repo.index()?;
+        let tree = repo.find_tree(index.write_tree()?)?;
+        let head = repo.head()?;
+        let branch_name = head.shorthand().unwrap_or("HEAD").to_string();
+        let head_commit = head.peel_to_commit()?;
+        let merge_head = repo.find_reference("MERGE_HEAD")?.peel_to_commit()?;
+        let signature = repo.signature()?;
+
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &format!("Merge branch '{}' into {}", base_branch_name, branch_name),
+            &tree,
+            &[&head_commit, &merge_head],
+        )?;
+        repo.cleanup_state()?;
+        Ok(())
+    }
+
+    /// The conflicts of the rebase or merge in progress in a worktree, or None if there is none
+    pub fn get_conflict_report(
+        &self,
+        worktree_path: &Path,
+        base_branch_name: &str,
+    ) -> Result<Option<ConflictReport>, GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        let operation = match Self::conflict_operation(&repo) {
+            Some(operation) => operation,
+            None => return Ok(None),
+        };
+
+        let index = repo.index()?;
+        let mut files = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            // While rebasing, git's "ours" is the branch being rebased onto
+            let (ours, theirs) = match operation {
+                ConflictOperation::Rebase => (conflict.their, conflict.our),
+                ConflictOperation::Merge => (conflict.our, conflict.their),
+            };
+            let Some(path) = ours
+                .as_ref()
+                .or(theirs.as_ref())
+                .or(conflict.ancestor.as_ref())
+                .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+            else {
+                continue;
+            };
+
+            // The checkout left the conflict in the worktree with diff3 style markers
+            let merged_content = repo
+                .workdir()
+                .and_then(|workdir| std::fs::read_to_string(workdir.join(&path)).ok())
+                .filter(|content| has_conflict_markers(content));
+            let hunks = merged_content
+                .as_deref()
+                .map(|content| {
+                    let mut hunks = parse_conflict_hunks(content);
+                    if operation == ConflictOperation::Rebase {
+                        for hunk in &mut hunks {
+                            std::mem::swap(&mut hunk.ours, &mut hunk.theirs);
+                        }
+                    }
+                    hunks
+                })
+                .unwrap_or_default();
+
+            let blob_text = |entry: &Option<git2::IndexEntry>| {
+                entry
+                    .as_ref()
+                    .and_then(|entry| repo.find_blob(entry.id).ok())
+                    .filter(|blob| !blob.is_binary())
+                    .and_then(|blob| String::from_utf8(blob.content().to_vec()).ok())
+            };
+
+            files.push(ConflictFile {
+                path,
+                ours: blob_text(&ours),
+                base: blob_text(&conflict.ancestor),
+                theirs: blob_text(&theirs),
+                merged_content,
+                hunks,
+            });
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Some(ConflictReport {
+            operation,
+            base_branch: base_branch_name.to_string(),
+            files,
+        }))
+    }
+
+    fn conflict_operation(repo: &Repository) -> Option<ConflictOperation> {
+        match repo.state() {
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Some(ConflictOperation::Rebase),
+            RepositoryState::Merge => Some(ConflictOperation::Merge),
+            _ => None,
+        }
+    }
+
+    /// Apply `resolutions` to the rebase or merge in progress and continue it once nothing
+    /// conflicts anymore. Conflicted files edited by hand, or by a coding agent, count as
+    /// resolved once they no longer contain conflict markers. Returns whether the operation
+    /// completed; it may stop again on the conflicts of a later rebased commit.
+    pub fn resolve_conflicts(
+        &self,
+        worktree_path: &Path,
+        base_branch_name: &str,
+        resolutions: &[ConflictResolution],
+    ) -> Result<bool, GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        let operation =
+            Self::conflict_operation(&repo).ok_or(GitServiceError::NoConflictResolution)?;
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| GitServiceError::InvalidRepository("Worktree has no workdir".into()))?
+            .to_path_buf();
+
+        let mut index = repo.index()?;
+        for resolution in resolutions {
+            let relative_path = Path::new(&resolution.path);
+            if relative_path.is_absolute()
+                || relative_path
+                    .components()
+                    .any(|c| matches!(c, std::path::Component::ParentDir))
+            {
+                return Err(GitServiceError::InvalidPath(resolution.path.clone()));
+            }
+
+            let file_path = workdir.join(relative_path);
+            match &resolution.content {
+                Some(content) => {
+                    if let Some(parent) = file_path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(&file_path, content)?;
+                    index.add_path(relative_path)?;
+                }
+                None => {
+                    if file_path.exists() {
+                        std::fs::remove_file(&file_path)?;
+                    }
+                    index.remove_path(relative_path)?;
+                }
+            }
+        }
+
+        for path in Self::conflicted_paths(&index)? {
+            if let Ok(content) = std::fs::read_to_string(workdir.join(&path)) {
+                if !has_conflict_markers(&content) {
+                    index.add_path(Path::new(&path))?;
+                }
+            }
+        }
+        index.write()?;
+
+        if index.has_conflicts() {
+            return Ok(false);
+        }
+
+        match operation {
+            ConflictOperation::Rebase => {
+                let signature = repo.signature()?;
+                let mut rebase_opts = Self::rebase_options();
+                let mut rebase = repo.open_rebase(Some(&mut rebase_opts))?;
+                Self::commit_rebase_operation(&mut rebase, &signature)?;
+                match Self::continue_rebase(&repo, &mut rebase, &signature) {
+                    Ok(()) => {}
+                    Err(GitServiceError::ConflictingFiles(_)) => return Ok(false),
+                    Err(e) => return Err(e),
+                }
+            }
+            ConflictOperation::Merge => Self::commit_merge(&repo, base_branch_name)?,
+        }
+
+        info!("Conflicts resolved in {}", worktree_path.display());
+        Ok(true)
+    }
+
+    /// Abort the rebase or merge in progress in a worktree, restoring the branch as it was
+    pub fn abort_conflict_resolution(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        match Self::conflict_operation(&repo) {
+            Some(ConflictOperation::Rebase) => repo.open_rebase(None)?.abort()?,
+            Some(ConflictOperation::Merge) => {
+                let head = repo.head()?.peel_to_commit()?;
+                repo.reset(head.as_object(), git2::ResetType::Hard, None)?;
+                repo.cleanup_state()?;
+            }
+            None => repo.cleanup_state()?,
+        }
+        Ok(())
     }
 
     /// Get enhanced diff for task attempts (from merge commit or worktree)
@@ -924,6 +1295,16 @@ impl GitService {
         Ok(commit_id.to_string())
     }
 
+    /// Get the commit a local branch points to
+    pub fn get_branch_head_commit(&self, branch_name: &str) -> Result<String, GitServiceError> {
+        let repo = self.open_repo()?;
+        let branch = repo
+            .find_branch(branch_name, BranchType::Local)
+            .map_err(|_| GitServiceError::BranchNotFound(branch_name.to_string()))?;
+        let commit_id = branch.get().peel_to_commit()?.id();
+        Ok(commit_id.to_string())
+    }
+
     /// Get the default branch name for the repository
     pub fn get_default_branch_name(&self) -> Result<String, GitServiceError> {
         let repo = self.open_repo()?;
@@ -1020,75 +1401,44 @@ impl GitService {
         Ok(stored_worktree_path.to_path_buf())
     }
 
-    /// Extract GitHub owner and repo name from git repo path
-    pub fn get_github_repo_info(&self) -> Result<(String, String), GitServiceError> {
+    /// URL of the repository's `origin` remote
+    pub fn get_remote_url(&self) -> Result<String, GitServiceError> {
         let repo = self.open_repo()?;
         let remote = repo.find_remote("origin").map_err(|_| {
             GitServiceError::InvalidRepository("No 'origin' remote found".to_string())
         })?;
 
-        let url = remote.url().ok_or_else(|| {
+        remote.url().map(str::to_string).ok_or_else(|| {
             GitServiceError::InvalidRepository("Remote origin has no URL".to_string())
-        })?;
-
-        // Parse GitHub URL (supports both HTTPS and SSH formats)
-        let github_regex = regex::Regex::new(r"github\.com[:/]([^/]+)/(.+?)(?:\.git)?/?$")
-            .map_err(|e| GitServiceError::InvalidRepository(format!("Regex error: {}", e)))?;
-
-        if let Some(captures) = github_regex.captures(url) {
-            let owner = captures.get(1).unwrap().as_str().to_string();
-            let repo_name = captures.get(2).unwrap().as_str().to_string();
-            Ok((owner, repo_name))
-        } else {
-            Err(GitServiceError::InvalidRepository(format!(
-                "Not a GitHub repository: {}",
-                url
-            )))
-        }
+        })
     }
 
-    /// Push the branch to GitHub remote
-    pub fn push_to_github(
+    /// Push the branch to `push_url`, authenticating with `username` and `token` when asked to
+    pub fn push_branch(
         &self,
         worktree_path: &Path,
         branch_name: &str,
-        github_token: &str,
+        push_url: &str,
+        username: &str,
+        token: &str,
     ) -> Result<(), GitServiceError> {
         let repo = Repository::open(worktree_path)?;
 
-        // Get the remote
-        let remote = repo.find_remote("origin")?;
-        let remote_url = remote.url().ok_or_else(|| {
-            GitServiceError::InvalidRepository("Remote origin has no URL".to_string())
-        })?;
-
-        // Convert SSH URL to HTTPS URL if necessary
-        let https_url = if remote_url.starts_with("git@github.com:") {
-            // Convert git@github.com:owner/repo.git to https://github.com/owner/repo.git
-            remote_url.replace("git@github.com:", "https://github.com/")
-        } else if remote_url.starts_with("ssh://git@github.com/") {
-            // Convert ssh://git@github.com/owner/repo.git to https://github.com/owner/repo.git
-            remote_url.replace("ssh://git@github.com/", "https://github.com/")
-        } else {
-            remote_url.to_string()
-        };
-
-        // Create a temporary remote with HTTPS URL for pushing
+        // Create a temporary remote with the push URL, leaving origin as the user set it up
         let temp_remote_name = "temp_https_origin";
 
         // Remove any existing temp remote
         let _ = repo.remote_delete(temp_remote_name);
 
-        // Create temporary HTTPS remote
-        let mut temp_remote = repo.remote(temp_remote_name, &https_url)?;
+        let mut temp_remote = repo.remote(temp_remote_name, push_url)?;
 
         // Create refspec for pushing the branch
         let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
 
-        // Set up authentication callback using the GitHub token
+        // Set up authentication callback using the provider token
         let mut callbacks = git2::RemoteCallbacks::new();
         callbacks.credentials(|_url, username_from_url, _allowed_types| {
-            git2::Cred::userpass_plaintext(username_from_url.unwrap_or("git"), github_token)
+            git2::Cred::userpass_plaintext(username_from_url.unwrap_or(username), token)
         });
 
         // Configure push options
@@ -1104,7 +1454,7 @@ impl GitService {
         // Check push result
         push_result?;
 
-        info!("Pushed branch {} to GitHub using HTTPS", branch_name);
+        info!("Pushed branch {}", branch_name);
         Ok(())
     }
 
@@ -1143,6 +1493,61 @@ impl GitService {
     }
 }
 
+/// Whether a file still contains git conflict markers
+fn has_conflict_markers(content: &str) -> bool {
+    content
+        .lines()
+        .any(|line| line.starts_with("<<<<<<<") || line.starts_with(">>>>>>>"))
+}
+
+/// Split content with diff3 style conflict markers into its conflicting regions
+fn parse_conflict_hunks(content: &str) -> Vec<ConflictHunk> {
+    enum Side {
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut hunks = Vec::new();
+    let mut current: Option<(Side, ConflictHunk)> = None;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let marker = line.trim_end_matches(['\r', '\n']);
+        if marker.starts_with("<<<<<<<") {
+            current = Some((
+                Side::Ours,
+                ConflictHunk {
+                    start_line: index + 1,
+                    ours: String::new(),
+                    base: String::new(),
+                    theirs: String::new(),
+                },
+            ));
+            continue;
+        }
+
+        let Some((side, hunk)) = current.as_mut() else {
+            continue;
+        };
+        if marker.starts_with("|||||||") {
+            *side = Side::Base;
+        } else if marker == "=======" {
+            *side = Side::Theirs;
+        } else if marker.starts_with(">>>>>>>") {
+            if let Some((_, hunk)) = current.take() {
+                hunks.push(hunk);
+            }
+        } else {
+            match side {
+                Side::Ours => hunk.ours.push_str(line),
+                Side::Base => hunk.base.push_str(line),
+                Side::Theirs => hunk.theirs.push_str(line),
+            }
+        }
+    }
+
+    hunks
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
@@ -1161,6 +1566,171 @@ mod tests {
         (temp_dir, repo)
     }
 
+    /// Commit `content` to `path` on top of `branch` without touching any working tree
+    fn commit_file(repo: &Repository, branch: &str, path: &str, content: &str) -> git2::Oid {
+        let parent = repo
+            .find_branch(branch, BranchType::Local)
+            .ok()
+            .map(|b| b.get().peel_to_commit().unwrap());
+
+        let mut builder = repo
+            .treebuilder(parent.as_ref().map(|p| p.tree().unwrap()).as_ref())
+            .unwrap();
+        builder
+            .insert(path, repo.blob(content.as_bytes()).unwrap(), 0o100644)
+            .unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+
+        let signature = repo.signature().unwrap();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some(&format!("refs/heads/{}", branch)),
+            &signature,
+            &signature,
+            &format!("Update {}", path),
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    /// A repo with `main`, and an attempt worktree on `task` with one commit to task.txt.
+    /// `main` then moves on with a commit to `base_file`.
+    fn setup_diverged_attempt(base_file: &str) -> (TempDir, TempDir, Repository, GitService) {
+        let (temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "main", "shared.txt", "one\n");
+        repo.set_head("refs/heads/main").unwrap();
+
+        let git_service = GitService::new(temp_dir.path()).unwrap();
+        let worktree_dir = TempDir::new().unwrap();
+        let worktree_path = worktree_dir.path().join("task");
+        git_service
+            .create_worktree("task", &worktree_path, Some("main"))
+            .unwrap();
+
+        let worktree_repo = Repository::open(&worktree_path).unwrap();
+        commit_file(&worktree_repo, "task", "task.txt", "task change\n");
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.force();
+        worktree_repo.checkout_head(Some(&mut checkout)).unwrap();
+
+        commit_file(&repo, "main", base_file, "base change\n");
+
+        (temp_dir, worktree_dir, repo, git_service)
+    }
+
+    fn main_tree_has(repo: &Repository, path: &str) -> bool {
+        let main = repo.find_branch("main", BranchType::Local).unwrap();
+        let tree = main.get().peel_to_tree().unwrap();
+        tree.get_path(Path::new(path)).is_ok()
+    }
+
+    #[test]
+    fn test_merge_strategies_keep_base_branch_changes() {
+        for strategy in [
+            MergeStrategy::Squash,
+            MergeStrategy::Merge,
+            MergeStrategy::Rebase,
+        ] {
+            let (_temp_dir, worktree_dir, repo, git_service) = setup_diverged_attempt("base.txt");
+
+            let merge_commit = git_service
+                .merge_changes(
+                    &worktree_dir.path().join("task"),
+                    "task",
+                    "main",
+                    "Merge task",
+                    strategy,
+                )
+                .unwrap();
+
+            let main = repo.find_branch("main", BranchType::Local).unwrap();
+            let head = main.get().peel_to_commit().unwrap();
+            assert_eq!(head.id().to_string(), merge_commit);
+            assert!(main_tree_has(&repo, "task.txt"), "{:?}", strategy);
+            assert!(main_tree_has(&repo, "base.txt"), "{:?}", strategy);
+
+            let expected_parents = match strategy {
+                MergeStrategy::Merge => 2,
+                _ => 1,
+            };
+            assert_eq!(head.parent_count(), expected_parents, "{:?}", strategy);
+        }
+    }
+
+    #[test]
+    fn test_keep_commits_fast_forwards_when_possible() {
+        let (temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "main", "shared.txt", "one\n");
+        repo.set_head("refs/heads/main").unwrap();
+        let git_service = GitService::new(temp_dir.path()).unwrap();
+
+        let worktree_dir = TempDir::new().unwrap();
+        let worktree_path = worktree_dir.path().join("task");
+        git_service
+            .create_worktree("task", &worktree_path, Some("main"))
+            .unwrap();
+        let worktree_repo = Repository::open(&worktree_path).unwrap();
+        let task_commit = commit_file(&worktree_repo, "task", "task.txt", "task change\n");
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.force();
+        worktree_repo.checkout_head(Some(&mut checkout)).unwrap();
+
+        let merge_commit = git_service
+            .merge_changes(
+                &worktree_path,
+                "task",
+                "main",
+                "Merge task",
+                MergeStrategy::KeepCommits,
+            )
+            .unwrap();
+        assert_eq!(merge_commit, task_commit.to_string());
+    }
+
+    #[test]
+    fn test_keep_commits_refuses_to_merge_a_diverged_base() {
+        let (_temp_dir, worktree_dir, repo, git_service) = setup_diverged_attempt("base.txt");
+        let main_before = repo.refname_to_id("refs/heads/main").unwrap();
+
+        match git_service.merge_changes(
+            &worktree_dir.path().join("task"),
+            "task",
+            "main",
+            "Merge task",
+            MergeStrategy::KeepCommits,
+        ) {
+            Err(GitServiceError::CannotFastForward(branch)) => assert_eq!(branch, "main"),
+            other => panic!("expected a fast-forward failure, got {:?}", other),
+        }
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap(), main_before);
+        assert!(!main_tree_has(&repo, "task.txt"));
+    }
+
+    #[test]
+    fn test_merge_reports_conflicting_files() {
+        // Both sides change shared.txt
+        let (_temp_dir, worktree_dir, repo, git_service) = setup_diverged_attempt("shared.txt");
+        let worktree_path = worktree_dir.path().join("task");
+        let worktree_repo = Repository::open(&worktree_path).unwrap();
+        commit_file(&worktree_repo, "task", "shared.txt", "task version\n");
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.force();
+        worktree_repo.checkout_head(Some(&mut checkout)).unwrap();
+
+        let main_before = repo.refname_to_id("refs/heads/main").unwrap();
+        for strategy in [MergeStrategy::Squash, MergeStrategy::Rebase] {
+            match git_service.merge_changes(&worktree_path, "task", "main", "Merge task", strategy)
+            {
+                Err(GitServiceError::ConflictingFiles(files)) => {
+                    assert_eq!(files, vec!["shared.txt".to_string()])
+                }
+                other => panic!("expected conflicts, got {:?}", other),
+            }
+        }
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap(), main_before);
+    }
+
     #[test]
     fn test_git_service_creation() {
         let (temp_dir, _repo) = create_test_repo();
@@ -1180,4 +1750,190 @@ mod tests {
         let branch_name = git_service.get_default_branch_name().unwrap();
         assert_eq!(branch_name, "main");
     }
+
+    /// `setup_diverged_attempt` where both sides changed shared.txt
+    fn setup_conflicting_attempt() -> (TempDir, TempDir, Repository, GitService) {
+        let (temp_dir, worktree_dir, repo, git_service) = setup_diverged_attempt("shared.txt");
+        let worktree_repo = Repository::open(worktree_dir.path().join("task")).unwrap();
+        commit_file(&worktree_repo, "task", "shared.txt", "task version\n");
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.force();
+        worktree_repo.checkout_head(Some(&mut checkout)).unwrap();
+        (temp_dir, worktree_dir, repo, git_service)
+    }
+
+    #[test]
+    fn test_parse_conflict_hunks() {
+        let content = "start\n<<<<<<< ours\na\n||||||| base\nb\n=======\nc\nc2\n>>>>>>> theirs\nmiddle\n<<<<<<< ours\nd\n=======\n>>>>>>> theirs\n";
+        let hunks = parse_conflict_hunks(content);
+
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].start_line, 2);
+        assert_eq!(hunks[0].ours, "a\n");
+        assert_eq!(hunks[0].base, "b\n");
+        assert_eq!(hunks[0].theirs, "c\nc2\n");
+        assert_eq!(hunks[1].start_line, 11);
+        assert_eq!(hunks[1].ours, "d\n");
+        assert_eq!(hunks[1].base, "");
+        assert_eq!(hunks[1].theirs, "");
+        assert!(has_conflict_markers(content));
+        assert!(!has_conflict_markers("start\nmiddle\n"));
+    }
+
+    #[test]
+    fn test_rebase_conflicts_can_be_resolved() {
+        let (_temp_dir, worktree_dir, repo, git_service) = setup_conflicting_attempt();
+        let worktree_path = worktree_dir.path().join("task");
+
+        let err = git_service
+            .rebase_branch(&worktree_path, Some("main"))
+            .unwrap_err();
+        assert!(
+            matches!(&err, GitServiceError::ConflictingFiles(files) if files == &["shared.txt"]),
+            "{}",
+            err
+        );
+
+        let report = git_service
+            .get_conflict_report(&worktree_path, "main")
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.operation, ConflictOperation::Rebase);
+        assert_eq!(report.files.len(), 1);
+        let file = &report.files[0];
+        assert_eq!(file.path, "shared.txt");
+        // Ours is the attempt's side even though git swaps them while rebasing
+        assert_eq!(file.ours.as_deref(), Some("task version\n"));
+        assert_eq!(file.base.as_deref(), Some("one\n"));
+        assert_eq!(file.theirs.as_deref(), Some("base change\n"));
+        assert_eq!(file.hunks.len(), 1);
+        assert_eq!(file.hunks[0].ours, "task version\n");
+        assert_eq!(file.hunks[0].theirs, "base change\n");
+
+        let completed = git_service
+            .resolve_conflicts(
+                &worktree_path,
+                "main",
+                &[ConflictResolution {
+                    path: "shared.txt".to_string(),
+                    content: Some("resolved\n".to_string()),
+                }],
+            )
+            .unwrap();
+        assert!(completed);
+
+        let worktree_repo = Repository::open(&worktree_path).unwrap();
+        assert_eq!(worktree_repo.state(), RepositoryState::Clean);
+        let head = worktree_repo.head().unwrap().peel_to_commit().unwrap();
+        let main_commit = repo
+            .find_branch("main", BranchType::Local)
+            .unwrap()
+            .get()
+            .peel_to_commit()
+            .unwrap();
+        assert!(worktree_repo
+            .graph_descendant_of(head.id(), main_commit.id())
+            .unwrap());
+        assert_eq!(
+            std::fs::read_to_string(worktree_path.join("shared.txt")).unwrap(),
+            "resolved\n"
+        );
+        assert!(git_service
+            .get_conflict_report(&worktree_path, "main")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_merge_conflicts_resolved_in_worktree() {
+        let (_temp_dir, worktree_dir, _repo, git_service) = setup_conflicting_attempt();
+        let worktree_path = worktree_dir.path().join("task");
+
+        let err = git_service
+            .merge_base_into_branch(&worktree_path, "main")
+            .unwrap_err();
+        assert!(
+            matches!(err, GitServiceError::ConflictingFiles(_)),
+            "{}",
+            err
+        );
+
+        // Markers still in the file keep the conflict open
+        assert!(!git_service
+            .resolve_conflicts(&worktree_path, "main", &[])
+            .unwrap());
+        let report = git_service
+            .get_conflict_report(&worktree_path, "main")
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.operation, ConflictOperation::Merge);
+        assert_eq!(report.files[0].hunks[0].ours, "task version\n");
+
+        // Edited by hand, as a coding agent would
+        std::fs::write(worktree_path.join("shared.txt"), "both\n").unwrap();
+        assert!(git_service
+            .resolve_conflicts(&worktree_path, "main", &[])
+            .unwrap());
+
+        let worktree_repo = Repository::open(&worktree_path).unwrap();
+        assert_eq!(worktree_repo.state(), RepositoryState::Clean);
+        let head = worktree_repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+        assert_eq!(head.message(), Some("Merge branch 'main' into task"));
+    }
+
+    #[test]
+    fn test_abort_conflict_resolution_restores_branch() {
+        let (_temp_dir, worktree_dir, _repo, git_service) = setup_conflicting_attempt();
+        let worktree_path = worktree_dir.path().join("task");
+        let worktree_repo = Repository::open(&worktree_path).unwrap();
+        let before = worktree_repo.head().unwrap().target().unwrap();
+
+        assert!(git_service
+            .rebase_branch(&worktree_path, Some("main"))
+            .is_err());
+        git_service
+            .abort_conflict_resolution(&worktree_path)
+            .unwrap();
+
+        assert_eq!(worktree_repo.state(), RepositoryState::Clean);
+        assert_eq!(worktree_repo.head().unwrap().target().unwrap(), before);
+        assert_eq!(
+            std::fs::read_to_string(worktree_path.join("shared.txt")).unwrap(),
+            "task version\n"
+        );
+        assert!(matches!(
+            git_service.resolve_conflicts(&worktree_path, "main", &[]),
+            Err(GitServiceError::NoConflictResolution)
+        ));
+    }
+
+    #[test]
+    fn test_push_branch_leaves_origin_alone() {
+        let (_temp_dir, worktree_dir, repo, git_service) = setup_diverged_attempt("other.txt");
+        let worktree_path = worktree_dir.path().join("task");
+        repo.remote("origin", "git@gitlab.example.com:team/app.git")
+            .unwrap();
+
+        let remote_dir = TempDir::new().unwrap();
+        let remote = Repository::init_bare(remote_dir.path()).unwrap();
+        git_service
+            .push_branch(
+                &worktree_path,
+                "task",
+                remote_dir.path().to_str().unwrap(),
+                "oauth2",
+                "token",
+            )
+            .unwrap();
+
+        let pushed = remote.find_branch("task", BranchType::Local).unwrap();
+        let local = Repository::open(&worktree_path).unwrap();
+        assert_eq!(pushed.get().target(), local.head().unwrap().target());
+        assert_eq!(
+            git_service.get_remote_url().unwrap(),
+            "git@gitlab.example.com:team/app.git"
+        );
+        assert_eq!(repo.remotes().unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,269 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    models::config::{GitHostConfig, GitProviderKind},
+    services::git_provider::{
+        CreatePrRequest, GitProvider, GitProviderError, GitRemote, PullRequestInfo, RestClient,
+    },
+};
+
+/// Pull requests on a Gitea (or Forgejo) server, through its REST API (v1)
+#[derive(Debug, Clone)]
+pub struct GiteaService {
+    client: RestClient,
+    web_url: String,
+    username: String,
+    token: String,
+}
+
+#[derive(Serialize)]
+struct CreatePullRequest<'a> {
+    head: &'a str,
+    base: &'a str,
+    title: &'a str,
+    body: &'a str,
+}
+
+#[derive(Deserialize)]
+struct PullRequest {
+    number: i64,
+    html_url: String,
+    state: String, // open or closed
+    merged: bool,
+    merged_at: Option<DateTime<Utc>>,
+    merge_commit_sha: Option<String>,
+}
+
+impl From<PullRequest> for PullRequestInfo {
+    fn from(pr: PullRequest) -> Self {
+        let status = match pr.state.as_str() {
+            _ if pr.merged => "merged",
+            "open" => "open",
+            "closed" => "closed",
+            _ => "unknown",
+        };
+        PullRequestInfo {
+            number: pr.number,
+            url: pr.html_url,
+            status: status.to_string(),
+            merged: pr.merged,
+            merged_at: pr.merged_at,
+            merge_commit_sha: pr.merge_commit_sha,
+        }
+    }
+}
+
+impl GiteaService {
+    pub fn new(host: &GitHostConfig, token: &str) -> Result<Self, GitProviderError> {
+        let mut headers = HeaderMap::new();
+        let mut authorization =
+            HeaderValue::from_str(&format!("token {}", token)).map_err(|_| {
+                GitProviderError::NotConfigured(
+                    "The Gitea token contains invalid characters".into(),
+                )
+            })?;
+        authorization.set_sensitive(true);
+        headers.insert(AUTHORIZATION, authorization);
+
+        Ok(Self {
+            client: RestClient::new(
+                GitProviderKind::Gitea,
+                format!("{}/api/v1", host.web_url()),
+                headers,
+            )?,
+            web_url: host.web_url().to_string(),
+            // Gitea takes a token as the password whatever the user name
+            username: host.username.clone().unwrap_or_else(|| "git".to_string()),
+            token: token.to_string(),
+        })
+    }
+
+    fn repo_path(remote: &GitRemote) -> String {
+        format!(
+            "/repos/{}/{}",
+            urlencoding::encode(remote.owner()),
+            urlencoding::encode(remote.name())
+        )
+    }
+}
+
+#[async_trait]
+impl GitProvider for GiteaService {
+    fn kind(&self) -> GitProviderKind {
+        GitProviderKind::Gitea
+    }
+
+    fn push_url(&self, remote: &GitRemote) -> String {
+        format!("{}/{}.git", self.web_url, remote.path)
+    }
+
+    fn push_credentials(&self) -> (&str, &str) {
+        (&self.username, &self.token)
+    }
+
+    async fn create_pull_request(
+        &self,
+        remote: &GitRemote,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitProviderError> {
+        let pr: PullRequest = self
+            .client
+            .post(
+                &format!("{}/pulls", Self::repo_path(remote)),
+                &CreatePullRequest {
+                    head: &request.head_branch,
+                    base: &request.base_branch,
+                    title: &request.title,
+                    body: request.body.as_deref().unwrap_or(""),
+                },
+            )
+            .await?;
+
+        info!(
+            "Created Gitea pull request #{} for branch {} in {}",
+            pr.number, request.head_branch, remote.path
+        );
+        Ok(pr.into())
+    }
+
+    async fn get_pull_request(
+        &self,
+        remote: &GitRemote,
+        number: i64,
+    ) -> Result<PullRequestInfo, GitProviderError> {
+        let pr: PullRequest = self
+            .client
+            .get(&format!("{}/pulls/{}", Self::repo_path(remote), number))
+            .await?;
+        Ok(pr.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        extract::Path,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::services::git_provider::serve_mock_api;
+
+    fn authorized(headers: &HeaderMap) -> Result<(), StatusCode> {
+        match headers.get("authorization") {
+            Some(value) if value == "token gitea-test" => Ok(()),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    async fn mock_gitea() -> String {
+        let router = Router::new()
+            .route(
+                "/api/v1/repos/:owner/:repo/pulls",
+                post(
+                    |Path((owner, repo)): Path<(String, String)>,
+                     headers: HeaderMap,
+                     Json(body): Json<Value>| async move {
+                        authorized(&headers).map_err(|status| (status, Json(json!({}))))?;
+                        assert_eq!((owner.as_str(), repo.as_str()), ("team", "app"));
+                        if body["base"] == "missing" {
+                            return Err((
+                                StatusCode::NOT_FOUND,
+                                Json(json!({ "message": "The target couldn't be found." })),
+                            ));
+                        }
+                        assert_eq!(body["head"], "vk-1234-add-thing");
+                        Ok(Json(json!({
+                            "number": 3,
+                            "html_url": "http://gitea.local:3000/team/app/pulls/3",
+                            "state": "open",
+                            "merged": false,
+                            "merged_at": null,
+                            "merge_commit_sha": null,
+                        })))
+                    },
+                ),
+            )
+            .route(
+                "/api/v1/repos/:owner/:repo/pulls/:number",
+                get(
+                    |Path((_, _, number)): Path<(String, String, i64)>| async move {
+                        Json(json!({
+                            "number": number,
+                            "html_url": "http://gitea.local:3000/team/app/pulls/3",
+                            "state": "closed",
+                            "merged": true,
+                            "merged_at": "2025-07-30T12:00:00+02:00",
+                            "merge_commit_sha": "def456",
+                        }))
+                    },
+                ),
+            );
+        serve_mock_api(router).await
+    }
+
+    #[tokio::test]
+    async fn test_pull_requests_against_mock_server() {
+        let base_url = mock_gitea().await;
+        let service = |token: &str| {
+            GiteaService::new(
+                &GitHostConfig {
+                    provider: GitProviderKind::Gitea,
+                    base_url: base_url.clone(),
+                    token: Some(token.to_string()),
+                    username: Some("alice".to_string()),
+                },
+                token,
+            )
+            .unwrap()
+        };
+        let gitea = service("gitea-test");
+        let remote = GitRemote::parse("ssh://git@gitea.local:2222/team/app.git").unwrap();
+        assert_eq!(gitea.push_credentials(), ("alice", "gitea-test"));
+        assert_eq!(
+            gitea.push_url(&remote),
+            format!("{}/team/app.git", base_url)
+        );
+
+        let mut request = CreatePrRequest {
+            title: "Add the thing".to_string(),
+            body: None,
+            head_branch: "vk-1234-add-thing".to_string(),
+            base_branch: "main".to_string(),
+        };
+        let created = gitea.create_pull_request(&remote, &request).await.unwrap();
+        assert_eq!(created.number, 3);
+        assert_eq!(created.status, "open");
+
+        // Gitea reports merged pull requests as closed
+        let merged = gitea.get_pull_request(&remote, 3).await.unwrap();
+        assert_eq!(merged.status, "merged");
+        assert_eq!(merged.merge_commit_sha.as_deref(), Some("def456"));
+        assert_eq!(
+            merged.merged_at.unwrap().to_rfc3339(),
+            "2025-07-30T10:00:00+00:00"
+        );
+
+        request.base_branch = "missing".to_string();
+        match gitea.create_pull_request(&remote, &request).await {
+            Err(GitProviderError::Api { status, message }) => {
+                assert_eq!(status, 404);
+                assert_eq!(message, "The target couldn't be found.");
+            }
+            other => panic!("expected an API error, got {:?}", other.map(|pr| pr.url)),
+        }
+        assert!(matches!(
+            service("wrong")
+                .create_pull_request(&remote, &request)
+                .await,
+            Err(GitProviderError::TokenInvalid(GitProviderKind::Gitea))
+        ));
+    }
+}
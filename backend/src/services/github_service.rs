@@ -1,10 +1,17 @@
 use std::time::Duration;
 
+use async_trait::async_trait;
 use octocrab::{Octocrab, OctocrabBuilder};
-use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 use tracing::{info, warn};
 
+use crate::{
+    models::config::{GitHostConfig, GitProviderKind},
+    services::git_provider::{
+        CreatePrRequest, GitProvider, GitProviderError, GitRemote, PullRequestInfo,
+    },
+};
+
 #[derive(Debug)]
 pub enum GitHubServiceError {
     Client(octocrab::Error),
@@ -57,28 +64,12 @@ pub struct GitHubRepoInfo {
     pub repo_name: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct CreatePrRequest {
-    pub title: String,
-    pub body: Option<String>,
-    pub head_branch: String,
-    pub base_branch: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PullRequestInfo {
-    pub number: i64,
-    pub url: String,
-    pub status: String,
-    pub merged: bool,
-    pub merged_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub merge_commit_sha: Option<String>,
-}
-
 #[derive(Debug, Clone)]
 pub struct GitHubService {
     client: Octocrab,
     retry_config: RetryConfig,
+    token: String,
+    web_url: String, // https://github.com, or a GitHub Enterprise server
 }
 
 #[derive(Debug, Clone)]
@@ -111,6 +102,25 @@ impl GitHubService {
         Ok(Self {
             client,
             retry_config: RetryConfig::default(),
+            token: github_token.to_string(),
+            web_url: "https://github.com".to_string(),
+        })
+    }
+
+    /// Create a service for a GitHub Enterprise server
+    pub fn for_host(host: &GitHostConfig, github_token: &str) -> Result<Self, GitHubServiceError> {
+        let client = OctocrabBuilder::new()
+            .base_uri(format!("{}/api/v3", host.web_url()))
+            .and_then(|builder| builder.personal_token(github_token.to_string()).build())
+            .map_err(|e| {
+                GitHubServiceError::Auth(format!("Failed to create GitHub client: {}", e))
+            })?;
+
+        Ok(Self {
+            client,
+            retry_config: RetryConfig::default(),
+            token: github_token.to_string(),
+            web_url: host.web_url().to_string(),
         })
     }
 
@@ -305,3 +315,47 @@ impl GitHubService {
         Err(last_error.unwrap())
     }
 }
+
+#[async_trait]
+impl GitProvider for GitHubService {
+    fn kind(&self) -> GitProviderKind {
+        GitProviderKind::GitHub
+    }
+
+    fn push_url(&self, remote: &GitRemote) -> String {
+        format!("{}/{}.git", self.web_url, remote.path)
+    }
+
+    fn push_credentials(&self) -> (&str, &str) {
+        ("git", &self.token)
+    }
+
+    async fn create_pull_request(
+        &self,
+        remote: &GitRemote,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitProviderError> {
+        Ok(self
+            .create_pr(&GitHubRepoInfo::from(remote), request)
+            .await?)
+    }
+
+    async fn get_pull_request(
+        &self,
+        remote: &GitRemote,
+        number: i64,
+    ) -> Result<PullRequestInfo, GitProviderError> {
+        Ok(self
+            .update_pr_status(&GitHubRepoInfo::from(remote), number)
+            .await?)
+    }
+}
+
+impl From<&GitRemote> for GitHubRepoInfo {
+    fn from(remote: &GitRemote) -> Self {
+        Self {
+            owner: remote.owner().to_string(),
+            repo_name: remote.name().to_string(),
+        }
+    }
+}
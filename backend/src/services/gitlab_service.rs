@@ -0,0 +1,262 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::{
+    models::config::{GitHostConfig, GitProviderKind},
+    services::git_provider::{
+        CreatePrRequest, GitProvider, GitProviderError, GitRemote, PullRequestInfo, RestClient,
+    },
+};
+
+/// Merge requests on a GitLab server, through its REST API (v4)
+#[derive(Debug, Clone)]
+pub struct GitLabService {
+    client: RestClient,
+    web_url: String,
+    username: String,
+    token: String,
+}
+
+#[derive(Serialize)]
+struct CreateMergeRequest<'a> {
+    source_branch: &'a str,
+    target_branch: &'a str,
+    title: &'a str,
+    description: &'a str,
+    remove_source_branch: bool,
+}
+
+#[derive(Deserialize)]
+struct MergeRequest {
+    iid: i64,
+    web_url: String,
+    state: String, // opened, closed, locked or merged
+    merged_at: Option<DateTime<Utc>>,
+    merge_commit_sha: Option<String>,
+    squash_commit_sha: Option<String>,
+}
+
+impl From<MergeRequest> for PullRequestInfo {
+    fn from(mr: MergeRequest) -> Self {
+        let status = match mr.state.as_str() {
+            "opened" => "open",
+            "merged" => "merged",
+            "closed" | "locked" => "closed",
+            _ => "unknown",
+        };
+        PullRequestInfo {
+            number: mr.iid,
+            url: mr.web_url,
+            status: status.to_string(),
+            merged: status == "merged",
+            merged_at: mr.merged_at,
+            // Squashed merge requests may land as the squash commit alone
+            merge_commit_sha: mr.merge_commit_sha.or(mr.squash_commit_sha),
+        }
+    }
+}
+
+impl GitLabService {
+    pub fn new(host: &GitHostConfig, token: &str) -> Result<Self, GitProviderError> {
+        let mut headers = HeaderMap::new();
+        let mut token_header = HeaderValue::from_str(token).map_err(|_| {
+            GitProviderError::NotConfigured("The GitLab token contains invalid characters".into())
+        })?;
+        token_header.set_sensitive(true);
+        headers.insert("PRIVATE-TOKEN", token_header);
+
+        Ok(Self {
+            client: RestClient::new(
+                GitProviderKind::GitLab,
+                format!("{}/api/v4", host.web_url()),
+                headers,
+            )?,
+            web_url: host.web_url().to_string(),
+            // Any user name works with a personal access token, this is the documented one
+            username: host
+                .username
+                .clone()
+                .unwrap_or_else(|| "oauth2".to_string()),
+            token: token.to_string(),
+        })
+    }
+
+    fn project_path(remote: &GitRemote) -> String {
+        format!("/projects/{}", urlencoding::encode(&remote.path))
+    }
+}
+
+#[async_trait]
+impl GitProvider for GitLabService {
+    fn kind(&self) -> GitProviderKind {
+        GitProviderKind::GitLab
+    }
+
+    fn push_url(&self, remote: &GitRemote) -> String {
+        format!("{}/{}.git", self.web_url, remote.path)
+    }
+
+    fn push_credentials(&self) -> (&str, &str) {
+        (&self.username, &self.token)
+    }
+
+    async fn create_pull_request(
+        &self,
+        remote: &GitRemote,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitProviderError> {
+        let mr: MergeRequest = self
+            .client
+            .post(
+                &format!("{}/merge_requests", Self::project_path(remote)),
+                &CreateMergeRequest {
+                    source_branch: &request.head_branch,
+                    target_branch: &request.base_branch,
+                    title: &request.title,
+                    description: request.body.as_deref().unwrap_or(""),
+                    remove_source_branch: false,
+                },
+            )
+            .await?;
+
+        info!(
+            "Created GitLab merge request !{} for branch {} in {}",
+            mr.iid, request.head_branch, remote.path
+        );
+        Ok(mr.into())
+    }
+
+    async fn get_pull_request(
+        &self,
+        remote: &GitRemote,
+        number: i64,
+    ) -> Result<PullRequestInfo, GitProviderError> {
+        let mr: MergeRequest = self
+            .client
+            .get(&format!(
+                "{}/merge_requests/{}",
+                Self::project_path(remote),
+                number
+            ))
+            .await?;
+        Ok(mr.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        extract::Path,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::services::git_provider::serve_mock_api;
+
+    fn authorized(headers: &HeaderMap) -> Result<(), StatusCode> {
+        match headers.get("PRIVATE-TOKEN") {
+            Some(token) if token == "glpat-test" => Ok(()),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    async fn mock_gitlab() -> String {
+        let router = Router::new()
+            .route(
+                "/api/v4/projects/:project/merge_requests",
+                post(
+                    |Path(project): Path<String>,
+                     headers: HeaderMap,
+                     Json(body): Json<Value>| async move {
+                        authorized(&headers)?;
+                        assert_eq!(project, "group/sub/app");
+                        if body["source_branch"] == "taken" {
+                            return Err(StatusCode::CONFLICT);
+                        }
+                        assert_eq!(body["target_branch"], "main");
+                        assert_eq!(body["description"], "Adds the thing");
+                        Ok(Json(json!({
+                            "iid": 7,
+                            "web_url": "https://gitlab.example.com/group/sub/app/-/merge_requests/7",
+                            "state": "opened",
+                            "merged_at": null,
+                            "merge_commit_sha": null,
+                            "squash_commit_sha": null,
+                        })))
+                    },
+                ),
+            )
+            .route(
+                "/api/v4/projects/:project/merge_requests/:iid",
+                get(
+                    |Path((_, iid)): Path<(String, i64)>, headers: HeaderMap| async move {
+                        authorized(&headers)?;
+                        Ok::<_, StatusCode>(Json(json!({
+                            "iid": iid,
+                            "web_url": "https://gitlab.example.com/group/sub/app/-/merge_requests/7",
+                            "state": "merged",
+                            "merged_at": "2025-07-30T10:15:00.000Z",
+                            "merge_commit_sha": null,
+                            "squash_commit_sha": "abc123",
+                        })))
+                    },
+                ),
+            );
+        serve_mock_api(router).await
+    }
+
+    fn service(base_url: &str, token: &str) -> GitLabService {
+        GitLabService::new(
+            &GitHostConfig {
+                provider: GitProviderKind::GitLab,
+                base_url: format!("{}/", base_url),
+                token: Some(token.to_string()),
+                username: None,
+            },
+            token,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_merge_requests_against_mock_server() {
+        let base_url = mock_gitlab().await;
+        let gitlab = service(&base_url, "glpat-test");
+        let remote = GitRemote::parse("git@gitlab.example.com:group/sub/app.git").unwrap();
+        let mut request = CreatePrRequest {
+            title: "Add the thing".to_string(),
+            body: Some("Adds the thing".to_string()),
+            head_branch: "vk-1234-add-thing".to_string(),
+            base_branch: "main".to_string(),
+        };
+
+        let created = gitlab.create_pull_request(&remote, &request).await.unwrap();
+        assert_eq!(created.number, 7);
+        assert_eq!(created.status, "open");
+        assert!(!created.merged);
+
+        let merged = gitlab.get_pull_request(&remote, 7).await.unwrap();
+        assert_eq!(merged.status, "merged");
+        assert!(merged.merged);
+        assert_eq!(merged.merge_commit_sha.as_deref(), Some("abc123"));
+        assert!(merged.merged_at.is_some());
+
+        request.head_branch = "taken".to_string();
+        assert!(matches!(
+            gitlab.create_pull_request(&remote, &request).await,
+            Err(GitProviderError::Api { status: 409, .. })
+        ));
+        assert!(matches!(
+            service(&base_url, "expired")
+                .get_pull_request(&remote, 7)
+                .await,
+            Err(GitProviderError::TokenInvalid(GitProviderKind::GitLab))
+        ));
+    }
+}
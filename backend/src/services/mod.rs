@@ -1,13 +1,27 @@
 pub mod analytics;
+pub mod auth_service;
+pub mod auto_fix_service;
+pub mod git_provider;
 pub mod git_service;
+pub mod gitea_service;
 pub mod github_service;
+pub mod gitlab_service;
 pub mod notification_service;
 pub mod pr_monitor;
 pub mod process_service;
+pub mod secret_service;
+pub mod webhook_service;
 
 pub use analytics::{generate_user_id, AnalyticsConfig, AnalyticsService};
+pub use auth_service::AuthService;
+pub use auto_fix_service::AutoFixService;
+pub use git_provider::{CreatePrRequest, GitProviderError, HostedRepo};
 pub use git_service::{GitService, GitServiceError};
-pub use github_service::{CreatePrRequest, GitHubRepoInfo, GitHubService, GitHubServiceError};
+pub use gitea_service::GiteaService;
+pub use github_service::{GitHubService, GitHubServiceError};
+pub use gitlab_service::GitLabService;
 pub use notification_service::{NotificationConfig, NotificationService};
 pub use pr_monitor::PrMonitorService;
 pub use process_service::ProcessService;
+pub use secret_service::{SecretError, SecretRedactor, SecretService};
+pub use webhook_service::WebhookService;
@@ -10,25 +10,23 @@ use crate::{
         config::Config,
         task::{Task, TaskStatus},
         task_attempt::TaskAttempt,
+        webhook::WebhookEvent,
     },
-    services::{GitHubRepoInfo, GitHubService, GitService},
+    services::{GitProviderError, HostedRepo, WebhookService},
 };
 
-/// Service to monitor GitHub PRs and update task status when they are merged
+/// Service to monitor pull requests and update task status when they are merged
 pub struct PrMonitorService {
     pool: SqlitePool,
     poll_interval: Duration,
 }
 
-#[derive(Debug)]
 pub struct PrInfo {
     pub attempt_id: Uuid,
     pub task_id: Uuid,
     pub project_id: Uuid,
     pub pr_number: i64,
-    pub repo_owner: String,
-    pub repo_name: String,
-    pub github_token: String,
+    pub repo: HostedRepo,
 }
 
 impl PrMonitorService {
@@ -51,35 +49,20 @@ impl PrMonitorService {
         loop {
             interval.tick().await;
 
-            // Get GitHub token from config
-            let github_token = {
-                let config_read = config.read().await;
-                if config_read.github.pat.is_some() {
-                    config_read.github.pat.clone()
-                } else {
-                    config_read.github.token.clone()
-                }
-            };
-
-            match github_token {
-                Some(token) => {
-                    if let Err(e) = self.check_all_open_prs_with_token(&token).await {
-                        error!("Error checking PRs: {}", e);
-                    }
-                }
-                None => {
-                    debug!("No GitHub token configured, skipping PR monitoring");
-                }
+            // Re-read the config, credentials may have changed since the last check
+            let current_config = config.read().await.clone();
+            if let Err(e) = self.check_all_open_prs(&current_config).await {
+                error!("Error checking PRs: {}", e);
             }
         }
     }
 
-    /// Check all open PRs for updates with the provided GitHub token
-    async fn check_all_open_prs_with_token(
+    /// Check all open PRs for updates with the credentials in `config`
+    async fn check_all_open_prs(
         &self,
-        github_token: &str,
+        config: &Config,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let open_prs = self.get_open_prs_with_token(github_token).await?;
+        let open_prs = self.get_open_prs(config).await?;
 
         if open_prs.is_empty() {
             debug!("No open PRs to check");
@@ -100,11 +83,8 @@ impl PrMonitorService {
         Ok(())
     }
 
-    /// Get all task attempts with open PRs using the provided GitHub token
-    async fn get_open_prs_with_token(
-        &self,
-        github_token: &str,
-    ) -> Result<Vec<PrInfo>, sqlx::Error> {
+    /// Get all task attempts with open PRs whose host we have credentials for
+    async fn get_open_prs(&self, config: &Config) -> Result<Vec<PrInfo>, sqlx::Error> {
         let rows = sqlx::query!(
             r#"SELECT 
                 ta.id as "attempt_id!: Uuid",
@@ -124,30 +104,23 @@ impl PrMonitorService {
         let mut pr_infos = Vec::new();
 
         for row in rows {
-            // Get GitHub repo info from local git repository
-            match GitService::new(&row.git_repo_path) {
-                Ok(git_service) => match git_service.get_github_repo_info() {
-                    Ok((owner, repo_name)) => {
-                        pr_infos.push(PrInfo {
-                            attempt_id: row.attempt_id,
-                            task_id: row.task_id,
-                            project_id: row.project_id,
-                            pr_number: row.pr_number,
-                            repo_owner: owner,
-                            repo_name,
-                            github_token: github_token.to_string(),
-                        });
-                    }
-                    Err(e) => {
-                        warn!(
-                            "Could not extract repo info from git path {}: {}",
-                            row.git_repo_path, e
-                        );
-                    }
-                },
+            match HostedRepo::for_repo(config, &row.git_repo_path) {
+                Ok(repo) => pr_infos.push(PrInfo {
+                    attempt_id: row.attempt_id,
+                    task_id: row.task_id,
+                    project_id: row.project_id,
+                    pr_number: row.pr_number,
+                    repo,
+                }),
+                Err(GitProviderError::NotConfigured(reason)) => {
+                    debug!(
+                        "Skipping PR #{} of attempt {}: {}",
+                        row.pr_number, row.attempt_id, reason
+                    );
+                }
                 Err(e) => {
                     warn!(
-                        "Could not create git service for path {}: {}",
+                        "Could not tell where {} is hosted: {}",
                         row.git_repo_path, e
                     );
                 }
@@ -162,15 +135,10 @@ impl PrMonitorService {
         &self,
         pr_info: &PrInfo,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let github_service = GitHubService::new(&pr_info.github_token)?;
-
-        let repo_info = GitHubRepoInfo {
-            owner: pr_info.repo_owner.clone(),
-            repo_name: pr_info.repo_name.clone(),
-        };
-
-        let pr_status = github_service
-            .update_pr_status(&repo_info, pr_info.pr_number)
+        let pr_status = pr_info
+            .repo
+            .provider
+            .get_pull_request(&pr_info.repo.remote, pr_info.pr_number)
             .await?;
 
         debug!(
@@ -206,6 +174,22 @@ impl PrMonitorService {
                     TaskStatus::Done,
                 )
                 .await?;
+
+                WebhookService::dispatch(
+                    &self.pool,
+                    WebhookEvent::PrMerged,
+                    pr_info.project_id,
+                    pr_info.task_id,
+                    Some(pr_info.attempt_id),
+                    serde_json::json!({
+                        "pr_number": pr_info.pr_number,
+                        "repo": pr_info.repo.remote.path,
+                        "provider": pr_info.repo.provider.kind(),
+                        "merge_commit": merge_commit_sha,
+                        "merged_at": pr_status.merged_at,
+                    }),
+                )
+                .await;
             }
         }
 
@@ -1,16 +1,27 @@
+use std::collections::{hash_map::Entry, HashMap};
+
 use sqlx::SqlitePool;
 use tracing::{debug, info};
 use uuid::Uuid;
 
 use crate::{
     executor::Executor,
+    executors::sandbox::{repository_git_dir, ContainerSandbox, SandboxContext},
     models::{
-        execution_process::{CreateExecutionProcess, ExecutionProcess, ExecutionProcessType},
+        execution_process::{
+            AutoFixOf, CreateExecutionProcess, ExecutionProcess, ExecutionProcessStatus,
+            ExecutionProcessType, QueuedExecution,
+        },
+        executor_profile::ExecutorProfile,
         executor_session::{CreateExecutorSession, ExecutorSession},
         project::Project,
+        project_check::{CheckStatus, ProjectCheck},
+        project_env_var::{ProjectEnvVar, ProjectEnvVarError},
         task::Task,
         task_attempt::{TaskAttempt, TaskAttemptError},
+        webhook::WebhookEvent,
     },
+    services::WebhookService,
     utils::shell::get_shell_command,
 };
 
@@ -25,7 +36,7 @@ impl ProcessService {
         attempt_id: Uuid,
         task_id: Uuid,
         project_id: Uuid,
-        operation: &str, // "dev_server", "coding_agent", or "followup"
+        operation: &str, // "dev_server", "coding_agent", "followup" or "checks"
         operation_params: Option<serde_json::Value>,
     ) -> Result<(), TaskAttemptError> {
         // Check if setup is completed for this worktree
@@ -60,6 +71,10 @@ impl ProcessService {
                 "coding_agent" => {
                     Self::start_coding_agent(pool, app_state, attempt_id, task_id, project_id).await
                 }
+                "checks" => {
+                    Self::start_checks_direct(pool, app_state, attempt_id, task_id, project_id)
+                        .await
+                }
                 "followup" => {
                     let prompt = operation_params
                         .as_ref()
@@ -67,7 +82,7 @@ impl ProcessService {
                         .and_then(|p| p.as_str())
                         .unwrap_or("");
                     Self::start_followup_execution_direct(
-                        pool, app_state, attempt_id, task_id, project_id, prompt,
+                        pool, app_state, attempt_id, task_id, project_id, prompt, None,
                     )
                     .await
                     .map(|_| ())
@@ -173,6 +188,16 @@ impl ProcessService {
         // Update task status to indicate execution has started
         Task::update_status(pool, task_id, project_id, TaskStatus::InProgress).await?;
 
+        WebhookService::dispatch(
+            pool,
+            WebhookEvent::AttemptStarted,
+            project_id,
+            task_id,
+            Some(attempt_id),
+            serde_json::json!({ "attempt": task_attempt }),
+        )
+        .await;
+
         // Determine execution sequence based on project configuration
         if Self::should_run_setup_script(&project) {
             Self::start_setup_script(
@@ -270,12 +295,25 @@ impl ProcessService {
             ));
         }
 
+        let port = Self::allocate_dev_server_port(pool).await?;
+        let settings = project.dev_server_settings.0;
+        tracing::info!(
+            "Allocated port {} to the dev server of attempt {}",
+            port,
+            attempt_id
+        );
+
         let result = Self::start_process_execution(
             pool,
             app_state,
             attempt_id,
             task_id,
-            crate::executor::ExecutorType::DevServer(dev_script),
+            crate::executor::ExecutorType::DevServer {
+                script: dev_script,
+                port,
+                env_vars: settings.render_env(port, attempt_id),
+                ready_pattern: settings.ready_pattern,
+            },
             "Starting dev server".to_string(),
             ExecutionProcessType::DevServer,
             &worktree_path,
@@ -298,6 +336,77 @@ impl ProcessService {
         result
     }
 
+    /// Run the project's check scripts for this task attempt (with automatic setup)
+    pub async fn start_checks(
+        pool: &SqlitePool,
+        app_state: &crate::app_state::AppState,
+        attempt_id: Uuid,
+        task_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<(), TaskAttemptError> {
+        if ProjectCheck::find_by_project_id(pool, project_id)
+            .await?
+            .is_empty()
+        {
+            return Err(TaskAttemptError::ValidationError(
+                "No checks configured for this project".to_string(),
+            ));
+        }
+
+        TaskAttempt::ensure_worktree_exists(pool, attempt_id, project_id, "checks").await?;
+
+        Self::auto_setup_and_execute(
+            pool, app_state, attempt_id, task_id, project_id, "checks", None,
+        )
+        .await
+    }
+
+    /// Run the project's check scripts in the attempt's worktree, all in parallel,
+    /// without setup check (internal method)
+    pub async fn start_checks_direct(
+        pool: &SqlitePool,
+        app_state: &crate::app_state::AppState,
+        attempt_id: Uuid,
+        task_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<(), TaskAttemptError> {
+        let checks = ProjectCheck::find_by_project_id(pool, project_id).await?;
+        let worktree_path =
+            TaskAttempt::ensure_worktree_exists(pool, attempt_id, project_id, "checks").await?;
+
+        let head_commit = ProjectCheck::attempt_head_commit(pool, project_id, attempt_id).await?;
+
+        // Don't start a second run of a check that is still going
+        let running: Vec<Uuid> =
+            ProjectCheck::find_results_for_attempt(pool, project_id, attempt_id)
+                .await?
+                .into_iter()
+                .filter(|result| result.status == CheckStatus::Running)
+                .map(|result| result.check_id)
+                .collect();
+
+        for check in checks.into_iter().filter(|c| !running.contains(&c.id)) {
+            Self::start_process_execution(
+                pool,
+                app_state,
+                attempt_id,
+                task_id,
+                crate::executor::ExecutorType::CheckScript {
+                    check_id: check.id,
+                    name: check.name.clone(),
+                    script: check.script,
+                    head_commit: head_commit.clone(),
+                },
+                format!("check '{}'", check.name),
+                ExecutionProcessType::CheckScript,
+                &worktree_path,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
     /// Start a follow-up execution using the same executor type as the first process (with automatic setup)
     /// Returns the attempt_id that was actually used (always the original attempt_id for session continuity)
     pub async fn start_followup_execution(
@@ -358,6 +467,7 @@ impl ProcessService {
     }
 
     /// Start a follow-up execution directly without setup check (internal method)
+    /// `auto_fix` links the follow-up to the failed process it was started to fix
     pub async fn start_followup_execution_direct(
         pool: &SqlitePool,
         app_state: &crate::app_state::AppState,
@@ -365,6 +475,7 @@ impl ProcessService {
         task_id: Uuid,
         project_id: Uuid,
         prompt: &str,
+        auto_fix: Option<AutoFixOf>,
     ) -> Result<Uuid, TaskAttemptError> {
         // Ensure worktree exists (recreate if needed for cold task support)
         // This will resurrect the worktree at the exact same path for session continuity
@@ -374,7 +485,7 @@ impl ProcessService {
         // Find the most recent coding agent execution process to get the executor type
         // Look up processes from the ORIGINAL attempt to find the session
         let execution_processes =
-            ExecutionProcess::find_by_task_attempt_id(pool, attempt_id).await?;
+            ExecutionProcess::find_summaries_by_task_attempt_id(pool, attempt_id).await?;
         let most_recent_coding_agent = execution_processes
             .iter()
             .rev() // Reverse to get most recent first (since they're ordered by created_at ASC)
@@ -405,11 +516,13 @@ impl ProcessService {
                     )
                 })?;
 
+        // Custom executors can be removed from executors.json since the attempt started
         let executor_config: crate::executor::ExecutorConfig = match most_recent_coding_agent
             .executor_type
             .as_deref()
+            .map(str::parse)
         {
-            Some(executor_str) => executor_str.parse().unwrap(),
+            Some(Ok(config)) => config,
             _ => {
                 tracing::error!(
                                     "Invalid or missing executor type '{}' for execution process {} (task attempt {})",
@@ -449,7 +562,7 @@ impl ProcessService {
         };
 
         // Try to start the follow-up execution
-        let execution_result = Self::start_process_execution(
+        let execution_result = Self::start_process_execution_with_auto_fix(
             pool,
             app_state,
             attempt_id,
@@ -458,6 +571,7 @@ impl ProcessService {
             "Starting follow-up executor".to_string(),
             ExecutionProcessType::CodingAgent,
             &worktree_path,
+            auto_fix,
         )
         .await;
 
@@ -474,7 +588,7 @@ impl ProcessService {
             // Create a new session instead of trying to resume
             let new_session_executor = crate::executor::ExecutorType::CodingAgent(executor_config);
 
-            Self::start_process_execution(
+            Self::start_process_execution_with_auto_fix(
                 pool,
                 app_state,
                 attempt_id,
@@ -483,6 +597,7 @@ impl ProcessService {
                 "Starting new executor session (follow-up session failed)".to_string(),
                 ExecutionProcessType::CodingAgent,
                 &worktree_path,
+                auto_fix,
             )
             .await?;
         } else {
@@ -504,8 +619,46 @@ impl ProcessService {
         activity_note: String,
         process_type: ExecutionProcessType,
         worktree_path: &str,
+    ) -> Result<(), TaskAttemptError> {
+        Self::start_process_execution_with_auto_fix(
+            pool,
+            app_state,
+            attempt_id,
+            task_id,
+            executor_type,
+            activity_note,
+            process_type,
+            worktree_path,
+            None,
+        )
+        .await
+    }
+
+    /// Start a process execution, recording the failed process it auto-fixes on its record
+    #[allow(clippy::too_many_arguments)]
+    async fn start_process_execution_with_auto_fix(
+        pool: &SqlitePool,
+        app_state: &crate::app_state::AppState,
+        attempt_id: Uuid,
+        task_id: Uuid,
+        executor_type: crate::executor::ExecutorType,
+        activity_note: String,
+        process_type: ExecutionProcessType,
+        worktree_path: &str,
+        auto_fix: Option<AutoFixOf>,
     ) -> Result<(), TaskAttemptError> {
         let process_id = Uuid::new_v4();
+        let is_coding_agent = matches!(process_type, ExecutionProcessType::CodingAgent);
+
+        // Coding agents are subject to the concurrency limits: hold the queue lock
+        // until the record exists so the capacity check and the insert are atomic
+        let queue_guard = if is_coding_agent {
+            Some(app_state.lock_execution_queue().await)
+        } else {
+            None
+        };
+        let queued =
+            is_coding_agent && !Self::has_coding_agent_capacity(pool, app_state, task_id).await?;
 
         // Create execution process record
         let _execution_process = Self::create_execution_process_record(
@@ -515,8 +668,15 @@ impl ProcessService {
             &executor_type,
             process_type.clone(),
             worktree_path,
+            if queued {
+                ExecutionProcessStatus::Queued
+            } else {
+                ExecutionProcessStatus::Running
+            },
+            auto_fix,
         )
         .await?;
+        drop(queue_guard);
 
         // Create executor session for coding agents
         if matches!(process_type, ExecutionProcessType::CodingAgent) {
@@ -537,6 +697,25 @@ impl ProcessService {
             .await?;
         }
 
+        if queued {
+            // Remember which session a queued follow-up resumes, the drain rebuilds it from here
+            if let crate::executor::ExecutorType::FollowUpCodingAgent {
+                session_id: Some(session_id),
+                ..
+            } = &executor_type
+            {
+                ExecutorSession::update_session_id(pool, process_id, session_id).await?;
+            }
+
+            tracing::info!(
+                "Queued {} for task attempt {} (execution {})",
+                activity_note,
+                attempt_id,
+                process_id
+            );
+            return Ok(());
+        }
+
         // Process started successfully
 
         tracing::info!("Starting {} for task attempt {}", activity_note, attempt_id);
@@ -564,6 +743,256 @@ impl ProcessService {
         Ok(())
     }
 
+    /// Check whether a coding agent for `task_id` may start now under the global and
+    /// per-project concurrency limits. Must be called with the execution queue lock held.
+    async fn has_coding_agent_capacity(
+        pool: &SqlitePool,
+        app_state: &crate::app_state::AppState,
+        task_id: Uuid,
+    ) -> Result<bool, TaskAttemptError> {
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(TaskAttemptError::TaskNotFound)?;
+        let project = Project::find_by_id(pool, task.project_id)
+            .await?
+            .ok_or(TaskAttemptError::ProjectNotFound)?;
+        let global_limit = app_state.get_max_concurrent_agents().await;
+
+        if global_limit.is_none() && project.max_concurrent_agents.is_none() {
+            return Ok(true);
+        }
+
+        // Don't jump ahead of executions already waiting for the same slots
+        let queued = ExecutionProcess::find_queued(pool).await?;
+        if queued.iter().any(|q| q.project_id == project.id)
+            || (global_limit.is_some() && !queued.is_empty())
+        {
+            return Ok(false);
+        }
+
+        if let Some(limit) = global_limit {
+            let running = ExecutionProcess::count_running_coding_agents(pool, None).await?;
+            if running >= i64::from(limit) {
+                return Ok(false);
+            }
+        }
+        if let Some(limit) = project.max_concurrent_agents {
+            let running =
+                ExecutionProcess::count_running_coding_agents(pool, Some(project.id)).await?;
+            if running >= limit {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Start queued coding agents, oldest first, while the concurrency limits allow it
+    pub async fn drain_execution_queue(app_state: &crate::app_state::AppState) {
+        let pool = &app_state.db_pool;
+        let _queue_guard = app_state.lock_execution_queue().await;
+
+        let queued = match ExecutionProcess::find_queued(pool).await {
+            Ok(queued) => queued,
+            Err(e) => {
+                tracing::error!("Failed to query queued execution processes: {}", e);
+                return;
+            }
+        };
+        if queued.is_empty() {
+            return;
+        }
+
+        let global_limit = app_state.get_max_concurrent_agents().await.map(i64::from);
+        let mut global_running =
+            match ExecutionProcess::count_running_coding_agents(pool, None).await {
+                Ok(count) => count,
+                Err(e) => {
+                    tracing::error!("Failed to count running coding agents: {}", e);
+                    return;
+                }
+            };
+        // project_id -> (limit, running)
+        let mut projects: HashMap<Uuid, (Option<i64>, i64)> = HashMap::new();
+
+        for entry in queued {
+            if global_limit.is_some_and(|limit| global_running >= limit) {
+                break;
+            }
+
+            let (project_limit, project_running) = match projects.entry(entry.project_id) {
+                Entry::Occupied(occupied) => *occupied.get(),
+                Entry::Vacant(vacant) => {
+                    match Self::load_project_agent_usage(pool, entry.project_id).await {
+                        Ok(usage) => *vacant.insert(usage),
+                        Err(e) => {
+                            tracing::error!(
+                                "Failed to load agent usage for project {}: {}",
+                                entry.project_id,
+                                e
+                            );
+                            continue;
+                        }
+                    }
+                }
+            };
+            if project_limit.is_some_and(|limit| project_running >= limit) {
+                continue;
+            }
+
+            match Self::start_queued_execution(pool, app_state, &entry).await {
+                Ok(true) => {
+                    global_running += 1;
+                    if let Some((_, running)) = projects.get_mut(&entry.project_id) {
+                        *running += 1;
+                    }
+                }
+                Ok(false) => {} // Cancelled while we were looking at it
+                Err(e) => {
+                    tracing::error!("Failed to start queued execution {}: {}", entry.id, e);
+                    if let Err(e) = ExecutionProcess::update_completion(
+                        pool,
+                        entry.id,
+                        ExecutionProcessStatus::Failed,
+                        None,
+                    )
+                    .await
+                    {
+                        tracing::error!(
+                            "Failed to mark queued execution {} as failed: {}",
+                            entry.id,
+                            e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Per-project agent limit and number of coding agents currently running in the project
+    async fn load_project_agent_usage(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<(Option<i64>, i64), sqlx::Error> {
+        let limit = Project::find_by_id(pool, project_id)
+            .await?
+            .and_then(|p| p.max_concurrent_agents);
+        let running = ExecutionProcess::count_running_coding_agents(pool, Some(project_id)).await?;
+        Ok((limit, running))
+    }
+
+    /// Spawn a queued coding agent. Returns false if it was no longer queued.
+    async fn start_queued_execution(
+        pool: &SqlitePool,
+        app_state: &crate::app_state::AppState,
+        entry: &QueuedExecution,
+    ) -> Result<bool, TaskAttemptError> {
+        let process = ExecutionProcess::find_summary_by_id(pool, entry.id)
+            .await?
+            .ok_or(TaskAttemptError::TaskNotFound)?;
+        let session = ExecutorSession::find_by_execution_process_id(pool, entry.id).await?;
+
+        let executor_config: crate::executor::ExecutorConfig = entry
+            .executor_type
+            .as_deref()
+            .unwrap_or_default()
+            .parse()
+            .map_err(TaskAttemptError::ValidationError)?;
+        let resume_session_id = session.as_ref().and_then(|s| s.session_id.clone());
+
+        let executor_type = match (process.command.as_str(), resume_session_id) {
+            ("followup_executor", Some(session_id)) => {
+                crate::executor::ExecutorType::FollowUpCodingAgent {
+                    config: executor_config.clone(),
+                    session_id: Some(session_id),
+                    prompt: session.and_then(|s| s.prompt).unwrap_or_default(),
+                }
+            }
+            _ => crate::executor::ExecutorType::CodingAgent(executor_config.clone()),
+        };
+
+        if !ExecutionProcess::mark_started(pool, entry.id).await? {
+            return Ok(false);
+        }
+
+        tracing::info!(
+            "Starting queued execution {} for task attempt {}",
+            entry.id,
+            entry.task_attempt_id
+        );
+
+        let child = match Self::execute_process(
+            &executor_type,
+            pool,
+            entry.task_id,
+            entry.task_attempt_id,
+            entry.id,
+            &process.working_directory,
+        )
+        .await
+        {
+            Ok(child) => child,
+            Err(e)
+                if matches!(
+                    executor_type,
+                    crate::executor::ExecutorType::FollowUpCodingAgent { .. }
+                ) =>
+            {
+                // Same fallback as a direct follow-up: start a new session instead
+                tracing::warn!(
+                    "Queued follow-up {} failed to resume its session, starting a new one: {}",
+                    entry.id,
+                    e
+                );
+                Self::execute_process(
+                    &crate::executor::ExecutorType::CodingAgent(executor_config),
+                    pool,
+                    entry.task_id,
+                    entry.task_attempt_id,
+                    entry.id,
+                    &process.working_directory,
+                )
+                .await?
+            }
+            Err(e) => return Err(e),
+        };
+
+        Self::register_for_monitoring(
+            app_state,
+            entry.id,
+            entry.task_attempt_id,
+            &ExecutionProcessType::CodingAgent,
+            child,
+        )
+        .await;
+
+        Ok(true)
+    }
+
+    /// A free local port that no running dev server was given yet
+    async fn allocate_dev_server_port(pool: &SqlitePool) -> Result<u16, TaskAttemptError> {
+        let taken = ExecutionProcess::find_running_dev_server_ports(pool).await?;
+        // The OS hands out a free port; retry in case it is one a dev server was given but
+        // hasn't bound yet
+        for _ in 0..20 {
+            let port = std::net::TcpListener::bind(("127.0.0.1", 0))
+                .and_then(|listener| listener.local_addr())
+                .map(|addr| addr.port())
+                .map_err(|e| {
+                    TaskAttemptError::ValidationError(format!(
+                        "Failed to find a free port for the dev server: {}",
+                        e
+                    ))
+                })?;
+            if !taken.contains(&i64::from(port)) {
+                return Ok(port);
+            }
+        }
+        Err(TaskAttemptError::ValidationError(
+            "Failed to find a free port for the dev server".to_string(),
+        ))
+    }
+
     /// Load the execution context (task attempt and project) with validation
     async fn load_execution_context(
         pool: &SqlitePool,
@@ -616,19 +1045,17 @@ impl ProcessService {
 
     /// Resolve executor configuration from string name
     fn resolve_executor_config(executor_name: &Option<String>) -> crate::executor::ExecutorConfig {
-        match executor_name.as_ref().map(|s| s.as_str()) {
-            Some("claude") => crate::executor::ExecutorConfig::Claude,
-            Some("claude-plan") => crate::executor::ExecutorConfig::ClaudePlan,
-            Some("claude-code-router") => crate::executor::ExecutorConfig::ClaudeCodeRouter,
-            Some("amp") => crate::executor::ExecutorConfig::Amp,
-            Some("gemini") => crate::executor::ExecutorConfig::Gemini,
-            Some("charm-opencode") => crate::executor::ExecutorConfig::CharmOpencode,
-            Some("sst-opencode") => crate::executor::ExecutorConfig::SstOpencode,
-            _ => crate::executor::ExecutorConfig::Echo, // Default for "echo" or None
+        match executor_name.as_deref().map(str::parse) {
+            // Setup scripts aren't coding agents
+            Some(Ok(crate::executor::ExecutorConfig::SetupScript { .. })) | Some(Err(_)) | None => {
+                crate::executor::ExecutorConfig::Echo // Default for "echo" or None
+            }
+            Some(Ok(config)) => config,
         }
     }
 
     /// Create execution process database record
+    #[allow(clippy::too_many_arguments)]
     async fn create_execution_process_record(
         pool: &SqlitePool,
         attempt_id: Uuid,
@@ -636,6 +1063,8 @@ impl ProcessService {
         executor_type: &crate::executor::ExecutorType,
         process_type: ExecutionProcessType,
         worktree_path: &str,
+        status: ExecutionProcessStatus,
+        auto_fix: Option<AutoFixOf>,
     ) -> Result<ExecutionProcess, TaskAttemptError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let (command, args, executor_type_string) = match executor_type {
@@ -644,11 +1073,16 @@ impl ProcessService {
                 Some(serde_json::to_string(&[shell_arg, "setup-script"]).unwrap()),
                 Some("setup-script".to_string()),
             ),
-            crate::executor::ExecutorType::DevServer(_) => (
+            crate::executor::ExecutorType::DevServer { .. } => (
                 shell_cmd.to_string(),
                 Some(serde_json::to_string(&[shell_arg, "dev_server"]).unwrap()),
                 None, // Dev servers don't have an executor type
             ),
+            crate::executor::ExecutorType::CheckScript { name, .. } => (
+                shell_cmd.to_string(),
+                Some(serde_json::to_string(&[shell_arg, "check-script", name]).unwrap()),
+                Some("check-script".to_string()),
+            ),
             crate::executor::ExecutorType::CodingAgent(config) => {
                 ("executor".to_string(), None, Some(format!("{}", config)))
             }
@@ -659,6 +1093,19 @@ impl ProcessService {
             ),
         };
 
+        let (project_check_id, head_commit) = match executor_type {
+            crate::executor::ExecutorType::CheckScript {
+                check_id,
+                head_commit,
+                ..
+            } => (Some(*check_id), head_commit.clone()),
+            _ => (None, None),
+        };
+        let port = match executor_type {
+            crate::executor::ExecutorType::DevServer { port, .. } => Some(i64::from(*port)),
+            _ => None,
+        };
+
         let create_process = CreateExecutionProcess {
             task_attempt_id: attempt_id,
             process_type,
@@ -666,9 +1113,14 @@ impl ProcessService {
             command,
             args,
             working_directory: worktree_path.to_string(),
+            project_check_id,
+            head_commit,
+            port,
+            auto_fix_of_process_id: auto_fix.map(|a| a.process_id),
+            auto_fix_iteration: auto_fix.map(|a| a.iteration),
         };
 
-        ExecutionProcess::create(pool, &create_process, process_id)
+        ExecutionProcess::create_with_status(pool, &create_process, process_id, status)
             .await
             .map_err(TaskAttemptError::from)
     }
@@ -713,30 +1165,97 @@ impl ProcessService {
         process_id: Uuid,
         worktree_path: &str,
     ) -> Result<command_group::AsyncGroupChild, TaskAttemptError> {
-        use crate::executors::{DevServerExecutor, SetupScriptExecutor};
+        use crate::executors::{
+            sandbox::run_sandboxed, CheckScriptExecutor, DevServerExecutor, SetupScriptExecutor,
+        };
+
+        let project_env = Self::load_project_env(pool, task_id).await?;
+        let mut sandbox = Self::load_sandbox(pool, task_id, process_id, worktree_path).await?;
 
         let result = match executor_type {
             crate::executor::ExecutorType::SetupScript(script) => {
-                let executor = SetupScriptExecutor {
+                let executor = SetupScriptExecutor::new(script.clone()).with_env_vars(project_env);
+                run_sandboxed(
+                    sandbox,
+                    executor.execute_streaming(
+                        pool,
+                        task_id,
+                        attempt_id,
+                        process_id,
+                        worktree_path,
+                    ),
+                )
+                .await
+            }
+            crate::executor::ExecutorType::DevServer {
+                script,
+                port,
+                env_vars,
+                ready_pattern,
+            } => {
+                // The dev server's own variables, including PORT, win over the project's
+                let mut merged_env = project_env;
+                if let Some(context) = sandbox.as_mut() {
+                    // The dev script runs code the agent wrote, so it stays in the sandbox,
+                    // listening where the published port forwards to
+                    if context.sandbox.settings.isolate_network {
+                        return Err(TaskAttemptError::ValidationError(
+                            "Dev servers can't be previewed when the project's sandbox has no network access".to_string(),
+                        ));
+                    }
+                    context.published_port = Some(*port);
+                    merged_env.insert("HOST".to_string(), "0.0.0.0".to_string());
+                }
+                merged_env.extend(env_vars.clone());
+                let executor = DevServerExecutor {
                     script: script.clone(),
+                    port: *port,
+                    env_vars: merged_env,
+                    ready_pattern: ready_pattern.clone(),
                 };
-                executor
-                    .execute_streaming(pool, task_id, attempt_id, process_id, worktree_path)
-                    .await
+                run_sandboxed(
+                    sandbox,
+                    executor.execute_streaming(
+                        pool,
+                        task_id,
+                        attempt_id,
+                        process_id,
+                        worktree_path,
+                    ),
+                )
+                .await
             }
-            crate::executor::ExecutorType::DevServer(script) => {
-                let executor = DevServerExecutor {
+            crate::executor::ExecutorType::CheckScript { script, .. } => {
+                let executor = CheckScriptExecutor {
                     script: script.clone(),
+                    env_vars: project_env,
                 };
-                executor
-                    .execute_streaming(pool, task_id, attempt_id, process_id, worktree_path)
-                    .await
+                run_sandboxed(
+                    sandbox,
+                    executor.execute_streaming(
+                        pool,
+                        task_id,
+                        attempt_id,
+                        process_id,
+                        worktree_path,
+                    ),
+                )
+                .await
             }
             crate::executor::ExecutorType::CodingAgent(config) => {
-                let executor = config.create_executor();
-                executor
-                    .execute_streaming(pool, task_id, attempt_id, process_id, worktree_path)
-                    .await
+                let profile = Self::load_executor_profile(pool, attempt_id, config).await?;
+                let executor = config.create_executor_with_profile(profile.as_ref(), &project_env);
+                run_sandboxed(
+                    sandbox,
+                    executor.execute_streaming(
+                        pool,
+                        task_id,
+                        attempt_id,
+                        process_id,
+                        worktree_path,
+                    ),
+                )
+                .await
             }
             crate::executor::ExecutorType::FollowUpCodingAgent {
                 config,
@@ -744,91 +1263,234 @@ impl ProcessService {
                 prompt,
             } => {
                 use crate::executors::{
-                    AmpFollowupExecutor, CCRFollowupExecutor, CharmOpencodeFollowupExecutor,
-                    ClaudeFollowupExecutor, GeminiFollowupExecutor, SstOpencodeFollowupExecutor,
+                    AiderFollowupExecutor, AmpFollowupExecutor, CCRFollowupExecutor,
+                    CharmOpencodeFollowupExecutor, ClaudeFollowupExecutor, CodexFollowupExecutor,
+                    CustomFollowupExecutor, GeminiExecutor, GeminiFollowupExecutor,
+                    SstOpencodeFollowupExecutor,
                 };
 
+                let profile = Self::load_executor_profile(pool, attempt_id, config).await?;
+                let env_vars = crate::executor::ExecutorConfig::resolve_env_vars(
+                    profile.as_ref(),
+                    &project_env,
+                );
+                let command = config.resolve_command(profile.as_ref());
+
                 let executor: Box<dyn crate::executor::Executor> = match config {
                     crate::executor::ExecutorConfig::Claude => {
                         if let Some(sid) = session_id {
-                            Box::new(ClaudeFollowupExecutor::new(sid.clone(), prompt.clone()))
+                            Box::new(
+                                ClaudeFollowupExecutor::with_command(
+                                    sid.clone(),
+                                    prompt.clone(),
+                                    "Claude".to_string(),
+                                    command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
                         } else {
                             return Err(TaskAttemptError::TaskNotFound); // No session ID for followup
                         }
                     }
                     crate::executor::ExecutorConfig::ClaudePlan => {
                         if let Some(sid) = session_id {
-                            Box::new(ClaudeFollowupExecutor::new_plan_mode(
-                                sid.clone(),
-                                prompt.clone(),
-                            ))
+                            Box::new(
+                                ClaudeFollowupExecutor::plan_mode_with_command(
+                                    sid.clone(),
+                                    prompt.clone(),
+                                    &command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
                         } else {
                             return Err(TaskAttemptError::TaskNotFound); // No session ID for followup
                         }
                     }
                     crate::executor::ExecutorConfig::Amp => {
                         if let Some(tid) = session_id {
-                            Box::new(AmpFollowupExecutor {
-                                thread_id: tid.clone(),
-                                prompt: prompt.clone(),
-                            })
+                            Box::new(
+                                AmpFollowupExecutor::with_command(
+                                    tid.clone(),
+                                    prompt.clone(),
+                                    command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
                         } else {
                             return Err(TaskAttemptError::TaskNotFound); // No thread ID for followup
                         }
                     }
                     crate::executor::ExecutorConfig::Gemini => {
                         // For Gemini, we don't use real session IDs, we pass the context directly
-                        Box::new(GeminiFollowupExecutor {
+                        Box::new(GeminiFollowupExecutor::with_executor(
                             attempt_id,
-                            prompt: prompt.clone(),
-                        })
+                            prompt.clone(),
+                            GeminiExecutor::with_command(command).with_env_vars(env_vars),
+                        ))
                     }
                     crate::executor::ExecutorConfig::Echo => {
                         // Echo doesn't support followup, use regular echo
-                        config.create_executor()
+                        config.create_executor_with_profile(None, &project_env)
                     }
                     crate::executor::ExecutorConfig::CharmOpencode => {
                         if let Some(sid) = session_id {
-                            Box::new(CharmOpencodeFollowupExecutor {
-                                session_id: sid.clone(),
-                                prompt: prompt.clone(),
-                            })
+                            Box::new(
+                                CharmOpencodeFollowupExecutor::with_command(
+                                    sid.clone(),
+                                    prompt.clone(),
+                                    command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
                         } else {
                             return Err(TaskAttemptError::TaskNotFound); // No session ID for followup
                         }
                     }
                     crate::executor::ExecutorConfig::ClaudeCodeRouter => {
                         if let Some(sid) = session_id {
-                            Box::new(CCRFollowupExecutor::new(sid.clone(), prompt.clone()))
+                            Box::new(
+                                CCRFollowupExecutor::with_command(
+                                    sid.clone(),
+                                    prompt.clone(),
+                                    command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
                         } else {
                             return Err(TaskAttemptError::TaskNotFound); // No session ID for followup
                         }
                     }
                     crate::executor::ExecutorConfig::SstOpencode => {
                         if let Some(sid) = session_id {
-                            Box::new(SstOpencodeFollowupExecutor::new(
-                                sid.clone(),
-                                prompt.clone(),
-                            ))
+                            Box::new(
+                                SstOpencodeFollowupExecutor::with_command(
+                                    sid.clone(),
+                                    prompt.clone(),
+                                    command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
                         } else {
                             return Err(TaskAttemptError::TaskNotFound); // No session ID for followup
                         }
                     }
+                    crate::executor::ExecutorConfig::Aider => {
+                        if let Some(sid) = session_id {
+                            Box::new(
+                                AiderFollowupExecutor::with_command(
+                                    sid.clone(),
+                                    prompt.clone(),
+                                    command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
+                        } else {
+                            return Err(TaskAttemptError::TaskNotFound); // No chat history to restore for followup
+                        }
+                    }
+                    crate::executor::ExecutorConfig::Codex => {
+                        if let Some(sid) = session_id {
+                            Box::new(
+                                CodexFollowupExecutor::with_command(
+                                    sid.clone(),
+                                    prompt.clone(),
+                                    command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
+                        } else {
+                            return Err(TaskAttemptError::TaskNotFound); // No thread ID for followup
+                        }
+                    }
                     crate::executor::ExecutorConfig::SetupScript { .. } => {
                         // Setup scripts don't support followup, use regular setup script
-                        config.create_executor()
+                        config.create_executor_with_profile(None, &project_env)
+                    }
+                    crate::executor::ExecutorConfig::Custom { id } => {
+                        if let Some(sid) = session_id {
+                            Box::new(
+                                CustomFollowupExecutor::with_command(
+                                    id.clone(),
+                                    sid.clone(),
+                                    prompt.clone(),
+                                    command,
+                                )
+                                .with_env_vars(env_vars),
+                            )
+                        } else {
+                            return Err(TaskAttemptError::TaskNotFound); // No session ID for followup
+                        }
                     }
                 };
 
-                executor
-                    .execute_streaming(pool, task_id, attempt_id, process_id, worktree_path)
-                    .await
+                run_sandboxed(
+                    sandbox,
+                    executor.execute_streaming(
+                        pool,
+                        task_id,
+                        attempt_id,
+                        process_id,
+                        worktree_path,
+                    ),
+                )
+                .await
             }
         };
 
         result.map_err(|e| TaskAttemptError::Git(git2::Error::from_str(&e.to_string())))
     }
 
+    /// Where the processes of an execution run, None when the project doesn't sandbox them
+    async fn load_sandbox(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        process_id: Uuid,
+        worktree_path: &str,
+    ) -> Result<Option<SandboxContext>, TaskAttemptError> {
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(TaskAttemptError::TaskNotFound)?;
+        let project = Project::find_by_id(pool, task.project_id)
+            .await?
+            .ok_or(TaskAttemptError::ProjectNotFound)?;
+
+        let sandbox = ContainerSandbox::for_settings(&project.sandbox_settings)
+            .map_err(TaskAttemptError::ValidationError)?;
+        Ok(sandbox.map(|sandbox| SandboxContext {
+            sandbox,
+            execution_process_id: process_id,
+            worktree_path: worktree_path.to_string(),
+            git_dir: repository_git_dir(worktree_path),
+            published_port: None,
+        }))
+    }
+
+    /// Load the project's environment variables for a task, with secrets decrypted
+    async fn load_project_env(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<HashMap<String, String>, TaskAttemptError> {
+        ProjectEnvVar::resolve_for_task(pool, task_id)
+            .await
+            .map_err(|e| match e {
+                ProjectEnvVarError::Database(e) => TaskAttemptError::Database(e),
+                e => TaskAttemptError::ValidationError(format!(
+                    "Failed to load project environment variables: {}",
+                    e
+                )),
+            })
+    }
+
+    /// Load the executor profile the attempt was started with, if it applies to `config`
+    async fn load_executor_profile(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        config: &crate::executor::ExecutorConfig,
+    ) -> Result<Option<ExecutorProfile>, TaskAttemptError> {
+        let profile = ExecutorProfile::find_by_task_attempt_id(pool, attempt_id).await?;
+        Ok(profile.filter(|p| p.executor == config.to_string()))
+    }
+
     /// Register process for monitoring
     async fn register_for_monitoring(
         app_state: &crate::app_state::AppState,
@@ -841,6 +1503,7 @@ impl ProcessService {
             ExecutionProcessType::SetupScript => crate::app_state::ExecutionType::SetupScript,
             ExecutionProcessType::CodingAgent => crate::app_state::ExecutionType::CodingAgent,
             ExecutionProcessType::DevServer => crate::app_state::ExecutionType::DevServer,
+            ExecutionProcessType::CheckScript => crate::app_state::ExecutionType::CheckScript,
         };
 
         app_state
@@ -881,6 +1544,11 @@ impl ProcessService {
             command: shell_cmd.to_string(),
             args: Some(args_with_delegation.to_string()),
             working_directory: worktree_path.to_string(),
+            project_check_id: None,
+            head_commit: None,
+            port: None,
+            auto_fix_of_process_id: None,
+            auto_fix_iteration: None,
         };
 
         ExecutionProcess::create(pool, &create_process, process_id)
@@ -897,15 +1565,18 @@ impl ProcessService {
         process_id: Uuid,
         worktree_path: &str,
     ) -> Result<command_group::AsyncGroupChild, TaskAttemptError> {
-        use crate::executors::SetupScriptExecutor;
+        use crate::executors::{sandbox::run_sandboxed, SetupScriptExecutor};
 
-        let executor = SetupScriptExecutor {
-            script: setup_script.to_string(),
-        };
+        let project_env = Self::load_project_env(pool, task_id).await?;
+        let sandbox = Self::load_sandbox(pool, task_id, process_id, worktree_path).await?;
+        let executor =
+            SetupScriptExecutor::new(setup_script.to_string()).with_env_vars(project_env);
 
-        executor
-            .execute_streaming(pool, task_id, attempt_id, process_id, worktree_path)
-            .await
-            .map_err(|e| TaskAttemptError::Git(git2::Error::from_str(&e.to_string())))
+        run_sandboxed(
+            sandbox,
+            executor.execute_streaming(pool, task_id, attempt_id, process_id, worktree_path),
+        )
+        .await
+        .map_err(|e| TaskAttemptError::Git(git2::Error::from_str(&e.to_string())))
     }
 }
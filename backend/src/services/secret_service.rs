@@ -0,0 +1,298 @@
+use std::{borrow::Cow, io::Write, path::Path, sync::OnceLock};
+
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    rand::{SecureRandom, SystemRandom},
+};
+use uuid::Uuid;
+
+use crate::{
+    models::{executor_profile::ExecutorProfile, project_env_var::ProjectEnvVar},
+    utils::secret_key_path,
+};
+
+/// Prefix of stored secrets, so the format can change without guessing
+const SEALED_PREFIX: &str = "v1:";
+const KEY_LEN: usize = 32;
+
+#[derive(Debug)]
+pub enum SecretError {
+    Io(std::io::Error),
+    InvalidKey,
+    Crypto,
+}
+
+impl std::fmt::Display for SecretError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecretError::Io(e) => write!(f, "Failed to access the secret key: {}", e),
+            SecretError::InvalidKey => write!(f, "The secret key file is invalid"),
+            SecretError::Crypto => write!(
+                f,
+                "Failed to decrypt a secret, the secret key may have changed since it was saved"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SecretError {}
+
+impl From<std::io::Error> for SecretError {
+    fn from(err: std::io::Error) -> Self {
+        SecretError::Io(err)
+    }
+}
+
+impl From<ring::error::Unspecified> for SecretError {
+    fn from(_: ring::error::Unspecified) -> Self {
+        SecretError::Crypto
+    }
+}
+
+/// Encrypts project secrets at rest with AES-256-GCM, using a key kept next to the config
+pub struct SecretService;
+
+impl SecretService {
+    /// Encrypt `plaintext` with the local secret key
+    pub fn encrypt(plaintext: &str) -> Result<String, SecretError> {
+        Self::seal(&Self::key()?, plaintext)
+    }
+
+    /// Decrypt a value produced by `encrypt`
+    pub fn decrypt(stored: &str) -> Result<String, SecretError> {
+        Self::open(&Self::key()?, stored)
+    }
+
+    /// Whether `value` was produced by `encrypt`, rather than stored as is
+    pub fn is_sealed(value: &str) -> bool {
+        value.starts_with(SEALED_PREFIX)
+    }
+
+    fn key() -> Result<[u8; KEY_LEN], SecretError> {
+        static KEY: OnceLock<[u8; KEY_LEN]> = OnceLock::new();
+        if let Some(key) = KEY.get() {
+            return Ok(*key);
+        }
+        let key = Self::load_or_create_key(&secret_key_path())?;
+        Ok(*KEY.get_or_init(|| key))
+    }
+
+    /// Read the key at `path`, generating it (readable by the owner only) if missing
+    pub fn load_or_create_key(path: &Path) -> Result<[u8; KEY_LEN], SecretError> {
+        match std::fs::read(path) {
+            Ok(bytes) => return bytes.try_into().map_err(|_| SecretError::InvalidKey),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut key = [0u8; KEY_LEN];
+        SystemRandom::new().fill(&mut key)?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        match options.open(path) {
+            Ok(mut file) => {
+                file.write_all(&key)?;
+                tracing::info!("Created secret key at {}", path.display());
+                Ok(key)
+            }
+            // Another caller created it first
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                Self::load_or_create_key(path)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Encrypt `plaintext` with `key` under a fresh random nonce
+    pub fn seal(key: &[u8; KEY_LEN], plaintext: &str) -> Result<String, SecretError> {
+        let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key)?);
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new().fill(&mut nonce)?;
+
+        let mut sealed = plaintext.as_bytes().to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut sealed,
+        )?;
+        Ok(format!(
+            "{}{}{}",
+            SEALED_PREFIX,
+            hex::encode(nonce),
+            hex::encode(sealed)
+        ))
+    }
+
+    /// Decrypt a value produced by `seal` with the same key
+    pub fn open(key: &[u8; KEY_LEN], stored: &str) -> Result<String, SecretError> {
+        let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key)?);
+        let bytes = stored
+            .strip_prefix(SEALED_PREFIX)
+            .and_then(|hex_value| hex::decode(hex_value).ok())
+            .filter(|bytes| bytes.len() >= NONCE_LEN)
+            .ok_or(SecretError::Crypto)?;
+        let (nonce, sealed) = bytes.split_at(NONCE_LEN);
+
+        let mut sealed = sealed.to_vec();
+        let nonce = Nonce::try_assume_unique_for_key(nonce)?;
+        let plaintext = key.open_in_place(nonce, Aad::empty(), &mut sealed)?;
+        String::from_utf8(plaintext.to_vec()).map_err(|_| SecretError::Crypto)
+    }
+}
+
+/// Replaces the values of project secrets in process output before it is stored
+#[derive(Debug, Default)]
+pub struct SecretRedactor {
+    secrets: Vec<String>, // Longest first, so a secret containing another is hidden whole
+}
+
+impl SecretRedactor {
+    pub const PLACEHOLDER: &'static str = "[REDACTED]";
+
+    pub fn new(secrets: impl IntoIterator<Item = String>) -> Self {
+        let mut secrets: Vec<String> = secrets
+            .into_iter()
+            .filter(|secret| !secret.is_empty())
+            .collect();
+        secrets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        secrets.dedup();
+        Self { secrets }
+    }
+
+    /// Redactor for the secrets of the project an execution process runs for, and the
+    /// variables of the executor profile its attempt was started with
+    pub async fn for_process(pool: &sqlx::SqlitePool, execution_process_id: Uuid) -> Self {
+        let secrets = match ProjectEnvVar::find_secrets_for_process(pool, execution_process_id)
+            .await
+        {
+            Ok(secrets) => ExecutorProfile::find_secrets_for_process(pool, execution_process_id)
+                .await
+                .map(|profile_secrets| secrets.into_iter().chain(profile_secrets))
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match secrets {
+            Ok(secrets) => Self::new(secrets),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load secrets to redact from execution process {}: {}",
+                    execution_process_id,
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for secret in &self.secrets {
+            if text.contains(secret.as_str()) {
+                text = Cow::Owned(text.replace(secret.as_str(), Self::PLACEHOLDER));
+            }
+        }
+        text
+    }
+
+    /// Redact output read in chunks of any size, which can split a secret in two. The chunk
+    /// is added to `pending`, and the redacted text is returned except for an end that could
+    /// still be the start of a secret, left in `pending` for the next chunk.
+    pub fn redact_chunk(&self, pending: &mut String, chunk: &str) -> String {
+        pending.push_str(chunk);
+        let redacted = self.redact(pending).into_owned();
+        let split = redacted.len() - self.partial_secret_len(&redacted);
+        *pending = redacted[split..].to_string();
+        redacted[..split].to_string()
+    }
+
+    /// Length of the longest end of `text` that some secret starts with
+    fn partial_secret_len(&self, text: &str) -> usize {
+        self.secrets
+            .iter()
+            .filter_map(|secret| {
+                (1..secret.len())
+                    .rev()
+                    .find(|&len| secret.is_char_boundary(len) && text.ends_with(&secret[..len]))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = [7u8; KEY_LEN];
+        let sealed = SecretService::seal(&key, "sk-live-123").unwrap();
+        assert!(sealed.starts_with(SEALED_PREFIX));
+        assert!(!sealed.contains("sk-live-123"));
+        // A fresh nonce every time
+        assert_ne!(sealed, SecretService::seal(&key, "sk-live-123").unwrap());
+        assert_eq!(SecretService::open(&key, &sealed).unwrap(), "sk-live-123");
+
+        assert!(SecretService::open(&[8u8; KEY_LEN], &sealed).is_err());
+        assert!(SecretService::open(&key, "sk-live-123").is_err());
+        assert!(SecretService::open(&key, "v1:00").is_err());
+    }
+
+    #[test]
+    fn test_load_or_create_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("secret.key");
+
+        let key = SecretService::load_or_create_key(&path).unwrap();
+        assert_eq!(SecretService::load_or_create_key(&path).unwrap(), key);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        std::fs::write(&path, b"too short").unwrap();
+        assert!(matches!(
+            SecretService::load_or_create_key(&path),
+            Err(SecretError::InvalidKey)
+        ));
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let redactor =
+            SecretRedactor::new(vec!["abc".to_string(), "abc123".to_string(), String::new()]);
+        assert_eq!(
+            redactor.redact("token=abc123 short=abc"),
+            "token=[REDACTED] short=[REDACTED]"
+        );
+        assert!(matches!(
+            redactor.redact("nothing here"),
+            Cow::Borrowed("nothing here")
+        ));
+        assert_eq!(SecretRedactor::default().redact("abc"), "abc");
+    }
+
+    #[test]
+    fn test_redact_chunks_splitting_a_secret() {
+        let redactor = SecretRedactor::new(vec!["sk-live-123".to_string()]);
+        let output = "key=sk-live-123 and sk-live-12 alone";
+        for split in 0..=output.len() {
+            let mut pending = String::new();
+            let mut stored = redactor.redact_chunk(&mut pending, &output[..split]);
+            stored.push_str(&redactor.redact_chunk(&mut pending, &output[split..]));
+            stored.push_str(&pending);
+            assert_eq!(stored, "key=[REDACTED] and sk-live-12 alone");
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::{sync::OnceLock, time::Duration};
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
+use serde::Serialize;
+use sha2::Sha256;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::models::webhook::{Webhook, WebhookEvent};
+
+const MAX_DELIVERY_ATTEMPTS: u32 = 5;
+
+/// Body POSTed to webhook URLs
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    pub delivery_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub project_id: Uuid,
+    pub task_id: Uuid,
+    pub attempt_id: Option<Uuid>,
+    pub data: serde_json::Value,
+}
+
+/// Delivers lifecycle events to a project's webhooks. Each request carries the event name, a
+/// delivery id and an `X-Vibe-Kanban-Signature: sha256=<hex>` HMAC of the body keyed by the
+/// webhook secret. Failed deliveries are retried with exponential backoff.
+pub struct WebhookService;
+
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+impl WebhookService {
+    /// Send `event` to every enabled webhook of the project subscribed to it. Deliveries run
+    /// in the background so callers are never held up by slow endpoints.
+    pub async fn dispatch(
+        pool: &SqlitePool,
+        event: WebhookEvent,
+        project_id: Uuid,
+        task_id: Uuid,
+        attempt_id: Option<Uuid>,
+        data: serde_json::Value,
+    ) {
+        let webhooks = match Webhook::find_subscribed(pool, project_id, event).await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                tracing::error!("Failed to load webhooks for project {}: {}", project_id, e);
+                return;
+            }
+        };
+
+        for webhook in webhooks {
+            let payload = WebhookPayload {
+                event,
+                delivery_id: Uuid::new_v4(),
+                timestamp: Utc::now(),
+                project_id,
+                task_id,
+                attempt_id,
+                data: data.clone(),
+            };
+            tokio::spawn(async move { Self::deliver(&webhook, &payload).await });
+        }
+    }
+
+    async fn deliver(webhook: &Webhook, payload: &WebhookPayload) {
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Failed to serialize webhook payload: {}", e);
+                return;
+            }
+        };
+        let signature = format!("sha256={}", sign_payload(&webhook.secret, &body));
+        let client = HTTP_CLIENT.get_or_init(|| {
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default()
+        });
+
+        for attempt in 1..=MAX_DELIVERY_ATTEMPTS {
+            let result = client
+                .post(&webhook.url)
+                .header("Content-Type", "application/json")
+                .header("User-Agent", "vibe-kanban-webhooks")
+                .header("X-Vibe-Kanban-Event", payload.event.as_str())
+                .header("X-Vibe-Kanban-Delivery", payload.delivery_id.to_string())
+                .header("X-Vibe-Kanban-Signature", &signature)
+                .body(body.clone())
+                .send()
+                .await;
+
+            let status = match result {
+                Ok(response) if response.status().is_success() => {
+                    tracing::debug!(
+                        "Delivered {} webhook {} to {}",
+                        payload.event.as_str(),
+                        payload.delivery_id,
+                        webhook.url
+                    );
+                    return;
+                }
+                Ok(response) => {
+                    tracing::warn!(
+                        "Webhook {} to {} returned {} (attempt {}/{})",
+                        payload.delivery_id,
+                        webhook.url,
+                        response.status(),
+                        attempt,
+                        MAX_DELIVERY_ATTEMPTS
+                    );
+                    Some(response.status())
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Webhook {} to {} failed: {} (attempt {}/{})",
+                        payload.delivery_id,
+                        webhook.url,
+                        e,
+                        attempt,
+                        MAX_DELIVERY_ATTEMPTS
+                    );
+                    None
+                }
+            };
+
+            if !should_retry(status) || attempt == MAX_DELIVERY_ATTEMPTS {
+                break;
+            }
+            tokio::time::sleep(retry_delay(attempt)).await;
+        }
+
+        tracing::error!(
+            "Giving up on {} webhook {} to {}",
+            payload.event.as_str(),
+            payload.delivery_id,
+            webhook.url
+        );
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of `body` keyed by `secret`
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Connection failures, rate limiting and server errors are retried; other client errors
+/// would fail the same way again
+fn should_retry(status: Option<StatusCode>) -> bool {
+    match status {
+        None => true,
+        Some(status) => status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+    }
+}
+
+/// Backoff before retrying after the `attempt`-th failed delivery: 1s, 2s, 4s, 8s, ...
+fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1 << attempt.saturating_sub(1).min(6))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        assert!(should_retry(None));
+        assert!(should_retry(Some(StatusCode::BAD_GATEWAY)));
+        assert!(should_retry(Some(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(!should_retry(Some(StatusCode::NOT_FOUND)));
+
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(2), Duration::from_secs(2));
+        assert_eq!(retry_delay(4), Duration::from_secs(8));
+        assert_eq!(retry_delay(100), Duration::from_secs(64));
+    }
+}
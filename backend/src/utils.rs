@@ -5,6 +5,7 @@ use directories::ProjectDirs;
 pub mod path;
 pub mod shell;
 pub mod text;
+pub mod toml;
 pub mod worktree_manager;
 
 const PROJECT_ROOT: &str = env!("CARGO_MANIFEST_DIR");
@@ -53,6 +54,37 @@ pub fn config_path() -> std::path::PathBuf {
     asset_dir().join("config.json")
 }
 
+/// Definitions of the custom CLI executors, loaded once at startup
+pub fn custom_executors_path() -> std::path::PathBuf {
+    asset_dir().join("executors.json")
+}
+
+/// Where the running server records the port it listens on, for local clients like the CLI
+pub fn server_port_path() -> std::path::PathBuf {
+    asset_dir().join("server.port")
+}
+
+/// Key that project secrets are encrypted with, created on first use
+pub fn secret_key_path() -> std::path::PathBuf {
+    asset_dir().join("secret.key")
+}
+
+/// Whether the server makes users sign in, set `VIBE_KANBAN_MULTI_USER=1` on instances
+/// shared by a team
+pub fn is_multi_user() -> bool {
+    env::var("VIBE_KANBAN_MULTI_USER")
+        .map(|value| matches!(value.trim(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// API token local clients like the CLI and the MCP server authenticate with, if any
+pub fn client_api_token() -> Option<String> {
+    env::var("VIBE_KANBAN_TOKEN")
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
 pub fn cache_dir() -> std::path::PathBuf {
     let proj = if cfg!(debug_assertions) {
         ProjectDirs::from("ai", "bloop-dev", env!("CARGO_PKG_NAME"))
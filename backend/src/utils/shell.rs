@@ -17,3 +17,13 @@ pub fn get_shell_command() -> (&'static str, &'static str) {
         }
     }
 }
+
+/// Quote a value so the shell from `get_shell_command` passes it to the program as a
+/// single argument, verbatim
+pub fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
@@ -0,0 +1,161 @@
+//! Reading and editing TOML config files as JSON, for executors configured in TOML
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table};
+
+/// The JSON equivalent of a TOML document, dates becoming strings
+pub fn document_to_json(document: &DocumentMut) -> Value {
+    table_to_json(document.as_table())
+}
+
+fn table_to_json(table: &Table) -> Value {
+    Value::Object(
+        table
+            .iter()
+            .map(|(key, item)| (key.to_string(), item_to_json(item)))
+            .collect(),
+    )
+}
+
+fn item_to_json(item: &Item) -> Value {
+    match item {
+        Item::None => Value::Null,
+        Item::Value(value) => value_to_json(value),
+        Item::Table(table) => table_to_json(table),
+        Item::ArrayOfTables(tables) => Value::Array(tables.iter().map(table_to_json).collect()),
+    }
+}
+
+fn value_to_json(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::String(s.value().clone()),
+        toml_edit::Value::Integer(i) => Value::from(*i.value()),
+        toml_edit::Value::Float(f) => Value::from(*f.value()),
+        toml_edit::Value::Boolean(b) => Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::String(d.value().to_string()),
+        toml_edit::Value::Array(array) => Value::Array(array.iter().map(value_to_json).collect()),
+        toml_edit::Value::InlineTable(table) => Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.to_string(), value_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// A JSON value as a TOML one, None for nulls which TOML has no equivalent of
+fn json_to_value(value: &Value) -> Option<toml_edit::Value> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64()?.into(),
+        },
+        Value::String(s) => s.as_str().into(),
+        Value::Array(values) => values
+            .iter()
+            .filter_map(json_to_value)
+            .collect::<Array>()
+            .into(),
+        Value::Object(object) => object
+            .iter()
+            .filter_map(|(key, value)| Some((key.as_str(), json_to_value(value)?)))
+            .collect::<InlineTable>()
+            .into(),
+    })
+}
+
+/// A JSON object as a TOML table, its nested objects becoming inline tables
+fn object_to_table(object: &Map<String, Value>) -> Table {
+    let mut table = Table::new();
+    for (key, value) in object {
+        if let Some(value) = json_to_value(value) {
+            table.insert(key, Item::Value(value));
+        }
+    }
+    table
+}
+
+/// Replace the table at `path` with one table per entry of `tables`, e.g. `[mcp_servers.name]`,
+/// keeping the rest of the document and its comments as they are
+pub fn set_tables(
+    document: &mut DocumentMut,
+    path: &[&str],
+    tables: &HashMap<String, Value>,
+) -> Result<(), String> {
+    let mut parent = document.as_table_mut();
+    for &key in path {
+        let item = parent
+            .entry(key)
+            .or_insert_with(|| Item::Table(Table::new()));
+        if !item.is_table() {
+            *item = Item::Table(Table::new());
+        }
+        parent = item.as_table_mut().expect("item was just made a table");
+    }
+
+    parent.clear();
+    // Only the tables of the entries get a header
+    parent.set_implicit(true);
+    let mut names: Vec<&String> = tables.keys().collect();
+    names.sort();
+    for name in names {
+        let object = tables[name]
+            .as_object()
+            .ok_or_else(|| format!("'{}' must be an object", name))?;
+        parent.insert(name, Item::Table(object_to_table(object)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_tables_keeps_the_rest_of_the_document() {
+        let mut document: DocumentMut = r#"# Picked for speed
+model = "gpt-5"
+
+[mcp_servers.old]
+command = "old-server"
+"#
+        .parse()
+        .unwrap();
+
+        let servers = HashMap::from([(
+            "vibe_kanban".to_string(),
+            serde_json::json!({
+                "command": "npx",
+                "args": ["-y", "vibe-kanban", "--mcp"],
+                "env": { "TOKEN": "secret" },
+                "cwd": null
+            }),
+        )]);
+        set_tables(&mut document, &["mcp_servers"], &servers).unwrap();
+
+        assert_eq!(
+            document.to_string(),
+            r#"# Picked for speed
+model = "gpt-5"
+
+[mcp_servers.vibe_kanban]
+args = ["-y", "vibe-kanban", "--mcp"]
+command = "npx"
+env = { TOKEN = "secret" }
+"#
+        );
+        assert_eq!(
+            document_to_json(&document)["mcp_servers"]["vibe_kanban"],
+            serde_json::json!({
+                "args": ["-y", "vibe-kanban", "--mcp"],
+                "command": "npx",
+                "env": { "TOKEN": "secret" }
+            })
+        );
+    }
+}
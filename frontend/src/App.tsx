@@ -6,9 +6,11 @@ import { ProjectTasks } from '@/pages/project-tasks';
 
 import { Settings } from '@/pages/Settings';
 import { McpServers } from '@/pages/McpServers';
+import { Account } from '@/pages/Account';
 import { DisclaimerDialog } from '@/components/DisclaimerDialog';
 import { OnboardingDialog } from '@/components/OnboardingDialog';
 import { PrivacyOptInDialog } from '@/components/PrivacyOptInDialog';
+import { AuthProvider, useAuth } from '@/components/auth-provider';
 import { ConfigProvider, useConfig } from '@/components/config-provider';
 import { ThemeProvider } from '@/components/theme-provider';
 import type { EditorType, ExecutorConfig } from 'shared/types';
@@ -21,6 +23,7 @@ const SentryRoutes = Sentry.withSentryReactRouterV6Routing(Routes);
 
 function AppContent() {
   const { config, updateConfig, loading } = useConfig();
+  const { isAdmin } = useAuth();
   const [showDisclaimer, setShowDisclaimer] = useState(false);
   const [showOnboarding, setShowOnboarding] = useState(false);
   const [showPrivacyOptIn, setShowPrivacyOptIn] = useState(false);
@@ -28,7 +31,8 @@ function AppContent() {
   const showNavbar = true;
 
   useEffect(() => {
-    if (config) {
+    // Only admins can save the answers, in the config the server shares
+    if (config && isAdmin) {
       setShowDisclaimer(!config.disclaimer_acknowledged);
       if (config.disclaimer_acknowledged) {
         setShowOnboarding(!config.onboarding_acknowledged);
@@ -41,7 +45,7 @@ function AppContent() {
         }
       }
     }
-  }, [config]);
+  }, [config, isAdmin]);
 
   const handleDisclaimerAccept = async () => {
     if (!config) return;
@@ -167,6 +171,7 @@ function AppContent() {
 
             <Route path="/settings" element={<Settings />} />
             <Route path="/mcp-servers" element={<McpServers />} />
+            <Route path="/account" element={<Account />} />
           </SentryRoutes>
         </div>
       </div>
@@ -177,9 +182,11 @@ function AppContent() {
 function App() {
   return (
     <BrowserRouter>
-      <ConfigProvider>
-        <AppContent />
-      </ConfigProvider>
+      <AuthProvider>
+        <ConfigProvider>
+          <AppContent />
+        </ConfigProvider>
+      </AuthProvider>
     </BrowserRouter>
   );
 }
@@ -20,12 +20,9 @@ import { Input } from '@/components/ui/input';
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
 import { Sparkles, Code } from 'lucide-react';
 import type { EditorType, ExecutorConfig } from 'shared/types';
-import {
-  EXECUTOR_TYPES,
-  EDITOR_TYPES,
-  EXECUTOR_LABELS,
-  EDITOR_LABELS,
-} from 'shared/types';
+import { EDITOR_TYPES, EDITOR_LABELS } from 'shared/types';
+import { useConfig } from '@/components/config-provider';
+import { executorId } from '@/lib/utils';
 
 interface OnboardingDialogProps {
   open: boolean;
@@ -36,6 +33,7 @@ interface OnboardingDialogProps {
 }
 
 export function OnboardingDialog({ open, onComplete }: OnboardingDialogProps) {
+  const { executors } = useConfig();
   const [executor, setExecutor] = useState<ExecutorConfig>({ type: 'claude' });
   const [editorType, setEditorType] = useState<EditorType>('vscode');
   const [customCommand, setCustomCommand] = useState<string>('');
@@ -80,16 +78,19 @@ export function OnboardingDialog({ open, onComplete }: OnboardingDialogProps) {
               <div className="space-y-2">
                 <Label htmlFor="executor">Default Executor</Label>
                 <Select
-                  value={executor.type}
-                  onValueChange={(value) => setExecutor({ type: value as any })}
+                  value={executorId(executor)}
+                  onValueChange={(value) => {
+                    const option = executors.find((e) => e.id === value);
+                    if (option) setExecutor(option.config);
+                  }}
                 >
                   <SelectTrigger id="executor">
                     <SelectValue placeholder="Select your preferred coding agent" />
                   </SelectTrigger>
                   <SelectContent>
-                    {EXECUTOR_TYPES.map((type) => (
-                      <SelectItem key={type} value={type}>
-                        {EXECUTOR_LABELS[type]}
+                    {executors.map((option) => (
+                      <SelectItem key={option.id} value={option.id}>
+                        {option.label}
                       </SelectItem>
                     ))}
                   </SelectContent>
@@ -102,8 +103,13 @@ export function OnboardingDialog({ open, onComplete }: OnboardingDialogProps) {
                     'Charm/Opencode AI assistant'}
                   {executor.type === 'claude-code-router' &&
                     'Claude Code Router'}
+                  {executor.type === 'aider' &&
+                    'AI pair programming in your terminal'}
+                  {executor.type === 'codex' && 'Codex CLI from OpenAI'}
                   {executor.type === 'echo' &&
                     'This is just for debugging vibe-kanban itself'}
+                  {executor.type === 'custom' &&
+                    'A CLI agent defined in executors.json'}
                 </p>
               </div>
             </CardContent>
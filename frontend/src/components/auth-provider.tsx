@@ -0,0 +1,115 @@
+import {
+  createContext,
+  ReactNode,
+  useCallback,
+  useContext,
+  useEffect,
+  useState,
+} from 'react';
+import type { AuthSession, User } from 'shared/types';
+import { authApi, UNAUTHORIZED_EVENT, usersApi } from '../lib/api';
+import { Loader } from '@/components/ui/loader';
+import { Login } from '@/pages/Login';
+
+interface AuthContextType {
+  // Whether the server is shared by a team, which makes users sign in
+  multiUser: boolean;
+  user: User | null;
+  // Whether the user can change server-wide settings, as anyone can in
+  // single-user mode
+  isAdmin: boolean;
+  // Name of the user with the given id, for tasks and attempts they started
+  usernameOf: (userId: string | null) => string | null;
+  // Port previews of dev servers are served from, when it isn't the app's own
+  previewPort: number | null;
+  logout: () => Promise<void>;
+}
+
+const AuthContext = createContext<AuthContextType | undefined>(undefined);
+
+interface AuthProviderProps {
+  children: ReactNode;
+}
+
+export function AuthProvider({ children }: AuthProviderProps) {
+  const [session, setSession] = useState<AuthSession | null>(null);
+  const [loading, setLoading] = useState(true);
+  const [users, setUsers] = useState<User[]>([]);
+
+  const loadSession = useCallback(async () => {
+    try {
+      setSession(await authApi.getSession());
+    } catch (err) {
+      console.error('Error loading session:', err);
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadSession();
+    window.addEventListener(UNAUTHORIZED_EVENT, loadSession);
+    return () => window.removeEventListener(UNAUTHORIZED_EVENT, loadSession);
+  }, [loadSession]);
+
+  const signedInUserId = session?.user?.id;
+  useEffect(() => {
+    if (!signedInUserId) return;
+    usersApi
+      .list()
+      .then(setUsers)
+      .catch((err) => console.error('Error loading users:', err));
+  }, [signedInUserId]);
+
+  const usernameOf = useCallback(
+    (userId: string | null) =>
+      users.find((user) => user.id === userId)?.username ?? null,
+    [users]
+  );
+
+  const logout = useCallback(async () => {
+    try {
+      await authApi.logout();
+    } catch (err) {
+      console.error('Error signing out:', err);
+    }
+    await loadSession();
+  }, [loadSession]);
+
+  if (loading) {
+    return (
+      <div className="min-h-screen bg-background flex items-center justify-center">
+        <Loader message="Loading..." size={32} />
+      </div>
+    );
+  }
+
+  if (session?.multi_user && !session.user) {
+    return (
+      <Login needsSetup={session.needs_setup} onSignedIn={loadSession} />
+    );
+  }
+
+  return (
+    <AuthContext.Provider
+      value={{
+        multiUser: session?.multi_user ?? false,
+        user: session?.user ?? null,
+        isAdmin: !session?.multi_user || !!session.user?.is_admin,
+        usernameOf,
+        previewPort: session?.preview_port ?? null,
+        logout,
+      }}
+    >
+      {children}
+    </AuthContext.Provider>
+  );
+}
+
+export function useAuth() {
+  const context = useContext(AuthContext);
+  if (context === undefined) {
+    throw new Error('useAuth must be used within an AuthProvider');
+  }
+  return context;
+}
@@ -6,11 +6,14 @@ import {
   useEffect,
   useState,
 } from 'react';
-import type { Config } from 'shared/types';
+import type { Config, ExecutorOption } from 'shared/types';
 import { configApi, githubAuthApi } from '../lib/api';
+import { BUILT_IN_EXECUTORS } from '../lib/utils';
 
 interface ConfigContextType {
   config: Config | null;
+  // Built-in executors and the custom ones defined on the server
+  executors: ExecutorOption[];
   updateConfig: (updates: Partial<Config>) => void;
   updateAndSaveConfig: (updates: Partial<Config>) => void;
   saveConfig: () => Promise<boolean>;
@@ -28,6 +31,8 @@ export function ConfigProvider({ children }: ConfigProviderProps) {
   const [config, setConfig] = useState<Config | null>(null);
   const [loading, setLoading] = useState(true);
   const [githubTokenInvalid, setGithubTokenInvalid] = useState(false);
+  const [executors, setExecutors] =
+    useState<ExecutorOption[]>(BUILT_IN_EXECUTORS);
 
   useEffect(() => {
     const loadConfig = async () => {
@@ -42,6 +47,10 @@ export function ConfigProvider({ children }: ConfigProviderProps) {
     };
 
     loadConfig();
+    configApi
+      .getConstants()
+      .then((constants) => setExecutors(constants.executors))
+      .catch((err) => console.error('Error loading executors:', err));
   }, []);
 
   // Check GitHub token validity after config loads
@@ -98,6 +107,7 @@ export function ConfigProvider({ children }: ConfigProviderProps) {
     <ConfigContext.Provider
       value={{
         config,
+        executors,
         updateConfig,
         saveConfig,
         loading,
@@ -38,8 +38,8 @@ const TaskDetailsProvider: FC<{
   task: TaskWithAttemptStatus;
   projectId: string;
   children: ReactNode;
-  activeTab: 'logs' | 'diffs' | 'related';
-  setActiveTab: Dispatch<SetStateAction<'logs' | 'diffs' | 'related'>>;
+  activeTab: 'logs' | 'diffs' | 'related' | 'compare';
+  setActiveTab: Dispatch<SetStateAction<'logs' | 'diffs' | 'related' | 'compare'>>;
   setShowEditorDialog: Dispatch<SetStateAction<boolean>>;
   userSelectedTab: boolean;
   projectHasDevScript?: boolean;
@@ -306,7 +306,7 @@ const TaskDetailsProvider: FC<{
       (process: ExecutionProcessSummary) =>
         (process.process_type === 'codingagent' ||
           process.process_type === 'setupscript') &&
-        process.status === 'running'
+        (process.status === 'running' || process.status === 'queued')
     );
   }, [selectedAttempt, attemptData.processes, isStopping]);
 
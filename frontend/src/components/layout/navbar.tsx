@@ -6,11 +6,15 @@ import {
   BookOpen,
   Server,
   MessageCircleQuestion,
+  LogOut,
+  User,
 } from 'lucide-react';
 import { Logo } from '@/components/logo';
+import { useAuth } from '@/components/auth-provider';
 
 export function Navbar() {
   const location = useLocation();
+  const { user, logout, isAdmin } = useAuth();
 
   return (
     <div className="border-b">
@@ -31,18 +35,20 @@ export function Navbar() {
                   Projects
                 </Link>
               </Button>
-              <Button
-                asChild
-                variant={
-                  location.pathname === '/mcp-servers' ? 'default' : 'ghost'
-                }
-                size="sm"
-              >
-                <Link to="/mcp-servers">
-                  <Server className="mr-2 h-4 w-4" />
-                  MCP Servers
-                </Link>
-              </Button>
+              {isAdmin && (
+                <Button
+                  asChild
+                  variant={
+                    location.pathname === '/mcp-servers' ? 'default' : 'ghost'
+                  }
+                  size="sm"
+                >
+                  <Link to="/mcp-servers">
+                    <Server className="mr-2 h-4 w-4" />
+                    MCP Servers
+                  </Link>
+                </Button>
+              )}
               <Button
                 asChild
                 variant={
@@ -78,6 +84,26 @@ export function Navbar() {
                 Support
               </a>
             </Button>
+            {user && (
+              <>
+                <Button
+                  asChild
+                  variant={
+                    location.pathname === '/account' ? 'default' : 'ghost'
+                  }
+                  size="sm"
+                >
+                  <Link to="/account">
+                    <User className="mr-2 h-4 w-4" />
+                    {user.username}
+                  </Link>
+                </Button>
+                <Button variant="ghost" size="sm" onClick={logout}>
+                  <LogOut className="mr-2 h-4 w-4" />
+                  Sign out
+                </Button>
+              </>
+            )}
           </div>
         </div>
       </div>
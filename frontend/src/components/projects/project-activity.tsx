@@ -0,0 +1,152 @@
+import { useCallback, useEffect, useState } from 'react';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Badge } from '@/components/ui/badge';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { History } from 'lucide-react';
+import type { AuditEvent } from 'shared/types';
+import { auditApi } from '@/lib/api';
+
+// Action prefixes to filter by, matching every action of the kind
+const ACTION_FILTERS: Record<string, string> = {
+  all: 'All changes',
+  'task.': 'Tasks',
+  'attempt.': 'Attempts',
+  'project.': 'Project',
+  'member.': 'Members',
+  'check.': 'Checks',
+  'env_var.': 'Environment variables',
+  'executor_profile.': 'Executor profiles',
+  'webhook.': 'Webhooks',
+  'template.': 'Templates',
+};
+
+const PAGE_SIZE = 25;
+
+interface ProjectActivityProps {
+  projectId: string;
+}
+
+export function ProjectActivity({ projectId }: ProjectActivityProps) {
+  const [events, setEvents] = useState<AuditEvent[]>([]);
+  const [hasMore, setHasMore] = useState(false);
+  const [filter, setFilter] = useState('all');
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadEvents = useCallback(
+    async (offset: number) => {
+      setLoading(true);
+      setError(null);
+      try {
+        const page = await auditApi.list({
+          project_id: projectId,
+          action: filter === 'all' ? null : filter,
+          limit: PAGE_SIZE,
+          offset,
+        });
+        setEvents((current) =>
+          offset === 0 ? page.events : [...current, ...page.events]
+        );
+        setHasMore(page.has_more);
+      } catch (err) {
+        setError(
+          err instanceof Error ? err.message : 'Failed to load activity'
+        );
+      } finally {
+        setLoading(false);
+      }
+    },
+    [projectId, filter]
+  );
+
+  useEffect(() => {
+    loadEvents(0);
+  }, [loadEvents]);
+
+  return (
+    <Card>
+      <CardHeader>
+        <div className="flex items-start justify-between gap-4">
+          <div className="space-y-1.5">
+            <CardTitle className="flex items-center">
+              <History className="mr-2 h-5 w-5" />
+              Activity
+            </CardTitle>
+            <CardDescription>
+              Every change made to the project, its tasks and attempts, from
+              the UI, the API or the MCP server.
+            </CardDescription>
+          </div>
+          <Select value={filter} onValueChange={setFilter}>
+            <SelectTrigger className="w-48">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {Object.entries(ACTION_FILTERS).map(([value, label]) => (
+                <SelectItem key={value} value={value}>
+                  {label}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+      </CardHeader>
+      <CardContent className="space-y-2">
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+        {!loading && events.length === 0 && (
+          <p className="text-sm text-muted-foreground">No changes yet</p>
+        )}
+        {events.map((event) => (
+          <div
+            key={event.id}
+            className="flex items-center justify-between gap-4 rounded-md border p-3 text-sm"
+          >
+            <div className="flex min-w-0 items-center gap-2">
+              <span className="font-medium">
+                {event.actor_name ?? 'Local user'}
+              </span>
+              <code className="text-xs">{event.action}</code>
+              {event.target && (
+                <span className="truncate text-muted-foreground">
+                  {event.target}
+                </span>
+              )}
+              {event.source === 'mcp' && <Badge variant="outline">MCP</Badge>}
+            </div>
+            <span className="shrink-0 text-muted-foreground">
+              {new Date(event.created_at).toLocaleString()}
+            </span>
+          </div>
+        ))}
+        {hasMore && (
+          <Button
+            variant="outline"
+            className="w-full"
+            disabled={loading}
+            onClick={() => loadEvents(events.length)}
+          >
+            Load more
+          </Button>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -10,9 +10,12 @@ import {
 } from '@/components/ui/card';
 import { Badge } from '@/components/ui/badge';
 import { Alert, AlertDescription } from '@/components/ui/alert';
-import { ProjectWithBranch } from 'shared/types';
+import { ProjectRole, ProjectWithBranch } from 'shared/types';
 import { ProjectForm } from './project-form';
-import { projectsApi } from '@/lib/api';
+import { ProjectMembers } from './project-members';
+import { ProjectActivity } from './project-activity';
+import { projectMembersApi, projectsApi } from '@/lib/api';
+import { useAuth } from '@/components/auth-provider';
 import {
   AlertCircle,
   ArrowLeft,
@@ -36,6 +39,9 @@ export function ProjectDetail({ projectId, onBack }: ProjectDetailProps) {
   const [loading, setLoading] = useState(false);
   const [showEditForm, setShowEditForm] = useState(false);
   const [error, setError] = useState('');
+  const [role, setRole] = useState<ProjectRole | null>(null);
+  const { multiUser } = useAuth();
+  const isMaintainer = role === 'maintainer';
 
   useKeyboardShortcuts({
     navigate,
@@ -86,6 +92,13 @@ export function ProjectDetail({ projectId, onBack }: ProjectDetailProps) {
     fetchProject();
   }, [fetchProject]);
 
+  useEffect(() => {
+    projectMembersApi
+      .getMyRole(projectId)
+      .then(setRole)
+      .catch((err) => console.error('Failed to fetch project role:', err));
+  }, [projectId]);
+
   if (loading) {
     return (
       <div className="flex items-center justify-center py-12">
@@ -148,18 +161,22 @@ export function ProjectDetail({ projectId, onBack }: ProjectDetailProps) {
             <CheckSquare className="mr-2 h-4 w-4" />
             View Tasks
           </Button>
-          <Button variant="outline" onClick={() => setShowEditForm(true)}>
-            <Edit className="mr-2 h-4 w-4" />
-            Edit
-          </Button>
-          <Button
-            variant="outline"
-            onClick={handleDelete}
-            className="text-destructive hover:text-destructive-foreground hover:bg-destructive/10"
-          >
-            <Trash2 className="mr-2 h-4 w-4" />
-            Delete
-          </Button>
+          {isMaintainer && (
+            <>
+              <Button variant="outline" onClick={() => setShowEditForm(true)}>
+                <Edit className="mr-2 h-4 w-4" />
+                Edit
+              </Button>
+              <Button
+                variant="outline"
+                onClick={handleDelete}
+                className="text-destructive hover:text-destructive-foreground hover:bg-destructive/10"
+              >
+                <Trash2 className="mr-2 h-4 w-4" />
+                Delete
+              </Button>
+            </>
+          )}
         </div>
       </div>
 
@@ -240,6 +257,12 @@ export function ProjectDetail({ projectId, onBack }: ProjectDetailProps) {
         </Card>
       </div>
 
+      {multiUser && (
+        <ProjectMembers projectId={projectId} canManage={isMaintainer} />
+      )}
+
+      {isMaintainer && <ProjectActivity projectId={projectId} />}
+
       <ProjectForm
         open={showEditForm}
         onClose={() => setShowEditForm(false)}
@@ -1,8 +1,23 @@
 import { Label } from '@/components/ui/label';
 import { Input } from '@/components/ui/input';
 import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
 import { Alert, AlertDescription } from '@/components/ui/alert';
 import { AlertCircle, Folder } from 'lucide-react';
+import type {
+  CommitMode,
+  CommitSettings,
+  ContainerRuntime,
+  MergeStrategy,
+  SandboxSettings,
+} from 'shared/types';
 
 interface ProjectFormFieldsProps {
   isEditing: boolean;
@@ -21,6 +36,18 @@ interface ProjectFormFieldsProps {
   setSetupScript: (script: string) => void;
   devScript: string;
   setDevScript: (script: string) => void;
+  mergeStrategy: MergeStrategy;
+  setMergeStrategy: (strategy: MergeStrategy) => void;
+  commitSettings: CommitSettings;
+  setCommitSettings: (settings: CommitSettings) => void;
+  autoFixMaxIterations: number;
+  setAutoFixMaxIterations: (iterations: number) => void;
+  devServerEnv: string;
+  setDevServerEnv: (env: string) => void;
+  devServerReadyPattern: string;
+  setDevServerReadyPattern: (pattern: string) => void;
+  sandboxSettings: SandboxSettings;
+  setSandboxSettings: (settings: SandboxSettings) => void;
   error: string;
 }
 
@@ -41,8 +68,25 @@ export function ProjectFormFields({
   setSetupScript,
   devScript,
   setDevScript,
+  mergeStrategy,
+  setMergeStrategy,
+  commitSettings,
+  setCommitSettings,
+  autoFixMaxIterations,
+  setAutoFixMaxIterations,
+  devServerEnv,
+  setDevServerEnv,
+  devServerReadyPattern,
+  setDevServerReadyPattern,
+  sandboxSettings,
+  setSandboxSettings,
   error,
 }: ProjectFormFieldsProps) {
+  const updateCommitSettings = (changes: Partial<CommitSettings>) =>
+    setCommitSettings({ ...commitSettings, ...changes });
+  const updateSandboxSettings = (changes: Partial<SandboxSettings>) =>
+    setSandboxSettings({ ...sandboxSettings, ...changes });
+
   return (
     <>
       {!isEditing && (
@@ -206,6 +250,326 @@ export function ProjectFormFields({
         </p>
       </div>
 
+      {isEditing && (
+        <div className="space-y-3">
+          <Label>Dev Server</Label>
+          <div className="space-y-2">
+            <Label htmlFor="dev-server-env" className="text-sm">
+              Environment
+            </Label>
+            <textarea
+              id="dev-server-env"
+              value={devServerEnv}
+              onChange={(e) => setDevServerEnv(e.target.value)}
+              placeholder="VITE_BASE={preview_path}&#10;API_URL=http://localhost:{port}/api"
+              rows={3}
+              className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md resize-vertical focus:outline-none focus:ring-2 focus:ring-ring font-mono text-sm"
+            />
+            <p className="text-sm text-muted-foreground">
+              Each attempt's dev server gets its own free port as PORT. Add
+              more variables as KEY=value lines; values can use {'{port}'},{' '}
+              {'{attempt_id}'} and {'{preview_path}'}. Previews are served
+              under {'{preview_path}'}, so apps using absolute paths for their
+              assets need it as their base URL.
+            </p>
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="dev-server-ready-pattern" className="text-sm">
+              Ready Pattern
+            </Label>
+            <Input
+              id="dev-server-ready-pattern"
+              value={devServerReadyPattern}
+              onChange={(e) => setDevServerReadyPattern(e.target.value)}
+              placeholder="Compiled successfully"
+              className="font-mono"
+            />
+            <p className="text-sm text-muted-foreground">
+              Regex matched against output lines to tell the server is ready.
+              Leave empty to wait for a line mentioning the port or saying
+              ready or listening.
+            </p>
+          </div>
+        </div>
+      )}
+
+      {isEditing && (
+        <div className="space-y-3">
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="sandbox-enabled"
+              checked={sandboxSettings.enabled}
+              onCheckedChange={(checked: boolean) =>
+                updateSandboxSettings({ enabled: checked })
+              }
+            />
+            <div className="space-y-0.5">
+              <Label htmlFor="sandbox-enabled" className="cursor-pointer">
+                Run Executions in a Container
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                Coding agents, scripts and checks run in a container with only
+                the attempt's worktree mounted. Dev servers must listen on
+                0.0.0.0 (HOST is set to it) to be previewed, and need network
+                access. Pass credentials as project environment variables.
+              </p>
+            </div>
+          </div>
+          {sandboxSettings.enabled && (
+            <div className="grid grid-cols-2 gap-2">
+              <div className="space-y-2 col-span-2">
+                <Label htmlFor="sandbox-image" className="text-sm">
+                  Image
+                </Label>
+                <Input
+                  id="sandbox-image"
+                  value={sandboxSettings.image}
+                  onChange={(e) =>
+                    updateSandboxSettings({ image: e.target.value })
+                  }
+                  placeholder="node:20"
+                  className="font-mono"
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="sandbox-runtime" className="text-sm">
+                  Runtime
+                </Label>
+                <Select
+                  value={sandboxSettings.runtime ?? 'auto'}
+                  onValueChange={(value: ContainerRuntime | 'auto') =>
+                    updateSandboxSettings({
+                      runtime: value === 'auto' ? null : value,
+                    })
+                  }
+                >
+                  <SelectTrigger id="sandbox-runtime">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="auto">Detect</SelectItem>
+                    <SelectItem value="docker">Docker</SelectItem>
+                    <SelectItem value="podman">Podman</SelectItem>
+                  </SelectContent>
+                </Select>
+              </div>
+              <div className="flex items-center space-x-2 pt-6">
+                <Checkbox
+                  id="sandbox-isolate-network"
+                  checked={sandboxSettings.isolate_network}
+                  onCheckedChange={(checked: boolean) =>
+                    updateSandboxSettings({ isolate_network: checked })
+                  }
+                />
+                <Label
+                  htmlFor="sandbox-isolate-network"
+                  className="text-sm cursor-pointer"
+                >
+                  No network access
+                </Label>
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="sandbox-cpus" className="text-sm">
+                  CPU Limit
+                </Label>
+                <Input
+                  id="sandbox-cpus"
+                  type="number"
+                  min={0}
+                  step={0.5}
+                  value={sandboxSettings.cpus ?? ''}
+                  onChange={(e) =>
+                    updateSandboxSettings({
+                      cpus: Number(e.target.value) || null,
+                    })
+                  }
+                  placeholder="No limit"
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="sandbox-memory" className="text-sm">
+                  Memory Limit (MB)
+                </Label>
+                <Input
+                  id="sandbox-memory"
+                  type="number"
+                  min={6}
+                  value={sandboxSettings.memory_mb ?? ''}
+                  onChange={(e) =>
+                    updateSandboxSettings({
+                      memory_mb: Number(e.target.value) || null,
+                    })
+                  }
+                  placeholder="No limit"
+                />
+              </div>
+            </div>
+          )}
+        </div>
+      )}
+
+      {isEditing && (
+        <div className="space-y-2">
+          <Label htmlFor="merge-strategy">Merge Strategy</Label>
+          <Select
+            value={mergeStrategy}
+            onValueChange={(value: MergeStrategy) => setMergeStrategy(value)}
+          >
+            <SelectTrigger id="merge-strategy">
+              <SelectValue placeholder="Select merge strategy" />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="squash">Squash into one commit</SelectItem>
+              <SelectItem value="merge">Merge commit</SelectItem>
+              <SelectItem value="rebase">Rebase and fast-forward</SelectItem>
+              <SelectItem value="keep-commits">
+                Keep individual agent commits (fast-forward only)
+              </SelectItem>
+            </SelectContent>
+          </Select>
+          <p className="text-sm text-muted-foreground">
+            How task attempts are merged into their base branch.
+          </p>
+        </div>
+      )}
+
+      {isEditing && (
+        <div className="space-y-2">
+          <Label htmlFor="auto-fix-max-iterations">Auto-fix Iterations</Label>
+          <Input
+            id="auto-fix-max-iterations"
+            type="number"
+            min={0}
+            max={10}
+            value={autoFixMaxIterations}
+            onChange={(e) =>
+              setAutoFixMaxIterations(
+                Math.max(0, Math.min(10, Number(e.target.value) || 0))
+              )
+            }
+          />
+          <p className="text-sm text-muted-foreground">
+            When the setup script, dev server or a check fails, send its output
+            back to the coding agent up to this many times in a row. 0 turns
+            auto-fix off.
+          </p>
+        </div>
+      )}
+
+      {isEditing && (
+        <div className="space-y-3">
+          <Label>Agent Commits</Label>
+          <div className="space-y-2">
+            <Label htmlFor="commit-message-template" className="text-sm">
+              Message Template
+            </Label>
+            <textarea
+              id="commit-message-template"
+              value={commitSettings.message_template ?? ''}
+              onChange={(e) =>
+                updateCommitSettings({
+                  message_template: e.target.value || null,
+                })
+              }
+              placeholder="{task_title}&#10;&#10;{summary}"
+              rows={3}
+              className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md resize-vertical focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            <p className="text-sm text-muted-foreground">
+              Supports {'{task_title}'}, {'{task_id}'}, {'{attempt_id}'},{' '}
+              {'{executor}'} and {'{summary}'}. Defaults to the agent's
+              summary.
+            </p>
+          </div>
+          <div className="grid grid-cols-2 gap-2">
+            <div className="space-y-2">
+              <Label htmlFor="commit-prefix" className="text-sm">
+                Conventional Prefix
+              </Label>
+              <Input
+                id="commit-prefix"
+                value={commitSettings.conventional_prefix ?? ''}
+                onChange={(e) =>
+                  updateCommitSettings({
+                    conventional_prefix: e.target.value || null,
+                  })
+                }
+                placeholder="feat"
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="commit-mode" className="text-sm">
+                Mode
+              </Label>
+              <Select
+                value={commitSettings.mode}
+                onValueChange={(value: CommitMode) =>
+                  updateCommitSettings({ mode: value })
+                }
+              >
+                <SelectTrigger id="commit-mode">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="per-execution">
+                    One commit per execution
+                  </SelectItem>
+                  <SelectItem value="amend">Amend the last commit</SelectItem>
+                </SelectContent>
+              </Select>
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="commit-author-name" className="text-sm">
+                Author Name
+              </Label>
+              <Input
+                id="commit-author-name"
+                value={commitSettings.author_name ?? ''}
+                onChange={(e) =>
+                  updateCommitSettings({ author_name: e.target.value || null })
+                }
+                placeholder="vibe-bot"
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="commit-author-email" className="text-sm">
+                Author Email
+              </Label>
+              <Input
+                id="commit-author-email"
+                value={commitSettings.author_email ?? ''}
+                onChange={(e) =>
+                  updateCommitSettings({
+                    author_email: e.target.value || null,
+                  })
+                }
+                placeholder="vibe-bot@example.com"
+              />
+            </div>
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="commit-co-authors" className="text-sm">
+              Co-authors
+            </Label>
+            <textarea
+              id="commit-co-authors"
+              value={commitSettings.co_authors.join('\n')}
+              onChange={(e) =>
+                updateCommitSettings({
+                  co_authors: e.target.value.split('\n'),
+                })
+              }
+              placeholder="Jane Doe <jane@example.com>"
+              rows={2}
+              className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md resize-vertical focus:outline-none focus:ring-2 focus:ring-ring"
+            />
+            <p className="text-sm text-muted-foreground">
+              One per line, added as Co-authored-by trailers.
+            </p>
+          </div>
+        </div>
+      )}
+
       {error && (
         <Alert variant="destructive">
           <AlertCircle className="h-4 w-4" />
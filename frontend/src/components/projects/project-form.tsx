@@ -12,9 +12,52 @@ import { Tabs, TabsContent, TabsList, TabsTrigger } from '@/components/ui/tabs';
 import { FolderPicker } from '@/components/ui/folder-picker';
 import { TaskTemplateManager } from '@/components/TaskTemplateManager';
 import { ProjectFormFields } from './project-form-fields';
-import { CreateProject, Project, UpdateProject } from 'shared/types';
+import {
+  CommitSettings,
+  CreateProject,
+  DevServerSettings,
+  MergeStrategy,
+  Project,
+  SandboxSettings,
+  UpdateProject,
+} from 'shared/types';
 import { projectsApi } from '@/lib/api';
 
+const defaultCommitSettings: CommitSettings = {
+  message_template: null,
+  conventional_prefix: null,
+  author_name: null,
+  author_email: null,
+  co_authors: [],
+  mode: 'per-execution',
+};
+
+const defaultSandboxSettings: SandboxSettings = {
+  enabled: false,
+  runtime: null,
+  image: '',
+  isolate_network: false,
+  cpus: null,
+  memory_mb: null,
+};
+
+// Dev server environment is edited as KEY=value lines
+function envToText(env: DevServerSettings['env']): string {
+  return Object.entries(env)
+    .map(([key, value]) => `${key}=${value}`)
+    .join('\n');
+}
+
+function textToEnv(text: string): DevServerSettings['env'] {
+  const env: DevServerSettings['env'] = {};
+  for (const line of text.split('\n')) {
+    const separator = line.indexOf('=');
+    if (separator <= 0) continue;
+    env[line.slice(0, separator).trim()] = line.slice(separator + 1);
+  }
+  return env;
+}
+
 interface ProjectFormProps {
   open: boolean;
   onClose: () => void;
@@ -32,6 +75,24 @@ export function ProjectForm({
   const [gitRepoPath, setGitRepoPath] = useState(project?.git_repo_path || '');
   const [setupScript, setSetupScript] = useState(project?.setup_script ?? '');
   const [devScript, setDevScript] = useState(project?.dev_script ?? '');
+  const [mergeStrategy, setMergeStrategy] = useState<MergeStrategy>(
+    project?.merge_strategy ?? 'squash'
+  );
+  const [commitSettings, setCommitSettings] = useState<CommitSettings>(
+    project?.commit_settings ?? defaultCommitSettings
+  );
+  const [autoFixMaxIterations, setAutoFixMaxIterations] = useState<number>(
+    project?.auto_fix_max_iterations ?? 0
+  );
+  const [devServerEnv, setDevServerEnv] = useState(
+    envToText(project?.dev_server_settings.env ?? {})
+  );
+  const [devServerReadyPattern, setDevServerReadyPattern] = useState(
+    project?.dev_server_settings.ready_pattern ?? ''
+  );
+  const [sandboxSettings, setSandboxSettings] = useState<SandboxSettings>(
+    project?.sandbox_settings ?? defaultSandboxSettings
+  );
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState('');
   const [showFolderPicker, setShowFolderPicker] = useState(false);
@@ -48,11 +109,23 @@ export function ProjectForm({
       setGitRepoPath(project.git_repo_path || '');
       setSetupScript(project.setup_script ?? '');
       setDevScript(project.dev_script ?? '');
+      setMergeStrategy(project.merge_strategy);
+      setCommitSettings(project.commit_settings);
+      setAutoFixMaxIterations(project.auto_fix_max_iterations);
+      setDevServerEnv(envToText(project.dev_server_settings.env));
+      setDevServerReadyPattern(project.dev_server_settings.ready_pattern ?? '');
+      setSandboxSettings(project.sandbox_settings);
     } else {
       setName('');
       setGitRepoPath('');
       setSetupScript('');
       setDevScript('');
+      setMergeStrategy('squash');
+      setCommitSettings(defaultCommitSettings);
+      setAutoFixMaxIterations(0);
+      setDevServerEnv('');
+      setDevServerReadyPattern('');
+      setSandboxSettings(defaultSandboxSettings);
     }
   }, [project]);
 
@@ -93,6 +166,23 @@ export function ProjectForm({
           git_repo_path: finalGitRepoPath,
           setup_script: setupScript.trim() || null,
           dev_script: devScript.trim() || null,
+          max_concurrent_agents: project?.max_concurrent_agents ?? null,
+          merge_strategy: mergeStrategy,
+          commit_settings: {
+            ...commitSettings,
+            co_authors: commitSettings.co_authors
+              .map((coAuthor) => coAuthor.trim())
+              .filter(Boolean),
+          },
+          auto_fix_max_iterations: autoFixMaxIterations,
+          dev_server_settings: {
+            env: textToEnv(devServerEnv),
+            ready_pattern: devServerReadyPattern.trim() || null,
+          },
+          sandbox_settings: {
+            ...sandboxSettings,
+            image: sandboxSettings.image.trim(),
+          },
         };
 
         try {
@@ -137,11 +227,19 @@ export function ProjectForm({
       setGitRepoPath(project.git_repo_path || '');
       setSetupScript(project.setup_script ?? '');
       setDevScript(project.dev_script ?? '');
+      setMergeStrategy(project.merge_strategy);
+      setCommitSettings(project.commit_settings);
+      setAutoFixMaxIterations(project.auto_fix_max_iterations);
+      setDevServerEnv(envToText(project.dev_server_settings.env));
+      setDevServerReadyPattern(project.dev_server_settings.ready_pattern ?? '');
+      setSandboxSettings(project.sandbox_settings);
     } else {
       setName('');
       setGitRepoPath('');
       setSetupScript('');
       setDevScript('');
+      setMergeStrategy('squash');
+      setCommitSettings(defaultCommitSettings);
     }
     setParentPath('');
     setFolderName('');
@@ -190,6 +288,18 @@ export function ProjectForm({
                   setSetupScript={setSetupScript}
                   devScript={devScript}
                   setDevScript={setDevScript}
+                  mergeStrategy={mergeStrategy}
+                  setMergeStrategy={setMergeStrategy}
+                  commitSettings={commitSettings}
+                  setCommitSettings={setCommitSettings}
+                  autoFixMaxIterations={autoFixMaxIterations}
+                  setAutoFixMaxIterations={setAutoFixMaxIterations}
+                  devServerEnv={devServerEnv}
+                  setDevServerEnv={setDevServerEnv}
+                  devServerReadyPattern={devServerReadyPattern}
+                  setDevServerReadyPattern={setDevServerReadyPattern}
+                  sandboxSettings={sandboxSettings}
+                  setSandboxSettings={setSandboxSettings}
                   error={error}
                 />
                 <DialogFooter>
@@ -233,6 +343,18 @@ export function ProjectForm({
               setSetupScript={setSetupScript}
               devScript={devScript}
               setDevScript={setDevScript}
+              mergeStrategy={mergeStrategy}
+              setMergeStrategy={setMergeStrategy}
+              commitSettings={commitSettings}
+              setCommitSettings={setCommitSettings}
+              autoFixMaxIterations={autoFixMaxIterations}
+              setAutoFixMaxIterations={setAutoFixMaxIterations}
+              devServerEnv={devServerEnv}
+              setDevServerEnv={setDevServerEnv}
+              devServerReadyPattern={devServerReadyPattern}
+              setDevServerReadyPattern={setDevServerReadyPattern}
+              sandboxSettings={sandboxSettings}
+              setSandboxSettings={setSandboxSettings}
               error={error}
             />
             <DialogFooter>
@@ -12,9 +12,11 @@ import { ProjectForm } from './project-form';
 import { projectsApi } from '@/lib/api';
 import { AlertCircle, Loader2, Plus } from 'lucide-react';
 import ProjectCard from '@/components/projects/ProjectCard.tsx';
+import { useAuth } from '@/components/auth-provider';
 
 export function ProjectList() {
   const navigate = useNavigate();
+  const { isAdmin } = useAuth();
   const [projects, setProjects] = useState<Project[]>([]);
   const [loading, setLoading] = useState(false);
   const [showForm, setShowForm] = useState(false);
@@ -97,7 +99,7 @@ export function ProjectList() {
 
   useKeyboardShortcuts({
     ignoreEscape: true,
-    onC: () => setShowForm(true),
+    onC: isAdmin ? () => setShowForm(true) : undefined,
     navigate,
     currentPath: '/projects',
   });
@@ -136,10 +138,12 @@ export function ProjectList() {
             Manage your projects and track their progress
           </p>
         </div>
-        <Button onClick={() => setShowForm(true)}>
-          <Plus className="mr-2 h-4 w-4" />
-          Create Project
-        </Button>
+        {isAdmin && (
+          <Button onClick={() => setShowForm(true)}>
+            <Plus className="mr-2 h-4 w-4" />
+            Create Project
+          </Button>
+        )}
       </div>
 
       {error && (
@@ -162,12 +166,16 @@ export function ProjectList() {
             </div>
             <h3 className="mt-4 text-lg font-semibold">No projects yet</h3>
             <p className="mt-2 text-sm text-muted-foreground">
-              Get started by creating your first project.
+              {isAdmin
+                ? 'Get started by creating your first project.'
+                : 'Ask an admin to create a project or add you to one.'}
             </p>
-            <Button className="mt-4" onClick={() => setShowForm(true)}>
-              <Plus className="mr-2 h-4 w-4" />
-              Create your first project
-            </Button>
+            {isAdmin && (
+              <Button className="mt-4" onClick={() => setShowForm(true)}>
+                <Plus className="mr-2 h-4 w-4" />
+                Create your first project
+              </Button>
+            )}
           </CardContent>
         </Card>
       ) : (
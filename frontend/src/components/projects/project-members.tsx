@@ -0,0 +1,191 @@
+import { useCallback, useEffect, useState } from 'react';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { Trash2, Users } from 'lucide-react';
+import type { ProjectMember, ProjectRole, User } from 'shared/types';
+import { projectMembersApi, usersApi } from '@/lib/api';
+
+const ROLE_LABELS: Record<ProjectRole, string> = {
+  viewer: 'Viewer',
+  contributor: 'Contributor',
+  maintainer: 'Maintainer',
+};
+
+const ROLES = Object.keys(ROLE_LABELS) as ProjectRole[];
+
+interface ProjectMembersProps {
+  projectId: string;
+  // Whether the signed in user maintains the project, and so can change roles
+  canManage: boolean;
+}
+
+export function ProjectMembers({ projectId, canManage }: ProjectMembersProps) {
+  const [members, setMembers] = useState<ProjectMember[]>([]);
+  const [users, setUsers] = useState<User[]>([]);
+  const [newUserId, setNewUserId] = useState('');
+  const [newRole, setNewRole] = useState<ProjectRole>('contributor');
+  const [error, setError] = useState<string | null>(null);
+
+  const loadMembers = useCallback(async () => {
+    try {
+      setMembers(await projectMembersApi.list(projectId));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to load members');
+    }
+  }, [projectId]);
+
+  useEffect(() => {
+    loadMembers();
+  }, [loadMembers]);
+
+  useEffect(() => {
+    if (!canManage) return;
+    usersApi
+      .list()
+      .then(setUsers)
+      .catch((err) => console.error('Error loading users:', err));
+  }, [canManage]);
+
+  const setRole = async (userId: string, role: ProjectRole) => {
+    setError(null);
+    try {
+      await projectMembersApi.set(projectId, userId, { role });
+      await loadMembers();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to set the role');
+    }
+  };
+
+  const handleAdd = async () => {
+    await setRole(newUserId, newRole);
+    setNewUserId('');
+  };
+
+  const handleRemove = async (member: ProjectMember) => {
+    if (!confirm(`Remove ${member.username} from the project?`)) return;
+    setError(null);
+    try {
+      await projectMembersApi.delete(projectId, member.user_id);
+      await loadMembers();
+    } catch (err) {
+      setError(
+        err instanceof Error ? err.message : 'Failed to remove the member'
+      );
+    }
+  };
+
+  const nonMembers = users.filter(
+    (user) => !members.some((member) => member.user_id === user.id)
+  );
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center">
+          <Users className="mr-2 h-5 w-5" />
+          Members
+        </CardTitle>
+        <CardDescription>
+          Viewers see the board, contributors also work on tasks, and
+          maintainers merge attempts and change the project's settings. Admins
+          can do everything on every project.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+        {members.map((member) => (
+          <div
+            key={member.user_id}
+            className="flex items-center justify-between rounded-md border p-3"
+          >
+            <span className="text-sm font-medium">{member.username}</span>
+            {canManage ? (
+              <div className="flex items-center gap-2">
+                <Select
+                  value={member.role}
+                  onValueChange={(role: ProjectRole) =>
+                    setRole(member.user_id, role)
+                  }
+                >
+                  <SelectTrigger className="w-36">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {ROLES.map((role) => (
+                      <SelectItem key={role} value={role}>
+                        {ROLE_LABELS[role]}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => handleRemove(member)}
+                >
+                  <Trash2 className="h-4 w-4" />
+                </Button>
+              </div>
+            ) : (
+              <span className="text-sm text-muted-foreground">
+                {ROLE_LABELS[member.role]}
+              </span>
+            )}
+          </div>
+        ))}
+        {canManage && nonMembers.length > 0 && (
+          <div className="flex items-center gap-2">
+            <Select value={newUserId} onValueChange={setNewUserId}>
+              <SelectTrigger className="flex-1">
+                <SelectValue placeholder="Add a user" />
+              </SelectTrigger>
+              <SelectContent>
+                {nonMembers.map((user) => (
+                  <SelectItem key={user.id} value={user.id}>
+                    {user.username}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <Select
+              value={newRole}
+              onValueChange={(role: ProjectRole) => setNewRole(role)}
+            >
+              <SelectTrigger className="w-36">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {ROLES.map((role) => (
+                  <SelectItem key={role} value={role}>
+                    {ROLE_LABELS[role]}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <Button onClick={handleAdd} disabled={!newUserId}>
+              Add
+            </Button>
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
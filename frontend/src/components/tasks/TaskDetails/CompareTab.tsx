@@ -0,0 +1,192 @@
+import { useCallback, useContext, useEffect, useState } from 'react';
+import { CheckCircle, Clock, Loader2, XCircle } from 'lucide-react';
+import {
+  TaskDetailsContext,
+  TaskSelectedAttemptContext,
+} from '@/components/context/taskDetailsContext.ts';
+import { attemptsApi } from '@/lib/api.ts';
+import { Button } from '@/components/ui/button';
+import { useConfig } from '@/components/config-provider';
+import type { AttemptComparison } from 'shared/types';
+
+function formatDuration(seconds: number | bigint | null) {
+  if (seconds === null) return '-';
+  const total = Number(seconds);
+  const minutes = Math.floor(total / 60);
+  return minutes > 0 ? `${minutes}m ${total % 60}s` : `${total}s`;
+}
+
+function AgentStatus({ comparison }: { comparison: AttemptComparison }) {
+  switch (comparison.agent_status) {
+    case 'completed':
+      return (
+        <span className="flex items-center gap-1 text-green-600">
+          <CheckCircle className="h-4 w-4" />
+          Completed
+        </span>
+      );
+    case 'failed':
+    case 'killed':
+      return (
+        <span className="flex items-center gap-1 text-red-600">
+          <XCircle className="h-4 w-4" />
+          {comparison.agent_status === 'killed' ? 'Killed' : 'Failed'}
+          {comparison.exit_code !== null && ` (${comparison.exit_code})`}
+        </span>
+      );
+    case 'running':
+      return (
+        <span className="flex items-center gap-1 text-blue-600">
+          <Loader2 className="h-4 w-4 animate-spin" />
+          Running
+        </span>
+      );
+    default:
+      return (
+        <span className="flex items-center gap-1 text-muted-foreground">
+          <Clock className="h-4 w-4" />
+          Not started
+        </span>
+      );
+  }
+}
+
+function CompareTab() {
+  const { task, projectId } = useContext(TaskDetailsContext);
+  const { executors } = useConfig();
+  const { selectedAttempt, setSelectedAttempt } = useContext(
+    TaskSelectedAttemptContext
+  );
+  const [comparisons, setComparisons] = useState<AttemptComparison[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+
+  const fetchComparisons = useCallback(async () => {
+    try {
+      setComparisons(await attemptsApi.compare(projectId, task.id));
+      setError(null);
+    } catch (err) {
+      setError('Failed to load attempt comparison');
+    } finally {
+      setLoading(false);
+    }
+  }, [projectId, task.id]);
+
+  useEffect(() => {
+    fetchComparisons();
+  }, [fetchComparisons]);
+
+  // Keep refreshing while some attempt is still running
+  useEffect(() => {
+    if (!comparisons.some((c) => c.agent_status === 'running')) return;
+    const interval = setInterval(fetchComparisons, 5000);
+    return () => clearInterval(interval);
+  }, [comparisons, fetchComparisons]);
+
+  if (loading) {
+    return (
+      <div className="flex items-center justify-center py-8 text-muted-foreground">
+        <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+        Loading attempts...
+      </div>
+    );
+  }
+
+  if (error) {
+    return <div className="px-4 text-sm text-destructive">{error}</div>;
+  }
+
+  if (comparisons.length === 0) {
+    return (
+      <div className="px-4 text-sm text-muted-foreground">
+        No attempts to compare yet.
+      </div>
+    );
+  }
+
+  return (
+    <div className="px-4 overflow-auto">
+      <table className="w-full text-sm">
+        <thead>
+          <tr className="border-b text-left text-muted-foreground">
+            <th className="py-2 pr-4 font-medium">Executor</th>
+            <th className="py-2 pr-4 font-medium">Agent</th>
+            <th className="py-2 pr-4 font-medium">Duration</th>
+            <th className="py-2 pr-4 font-medium">Changes</th>
+            <th className="py-2 pr-4 font-medium">Checks</th>
+            <th className="py-2" />
+          </tr>
+        </thead>
+        <tbody>
+          {comparisons.map((comparison) => {
+            const { attempt, diff_stats, checks } = comparison;
+            const passed = checks.filter(
+              (c) => c.status === 'passed' && !c.outdated
+            ).length;
+            const failed = checks.filter((c) => c.status === 'failed').length;
+            const isSelected = selectedAttempt?.id === attempt.id;
+            return (
+              <tr
+                key={attempt.id}
+                className={`border-b ${isSelected ? 'bg-muted/50' : ''}`}
+              >
+                <td className="py-2 pr-4">
+                  <div className="font-medium">
+                    {executors.find((e) => e.id === attempt.executor)?.label ||
+                      attempt.executor ||
+                      'default'}
+                  </div>
+                  <div className="text-xs text-muted-foreground font-mono">
+                    {attempt.branch}
+                  </div>
+                </td>
+                <td className="py-2 pr-4">
+                  <AgentStatus comparison={comparison} />
+                </td>
+                <td className="py-2 pr-4">
+                  {formatDuration(comparison.agent_duration_seconds)}
+                </td>
+                <td className="py-2 pr-4 whitespace-nowrap">
+                  {diff_stats ? (
+                    <>
+                      {diff_stats.files_changed} files{' '}
+                      <span className="text-green-600">
+                        +{diff_stats.lines_added}
+                      </span>{' '}
+                      <span className="text-red-600">
+                        -{diff_stats.lines_removed}
+                      </span>
+                    </>
+                  ) : (
+                    '-'
+                  )}
+                </td>
+                <td className="py-2 pr-4 whitespace-nowrap">
+                  {checks.length === 0 ? (
+                    '-'
+                  ) : (
+                    <span className={failed > 0 ? 'text-red-600' : ''}>
+                      {passed}/{checks.length} passed
+                    </span>
+                  )}
+                </td>
+                <td className="py-2 text-right">
+                  <Button
+                    size="sm"
+                    variant={isSelected ? 'secondary' : 'outline'}
+                    disabled={isSelected}
+                    onClick={() => setSelectedAttempt(attempt)}
+                  >
+                    {isSelected ? 'Selected' : 'Select'}
+                  </Button>
+                </td>
+              </tr>
+            );
+          })}
+        </tbody>
+      </table>
+    </div>
+  );
+}
+
+export default CompareTab;
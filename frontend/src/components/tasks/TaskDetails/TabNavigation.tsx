@@ -1,4 +1,4 @@
-import { GitCompare, MessageSquare, Network } from 'lucide-react';
+import { Columns, GitCompare, MessageSquare, Network } from 'lucide-react';
 import { useContext } from 'react';
 import {
   TaskDiffContext,
@@ -6,8 +6,8 @@ import {
 } from '@/components/context/taskDetailsContext.ts';
 
 type Props = {
-  activeTab: 'logs' | 'diffs' | 'related';
-  setActiveTab: (tab: 'logs' | 'diffs' | 'related') => void;
+  activeTab: 'logs' | 'diffs' | 'related' | 'compare';
+  setActiveTab: (tab: 'logs' | 'diffs' | 'related' | 'compare') => void;
   setUserSelectedTab: (tab: boolean) => void;
 };
 
@@ -74,6 +74,20 @@ function TabNavigation({ activeTab, setActiveTab, setUserSelectedTab }: Props) {
             </span>
           )}
         </button>
+        <button
+          onClick={() => {
+            setActiveTab('compare');
+            setUserSelectedTab(true);
+          }}
+          className={`flex items-center px-4 py-2 text-sm font-medium border-b-2 transition-colors ${
+            activeTab === 'compare'
+              ? 'border-primary text-primary bg-background'
+              : 'border-transparent text-muted-foreground hover:text-foreground hover:bg-muted/50'
+          }`}
+        >
+          <Columns className="h-4 w-4 mr-2" />
+          Compare
+        </button>
       </div>
     </div>
   );
@@ -10,6 +10,7 @@ import {
 } from '@/components/ui/tooltip';
 import type { TaskStatus, TaskWithAttemptStatus } from 'shared/types';
 import { TaskDetailsContext } from '@/components/context/taskDetailsContext.ts';
+import { useAuth } from '@/components/auth-provider';
 
 interface TaskDetailsHeaderProps {
   onClose: () => void;
@@ -48,6 +49,8 @@ function TaskDetailsHeader({
   onDeleteTask,
 }: TaskDetailsHeaderProps) {
   const { task } = useContext(TaskDetailsContext);
+  const { usernameOf } = useAuth();
+  const createdBy = usernameOf(task.created_by);
   const [isDescriptionExpanded, setIsDescriptionExpanded] = useState(false);
 
   return (
@@ -63,6 +66,7 @@ function TaskDetailsHeader({
               <Chip dotColor={getTaskStatusDotColor(task.status)}>
                 {statusLabels[task.status]}
               </Chip>
+              {createdBy && <span>Created by {createdBy}</span>}
             </div>
           </div>
           <div className="flex items-center gap-1">
@@ -10,6 +10,7 @@ import type { TaskWithAttemptStatus } from 'shared/types';
 import DiffTab from '@/components/tasks/TaskDetails/DiffTab.tsx';
 import LogsTab from '@/components/tasks/TaskDetails/LogsTab.tsx';
 import RelatedTasksTab from '@/components/tasks/TaskDetails/RelatedTasksTab.tsx';
+import CompareTab from '@/components/tasks/TaskDetails/CompareTab.tsx';
 import DeleteFileConfirmationDialog from '@/components/tasks/DeleteFileConfirmationDialog.tsx';
 import TabNavigation from '@/components/tasks/TaskDetails/TabNavigation.tsx';
 import CollapsibleToolbar from '@/components/tasks/TaskDetails/CollapsibleToolbar.tsx';
@@ -37,7 +38,7 @@ export function TaskDetailsPanel({
   const [showEditorDialog, setShowEditorDialog] = useState(false);
 
   // Tab and collapsible state
-  const [activeTab, setActiveTab] = useState<'logs' | 'diffs' | 'related'>(
+  const [activeTab, setActiveTab] = useState<'logs' | 'diffs' | 'related' | 'compare'>(
     'logs'
   );
   const [userSelectedTab, setUserSelectedTab] = useState<boolean>(false);
@@ -107,6 +108,8 @@ export function TaskDetailsPanel({
                   <DiffTab />
                 ) : activeTab === 'related' ? (
                   <RelatedTasksTab />
+                ) : activeTab === 'compare' ? (
+                  <CompareTab />
                 ) : (
                   <LogsTab />
                 )}
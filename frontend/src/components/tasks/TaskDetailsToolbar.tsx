@@ -1,11 +1,10 @@
-import { useCallback, useContext, useEffect, useState } from 'react';
+import { useCallback, useContext, useEffect, useMemo, useState } from 'react';
 import { useLocation } from 'react-router-dom';
 import { Play } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import { useConfig } from '@/components/config-provider';
 import { attemptsApi, projectsApi } from '@/lib/api';
 import type { GitBranch, TaskAttempt } from 'shared/types';
-import { EXECUTOR_TYPES, EXECUTOR_LABELS } from 'shared/types';
 import {
   TaskAttemptDataContext,
   TaskAttemptLoadingContext,
@@ -17,11 +16,7 @@ import {
 import CreatePRDialog from '@/components/tasks/Toolbar/CreatePRDialog.tsx';
 import CreateAttempt from '@/components/tasks/Toolbar/CreateAttempt.tsx';
 import CurrentAttempt from '@/components/tasks/Toolbar/CurrentAttempt.tsx';
-
-const availableExecutors = EXECUTOR_TYPES.map((id) => ({
-  id,
-  name: EXECUTOR_LABELS[id] || id,
-}));
+import { executorId } from '@/lib/utils';
 
 function TaskDetailsToolbar() {
   const { task, projectId } = useContext(TaskDetailsContext);
@@ -39,13 +34,17 @@ function TaskDetailsToolbar() {
   const [taskAttempts, setTaskAttempts] = useState<TaskAttempt[]>([]);
   const location = useLocation();
 
-  const { config } = useConfig();
+  const { config, executors } = useConfig();
+  const availableExecutors = useMemo(
+    () => executors.map((e) => ({ id: e.id, name: e.label })),
+    [executors]
+  );
 
   const [branches, setBranches] = useState<GitBranch[]>([]);
   const [selectedBranch, setSelectedBranch] = useState<string | null>(null);
 
   const [selectedExecutor, setSelectedExecutor] = useState<string>(
-    config?.executor.type || 'claude'
+    config ? executorId(config.executor) : 'claude'
   );
 
   // State for create attempt mode
@@ -78,8 +77,8 @@ function TaskDetailsToolbar() {
 
   // Set default executor from config
   useEffect(() => {
-    if (config && config.executor.type !== selectedExecutor) {
-      setSelectedExecutor(config.executor.type);
+    if (config && executorId(config.executor) !== selectedExecutor) {
+      setSelectedExecutor(executorId(config.executor));
     }
   }, [config, selectedExecutor]);
 
@@ -223,7 +222,13 @@ function TaskDetailsToolbar() {
       setCreateAttemptBranch(selectedBranch);
       setCreateAttemptExecutor(selectedExecutor);
     }
-  }, [taskAttempts, branches, selectedBranch, selectedExecutor]);
+  }, [
+    taskAttempts,
+    branches,
+    selectedBranch,
+    selectedExecutor,
+    availableExecutors,
+  ]);
 
   return (
     <>
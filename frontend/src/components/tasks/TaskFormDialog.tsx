@@ -19,6 +19,7 @@ import {
 } from '@/components/ui/select';
 import { useConfig } from '@/components/config-provider';
 import { templatesApi } from '@/lib/api';
+import { executorId } from '@/lib/utils';
 import type { TaskStatus, ExecutorConfig, TaskTemplate } from 'shared/types';
 
 interface Task {
@@ -41,7 +42,8 @@ interface TaskFormDialogProps {
   onCreateAndStartTask?: (
     title: string,
     description: string,
-    executor?: ExecutorConfig
+    executor?: ExecutorConfig,
+    executors?: ExecutorConfig[]
   ) => Promise<void>;
   onUpdateTask?: (
     title: string,
@@ -67,8 +69,11 @@ export function TaskFormDialog({
   const [isSubmittingAndStart, setIsSubmittingAndStart] = useState(false);
   const [templates, setTemplates] = useState<TaskTemplate[]>([]);
   const [selectedTemplate, setSelectedTemplate] = useState<string>('');
+  // Executors to run alongside the default one, each in its own attempt
+  const [extraExecutors, setExtraExecutors] = useState<string[]>([]);
 
-  const { config } = useConfig();
+  const { config, executors: executorOptions } = useConfig();
+  const defaultExecutorId = config ? executorId(config.executor) : '';
   const isEditMode = Boolean(task);
 
   useEffect(() => {
@@ -90,6 +95,7 @@ export function TaskFormDialog({
       setStatus('todo');
       setSelectedTemplate('');
     }
+    setExtraExecutors([]);
   }, [task, initialTemplate, isOpen]);
 
   // Fetch templates when dialog opens in create mode
@@ -154,7 +160,21 @@ export function TaskFormDialog({
     setIsSubmittingAndStart(true);
     try {
       if (!isEditMode && onCreateAndStartTask) {
-        await onCreateAndStartTask(title, description, config?.executor);
+        const executors =
+          extraExecutors.length > 0 && config?.executor
+            ? [
+                config.executor,
+                ...executorOptions
+                  .filter((option) => extraExecutors.includes(option.id))
+                  .map((option) => option.config),
+              ]
+            : undefined;
+        await onCreateAndStartTask(
+          title,
+          description,
+          config?.executor,
+          executors
+        );
       }
 
       // Reset form on successful creation
@@ -170,6 +190,8 @@ export function TaskFormDialog({
     title,
     description,
     config?.executor,
+    executorOptions,
+    extraExecutors,
     isEditMode,
     onCreateAndStartTask,
     onOpenChange,
@@ -327,6 +349,63 @@ export function TaskFormDialog({
             </div>
           )}
 
+          {!isEditMode && onCreateAndStartTask && (
+            <div className="pt-2">
+              <details className="group">
+                <summary className="cursor-pointer text-sm text-muted-foreground hover:text-foreground transition-colors list-none flex items-center gap-2">
+                  <svg
+                    className="h-3 w-3 transition-transform group-open:rotate-90"
+                    viewBox="0 0 20 20"
+                    fill="currentColor"
+                  >
+                    <path
+                      fillRule="evenodd"
+                      d="M7.293 14.707a1 1 0 010-1.414L10.586 10 7.293 6.707a1 1 0 011.414-1.414l4 4a1 1 0 010 1.414l-4 4a1 1 0 01-1.414 0z"
+                      clipRule="evenodd"
+                    />
+                  </svg>
+                  Compare executors
+                  {extraExecutors.length > 0 &&
+                    ` (${extraExecutors.length + 1} attempts)`}
+                </summary>
+                <div className="mt-3 space-y-2">
+                  <p className="text-xs text-muted-foreground">
+                    Also start the task on these executors, each in its own
+                    worktree, next to{' '}
+                    {executorOptions.find(
+                      (option) => option.id === defaultExecutorId
+                    )?.label ?? 'the default executor'}
+                    .
+                  </p>
+                  <div className="flex flex-wrap gap-2">
+                    {executorOptions
+                      .filter((option) => option.id !== defaultExecutorId)
+                      .map((option) => (
+                        <label
+                          key={option.id}
+                          className="flex items-center gap-1.5 text-sm cursor-pointer"
+                        >
+                          <input
+                            type="checkbox"
+                            checked={extraExecutors.includes(option.id)}
+                            onChange={(e) =>
+                              setExtraExecutors((prev) =>
+                                e.target.checked
+                                  ? [...prev, option.id]
+                                  : prev.filter((id) => id !== option.id)
+                              )
+                            }
+                            disabled={isSubmitting || isSubmittingAndStart}
+                          />
+                          {option.label}
+                        </label>
+                      ))}
+                  </div>
+                </div>
+              </details>
+            </div>
+          )}
+
           {isEditMode && (
             <div className="pt-2">
               <Label htmlFor="task-status" className="text-sm font-medium">
@@ -25,7 +25,8 @@ import {
 import { ApiError, attemptsApi } from '@/lib/api.ts';
 import { ProvidePatDialog } from '@/components/ProvidePatDialog';
 import { GitHubLoginDialog } from '@/components/GitHubLoginDialog';
-import { GitBranch } from 'shared/types.ts';
+import { CheckResult, GitBranch } from 'shared/types.ts';
+import { blockingChecks } from '@/lib/utils';
 
 type Props = {
   showCreatePRDialog: boolean;
@@ -54,11 +55,20 @@ function CreatePrDialog({
   const [showPatDialog, setShowPatDialog] = useState(false);
   const [patDialogError, setPatDialogError] = useState<string | null>(null);
   const [showGitHubLoginDialog, setShowGitHubLoginDialog] = useState(false);
+  const [failingChecks, setFailingChecks] = useState<CheckResult[]>([]);
+  const [skipChecks, setSkipChecks] = useState(false);
 
   useEffect(() => {
     if (showCreatePRDialog) {
       setPrTitle(`${task.title} (vibe-kanban)`);
       setPrBody(task.description || '');
+      setSkipChecks(false);
+      if (projectId && selectedAttempt) {
+        attemptsApi
+          .getChecks(projectId, selectedAttempt.task_id, selectedAttempt.id)
+          .then((results) => setFailingChecks(blockingChecks(results)))
+          .catch(() => setFailingChecks([]));
+      }
     }
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [showCreatePRDialog]);
@@ -84,6 +94,7 @@ function CreatePrDialog({
           title: prTitle,
           body: prBody || null,
           base_branch: prBaseBranch || null,
+          skip_checks: skipChecks,
         }
       );
       // Open the PR URL in a new tab
@@ -122,7 +133,7 @@ function CreatePrDialog({
         );
         setShowPatDialog(true);
       } else {
-        setError(error.message || 'Failed to create GitHub PR');
+        setError(error.message || 'Failed to create PR');
       }
     } finally {
       setCreatingPR(false);
@@ -133,6 +144,7 @@ function CreatePrDialog({
     prBaseBranch,
     prBody,
     prTitle,
+    skipChecks,
     setCreatingPR,
     setError,
     setShowCreatePRDialog,
@@ -157,9 +169,10 @@ function CreatePrDialog({
       >
         <DialogContent className="sm:max-w-[525px]">
           <DialogHeader>
-            <DialogTitle>Create GitHub Pull Request</DialogTitle>
+            <DialogTitle>Create Pull Request</DialogTitle>
             <DialogDescription>
-              Create a pull request for this task attempt on GitHub.
+              Push this task attempt and open a pull request for it where the
+              repository is hosted.
             </DialogDescription>
           </DialogHeader>
           <div className="space-y-4 py-4">
@@ -207,6 +220,22 @@ function CreatePrDialog({
                 </SelectContent>
               </Select>
             </div>
+            {failingChecks.length > 0 && (
+              <div className="space-y-2 rounded border border-orange-300 bg-orange-50 p-3 text-sm text-orange-800">
+                <p>
+                  Required checks have not passed:{' '}
+                  {failingChecks.map((check) => check.name).join(', ')}
+                </p>
+                <label className="flex items-center gap-2">
+                  <input
+                    type="checkbox"
+                    checked={skipChecks}
+                    onChange={(e) => setSkipChecks(e.target.checked)}
+                  />
+                  Create the pull request anyway
+                </label>
+              </div>
+            )}
           </div>
           <DialogFooter>
             <Button variant="outline" onClick={handleCancelCreatePR}>
@@ -214,7 +243,11 @@ function CreatePrDialog({
             </Button>
             <Button
               onClick={handleConfirmCreatePR}
-              disabled={creatingPR || !prTitle.trim()}
+              disabled={
+                creatingPR ||
+                !prTitle.trim() ||
+                (failingChecks.length > 0 && !skipChecks)
+              }
               className="bg-blue-600 hover:bg-blue-700"
             >
               {creatingPR ? 'Creating...' : 'Create PR'}
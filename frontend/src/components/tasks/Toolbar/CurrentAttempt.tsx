@@ -1,4 +1,5 @@
 import {
+  CheckCircle2,
   ExternalLink,
   GitBranch as GitBranchIcon,
   GitPullRequest,
@@ -8,8 +9,9 @@ import {
   RefreshCw,
   Settings,
   StopCircle,
+  XCircle,
 } from 'lucide-react';
-import { is_planning_executor_type } from '@/lib/utils';
+import { blockingChecks, is_planning_executor_type } from '@/lib/utils';
 import {
   Tooltip,
   TooltipContent,
@@ -50,6 +52,7 @@ import {
 } from 'react';
 import type {
   BranchStatus,
+  CheckResult,
   ExecutionProcess,
   GitBranch,
   TaskAttempt,
@@ -63,6 +66,7 @@ import {
   TaskSelectedAttemptContext,
 } from '@/components/context/taskDetailsContext.ts';
 import { useConfig } from '@/components/config-provider.tsx';
+import { useAuth } from '@/components/auth-provider';
 import { useKeyboardShortcuts } from '@/lib/keyboard-shortcuts.ts';
 import { useNavigate } from 'react-router-dom';
 
@@ -115,6 +119,7 @@ function CurrentAttempt({
   const { task, projectId, handleOpenInEditor, projectHasDevScript } =
     useContext(TaskDetailsContext);
   const { config } = useConfig();
+  const { usernameOf, previewPort } = useAuth();
   const { setSelectedAttempt } = useContext(TaskSelectedAttemptContext);
   const navigate = useNavigate();
   const { isStopping, setIsStopping } = useContext(TaskAttemptStoppingContext);
@@ -138,6 +143,14 @@ function CurrentAttempt({
   const [selectedRebaseBranch, setSelectedRebaseBranch] = useState<string>('');
   const [showStopConfirmation, setShowStopConfirmation] = useState(false);
   const [isApprovingPlan, setIsApprovingPlan] = useState(false);
+  const [checkResults, setCheckResults] = useState<CheckResult[]>([]);
+  const [isStartingChecks, setIsStartingChecks] = useState(false);
+  const [showChecksOverride, setShowChecksOverride] = useState(false);
+
+  const failingChecks = useMemo(
+    () => blockingChecks(checkResults),
+    [checkResults]
+  );
 
   const processedDevServerLogs = useMemo(() => {
     if (!devServerDetails) return 'No output yet...';
@@ -278,9 +291,66 @@ function CurrentAttempt({
     [fetchAttemptData, fetchExecutionState, setSelectedAttempt]
   );
 
+  const fetchChecks = useCallback(async () => {
+    if (!projectId || !selectedAttempt?.id || !selectedAttempt?.task_id) return;
+
+    try {
+      const results = await attemptsApi.getChecks(
+        projectId,
+        selectedAttempt.task_id,
+        selectedAttempt.id
+      );
+      setCheckResults(results);
+    } catch (err) {
+      console.error('Failed to load checks:', err);
+    }
+  }, [projectId, selectedAttempt?.id, selectedAttempt?.task_id]);
+
+  // Check runs show up as attempt processes, so refetch whenever those change
+  useEffect(() => {
+    fetchChecks();
+  }, [fetchChecks, attemptData.processes]);
+
+  const checksRunning = checkResults.some(
+    (check) => check.status === 'running'
+  );
+
+  useEffect(() => {
+    if (!checksRunning) return;
+
+    const interval = setInterval(fetchChecks, 2000);
+    return () => clearInterval(interval);
+  }, [checksRunning, fetchChecks]);
+
+  const runChecks = async () => {
+    if (!task || !selectedAttempt) return;
+
+    setIsStartingChecks(true);
+
+    try {
+      await attemptsApi.runChecks(
+        projectId,
+        selectedAttempt.task_id,
+        selectedAttempt.id
+      );
+      fetchChecks();
+    } catch (err) {
+      // @ts-expect-error it is type ApiError
+      setError(err.message || 'Failed to run checks');
+    } finally {
+      setIsStartingChecks(false);
+    }
+  };
+
   const handleMergeClick = async () => {
     if (!projectId || !selectedAttempt?.id || !selectedAttempt?.task_id) return;
 
+    // Required checks that haven't passed need an explicit override
+    if (failingChecks.length > 0) {
+      setShowChecksOverride(true);
+      return;
+    }
+
     // Directly perform merge without checking branch status
     await performMerge();
   };
@@ -313,7 +383,7 @@ function CurrentAttempt({
     }
   }, [selectedAttempt, fetchBranchStatus]);
 
-  const performMerge = async () => {
+  const performMerge = async (skipChecks = false) => {
     if (!projectId || !selectedAttempt?.id || !selectedAttempt?.task_id) return;
 
     try {
@@ -321,7 +391,9 @@ function CurrentAttempt({
       await attemptsApi.merge(
         projectId,
         selectedAttempt.task_id,
-        selectedAttempt.id
+        selectedAttempt.id,
+        null,
+        skipChecks
       );
       // Refetch branch status to show updated state
       fetchBranchStatus();
@@ -452,6 +524,8 @@ function CurrentAttempt({
   }, [selectedBranch]);
 
   // Get display name for the configured editor
+  const startedBy = usernameOf(selectedAttempt.started_by);
+
   const editorDisplayName = useMemo(() => {
     if (!config?.editor?.editor_type) return 'Editor';
     return getEditorDisplayName(config.editor.editor_type);
@@ -470,6 +544,9 @@ function CurrentAttempt({
               hour: '2-digit',
               minute: '2-digit',
             })}
+            {startedBy && (
+              <span className="text-muted-foreground"> by {startedBy}</span>
+            )}
           </div>
         </div>
 
@@ -585,6 +662,53 @@ function CurrentAttempt({
         </div>
       </div>
 
+      {checkResults.length > 0 && (
+        <div className="col-span-4">
+          <div className="flex items-center gap-1.5 mb-1">
+            <div className="text-xs font-medium text-muted-foreground uppercase tracking-wide mb-1">
+              Checks
+            </div>
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={runChecks}
+              disabled={isStartingChecks || isAttemptRunning || checksRunning}
+              className="h-6 px-2 text-xs hover:bg-muted gap-1"
+            >
+              <Play className="h-3 w-3" />
+              Run Checks
+            </Button>
+          </div>
+          <div className="flex flex-wrap gap-3">
+            {checkResults.map((check) => (
+              <div
+                key={check.check_id}
+                className="flex items-center gap-1.5 text-sm"
+              >
+                {check.status === 'passed' ? (
+                  <CheckCircle2 className="h-3 w-3 text-green-600" />
+                ) : check.status === 'failed' ? (
+                  <XCircle className="h-3 w-3 text-red-600" />
+                ) : check.status === 'running' ? (
+                  <RefreshCw className="h-3 w-3 animate-spin text-muted-foreground" />
+                ) : (
+                  <div className="h-2 w-2 bg-gray-400 rounded-full" />
+                )}
+                <span className="font-medium">{check.name}</span>
+                {check.outdated && (
+                  <span className="text-xs text-orange-700">(outdated)</span>
+                )}
+                {!check.required && (
+                  <span className="text-xs text-muted-foreground">
+                    (optional)
+                  </span>
+                )}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
       <div className="col-span-4 flex flex-wrap items-center justify-between gap-2">
         <div className="flex items-center gap-2 flex-wrap">
           <div
@@ -641,6 +765,38 @@ function CurrentAttempt({
               </Tooltip>
             </TooltipProvider>
           </div>
+          {runningDevServer?.port != null && (
+            <TooltipProvider>
+              <Tooltip>
+                <TooltipTrigger asChild>
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    className="gap-1"
+                    onClick={() => {
+                      const path = `/preview/${selectedAttempt.id}/`;
+                      const { protocol, hostname } = window.location;
+                      window.open(
+                        previewPort == null
+                          ? path
+                          : `${protocol}//${hostname}:${previewPort}${path}`,
+                        '_blank'
+                      );
+                    }}
+                  >
+                    <ExternalLink className="h-3 w-3" />
+                    {runningDevServer.ready_at ? 'Preview' : 'Preview (starting)'}
+                  </Button>
+                </TooltipTrigger>
+                <TooltipContent>
+                  <p>
+                    Open the running app on port {runningDevServer.port} through
+                    the preview proxy
+                  </p>
+                </TooltipContent>
+              </Tooltip>
+            </TooltipProvider>
+          )}
         </div>
 
         <div className="flex items-center gap-2 flex-wrap">
@@ -830,6 +986,39 @@ function CurrentAttempt({
         </DialogContent>
       </Dialog>
 
+      {/* Failing Checks Override Dialog */}
+      <Dialog open={showChecksOverride} onOpenChange={setShowChecksOverride}>
+        <DialogContent className="sm:max-w-md">
+          <DialogHeader>
+            <DialogTitle>Required Checks Have Not Passed</DialogTitle>
+            <DialogDescription>
+              {failingChecks.map((check) => check.name).join(', ')}{' '}
+              {failingChecks.length === 1 ? 'has' : 'have'} not passed on the
+              latest changes. Merge anyway?
+            </DialogDescription>
+          </DialogHeader>
+          <DialogFooter>
+            <Button
+              variant="outline"
+              onClick={() => setShowChecksOverride(false)}
+              disabled={merging}
+            >
+              Cancel
+            </Button>
+            <Button
+              variant="destructive"
+              onClick={async () => {
+                setShowChecksOverride(false);
+                await performMerge(true);
+              }}
+              disabled={merging}
+            >
+              Merge Anyway
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
       {/* Stop Execution Confirmation Dialog */}
       <Dialog
         open={showStopConfirmation}
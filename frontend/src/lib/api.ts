@@ -1,30 +1,69 @@
 // Import all necessary types from shared types
 import {
+  ApiToken,
+  AttemptComparison,
+  AuditEventPage,
+  AuditEventQuery,
+  AuthSession,
   BranchStatus,
+  ChangePassword,
+  CheckResult,
   Config,
+  ConfigConstants,
+  ConflictOperation,
+  ConflictReport,
+  ConflictResolution,
+  CreateApiToken,
+  CreatedApiToken,
   CreateFollowUpAttempt,
   CreateProject,
+  CreateProjectCheck,
+  CreateProjectEnvVar,
   CreateTask,
   CreateTaskAndStart,
+  CreateExecutorProfile,
   CreateTaskAttempt,
+  CreateTaskDependency,
   CreateTaskTemplate,
+  CreateUser,
+  CreateWebhook,
   DeviceStartResponse,
   DirectoryEntry,
   type EditorType,
   ExecutionProcess,
   ExecutionProcessSummary,
+  ExecutorProfile,
   GitBranch,
+  LoginRequest,
+  MergeResult,
+  MergeStrategy,
+  MergeTaskAttempt,
   ProcessLogsResponse,
   Project,
+  ProjectCheck,
+  ProjectEnvVar,
+  ProjectMember,
+  ProjectRole,
   ProjectWithBranch,
+  ResolveConflicts,
+  SetProjectMember,
+  StartConflictResolution,
   Task,
   TaskAttempt,
   TaskAttemptState,
+  TaskDependencies,
+  TaskDependency,
   TaskTemplate,
   TaskWithAttemptStatus,
+  UpdateExecutorProfile,
   UpdateProject,
+  UpdateProjectCheck,
+  UpdateProjectEnvVar,
   UpdateTask,
   UpdateTaskTemplate,
+  UpdateWebhook,
+  User,
+  Webhook,
   WorktreeDiff,
 } from 'shared/types';
 
@@ -33,6 +72,8 @@ const getApiBaseUrl = () => {
   return import.meta.env.VITE_API_BASE_PATH || '';
 };
 
+export const UNAUTHORIZED_EVENT = 'vibe-kanban:unauthorized';
+
 export const makeRequest = async (url: string, options: RequestInit = {}) => {
   const headers = {
     'Content-Type': 'application/json',
@@ -43,10 +84,15 @@ export const makeRequest = async (url: string, options: RequestInit = {}) => {
   const baseUrl = getApiBaseUrl();
   const fullUrl = url.startsWith('/') ? `${baseUrl}${url}` : url;
 
-  return fetch(fullUrl, {
+  const response = await fetch(fullUrl, {
     ...options,
     headers,
   });
+  // The session of a multi-user server ended, the sign in form shows again
+  if (response.status === 401) {
+    window.dispatchEvent(new Event(UNAUTHORIZED_EVENT));
+  }
+  return response;
 };
 
 export interface ApiResponse<T> {
@@ -249,6 +295,45 @@ export const tasksApi = {
     return handleApiResponse<void>(response);
   },
 
+  getDependencies: async (
+    projectId: string,
+    taskId: string
+  ): Promise<TaskDependencies> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/dependencies`
+    );
+    return handleApiResponse<TaskDependencies>(response);
+  },
+
+  addDependency: async (
+    projectId: string,
+    taskId: string,
+    data: CreateTaskDependency
+  ): Promise<TaskDependency> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/dependencies`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskDependency>(response);
+  },
+
+  removeDependency: async (
+    projectId: string,
+    taskId: string,
+    blockedByTaskId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/dependencies/${blockedByTaskId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
   getChildren: async (
     projectId: string,
     taskId: string,
@@ -270,6 +355,16 @@ export const attemptsApi = {
     return handleApiResponse<TaskAttempt[]>(response);
   },
 
+  compare: async (
+    projectId: string,
+    taskId: string
+  ): Promise<AttemptComparison[]> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/attempts/compare`
+    );
+    return handleApiResponse<AttemptComparison[]>(response);
+  },
+
   create: async (
     projectId: string,
     taskId: string,
@@ -384,15 +479,19 @@ export const attemptsApi = {
   merge: async (
     projectId: string,
     taskId: string,
-    attemptId: string
-  ): Promise<void> => {
+    attemptId: string,
+    strategy: MergeStrategy | null = null,
+    skipChecks = false
+  ): Promise<MergeResult> => {
+    const body: MergeTaskAttempt = { strategy, skip_checks: skipChecks };
     const response = await makeRequest(
       `/api/projects/${projectId}/tasks/${taskId}/attempts/${attemptId}/merge`,
       {
         method: 'POST',
+        body: JSON.stringify(body),
       }
     );
-    return handleApiResponse<void>(response);
+    return handleApiResponse<MergeResult>(response);
   },
 
   rebase: async (
@@ -416,6 +515,79 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  getConflicts: async (
+    projectId: string,
+    taskId: string,
+    attemptId: string
+  ): Promise<ConflictReport | null> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/attempts/${attemptId}/conflicts`
+    );
+    return handleApiResponse<ConflictReport | null>(response);
+  },
+
+  startConflictResolution: async (
+    projectId: string,
+    taskId: string,
+    attemptId: string,
+    operation: ConflictOperation
+  ): Promise<ConflictReport | null> => {
+    const body: StartConflictResolution = { operation };
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/attempts/${attemptId}/conflicts/start`,
+      {
+        method: 'POST',
+        body: JSON.stringify(body),
+      }
+    );
+    return handleApiResponse<ConflictReport | null>(response);
+  },
+
+  resolveConflicts: async (
+    projectId: string,
+    taskId: string,
+    attemptId: string,
+    resolutions: ConflictResolution[]
+  ): Promise<ConflictReport | null> => {
+    const body: ResolveConflicts = { resolutions };
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/attempts/${attemptId}/conflicts/resolve`,
+      {
+        method: 'POST',
+        body: JSON.stringify(body),
+      }
+    );
+    return handleApiResponse<ConflictReport | null>(response);
+  },
+
+  abortConflicts: async (
+    projectId: string,
+    taskId: string,
+    attemptId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/attempts/${attemptId}/conflicts/abort`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  resolveConflictsWithAgent: async (
+    projectId: string,
+    taskId: string,
+    attemptId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/attempts/${attemptId}/conflicts/agent`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
   createPR: async (
     projectId: string,
     taskId: string,
@@ -424,6 +596,7 @@ export const attemptsApi = {
       title: string;
       body: string | null;
       base_branch: string | null;
+      skip_checks?: boolean;
     }
   ): Promise<string> => {
     const response = await makeRequest(
@@ -436,6 +609,31 @@ export const attemptsApi = {
     return handleApiResponse<string>(response);
   },
 
+  getChecks: async (
+    projectId: string,
+    taskId: string,
+    attemptId: string
+  ): Promise<CheckResult[]> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/attempts/${attemptId}/checks`
+    );
+    return handleApiResponse<CheckResult[]>(response);
+  },
+
+  runChecks: async (
+    projectId: string,
+    taskId: string,
+    attemptId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/tasks/${taskId}/attempts/${attemptId}/checks/run`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
   startDevServer: async (
     projectId: string,
     taskId: string,
@@ -528,6 +726,10 @@ export const configApi = {
     });
     return handleApiResponse<Config>(response);
   },
+  getConstants: async (): Promise<ConfigConstants> => {
+    const response = await makeRequest('/api/config/constants');
+    return handleApiResponse<ConfigConstants>(response);
+  },
 };
 
 // GitHub Device Auth APIs
@@ -610,6 +812,237 @@ export const templatesApi = {
   },
 };
 
+// Executor Profile APIs
+export const executorProfilesApi = {
+  list: async (projectId: string): Promise<ExecutorProfile[]> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/executor-profiles`
+    );
+    return handleApiResponse<ExecutorProfile[]>(response);
+  },
+
+  create: async (
+    projectId: string,
+    data: CreateExecutorProfile
+  ): Promise<ExecutorProfile> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/executor-profiles`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutorProfile>(response);
+  },
+
+  update: async (
+    projectId: string,
+    profileId: string,
+    data: UpdateExecutorProfile
+  ): Promise<ExecutorProfile> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/executor-profiles/${profileId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutorProfile>(response);
+  },
+
+  delete: async (projectId: string, profileId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/executor-profiles/${profileId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Webhooks APIs
+export const webhooksApi = {
+  list: async (projectId: string): Promise<Webhook[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/webhooks`);
+    return handleApiResponse<Webhook[]>(response);
+  },
+
+  create: async (projectId: string, data: CreateWebhook): Promise<Webhook> => {
+    const response = await makeRequest(`/api/projects/${projectId}/webhooks`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Webhook>(response);
+  },
+
+  update: async (
+    projectId: string,
+    webhookId: string,
+    data: UpdateWebhook
+  ): Promise<Webhook> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/webhooks/${webhookId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<Webhook>(response);
+  },
+
+  delete: async (projectId: string, webhookId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/webhooks/${webhookId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Project Check APIs
+export const projectChecksApi = {
+  list: async (projectId: string): Promise<ProjectCheck[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/checks`);
+    return handleApiResponse<ProjectCheck[]>(response);
+  },
+
+  create: async (
+    projectId: string,
+    data: CreateProjectCheck
+  ): Promise<ProjectCheck> => {
+    const response = await makeRequest(`/api/projects/${projectId}/checks`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectCheck>(response);
+  },
+
+  update: async (
+    projectId: string,
+    checkId: string,
+    data: UpdateProjectCheck
+  ): Promise<ProjectCheck> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/checks/${checkId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectCheck>(response);
+  },
+
+  delete: async (projectId: string, checkId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/checks/${checkId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Project Environment Variable APIs
+export const projectEnvVarsApi = {
+  list: async (projectId: string): Promise<ProjectEnvVar[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/env-vars`);
+    return handleApiResponse<ProjectEnvVar[]>(response);
+  },
+
+  create: async (
+    projectId: string,
+    data: CreateProjectEnvVar
+  ): Promise<ProjectEnvVar> => {
+    const response = await makeRequest(`/api/projects/${projectId}/env-vars`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectEnvVar>(response);
+  },
+
+  update: async (
+    projectId: string,
+    envVarId: string,
+    data: UpdateProjectEnvVar
+  ): Promise<ProjectEnvVar> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/env-vars/${envVarId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ProjectEnvVar>(response);
+  },
+
+  delete: async (projectId: string, envVarId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/env-vars/${envVarId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Who can work on a project, on multi-user servers
+export const projectMembersApi = {
+  list: async (projectId: string): Promise<ProjectMember[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/members`);
+    return handleApiResponse<ProjectMember[]>(response);
+  },
+
+  // Role of the signed in user, maintainer for admins and single-user servers
+  getMyRole: async (projectId: string): Promise<ProjectRole | null> => {
+    const response = await makeRequest(`/api/projects/${projectId}/role`);
+    return handleApiResponse<ProjectRole | null>(response);
+  },
+
+  set: async (
+    projectId: string,
+    userId: string,
+    data: SetProjectMember
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/members/${userId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  delete: async (projectId: string, userId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/members/${userId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Audit log APIs
+export const auditApi = {
+  list: async (query: Partial<AuditEventQuery>): Promise<AuditEventPage> => {
+    const params = new URLSearchParams();
+    Object.entries(query).forEach(([key, value]) => {
+      if (value !== null && value !== undefined) {
+        params.set(key, String(value));
+      }
+    });
+    const response = await makeRequest(`/api/audit-events?${params}`);
+    return handleApiResponse<AuditEventPage>(response);
+  },
+};
+
 // MCP Servers APIs
 export const mcpServersApi = {
   load: async (executor: string): Promise<any> => {
@@ -642,3 +1075,85 @@ export const mcpServersApi = {
     }
   },
 };
+
+// Sessions of multi-user servers
+export const authApi = {
+  getSession: async (): Promise<AuthSession> => {
+    const response = await makeRequest('/api/auth/session');
+    return handleApiResponse<AuthSession>(response);
+  },
+
+  login: async (data: LoginRequest): Promise<User> => {
+    const response = await makeRequest('/api/auth/login', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<User>(response);
+  },
+
+  setup: async (data: LoginRequest): Promise<User> => {
+    const response = await makeRequest('/api/auth/setup', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<User>(response);
+  },
+
+  logout: async (): Promise<void> => {
+    const response = await makeRequest('/api/auth/logout', {
+      method: 'POST',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Accounts and API tokens of multi-user servers
+export const usersApi = {
+  list: async (): Promise<User[]> => {
+    const response = await makeRequest('/api/users');
+    return handleApiResponse<User[]>(response);
+  },
+
+  create: async (data: CreateUser): Promise<User> => {
+    const response = await makeRequest('/api/users', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<User>(response);
+  },
+
+  delete: async (userId: string): Promise<void> => {
+    const response = await makeRequest(`/api/users/${userId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  changePassword: async (data: ChangePassword): Promise<void> => {
+    const response = await makeRequest('/api/users/me/password', {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  listTokens: async (): Promise<ApiToken[]> => {
+    const response = await makeRequest('/api/users/me/tokens');
+    return handleApiResponse<ApiToken[]>(response);
+  },
+
+  createToken: async (data: CreateApiToken): Promise<CreatedApiToken> => {
+    const response = await makeRequest('/api/users/me/tokens', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<CreatedApiToken>(response);
+  },
+
+  deleteToken: async (tokenId: string): Promise<void> => {
+    const response = await makeRequest(`/api/users/me/tokens/${tokenId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
@@ -1,10 +1,40 @@
 import { type ClassValue, clsx } from 'clsx';
 import { twMerge } from 'tailwind-merge';
+import type {
+  CheckResult,
+  ExecutorConfig,
+  ExecutorOption,
+} from 'shared/types';
+import { EXECUTOR_LABELS, EXECUTOR_TYPES } from 'shared/types';
 
 export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs));
 }
 
+// Id attempts select an executor by, the definition's id for custom executors
+export function executorId(config: ExecutorConfig): string {
+  return config.type === 'custom' ? config.id : config.type;
+}
+
+// The built-in executors, until the server lists the custom ones too
+export const BUILT_IN_EXECUTORS: ExecutorOption[] = EXECUTOR_TYPES.map(
+  (id) => ({
+    id,
+    label: EXECUTOR_LABELS[id] || id,
+    config: { type: id } as ExecutorConfig,
+    supports_mcp: id !== 'echo',
+    custom: false,
+  })
+);
+
 export function is_planning_executor_type(executorType: string): boolean {
   return executorType === 'claude-plan';
 }
+
+// Required checks that keep an attempt from being merged or opened as a PR
+export function blockingChecks(results: CheckResult[]): CheckResult[] {
+  return results.filter(
+    (result) =>
+      result.required && (result.status !== 'passed' || result.outdated)
+  );
+}
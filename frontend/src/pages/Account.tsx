@@ -0,0 +1,403 @@
+import { FormEvent, useCallback, useEffect, useState } from 'react';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { Copy, Key, Loader2, Lock, Trash2, Users } from 'lucide-react';
+import type { ApiToken, User } from 'shared/types';
+import { useAuth } from '@/components/auth-provider';
+import { usersApi } from '../lib/api';
+
+const errorMessage = (err: unknown, fallback: string) =>
+  err instanceof Error ? err.message : fallback;
+
+function ApiTokensCard() {
+  const [tokens, setTokens] = useState<ApiToken[]>([]);
+  const [name, setName] = useState('');
+  const [expiresInDays, setExpiresInDays] = useState('');
+  const [secret, setSecret] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadTokens = useCallback(async () => {
+    try {
+      setTokens(await usersApi.listTokens());
+    } catch (err) {
+      setError(errorMessage(err, 'Failed to load API tokens'));
+    }
+  }, []);
+
+  useEffect(() => {
+    loadTokens();
+  }, [loadTokens]);
+
+  const handleCreate = async (e: FormEvent) => {
+    e.preventDefault();
+    setError(null);
+    try {
+      const created = await usersApi.createToken({
+        name,
+        expires_in_days: expiresInDays ? Number(expiresInDays) : null,
+      });
+      setSecret(created.secret);
+      setName('');
+      setExpiresInDays('');
+      await loadTokens();
+    } catch (err) {
+      setError(errorMessage(err, 'Failed to create the API token'));
+    }
+  };
+
+  const handleDelete = async (tokenId: string) => {
+    setError(null);
+    try {
+      await usersApi.deleteToken(tokenId);
+      await loadTokens();
+    } catch (err) {
+      setError(errorMessage(err, 'Failed to revoke the API token'));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Key className="h-5 w-5" />
+          API Tokens
+        </CardTitle>
+        <CardDescription>
+          Tokens for the CLI, the MCP server and scripts. Set one in the
+          VIBE_KANBAN_TOKEN environment variable, or send it as a bearer token.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+        {secret && (
+          <Alert>
+            <AlertDescription className="space-y-2">
+              <p>Copy the token now, it won't be shown again.</p>
+              <div className="flex items-center gap-2">
+                <code className="flex-1 break-all rounded bg-muted p-2 text-xs">
+                  {secret}
+                </code>
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => navigator.clipboard?.writeText(secret)}
+                >
+                  <Copy className="h-4 w-4" />
+                </Button>
+              </div>
+            </AlertDescription>
+          </Alert>
+        )}
+        {tokens.map((token) => (
+          <div
+            key={token.id}
+            className="flex items-center justify-between rounded-md border p-3"
+          >
+            <div className="text-sm">
+              <div className="font-medium">{token.name}</div>
+              <div className="text-muted-foreground">
+                Created {new Date(token.created_at).toLocaleString()}
+                {' · '}
+                {token.last_used_at
+                  ? `Last used ${new Date(token.last_used_at).toLocaleString()}`
+                  : 'Never used'}
+                {token.expires_at &&
+                  ` · Expires ${new Date(token.expires_at).toLocaleString()}`}
+              </div>
+            </div>
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={() => handleDelete(token.id)}
+            >
+              <Trash2 className="h-4 w-4" />
+            </Button>
+          </div>
+        ))}
+        <form onSubmit={handleCreate} className="flex items-end gap-2">
+          <div className="flex-1 space-y-2">
+            <Label htmlFor="token-name">Name</Label>
+            <Input
+              id="token-name"
+              placeholder="Laptop CLI"
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+            />
+          </div>
+          <div className="w-36 space-y-2">
+            <Label htmlFor="token-expiry">Expires in days</Label>
+            <Input
+              id="token-expiry"
+              type="number"
+              min={1}
+              placeholder="Never"
+              value={expiresInDays}
+              onChange={(e) => setExpiresInDays(e.target.value)}
+            />
+          </div>
+          <Button type="submit" disabled={!name.trim()}>
+            Create token
+          </Button>
+        </form>
+      </CardContent>
+    </Card>
+  );
+}
+
+function PasswordCard() {
+  const [currentPassword, setCurrentPassword] = useState('');
+  const [newPassword, setNewPassword] = useState('');
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [success, setSuccess] = useState(false);
+
+  const handleSubmit = async (e: FormEvent) => {
+    e.preventDefault();
+    setSaving(true);
+    setError(null);
+    setSuccess(false);
+    try {
+      await usersApi.changePassword({
+        current_password: currentPassword,
+        new_password: newPassword,
+      });
+      setCurrentPassword('');
+      setNewPassword('');
+      setSuccess(true);
+    } catch (err) {
+      setError(errorMessage(err, 'Failed to change the password'));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Lock className="h-5 w-5" />
+          Password
+        </CardTitle>
+        <CardDescription>
+          Changing the password signs you out of your other browsers.
+        </CardDescription>
+      </CardHeader>
+      <CardContent>
+        <form onSubmit={handleSubmit} className="space-y-4">
+          {error && (
+            <Alert variant="destructive">
+              <AlertDescription>{error}</AlertDescription>
+            </Alert>
+          )}
+          {success && (
+            <Alert>
+              <AlertDescription>Password changed.</AlertDescription>
+            </Alert>
+          )}
+          <div className="space-y-2">
+            <Label htmlFor="current-password">Current password</Label>
+            <Input
+              id="current-password"
+              type="password"
+              autoComplete="current-password"
+              value={currentPassword}
+              onChange={(e) => setCurrentPassword(e.target.value)}
+            />
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="new-password">New password</Label>
+            <Input
+              id="new-password"
+              type="password"
+              autoComplete="new-password"
+              value={newPassword}
+              onChange={(e) => setNewPassword(e.target.value)}
+            />
+          </div>
+          <Button
+            type="submit"
+            disabled={saving || !currentPassword || !newPassword}
+          >
+            {saving && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+            Change password
+          </Button>
+        </form>
+      </CardContent>
+    </Card>
+  );
+}
+
+function UsersCard({ currentUser }: { currentUser: User }) {
+  const [users, setUsers] = useState<User[]>([]);
+  const [username, setUsername] = useState('');
+  const [password, setPassword] = useState('');
+  const [isAdmin, setIsAdmin] = useState(false);
+  const [creating, setCreating] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadUsers = useCallback(async () => {
+    try {
+      setUsers(await usersApi.list());
+    } catch (err) {
+      setError(errorMessage(err, 'Failed to load users'));
+    }
+  }, []);
+
+  useEffect(() => {
+    loadUsers();
+  }, [loadUsers]);
+
+  const handleCreate = async (e: FormEvent) => {
+    e.preventDefault();
+    setCreating(true);
+    setError(null);
+    try {
+      await usersApi.create({ username, password, is_admin: isAdmin });
+      setUsername('');
+      setPassword('');
+      setIsAdmin(false);
+      await loadUsers();
+    } catch (err) {
+      setError(errorMessage(err, 'Failed to create the user'));
+    } finally {
+      setCreating(false);
+    }
+  };
+
+  const handleDelete = async (user: User) => {
+    if (!confirm(`Delete ${user.username}? Their tasks stay on the board.`)) {
+      return;
+    }
+    setError(null);
+    try {
+      await usersApi.delete(user.id);
+      await loadUsers();
+    } catch (err) {
+      setError(errorMessage(err, 'Failed to delete the user'));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Users className="h-5 w-5" />
+          Users
+        </CardTitle>
+        <CardDescription>
+          Everyone who can sign in to this server. Admins can add and remove
+          users.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+        {users.map((user) => (
+          <div
+            key={user.id}
+            className="flex items-center justify-between rounded-md border p-3"
+          >
+            <div className="text-sm">
+              <span className="font-medium">{user.username}</span>
+              {user.is_admin && (
+                <span className="text-muted-foreground"> · Admin</span>
+              )}
+            </div>
+            {user.id !== currentUser.id && (
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => handleDelete(user)}
+              >
+                <Trash2 className="h-4 w-4" />
+              </Button>
+            )}
+          </div>
+        ))}
+        <form onSubmit={handleCreate} className="space-y-3">
+          <div className="flex items-end gap-2">
+            <div className="flex-1 space-y-2">
+              <Label htmlFor="new-user-name">User name</Label>
+              <Input
+                id="new-user-name"
+                value={username}
+                onChange={(e) => setUsername(e.target.value)}
+              />
+            </div>
+            <div className="flex-1 space-y-2">
+              <Label htmlFor="new-user-password">Password</Label>
+              <Input
+                id="new-user-password"
+                type="password"
+                autoComplete="new-password"
+                value={password}
+                onChange={(e) => setPassword(e.target.value)}
+              />
+            </div>
+            <Button type="submit" disabled={creating || !username || !password}>
+              {creating && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+              Add user
+            </Button>
+          </div>
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="new-user-admin"
+              checked={isAdmin}
+              onCheckedChange={(checked: boolean) => setIsAdmin(checked)}
+            />
+            <Label htmlFor="new-user-admin">Admin</Label>
+          </div>
+        </form>
+      </CardContent>
+    </Card>
+  );
+}
+
+export function Account() {
+  const { user } = useAuth();
+
+  if (!user) {
+    return (
+      <div className="container mx-auto px-4 py-8">
+        <Alert>
+          <AlertDescription>
+            Accounts are only used when the server runs in multi-user mode.
+          </AlertDescription>
+        </Alert>
+      </div>
+    );
+  }
+
+  return (
+    <div className="container mx-auto px-4 py-8">
+      <div className="space-y-6">
+        <div>
+          <h1 className="text-3xl font-bold">Account</h1>
+          <p className="text-muted-foreground">
+            Signed in as {user.username}
+          </p>
+        </div>
+        <ApiTokensCard />
+        <PasswordCard />
+        {user.is_admin && <UsersCard currentUser={user} />}
+      </div>
+    </div>
+  );
+}
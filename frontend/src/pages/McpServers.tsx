@@ -18,12 +18,12 @@ import { Label } from '@/components/ui/label';
 import { Alert, AlertDescription } from '@/components/ui/alert';
 import { Textarea } from '@/components/ui/textarea';
 import { Loader2 } from 'lucide-react';
-import { EXECUTOR_TYPES, EXECUTOR_LABELS } from 'shared/types';
 import { useConfig } from '@/components/config-provider';
 import { mcpServersApi } from '../lib/api';
+import { executorId } from '@/lib/utils';
 
 export function McpServers() {
-  const { config } = useConfig();
+  const { config, executors } = useConfig();
   const [mcpServers, setMcpServers] = useState('{}');
   const [mcpError, setMcpError] = useState<string | null>(null);
   const [mcpLoading, setMcpLoading] = useState(true);
@@ -34,10 +34,10 @@ export function McpServers() {
 
   // Initialize selected MCP executor when config loads
   useEffect(() => {
-    if (config?.executor?.type && !selectedMcpExecutor) {
-      setSelectedMcpExecutor(config.executor.type);
+    if (config?.executor && !selectedMcpExecutor) {
+      setSelectedMcpExecutor(executorId(config.executor));
     }
-  }, [config?.executor?.type, selectedMcpExecutor]);
+  }, [config?.executor, selectedMcpExecutor]);
 
   // Load existing MCP configuration when selected executor changes
   useEffect(() => {
@@ -314,9 +314,9 @@ export function McpServers() {
                   <SelectValue placeholder="Select executor" />
                 </SelectTrigger>
                 <SelectContent>
-                  {EXECUTOR_TYPES.map((type) => (
-                    <SelectItem key={type} value={type}>
-                      {EXECUTOR_LABELS[type]}
+                  {executors.map((executor) => (
+                    <SelectItem key={executor.id} value={executor.id}>
+                      {executor.label}
                     </SelectItem>
                   ))}
                 </SelectContent>
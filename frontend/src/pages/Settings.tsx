@@ -18,28 +18,41 @@ import { Label } from '@/components/ui/label';
 import { Alert, AlertDescription } from '@/components/ui/alert';
 import { Checkbox } from '@/components/ui/checkbox';
 import { Input } from '@/components/ui/input';
-import { Key, Loader2, Volume2 } from 'lucide-react';
-import type { EditorType, SoundFile, ThemeMode } from 'shared/types';
+import { GitBranch, Key, Loader2, Plus, Trash2, Volume2 } from 'lucide-react';
+import type {
+  EditorType,
+  GitHostConfig,
+  GitProviderKind,
+  SoundFile,
+  ThemeMode,
+} from 'shared/types';
 import {
   EDITOR_LABELS,
   EDITOR_TYPES,
-  EXECUTOR_LABELS,
-  EXECUTOR_TYPES,
   SOUND_FILES,
   SOUND_LABELS,
 } from 'shared/types';
 import { useTheme } from '@/components/theme-provider';
 import { useConfig } from '@/components/config-provider';
+import { useAuth } from '@/components/auth-provider';
 import { GitHubLoginDialog } from '@/components/GitHubLoginDialog';
 import { TaskTemplateManager } from '@/components/TaskTemplateManager';
+import { executorId } from '@/lib/utils';
 
 export function Settings() {
-  const { config, updateConfig, saveConfig, loading, updateAndSaveConfig } =
-    useConfig();
+  const {
+    config,
+    executors,
+    updateConfig,
+    saveConfig,
+    loading,
+    updateAndSaveConfig,
+  } = useConfig();
   const [saving, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [success, setSuccess] = useState(false);
   const { setTheme } = useTheme();
+  const { isAdmin } = useAuth();
   const [showGitHubLogin, setShowGitHubLogin] = useState(false);
 
   const playSound = async (soundFile: SoundFile) => {
@@ -93,6 +106,15 @@ export function Settings() {
 
   const isAuthenticated = !!(config?.github?.username && config?.github?.token);
 
+  const updateGitHost = (index: number, updates: Partial<GitHostConfig>) => {
+    if (!config) return;
+    updateConfig({
+      git_hosts: config.git_hosts.map((host, i) =>
+        i === index ? { ...host, ...updates } : host
+      ),
+    });
+  };
+
   const handleLogout = useCallback(async () => {
     if (!config) return;
     updateAndSaveConfig({
@@ -136,6 +158,15 @@ export function Settings() {
           </p>
         </div>
 
+        {!isAdmin && (
+          <Alert>
+            <AlertDescription>
+              The whole team shares these settings, only admins can change
+              them.
+            </AlertDescription>
+          </Alert>
+        )}
+
         {error && (
           <Alert variant="destructive">
             <AlertDescription>{error}</AlertDescription>
@@ -200,18 +231,19 @@ export function Settings() {
               <div className="space-y-2">
                 <Label htmlFor="executor">Default Executor</Label>
                 <Select
-                  value={config.executor.type}
-                  onValueChange={(value: 'echo' | 'claude' | 'amp') =>
-                    updateConfig({ executor: { type: value } })
-                  }
+                  value={executorId(config.executor)}
+                  onValueChange={(value: string) => {
+                    const option = executors.find((e) => e.id === value);
+                    if (option) updateConfig({ executor: option.config });
+                  }}
                 >
                   <SelectTrigger id="executor">
                     <SelectValue placeholder="Select executor" />
                   </SelectTrigger>
                   <SelectContent>
-                    {EXECUTOR_TYPES.map((type) => (
-                      <SelectItem key={type} value={type}>
-                        {EXECUTOR_LABELS[type]}
+                    {executors.map((executor) => (
+                      <SelectItem key={executor.id} value={executor.id}>
+                        {executor.label}
                       </SelectItem>
                     ))}
                   </SelectContent>
@@ -220,6 +252,27 @@ export function Settings() {
                   Choose the default executor for running tasks.
                 </p>
               </div>
+              <div className="flex items-center space-x-2">
+                <Checkbox
+                  id="auto-start-unblocked-tasks"
+                  checked={config.auto_start_unblocked_tasks}
+                  onCheckedChange={(checked: boolean) =>
+                    updateConfig({ auto_start_unblocked_tasks: checked })
+                  }
+                />
+                <div className="space-y-0.5">
+                  <Label
+                    htmlFor="auto-start-unblocked-tasks"
+                    className="cursor-pointer"
+                  >
+                    Auto-start Unblocked Tasks
+                  </Label>
+                  <p className="text-sm text-muted-foreground">
+                    Start tasks with the default executor once every task
+                    blocking them is merged or done.
+                  </p>
+                </div>
+              </div>
             </CardContent>
           </Card>
 
@@ -374,6 +427,129 @@ export function Settings() {
             </CardContent>
           </Card>
 
+          <Card>
+            <CardHeader>
+              <CardTitle className="flex items-center gap-2">
+                <GitBranch className="h-5 w-5" />
+                Self-hosted Git Servers
+              </CardTitle>
+              <CardDescription>
+                Push branches to and open merge requests on GitLab or Gitea
+                servers. Projects whose origin remote points at one of these
+                servers use it instead of GitHub.
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              {config.git_hosts.map((host, index) => (
+                <div key={index} className="space-y-3 rounded-md border p-4">
+                  <div className="flex items-end gap-2">
+                    <div className="w-36 space-y-2">
+                      <Label>Provider</Label>
+                      <Select
+                        value={host.provider}
+                        onValueChange={(value: GitProviderKind) =>
+                          updateGitHost(index, { provider: value })
+                        }
+                      >
+                        <SelectTrigger>
+                          <SelectValue />
+                        </SelectTrigger>
+                        <SelectContent>
+                          <SelectItem value="gitlab">GitLab</SelectItem>
+                          <SelectItem value="gitea">Gitea</SelectItem>
+                          <SelectItem value="github">
+                            GitHub Enterprise
+                          </SelectItem>
+                        </SelectContent>
+                      </Select>
+                    </div>
+                    <div className="flex-1 space-y-2">
+                      <Label htmlFor={`git-host-url-${index}`}>
+                        Server URL
+                      </Label>
+                      <Input
+                        id={`git-host-url-${index}`}
+                        placeholder="https://gitlab.example.com"
+                        value={host.base_url}
+                        onChange={(e) =>
+                          updateGitHost(index, { base_url: e.target.value })
+                        }
+                      />
+                    </div>
+                    <Button
+                      variant="ghost"
+                      size="icon"
+                      onClick={() =>
+                        updateConfig({
+                          git_hosts: config.git_hosts.filter(
+                            (_, i) => i !== index
+                          ),
+                        })
+                      }
+                    >
+                      <Trash2 className="h-4 w-4" />
+                    </Button>
+                  </div>
+                  <div className="flex gap-2">
+                    <div className="flex-1 space-y-2">
+                      <Label htmlFor={`git-host-token-${index}`}>
+                        Access Token
+                      </Label>
+                      <Input
+                        id={`git-host-token-${index}`}
+                        type="password"
+                        value={host.token || ''}
+                        onChange={(e) =>
+                          updateGitHost(index, {
+                            token: e.target.value || null,
+                          })
+                        }
+                      />
+                    </div>
+                    <div className="w-44 space-y-2">
+                      <Label htmlFor={`git-host-username-${index}`}>
+                        Username (optional)
+                      </Label>
+                      <Input
+                        id={`git-host-username-${index}`}
+                        value={host.username || ''}
+                        onChange={(e) =>
+                          updateGitHost(index, {
+                            username: e.target.value || null,
+                          })
+                        }
+                      />
+                    </div>
+                  </div>
+                </div>
+              ))}
+              <p className="text-sm text-muted-foreground">
+                The token needs API access and permission to push to the
+                repositories, e.g. the api and write_repository scopes on
+                GitLab.
+              </p>
+              <Button
+                variant="outline"
+                onClick={() =>
+                  updateConfig({
+                    git_hosts: [
+                      ...config.git_hosts,
+                      {
+                        provider: 'gitlab',
+                        base_url: '',
+                        token: null,
+                        username: null,
+                      },
+                    ],
+                  })
+                }
+              >
+                <Plus className="h-4 w-4 mr-2" />
+                Add Server
+              </Button>
+            </CardContent>
+          </Card>
+
           <Card>
             <CardHeader>
               <CardTitle>Notifications</CardTitle>
@@ -592,7 +768,7 @@ export function Settings() {
           <div className="container mx-auto max-w-4xl flex justify-end">
             <Button
               onClick={handleSave}
-              disabled={saving || success}
+              disabled={saving || success || !isAdmin}
               className={success ? 'bg-green-600 hover:bg-green-700' : ''}
             >
               {saving && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
@@ -191,7 +191,12 @@ export function ProjectTasks() {
   );
 
   const handleCreateAndStartTask = useCallback(
-    async (title: string, description: string, executor?: ExecutorConfig) => {
+    async (
+      title: string,
+      description: string,
+      executor?: ExecutorConfig,
+      executors?: ExecutorConfig[]
+    ) => {
       try {
         const payload: CreateTaskAndStart = {
           project_id: projectId!,
@@ -199,6 +204,8 @@ export function ProjectTasks() {
           description: description || null,
           parent_task_attempt: null,
           executor: executor || null,
+          executor_profile_id: null,
+          executors: executors ?? [],
         };
         const result = await tasksApi.createAndStart(projectId!, payload);
         await fetchTasks();
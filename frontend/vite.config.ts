@@ -26,6 +26,10 @@ export default defineConfig({
         target: `http://localhost:${process.env.BACKEND_PORT || '3001'}`,
         changeOrigin: true,
       },
+      '/preview': {
+        target: `http://localhost:${process.env.BACKEND_PORT || '3001'}`,
+        changeOrigin: true,
+      },
     },
   },
 
@@ -4,7 +4,13 @@
 
 export type ApiResponse<T> = { success: boolean, data: T | null, message: string | null, };
 
-export type Config = { theme: ThemeMode, executor: ExecutorConfig, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, sound_alerts: boolean, sound_file: SoundFile, push_notifications: boolean, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, };
+export type Config = { theme: ThemeMode, executor: ExecutorConfig, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, sound_alerts: boolean, sound_file: SoundFile, push_notifications: boolean, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, max_concurrent_agents: number | null, auto_start_unblocked_tasks: boolean, execution_timeouts: ExecutionTimeoutConfig, model_pricing: { [key: string]: ModelPricing }, git_hosts: Array<GitHostConfig>, };
+
+export type ExecutionTimeoutConfig = { max_runtime_minutes: number | null, idle_minutes: number | null, executors: { [key: string]: ExecutorTimeouts }, };
+
+export type ExecutorTimeouts = { max_runtime_minutes: number | null, idle_minutes: number | null, };
+
+export type ModelPricing = { input: number, output: number, cache_read: number, cache_write: number, };
 
 export type ThemeMode = "light" | "dark" | "system" | "purple" | "green" | "blue" | "orange" | "red";
 
@@ -12,6 +18,10 @@ export type EditorConfig = { editor_type: EditorType, custom_command: string | n
 
 export type GitHubConfig = { pat: string | null, token: string | null, username: string | null, primary_email: string | null, default_pr_base: string | null, };
 
+export type GitProviderKind = "github" | "gitlab" | "gitea";
+
+export type GitHostConfig = { provider: GitProviderKind, base_url: string, token: string | null, username: string | null, };
+
 export type EditorType = "vscode" | "cursor" | "windsurf" | "intellij" | "zed" | "custom";
 
 export type EditorConstants = { editor_types: Array<EditorType>, editor_labels: Array<string>, };
@@ -20,19 +30,59 @@ export type SoundFile = "abstract-sound1" | "abstract-sound2" | "abstract-sound3
 
 export type SoundConstants = { sound_files: Array<SoundFile>, sound_labels: Array<string>, };
 
-export type ConfigConstants = { editor: EditorConstants, sound: SoundConstants, };
+export type ConfigConstants = { editor: EditorConstants, sound: SoundConstants, container_runtimes: Array<ContainerRuntime>, executors: Array<ExecutorOption>, };
 
-export type ExecutorConfig = { "type": "echo" } | { "type": "claude" } | { "type": "claude-plan" } | { "type": "amp" } | { "type": "gemini" } | { "type": "setup-script", script: string, } | { "type": "claude-code-router" } | { "type": "charm-opencode" } | { "type": "sst-opencode" };
+export type ExecutorConfig = { "type": "echo" } | { "type": "claude" } | { "type": "claude-plan" } | { "type": "amp" } | { "type": "gemini" } | { "type": "setup-script", script: string, } | { "type": "claude-code-router" } | { "type": "charm-opencode" } | { "type": "sst-opencode" } | { "type": "aider" } | { "type": "codex" } | { "type": "custom", id: string, };
 
 export type ExecutorConstants = { executor_types: Array<ExecutorConfig>, executor_labels: Array<string>, };
 
-export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, };
-
-export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, created_at: Date, updated_at: Date, };
+export type ExecutorOption = { id: string, label: string, config: ExecutorConfig, supports_mcp: boolean, custom: boolean, };
 
-export type ProjectWithBranch = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, current_branch: string | null, created_at: Date, updated_at: Date, };
+export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, };
 
-export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, };
+export type MergeStrategy = "squash" | "merge" | "rebase" | "keep-commits";
+
+export type CommitMode = "per-execution" | "amend";
+
+export type CommitSettings = { 
+/**
+ * Supports {task_title}, {task_id}, {attempt_id}, {executor} and {summary}.
+ * Defaults to the agent's summary.
+ */
+message_template: string | null, conventional_prefix: string | null, author_name: string | null, author_email: string | null, co_authors: Array<string>, mode: CommitMode, };
+
+export type DevServerSettings = { 
+/**
+ * Set next to `PORT`. Values support {port}, {attempt_id} and {preview_path}, e.g.
+ * `"VITE_BASE": "{preview_path}"` for apps served under the preview proxy. The proxy
+ * only rewrites redirects, so an app linking to absolute paths like `/assets/app.js`
+ * without such a base loads them from vibe-kanban instead of its dev server.
+ */
+env: { [key: string]: string }, 
+/**
+ * Regex matched against output lines to tell the server is ready. Defaults to a line
+ * mentioning the allocated port or saying it is ready or listening.
+ */
+ready_pattern: string | null, };
+
+export type ContainerRuntime = "docker" | "podman";
+
+export type SandboxSettings = { enabled: boolean, 
+/**
+ * Defaults to docker, or podman when docker isn't installed
+ */
+runtime: ContainerRuntime | null, 
+/**
+ * Must provide the executor's CLI (e.g. node for npx-based agents). Only the attempt's
+ * worktree is mounted, so credentials are passed through project environment variables.
+ */
+image: string, isolate_network: boolean, cpus: number | null, memory_mb: number | null, };
+
+export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, max_concurrent_agents: bigint | null, merge_strategy: MergeStrategy, commit_settings: CommitSettings, auto_fix_max_iterations: number, dev_server_settings: DevServerSettings, sandbox_settings: SandboxSettings, created_at: Date, updated_at: Date, };
+
+export type ProjectWithBranch = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, max_concurrent_agents: bigint | null, merge_strategy: MergeStrategy, commit_settings: CommitSettings, auto_fix_max_iterations: number, dev_server_settings: DevServerSettings, sandbox_settings: SandboxSettings, current_branch: string | null, created_at: Date, updated_at: Date, };
+
+export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, max_concurrent_agents: bigint | null, merge_strategy: MergeStrategy | null, commit_settings: CommitSettings | null, auto_fix_max_iterations: number | null, dev_server_settings: DevServerSettings | null, sandbox_settings: SandboxSettings | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
@@ -44,16 +94,27 @@ export type CreateBranch = { name: string, base_branch: string | null, };
 
 export type CreateTask = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, };
 
-export type CreateTaskAndStart = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, executor: ExecutorConfig | null, };
+export type CreateTaskAndStart = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, executor: ExecutorConfig | null, executor_profile_id: string | null, 
+/**
+ * Start one attempt per executor, each in its own worktree, instead of a single
+ * attempt with `executor` or `executor_profile_id`
+ */
+executors: Array<ExecutorConfig>, };
 
 export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, created_at: string, updated_at: string, };
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, created_by: string | null, created_at: string, updated_at: string, };
 
-export type TaskWithAttemptStatus = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, created_at: string, updated_at: string, has_in_progress_attempt: boolean, has_merged_attempt: boolean, has_failed_attempt: boolean, latest_attempt_executor: string | null, };
+export type TaskWithAttemptStatus = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, created_by: string | null, created_at: string, updated_at: string, has_in_progress_attempt: boolean, has_merged_attempt: boolean, has_failed_attempt: boolean, latest_attempt_executor: string | null, };
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, };
 
+export type TaskDependency = { task_id: string, blocked_by_task_id: string, created_at: string, };
+
+export type CreateTaskDependency = { blocked_by_task_id: string, };
+
+export type TaskDependencies = { blocked_by: Array<Task>, blocking: Array<Task>, is_blocked: boolean, };
+
 export type TaskTemplate = { id: string, project_id: string | null, title: string, description: string | null, template_name: string, created_at: string, updated_at: string, };
 
 export type CreateTaskTemplate = { project_id: string | null, title: string, description: string | null, template_name: string, };
@@ -62,14 +123,72 @@ export type UpdateTaskTemplate = { title: string | null, description: string | n
 
 export type TaskAttemptStatus = "setuprunning" | "setupcomplete" | "setupfailed" | "executorrunning" | "executorcomplete" | "executorfailed";
 
-export type TaskAttempt = { id: string, task_id: string, worktree_path: string, branch: string, base_branch: string, merge_commit: string | null, executor: string | null, pr_url: string | null, pr_number: bigint | null, pr_status: string | null, pr_merged_at: string | null, worktree_deleted: boolean, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type TaskAttempt = { id: string, task_id: string, worktree_path: string, branch: string, base_branch: string, merge_commit: string | null, executor: string | null, pr_url: string | null, pr_number: bigint | null, pr_status: string | null, pr_merged_at: string | null, worktree_deleted: boolean, setup_completed_at: string | null, executor_profile_id: string | null, started_by: string | null, created_at: string, updated_at: string, };
 
-export type CreateTaskAttempt = { executor: string | null, base_branch: string | null, };
+export type CreateTaskAttempt = { executor: string | null, base_branch: string | null, executor_profile_id: string | null, };
 
 export type UpdateTaskAttempt = Record<string, never>;
 
 export type CreateFollowUpAttempt = { prompt: string, };
 
+export type MergeTaskAttempt = { strategy: MergeStrategy | null, skip_checks: boolean, };
+
+export type MergeResult = { merge_commit: string | null, conflicted_files: Array<string>, };
+
+export type WebhookEvent = "task.created" | "attempt.started" | "agent.finished" | "agent.failed" | "pr.opened" | "pr.merged";
+
+export type Webhook = { id: string, project_id: string, url: string, secret: string, events: Array<WebhookEvent>, enabled: boolean, created_at: string, updated_at: string, };
+
+export type CreateWebhook = { url: string, secret: string | null, events: Array<WebhookEvent> | null, enabled: boolean | null, };
+
+export type UpdateWebhook = { url: string | null, secret: string | null, events: Array<WebhookEvent> | null, enabled: boolean | null, };
+
+export type ProjectCheck = { id: string, project_id: string, name: string, script: string, required: boolean, created_at: string, updated_at: string, };
+
+export type CreateProjectCheck = { name: string, script: string, required: boolean | null, };
+
+export type UpdateProjectCheck = { name: string | null, script: string | null, required: boolean | null, };
+
+export type ProjectEnvVar = { id: string, project_id: string, name: string, value: string | null, is_secret: boolean, created_at: string, updated_at: string, };
+
+export type CreateProjectEnvVar = { name: string, value: string, is_secret: boolean | null, };
+
+export type UpdateProjectEnvVar = { name: string | null, value: string | null, is_secret: boolean | null, };
+
+export type User = { id: string, username: string, is_admin: boolean, created_at: string, updated_at: string, };
+
+export type CreateUser = { username: string, password: string, is_admin: boolean, };
+
+export type LoginRequest = { username: string, password: string, };
+
+export type ChangePassword = { current_password: string, new_password: string, };
+
+export type AuthSession = { multi_user: boolean, needs_setup: boolean, user: User | null, preview_port: number | null, };
+
+export type ApiToken = { id: string, user_id: string, name: string, created_at: string, last_used_at: string | null, expires_at: string | null, };
+
+export type CreateApiToken = { name: string, expires_in_days: number | null, };
+
+export type CreatedApiToken = { token: ApiToken, secret: string, };
+
+export type ProjectRole = "viewer" | "contributor" | "maintainer";
+
+export type ProjectMember = { project_id: string, user_id: string, username: string, role: ProjectRole, created_at: string, updated_at: string, };
+
+export type SetProjectMember = { role: ProjectRole, };
+
+export type AuditSource = "api" | "mcp";
+
+export type AuditEvent = { id: string, actor_id: string | null, actor_name: string | null, source: AuditSource, action: string, project_id: string | null, task_id: string | null, attempt_id: string | null, target: string | null, before: unknown, after: unknown, created_at: string, };
+
+export type AuditEventQuery = { project_id: string | null, task_id: string | null, attempt_id: string | null, actor_id: string | null, source: AuditSource | null, action: string | null, since: string | null, until: string | null, limit: number | null, offset: number | null, };
+
+export type AuditEventPage = { events: Array<AuditEvent>, has_more: boolean, };
+
+export type CheckStatus = "not-run" | "running" | "passed" | "failed";
+
+export type CheckResult = { check_id: string, name: string, required: boolean, status: CheckStatus, outdated: boolean, execution_process_id: string | null, exit_code: bigint | null, started_at: string | null, completed_at: string | null, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
@@ -86,26 +205,56 @@ export type FileDiff = { path: string, chunks: Array<DiffChunk>, };
 
 export type WorktreeDiff = { files: Array<FileDiff>, };
 
+export type DiffStats = { files_changed: number, lines_added: number, lines_removed: number, };
+
+export type AttemptComparison = { attempt: TaskAttempt, diff_stats: DiffStats | null, agent_status: ExecutionProcessStatus | null, exit_code: bigint | null, agent_duration_seconds: bigint | null, checks: Array<CheckResult>, };
+
+export type UsageRollup = { key: string, label: string | null, input_tokens: number, output_tokens: number, cache_read_input_tokens: number, cache_creation_input_tokens: number, cost_usd: number, };
+
+export type UsageReport = { total: UsageRollup, by_model: Array<UsageRollup>, by_task: Array<UsageRollup>, by_day: Array<UsageRollup>, unpriced_models: Array<string>, };
+
+export type ConflictOperation = "rebase" | "merge";
+
+export type ConflictHunk = { start_line: number, ours: string, base: string, theirs: string, };
+
+export type ConflictFile = { path: string, ours: string | null, base: string | null, theirs: string | null, merged_content: string | null, hunks: Array<ConflictHunk>, };
+
+export type ConflictReport = { operation: ConflictOperation, base_branch: string, files: Array<ConflictFile>, };
+
+export type StartConflictResolution = { operation: ConflictOperation, };
+
+export type ConflictResolution = { path: string, content: string | null, };
+
+export type ResolveConflicts = { resolutions: Array<ConflictResolution>, };
+
 export type BranchStatus = { is_behind: boolean, commits_behind: number, commits_ahead: number, up_to_date: boolean, merged: boolean, has_uncommitted_changes: boolean, base_branch_name: string, };
 
-export type ExecutionState = "NotStarted" | "SetupRunning" | "SetupComplete" | "SetupFailed" | "SetupStopped" | "CodingAgentRunning" | "CodingAgentComplete" | "CodingAgentFailed" | "CodingAgentStopped" | "Complete";
+export type ExecutionState = "NotStarted" | "SetupRunning" | "SetupComplete" | "SetupFailed" | "SetupStopped" | "CodingAgentQueued" | "CodingAgentRunning" | "CodingAgentComplete" | "CodingAgentFailed" | "CodingAgentStopped" | "Complete";
 
 export type TaskAttemptState = { execution_state: ExecutionState, has_changes: boolean, has_setup_script: boolean, setup_process_id: string | null, coding_agent_process_id: string | null, };
 
-export type ExecutionProcess = { id: string, task_attempt_id: string, process_type: ExecutionProcessType, executor_type: string | null, status: ExecutionProcessStatus, command: string, args: string | null, working_directory: string, stdout: string | null, stderr: string | null, exit_code: bigint | null, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+export type ExecutionProcess = { id: string, task_attempt_id: string, process_type: ExecutionProcessType, executor_type: string | null, status: ExecutionProcessStatus, command: string, args: string | null, working_directory: string, stdout: string | null, stderr: string | null, exit_code: bigint | null, started_at: string, completed_at: string | null, last_output_at: string | null, kill_reason: string | null, auto_fix_of_process_id: string | null, auto_fix_iteration: bigint | null, port: bigint | null, ready_at: string | null, created_at: string, updated_at: string, };
 
-export type ExecutionProcessSummary = { id: string, task_attempt_id: string, process_type: ExecutionProcessType, executor_type: string | null, status: ExecutionProcessStatus, command: string, args: string | null, working_directory: string, exit_code: bigint | null, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+export type ExecutionProcessSummary = { id: string, task_attempt_id: string, process_type: ExecutionProcessType, executor_type: string | null, status: ExecutionProcessStatus, command: string, args: string | null, working_directory: string, exit_code: bigint | null, started_at: string, completed_at: string | null, last_output_at: string | null, kill_reason: string | null, auto_fix_of_process_id: string | null, auto_fix_iteration: bigint | null, port: bigint | null, ready_at: string | null, created_at: string, updated_at: string, };
 
-export type ExecutionProcessStatus = "running" | "completed" | "failed" | "killed";
+export type ExecutionProcessStatus = "queued" | "running" | "completed" | "failed" | "killed";
 
-export type ExecutionProcessType = "setupscript" | "codingagent" | "devserver";
+export type QueuedExecution = { id: string, task_attempt_id: string, task_id: string, project_id: string, executor_type: string | null, position: bigint, queued_at: string, };
 
-export type CreateExecutionProcess = { task_attempt_id: string, process_type: ExecutionProcessType, executor_type: string | null, command: string, args: string | null, working_directory: string, };
+export type ExecutionProcessType = "setupscript" | "codingagent" | "devserver" | "checkscript";
+
+export type CreateExecutionProcess = { task_attempt_id: string, process_type: ExecutionProcessType, executor_type: string | null, command: string, args: string | null, working_directory: string, project_check_id: string | null, head_commit: string | null, port: bigint | null, auto_fix_of_process_id: string | null, auto_fix_iteration: bigint | null, };
 
 export type UpdateExecutionProcess = { status: ExecutionProcessStatus | null, exit_code: bigint | null, completed_at: string | null, };
 
 export type ExecutorSession = { id: string, task_attempt_id: string, execution_process_id: string, session_id: string | null, prompt: string | null, summary: string | null, created_at: string, updated_at: string, };
 
+export type ExecutorProfile = { id: string, project_id: string, name: string, executor: string, command: string | null, extra_args: string | null, env_vars: Record<string, string>, model: string | null, npm_version: string | null, created_at: string, updated_at: string, };
+
+export type CreateExecutorProfile = { name: string, executor: string, command: string | null, extra_args: string | null, env_vars: { [key: string]: string } | null, model: string | null, npm_version: string | null, };
+
+export type UpdateExecutorProfile = { name: string | null, executor: string | null, command: string | null, extra_args: string | null, env_vars: { [key: string]: string } | null, model: string | null, npm_version: string | null, };
+
 export type CreateExecutorSession = { task_attempt_id: string, execution_process_id: string, prompt: string | null, };
 
 export type UpdateExecutorSession = { session_id: string | null, prompt: string | null, summary: string | null, };
@@ -127,16 +276,9 @@ export const EXECUTOR_TYPES: string[] = [
     "gemini",
     "charm-opencode",
     "claude-code-router",
-    "sst-opencode"
-];
-
-export const EDITOR_TYPES: EditorType[] = [
-    "vscode",
-    "cursor", 
-    "windsurf",
-    "intellij",
-    "zed",
-    "custom"
+    "sst-opencode",
+    "aider",
+    "codex"
 ];
 
 export const EXECUTOR_LABELS: Record<string, string> = {
@@ -147,9 +289,20 @@ export const EXECUTOR_LABELS: Record<string, string> = {
     "gemini": "Gemini",
     "charm-opencode": "Charm Opencode",
     "claude-code-router": "Claude Code Router",
-    "sst-opencode": "SST Opencode"
+    "sst-opencode": "SST Opencode",
+    "aider": "Aider",
+    "codex": "Codex"
 };
 
+export const EDITOR_TYPES: EditorType[] = [
+    "vscode",
+    "cursor", 
+    "windsurf",
+    "intellij",
+    "zed",
+    "custom"
+];
+
 export const EDITOR_LABELS: Record<string, string> = {
     "vscode": "VS Code",
     "cursor": "Cursor",